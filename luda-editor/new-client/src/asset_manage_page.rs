@@ -21,7 +21,8 @@ impl Component for AssetManagePage {
         let screen_wh = namui::screen::size().map(|x| x.into_px());
         let (asset_tag, set_asset_tag) = ctx.state(|| luda_rpc::AssetSystemTag::SpriteCharacter);
 
-        let upload_asset = &|| {
+        // `None` opens the file picker.
+        let upload_asset = &|selected: Option<SelectedAssetFile>| {
             use reserve_team_asset_upload::*;
 
             ctx.spawn({
@@ -36,9 +37,15 @@ impl Component for AssetManagePage {
                         | luda_rpc::AssetSystemTag::AudioBackground => AssetKind::Audio,
                     };
 
-                    let Ok(SelectedAssetFile { name, bytes }) = select_asset_file().await else {
-                        toast::negative("에셋 파일 선택 실패");
-                        return;
+                    let SelectedAssetFile { name, bytes } = match selected {
+                        Some(selected) => selected,
+                        None => {
+                            let Ok(selected) = select_asset_file().await else {
+                                toast::negative("에셋 파일 선택 실패");
+                                return;
+                            };
+                            selected
+                        }
                     };
 
                     let bytes = match asset_tag {
@@ -102,23 +109,76 @@ impl Component for AssetManagePage {
                 }),
             ])(screen_wh, ctx);
         });
+
+        ctx.add(AssetDropZone {
+            wh: screen_wh,
+            asset_tag: *asset_tag,
+            upload_asset,
+        });
     }
 }
 
 struct AssetUploadButton<'a> {
     wh: Wh<Px>,
-    upload_asset: &'a dyn Fn(),
+    upload_asset: &'a dyn Fn(Option<SelectedAssetFile>),
 }
 impl Component for AssetUploadButton<'_> {
     fn render(self, ctx: &RenderCtx) {
         let Self { wh, upload_asset } = self;
 
         ctx.add(simple_button(wh, "에셋 업로드", |_| {
-            upload_asset();
+            upload_asset(None);
         }));
     }
 }
 
+/// Uploads files dropped from the OS, if they fit the selected tag.
+struct AssetDropZone<'a> {
+    wh: Wh<Px>,
+    asset_tag: luda_rpc::AssetSystemTag,
+    upload_asset: &'a dyn Fn(Option<SelectedAssetFile>),
+}
+impl Component for AssetDropZone<'_> {
+    fn render(self, ctx: &RenderCtx) {
+        let Self {
+            wh,
+            asset_tag,
+            upload_asset,
+        } = self;
+
+        let accepts = |file: &DroppedFile| {
+            let extension = file
+                .name
+                .rsplit_once('.')
+                .map(|(_, extension)| extension.to_ascii_lowercase())
+                .unwrap_or_default();
+            match asset_tag {
+                luda_rpc::AssetSystemTag::SpriteCharacter
+                | luda_rpc::AssetSystemTag::SpriteObject
+                | luda_rpc::AssetSystemTag::SpriteBackground => extension == "psd",
+                luda_rpc::AssetSystemTag::AudioCharacter
+                | luda_rpc::AssetSystemTag::AudioProp
+                | luda_rpc::AssetSystemTag::AudioBackground => {
+                    matches!(extension.as_str(), "mp3" | "ogg" | "wav" | "opus")
+                }
+            }
+        };
+
+        ctx.add(
+            simple_rect(wh, Color::TRANSPARENT, 0.px(), Color::TRANSPARENT).attach_event(|event| {
+                if let Some(drag_and_drop::DropTargetEvent::Drop { payload, .. }) =
+                    drag_and_drop::drop_target::<DroppedFile>(&event, accepts)
+                {
+                    upload_asset(Some(SelectedAssetFile {
+                        name: payload.name,
+                        bytes: payload.bytes.to_vec(),
+                    }));
+                }
+            }),
+        );
+    }
+}
+
 struct SelectedAssetFile {
     name: String,
     bytes: Vec<u8>,
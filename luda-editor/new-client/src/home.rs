@@ -1,6 +1,9 @@
 use super::*;
 use luda_rpc::*;
 
+/// Invalidate it when the teams of the user change.
+pub const MY_TEAMS_RESOURCE_KEY: &str = "my_teams";
+
 pub struct Home<'a> {
     pub initial_selection: &'a Selection,
 }
@@ -94,73 +97,98 @@ impl Component for TeamList<'_> {
     fn render(self, ctx: &RenderCtx) {
         let Self { wh, on_select_team } = self;
 
-        let title = || {
-            [fixed(24.px(), |wh, ctx| {
-                ctx.add(typography::center_text(
-                    wh,
-                    "팀 리스트",
-                    Color::WHITE,
-                    16.int_px(),
-                ));
-            })]
-        };
-
-        use crate::rpc::team::get_my_teams::*;
-        get_my_teams_render(
-            ctx,
-            |_| Some((RefRequest {}, ())),
-            (),
-            || {
-                ctx.compose(|ctx| vertical(title())(wh, ctx));
-            },
-            |err| {
-                ctx.add(typography::center_text(
-                    wh,
-                    format!("로딩 실패: {err:?}"),
-                    Color::RED,
-                    16.int_px(),
-                ));
+        ctx.add(ErrorBoundary {
+            fallback: |ctx: ComposeCtx, errors: &[ResourceError]| {
+                let error = errors[0].clone();
+                ctx.add(
+                    typography::center_text(
+                        wh,
+                        format!("로딩 실패: {} (클릭해서 다시 시도)", error.message),
+                        Color::RED,
+                        16.int_px(),
+                    )
+                    .attach_event(|event| {
+                        if let Event::MouseUp { event } = event {
+                            if event.is_local_xy_in() && event.button == Some(MouseButton::Left) {
+                                error.retry();
+                            }
+                        }
+                    }),
+                );
             },
-            |(Response { teams }, _)| {
-                ctx.compose(|ctx| {
-                    vertical(
-                        title()
-                            .into_iter()
-                            .chain(teams.iter().map(|team| {
-                                fixed(24.px(), move |wh, ctx| {
-                                    ctx.add(
-                                        typography::center_text(
-                                            wh,
-                                            &team.name,
-                                            Color::WHITE,
-                                            16.int_px(),
-                                        )
-                                        .attach_event(
-                                            |event| {
-                                                if let Event::MouseUp { event } = event {
-                                                    if event.is_local_xy_in()
-                                                        && event.button == Some(MouseButton::Left)
-                                                    {
-                                                        on_select_team(team);
-                                                    }
-                                                }
-                                            },
-                                        ),
-                                    );
-                                })
-                            }))
-                            .chain([fixed(24.px(), |wh, ctx| {
-                                ctx.add(simple_button(wh, "새 팀 만들기", |_event| {
-                                    router::route(router::Route::NewTeam);
-                                }));
-                            })])
-                            .chain([fixed(24.px(), |wh, ctx| {
-                                ctx.add(simple_button(wh, "팀 가입하기", |_event| {}));
-                            })]),
-                    )(wh, ctx)
+            children: |ctx: ComposeCtx| {
+                ctx.add(Suspense {
+                    fallback: |ctx: ComposeCtx| vertical([team_list_title()])(wh, ctx),
+                    children: |ctx: ComposeCtx| {
+                        ctx.add(TeamListItems { wh, on_select_team });
+                    },
                 });
             },
-        );
+        });
+    }
+}
+
+fn team_list_title<'a>() -> TableCell<'a> {
+    fixed(24.px(), |wh, ctx| {
+        ctx.add(typography::center_text(
+            wh,
+            "팀 리스트",
+            Color::WHITE,
+            16.int_px(),
+        ));
+    })
+}
+
+struct TeamListItems<'a> {
+    wh: Wh<Px>,
+    on_select_team: &'a dyn Fn(&luda_rpc::Team),
+}
+
+impl Component for TeamListItems<'_> {
+    fn render(self, ctx: &RenderCtx) {
+        let Self { wh, on_select_team } = self;
+
+        use crate::rpc::team::get_my_teams::*;
+        let response = ctx.resource(MY_TEAMS_RESOURCE_KEY, || async {
+            server_connection()
+                .get_my_teams(RefRequest {})
+                .await
+                .map_err(|err| format!("{err:?}"))
+        });
+        let Some(Response { teams }) = response.ready() else {
+            return;
+        };
+
+        ctx.compose(|ctx| {
+            vertical(
+                [team_list_title()]
+                    .into_iter()
+                    .chain(teams.iter().map(|team| {
+                        fixed(24.px(), move |wh, ctx| {
+                            ctx.add(
+                                typography::center_text(wh, &team.name, Color::WHITE, 16.int_px())
+                                    .attach_event(|event| {
+                                        if let Event::MouseUp { event } = event {
+                                            if event.is_local_xy_in()
+                                                && event.button == Some(MouseButton::Left)
+                                            {
+                                                on_select_team(team);
+                                            }
+                                        }
+                                    }),
+                            );
+                        })
+                    }))
+                    .chain([fixed(24.px(), |wh, ctx| {
+                        ctx.add(simple_button(wh, "새 팀 만들기", |_event| {
+                            router::route(router::Route::NewTeam);
+                        }));
+                    })])
+                    .chain([fixed(24.px(), |wh, ctx| {
+                        ctx.add(simple_button(wh, "팀 가입하기", |_event| {}));
+                    })]),
+            )(wh, ctx)
+        });
     }
 }
 
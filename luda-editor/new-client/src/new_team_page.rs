@@ -11,6 +11,7 @@ impl Component for NewTeamPage {
         let (team_name_validate_err, set_team_name_validate_err) =
             ctx.state::<Option<String>>(|| None);
         let (create_new_team_err, set_create_new_team_err) = ctx.state::<Option<Error>>(|| None);
+        let resources = ctx.resources();
 
         let (submit, on_progress) = make_create_new_team_fn(
             ctx,
@@ -28,6 +29,7 @@ impl Component for NewTeamPage {
             move |result| match result {
                 Ok((Response { team_id }, _)) => {
                     toast::positive("팀 생성 완료");
+                    resources.invalidate(home::MY_TEAMS_RESOURCE_KEY);
                     router::route(router::Route::Home {
                         initial_selection: home::Selection::Team { team_id },
                     });
@@ -0,0 +1,50 @@
+// Auto-generated from src/actions/create_webhook.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const WebhookEventSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("NewGroup"),
+  }),
+    z.object({
+    t: z.literal("Regressed"),
+  }),
+    z.object({
+    t: z.literal("Spike"),
+  })
+  ]);
+
+const InputSchema = z.object({
+    url: z.string(),
+    events: z.array(WebhookEventSchema),
+    spikeHitsPerHour: z.number().optional(),
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+    id: z.string(),
+    secretHex: z.string(),
+  }),
+    z.object({
+    t: z.literal("NotLoggedIn"),
+  }),
+    z.object({
+    t: z.literal("InvalidUrl"),
+  }),
+    z.object({
+    t: z.literal("NoEvents"),
+  }),
+    z.object({
+    t: z.literal("InvalidSpikeThreshold"),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function createWebhook(input: z.infer<typeof InputSchema>) {
+  return callAction("create_webhook", input, OutputSchema);
+}
@@ -0,0 +1,28 @@
+// Auto-generated from src/actions/delete_webhook.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const InputSchema = z.object({
+    id: z.string(),
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+  }),
+    z.object({
+    t: z.literal("NotLoggedIn"),
+  }),
+    z.object({
+    t: z.literal("NotFound"),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function deleteWebhook(input: z.infer<typeof InputSchema>) {
+  return callAction("delete_webhook", input, OutputSchema);
+}
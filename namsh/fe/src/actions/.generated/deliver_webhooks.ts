@@ -0,0 +1,32 @@
+// Auto-generated from src/actions/deliver_webhooks.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const DeliveryCountsSchema = z.object({
+    delivered: z.number(),
+    retrying: z.number(),
+    failed: z.number(),
+    pruned: z.number(),
+  });
+
+const InputSchema = z.object({
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+    counts: DeliveryCountsSchema,
+  }),
+    z.object({
+    t: z.literal("Unauthorized"),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function deliverWebhooks(input: z.infer<typeof InputSchema>) {
+  return callAction("deliver_webhooks", input, OutputSchema);
+}
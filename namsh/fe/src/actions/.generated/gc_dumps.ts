@@ -0,0 +1,27 @@
+// Auto-generated from src/actions/gc_dumps.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const InputSchema = z.object({
+    dryRun: z.boolean(),
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+    deletedDumpIds: z.array(z.string()),
+    failedKeys: z.array(z.string()),
+  }),
+    z.object({
+    t: z.literal("Unauthorized"),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function gcDumps(input: z.infer<typeof InputSchema>) {
+  return callAction("gc_dumps", input, OutputSchema);
+}
@@ -0,0 +1,59 @@
+// Auto-generated from src/actions/get_build_stats.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const GroupHitsSchema = z.object({
+    stackHash: z.string(),
+    hits: z.number(),
+    installs: z.number(),
+  });
+
+const BuildStatsSchema = z.object({
+    buildId: z.string(),
+    hits: z.number(),
+    installs: z.number(),
+    groups: z.array(GroupHitsSchema),
+  });
+
+const GroupChangeSchema = z.object({
+    stackHash: z.string(),
+    baseHits: z.number(),
+    hits: z.number(),
+  });
+
+const BuildDiffSchema = z.object({
+    new: z.array(GroupChangeSchema),
+    gone: z.array(GroupChangeSchema),
+    increased: z.array(GroupChangeSchema),
+  });
+
+const InputSchema = z.object({
+    buildId: z.string(),
+    baseBuildId: z.string().optional(),
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+    stats: BuildStatsSchema,
+    diff: BuildDiffSchema.optional(),
+  }),
+    z.object({
+    t: z.literal("NotLoggedIn"),
+  }),
+    z.object({
+    t: z.literal("NotFound"),
+  }),
+    z.object({
+    t: z.literal("BaseNotFound"),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function getBuildStats(input: z.infer<typeof InputSchema>) {
+  return callAction("get_build_stats", input, OutputSchema);
+}
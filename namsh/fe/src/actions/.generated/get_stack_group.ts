@@ -3,18 +3,86 @@
 import { z } from "zod";
 import { callAction } from "@forte/react";
 
+const CrashKindSchema = z.enum(["native", "panic"]);
+
+const PanicDetailsSchema = z.object({
+    message: z.string(),
+    location: z.string(),
+  });
+
+const BreadcrumbSchema = z.object({
+    timestampUnixMs: z.number(),
+    category: z.string(),
+    message: z.string(),
+    data: z.record(z.string(), z.string()),
+  });
+
 const CrashContextSchema = z.object({
     buildId: z.string(),
     installId: z.string(),
     sessionUptimeSec: z.number(),
     logTail: z.string().optional(),
+    kind: CrashKindSchema,
+    panic: PanicDetailsSchema.optional(),
+    breadcrumbs: z.array(BreadcrumbSchema),
   });
 
+const StackGroupStatusSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Open"),
+  }),
+    z.object({
+    t: z.literal("Resolved"),
+    buildId: z.string(),
+  }),
+    z.object({
+    t: z.literal("Ignored"),
+  }),
+    z.object({
+    t: z.literal("Regressed"),
+    resolvedBuildId: z.string(),
+    buildId: z.string(),
+  })
+  ]);
+
+const MinidumpInfoSchema = z.object({
+    os: z.string(),
+    osVersion: z.string(),
+    cpuArch: z.string(),
+    exceptionCode: z.number(),
+    exceptionName: z.string(),
+    crashingModule: z.string().optional(),
+  });
+
+const DumpProcessingSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Pending"),
+  }),
+    z.object({
+    t: z.literal("Processed"),
+    info: MinidumpInfoSchema,
+  }),
+    z.object({
+    t: z.literal("Failed"),
+    reason: z.string(),
+  }),
+    z.object({
+    t: z.literal("NotMinidump"),
+  })
+  ]);
+
 const DumpSummarySchema = z.object({
     dumpId: z.string(),
     buildId: z.string(),
     uploadedAt: z.coerce.date(),
     clientIp: z.string(),
+    processing: DumpProcessingSchema,
+  });
+
+const PlatformDumpsSchema = z.object({
+    os: z.string(),
+    cpuArch: z.string(),
+    dumps: z.number(),
   });
 
 const InputSchema = z.object({
@@ -29,6 +97,9 @@ const OutputSchema = z.discriminatedUnion("t", [
     lastSeen: z.coerce.date(),
     count: z.number(),
     latestContext: CrashContextSchema,
+    status: StackGroupStatusSchema,
+    buildIds: z.array(z.string()),
+    platforms: z.array(PlatformDumpsSchema),
     dumps: z.array(DumpSummarySchema),
   }),
     z.object({
@@ -3,6 +3,11 @@
 export { addUser } from "./add_user";
 export { approveCliAuthorization } from "./approve_cli_authorization";
 export { confirmPdbUploaded } from "./confirm_pdb_uploaded";
+export { createWebhook } from "./create_webhook";
+export { deleteWebhook } from "./delete_webhook";
+export { deliverWebhooks } from "./deliver_webhooks";
+export { gcDumps } from "./gc_dumps";
+export { getBuildStats } from "./get_build_stats";
 export { getStackGroup } from "./get_stack_group";
 export { intakeCrash } from "./intake_crash";
 export { issueToken } from "./issue_token";
@@ -10,9 +15,15 @@ export { listBuilds } from "./list_builds";
 export { listStackGroups } from "./list_stack_groups";
 export { listTokens } from "./list_tokens";
 export { listUsers } from "./list_users";
+export { listWebhooks } from "./list_webhooks";
 export { oauthCliExchange } from "./oauth_cli_exchange";
+export { processDumps } from "./process_dumps";
+export { purgeBuild } from "./purge_build";
 export { removeUser } from "./remove_user";
 export { requestDumpDownload } from "./request_dump_download";
 export { requestPdbDownload } from "./request_pdb_download";
 export { requestPdbUpload } from "./request_pdb_upload";
+export { retireBuild } from "./retire_build";
 export { revokeToken } from "./revoke_token";
+export { setRetentionPolicy } from "./set_retention_policy";
+export { setStackGroupStatus } from "./set_stack_group_status";
@@ -3,11 +3,28 @@
 import { z } from "zod";
 import { callAction } from "@forte/react";
 
+const CrashKindSchema = z.enum(["native", "panic"]);
+
+const PanicDetailsSchema = z.object({
+    message: z.string(),
+    location: z.string(),
+  });
+
+const BreadcrumbSchema = z.object({
+    timestampUnixMs: z.number(),
+    category: z.string(),
+    message: z.string(),
+    data: z.record(z.string(), z.string()),
+  });
+
 const CrashContextSchema = z.object({
     buildId: z.string(),
     installId: z.string(),
     sessionUptimeSec: z.number(),
     logTail: z.string().optional(),
+    kind: CrashKindSchema,
+    panic: PanicDetailsSchema.optional(),
+    breadcrumbs: z.array(BreadcrumbSchema),
   });
 
 const UploadGrantSchema = z.object({
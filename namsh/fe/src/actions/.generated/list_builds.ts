@@ -9,6 +9,8 @@ const BuildSummarySchema = z.object({
     uploadedBy: z.number(),
     pdbUploaded: z.boolean(),
     pdbSize: z.number().optional(),
+    hits: z.number(),
+    installs: z.number(),
   });
 
 const InputSchema = z.object({
@@ -3,6 +3,30 @@
 import { z } from "zod";
 import { callAction } from "@forte/react";
 
+const StackGroupStatusSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Open"),
+  }),
+    z.object({
+    t: z.literal("Resolved"),
+    buildId: z.string(),
+  }),
+    z.object({
+    t: z.literal("Ignored"),
+  }),
+    z.object({
+    t: z.literal("Regressed"),
+    resolvedBuildId: z.string(),
+    buildId: z.string(),
+  })
+  ]);
+
+const PlatformDumpsSchema = z.object({
+    os: z.string(),
+    cpuArch: z.string(),
+    dumps: z.number(),
+  });
+
 const StackGroupSummarySchema = z.object({
     stackHash: z.string(),
     firstSeen: z.coerce.date(),
@@ -10,9 +34,15 @@ const StackGroupSummarySchema = z.object({
     count: z.number(),
     storedDumps: z.number(),
     latestBuildId: z.string(),
+    status: StackGroupStatusSchema,
+    buildIds: z.array(z.string()),
+    platforms: z.array(PlatformDumpsSchema),
   });
 
 const InputSchema = z.object({
+    status: z.string().optional(),
+    buildId: z.string().optional(),
+    os: z.string().optional(),
   });
 
 const OutputSchema = z.discriminatedUnion("t", [
@@ -22,6 +52,9 @@ const OutputSchema = z.discriminatedUnion("t", [
   }),
     z.object({
     t: z.literal("NotLoggedIn"),
+  }),
+    z.object({
+    t: z.literal("InvalidStatus"),
   }),
     z.object({
     t: z.literal("Error"),
@@ -0,0 +1,70 @@
+// Auto-generated from src/actions/list_webhooks.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const WebhookEventSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("NewGroup"),
+  }),
+    z.object({
+    t: z.literal("Regressed"),
+  }),
+    z.object({
+    t: z.literal("Spike"),
+  })
+  ]);
+
+const WebhookSummarySchema = z.object({
+    id: z.string(),
+    url: z.string(),
+    events: z.array(WebhookEventSchema),
+    spikeHitsPerHour: z.number().optional(),
+    createdAt: z.coerce.date(),
+  });
+
+const DeliveryStatusSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Pending"),
+  }),
+    z.object({
+    t: z.literal("Delivered"),
+  }),
+    z.object({
+    t: z.literal("Failed"),
+  })
+  ]);
+
+const DeliverySummarySchema = z.object({
+    deliveryId: z.string(),
+    webhookId: z.string(),
+    event: WebhookEventSchema,
+    stackHash: z.string(),
+    createdAt: z.coerce.date(),
+    status: DeliveryStatusSchema,
+    attempts: z.number(),
+    nextAttemptAt: z.coerce.date(),
+    lastResponse: z.string().optional(),
+  });
+
+const InputSchema = z.object({
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+    webhooks: z.array(WebhookSummarySchema),
+    deliveries: z.array(DeliverySummarySchema),
+  }),
+    z.object({
+    t: z.literal("NotLoggedIn"),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function listWebhooks(input: z.infer<typeof InputSchema>) {
+  return callAction("list_webhooks", input, OutputSchema);
+}
@@ -0,0 +1,32 @@
+// Auto-generated from src/actions/process_dumps.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const ProcessCountsSchema = z.object({
+    processed: z.number(),
+    failed: z.number(),
+    waiting: z.number(),
+    notMinidump: z.number(),
+  });
+
+const InputSchema = z.object({
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+    counts: ProcessCountsSchema,
+  }),
+    z.object({
+    t: z.literal("Unauthorized"),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function processDumps(input: z.infer<typeof InputSchema>) {
+  return callAction("process_dumps", input, OutputSchema);
+}
@@ -0,0 +1,34 @@
+// Auto-generated from src/actions/purge_build.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const InputSchema = z.object({
+    buildId: z.string(),
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+    deletedDumps: z.number(),
+  }),
+    z.object({
+    t: z.literal("Unauthorized"),
+  }),
+    z.object({
+    t: z.literal("NotFound"),
+  }),
+    z.object({
+    t: z.literal("Incomplete"),
+    deletedDumps: z.number(),
+    failedKeys: z.array(z.string()),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function purgeBuild(input: z.infer<typeof InputSchema>) {
+  return callAction("purge_build", input, OutputSchema);
+}
@@ -0,0 +1,29 @@
+// Auto-generated from src/actions/retire_build.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const InputSchema = z.object({
+    buildId: z.string(),
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+    retiredAt: z.coerce.date(),
+  }),
+    z.object({
+    t: z.literal("NotLoggedIn"),
+  }),
+    z.object({
+    t: z.literal("NotFound"),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function retireBuild(input: z.infer<typeof InputSchema>) {
+  return callAction("retire_build", input, OutputSchema);
+}
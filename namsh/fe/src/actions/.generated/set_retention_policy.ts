@@ -0,0 +1,27 @@
+// Auto-generated from src/actions/set_retention_policy.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const InputSchema = z.object({
+    maxDumpsPerGroup: z.number(),
+    maxDumpAgeDays: z.number().optional(),
+    retiredBuildKeepDays: z.number().optional(),
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+  }),
+    z.object({
+    t: z.literal("Unauthorized"),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function setRetentionPolicy(input: z.infer<typeof InputSchema>) {
+  return callAction("set_retention_policy", input, OutputSchema);
+}
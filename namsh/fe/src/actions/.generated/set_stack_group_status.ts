@@ -0,0 +1,55 @@
+// Auto-generated from src/actions/set_stack_group_status.rs
+
+import { z } from "zod";
+import { callAction } from "@forte/react";
+
+const StackGroupStatusSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Open"),
+  }),
+    z.object({
+    t: z.literal("Resolved"),
+    buildId: z.string(),
+  }),
+    z.object({
+    t: z.literal("Ignored"),
+  }),
+    z.object({
+    t: z.literal("Regressed"),
+    resolvedBuildId: z.string(),
+    buildId: z.string(),
+  })
+  ]);
+
+const InputSchema = z.object({
+    stackHash: z.string(),
+    status: z.string(),
+    buildId: z.string().optional(),
+  });
+
+const OutputSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+    status: StackGroupStatusSchema,
+  }),
+    z.object({
+    t: z.literal("NotLoggedIn"),
+  }),
+    z.object({
+    t: z.literal("NotFound"),
+  }),
+    z.object({
+    t: z.literal("BuildNotFound"),
+  }),
+    z.object({
+    t: z.literal("InvalidStatus"),
+  }),
+    z.object({
+    t: z.literal("Error"),
+    message: z.string(),
+  })
+  ]);
+
+export function setStackGroupStatus(input: z.infer<typeof InputSchema>) {
+  return callAction("set_stack_group_status", input, OutputSchema);
+}
@@ -8,6 +8,8 @@ export const BuildSummarySchema = z.object({
     uploadedBy: z.number(),
     pdbUploaded: z.boolean(),
     pdbSize: z.number().optional(),
+    hits: z.number(),
+    installs: z.number(),
   });
 
 export type BuildSummary = z.infer<typeof BuildSummarySchema>;
@@ -0,0 +1,55 @@
+// Auto-generated from src/pages/builds/[build_id]/mod.rs
+
+import { z } from "zod";
+
+export const GroupHitsSchema = z.object({
+    stackHash: z.string(),
+    hits: z.number(),
+    installs: z.number(),
+  });
+
+export type GroupHits = z.infer<typeof GroupHitsSchema>;
+
+export const BuildStatsSchema = z.object({
+    buildId: z.string(),
+    hits: z.number(),
+    installs: z.number(),
+    groups: z.array(GroupHitsSchema),
+  });
+
+export type BuildStats = z.infer<typeof BuildStatsSchema>;
+
+export const GroupChangeSchema = z.object({
+    stackHash: z.string(),
+    baseHits: z.number(),
+    hits: z.number(),
+  });
+
+export type GroupChange = z.infer<typeof GroupChangeSchema>;
+
+export const BuildDiffSchema = z.object({
+    new: z.array(GroupChangeSchema),
+    gone: z.array(GroupChangeSchema),
+    increased: z.array(GroupChangeSchema),
+  });
+
+export type BuildDiff = z.infer<typeof BuildDiffSchema>;
+
+export const PropsSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Ok"),
+    githubLogin: z.string(),
+    createdAt: z.coerce.date(),
+    stats: BuildStatsSchema,
+    baseBuildId: z.string().optional(),
+    diff: BuildDiffSchema.optional(),
+    builds: z.array(z.string()),
+  }),
+    z.object({
+    t: z.literal("NotFound"),
+    githubLogin: z.string(),
+    buildId: z.string(),
+  })
+  ]);
+
+export type Props = z.infer<typeof PropsSchema>;
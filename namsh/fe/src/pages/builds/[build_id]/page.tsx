@@ -0,0 +1,157 @@
+import type { GroupChange, Props } from "./.props";
+
+export default function BuildPage(props: Props) {
+    if (props.t === "NotFound") {
+        return (
+            <div style={{ maxWidth: 720, margin: "2rem auto", fontFamily: "system-ui" }}>
+                <Header githubLogin={props.githubLogin} />
+                <h1>Build not found</h1>
+                <p><code>{props.buildId}</code></p>
+                <p><a href="/builds">Back to builds</a></p>
+            </div>
+        );
+    }
+
+    const { stats, diff } = props;
+    return (
+        <div style={{ maxWidth: 1080, margin: "2rem auto", fontFamily: "system-ui" }}>
+            <Header githubLogin={props.githubLogin} />
+            <h1>Build <code>{stats.buildId}</code></h1>
+            <p>
+                <strong>{stats.hits}</strong> hits ·
+                <strong> {stats.installs}</strong> installs affected ·
+                registered {new Date(props.createdAt).toLocaleString()}
+            </p>
+            <p style={{ color: "#666" }}>
+                <a href={`/?build=${encodeURIComponent(stats.buildId)}`}>All crashes in this build</a>
+            </p>
+
+            <h2>Top stack groups</h2>
+            {stats.groups.length === 0 ? (
+                <p style={{ color: "#666" }}>No crashes received for this build.</p>
+            ) : (
+                <table style={{ width: "100%", borderCollapse: "collapse" }}>
+                    <thead>
+                        <tr>
+                            <th style={cell}>Stack hash</th>
+                            <th style={cell}>Hits</th>
+                            <th style={cell}>Installs affected</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {stats.groups.map((g) => (
+                            <tr key={g.stackHash}>
+                                <td style={cell}><IssueLink stackHash={g.stackHash} /></td>
+                                <td style={cell}>{g.hits}</td>
+                                <td style={cell}>{g.installs}</td>
+                            </tr>
+                        ))}
+                    </tbody>
+                </table>
+            )}
+
+            <h2 style={{ marginTop: 32 }}>Compare with another build</h2>
+            <form method="get" style={{ display: "flex", gap: 8, marginBottom: 16 }}>
+                <select name="base" defaultValue={props.baseBuildId ?? props.builds[0] ?? ""}>
+                    {props.builds.map((b) => (
+                        <option key={b} value={b}>{b}</option>
+                    ))}
+                </select>
+                <button type="submit" disabled={props.builds.length === 0}>Compare</button>
+            </form>
+            {diff && props.baseBuildId && (
+                <>
+                    <ChangeTable
+                        title={`New since ${props.baseBuildId}`}
+                        changes={diff.new}
+                        baseBuildId={props.baseBuildId}
+                        buildId={stats.buildId}
+                    />
+                    <ChangeTable
+                        title="Increased"
+                        changes={diff.increased}
+                        baseBuildId={props.baseBuildId}
+                        buildId={stats.buildId}
+                    />
+                    <ChangeTable
+                        title={`Gone since ${props.baseBuildId}`}
+                        changes={diff.gone}
+                        baseBuildId={props.baseBuildId}
+                        buildId={stats.buildId}
+                    />
+                </>
+            )}
+        </div>
+    );
+}
+
+function ChangeTable({
+    title,
+    changes,
+    baseBuildId,
+    buildId,
+}: {
+    title: string;
+    changes: GroupChange[];
+    baseBuildId: string;
+    buildId: string;
+}) {
+    return (
+        <>
+            <h3>{title} ({changes.length})</h3>
+            {changes.length === 0 ? (
+                <p style={{ color: "#666" }}>None.</p>
+            ) : (
+                <table style={{ width: "100%", borderCollapse: "collapse" }}>
+                    <thead>
+                        <tr>
+                            <th style={cell}>Stack hash</th>
+                            <th style={cell}>Hits in <code>{baseBuildId}</code></th>
+                            <th style={cell}>Hits in <code>{buildId}</code></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {changes.map((c) => (
+                            <tr key={c.stackHash}>
+                                <td style={cell}><IssueLink stackHash={c.stackHash} /></td>
+                                <td style={cell}>{c.baseHits}</td>
+                                <td style={cell}>{c.hits}</td>
+                            </tr>
+                        ))}
+                    </tbody>
+                </table>
+            )}
+        </>
+    );
+}
+
+function IssueLink({ stackHash }: { stackHash: string }) {
+    return (
+        <a href={`/issues/${encodeURIComponent(stackHash)}`}>
+            <code>{shortHash(stackHash)}</code>
+        </a>
+    );
+}
+
+function Header({ githubLogin }: { githubLogin: string }) {
+    return (
+        <nav style={{ display: "flex", gap: 16, marginBottom: 24, alignItems: "baseline" }}>
+            <a href="/"><strong>namsh</strong></a>
+            <a href="/">Crashes</a>
+            <a href="/builds">Builds</a>
+            <a href="/tokens">Tokens</a>
+            <a href="/webhooks">Webhooks</a>
+            <span style={{ marginLeft: "auto", color: "#666" }}>{githubLogin}</span>
+        </nav>
+    );
+}
+
+function shortHash(hash: string): string {
+    return hash.length > 12 ? `${hash.slice(0, 12)}…` : hash;
+}
+
+const cell: React.CSSProperties = {
+    padding: 8,
+    borderBottom: "1px solid #eee",
+    textAlign: "left",
+};
@@ -11,6 +11,8 @@ type BuildRow = {
     uploadedBy: number;
     pdbUploaded: boolean;
     pdbSize: number | null;
+    hits: number;
+    installs: number;
 };
 
 type IssuedKey = {
@@ -27,6 +29,8 @@ export default function BuildsPage(props: Props) {
             uploadedBy: b.uploadedBy,
             pdbUploaded: b.pdbUploaded,
             pdbSize: b.pdbSize ?? null,
+            hits: b.hits,
+            installs: b.installs,
         })),
     );
     const [newBuildId, setNewBuildId] = useState("");
@@ -46,6 +50,8 @@ export default function BuildsPage(props: Props) {
                     uploadedBy: b.uploadedBy,
                     pdbUploaded: b.pdbUploaded,
                     pdbSize: b.pdbSize ?? null,
+                    hits: b.hits,
+                    installs: b.installs,
                 })),
             );
         }
@@ -190,6 +196,8 @@ export default function BuildsPage(props: Props) {
                             <th style={cell}>Build id</th>
                             <th style={cell}>Created</th>
                             <th style={cell}>By</th>
+                            <th style={cell}>Hits</th>
+                            <th style={cell}>Installs affected</th>
                             <th style={cell}>PDB</th>
                             <th style={cell}></th>
                         </tr>
@@ -197,9 +205,15 @@ export default function BuildsPage(props: Props) {
                     <tbody>
                         {builds.map((b) => (
                             <tr key={b.buildId}>
-                                <td style={cell}><code>{b.buildId}</code></td>
+                                <td style={cell}>
+                                    <a href={`/builds/${encodeURIComponent(b.buildId)}`}>
+                                        <code>{b.buildId}</code>
+                                    </a>
+                                </td>
                                 <td style={cell}>{new Date(b.createdAt).toLocaleString()}</td>
                                 <td style={cell}>{b.uploadedBy}</td>
+                                <td style={cell}>{b.hits}</td>
+                                <td style={cell}>{b.installs}</td>
                                 <td style={cell}>
                                     {b.pdbUploaded
                                         ? `${b.pdbSize ?? "?"} bytes`
@@ -240,6 +254,7 @@ function Header({ githubLogin }: { githubLogin: string }) {
             <a href="/">Crashes</a>
             <a href="/builds">Builds</a>
             <a href="/tokens">Tokens</a>
+            <a href="/webhooks">Webhooks</a>
             <span style={{ marginLeft: "auto", color: "#666" }}>{githubLogin}</span>
         </nav>
     );
@@ -2,6 +2,34 @@
 
 import { z } from "zod";
 
+export const StackGroupStatusSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Open"),
+  }),
+    z.object({
+    t: z.literal("Resolved"),
+    buildId: z.string(),
+  }),
+    z.object({
+    t: z.literal("Ignored"),
+  }),
+    z.object({
+    t: z.literal("Regressed"),
+    resolvedBuildId: z.string(),
+    buildId: z.string(),
+  })
+  ]);
+
+export type StackGroupStatus = z.infer<typeof StackGroupStatusSchema>;
+
+export const PlatformDumpsSchema = z.object({
+    os: z.string(),
+    cpuArch: z.string(),
+    dumps: z.number(),
+  });
+
+export type PlatformDumps = z.infer<typeof PlatformDumpsSchema>;
+
 export const StackGroupRowSchema = z.object({
     stackHash: z.string(),
     firstSeen: z.coerce.date(),
@@ -9,6 +37,8 @@ export const StackGroupRowSchema = z.object({
     count: z.number(),
     storedDumps: z.number(),
     latestBuildId: z.string(),
+    status: StackGroupStatusSchema,
+    platforms: z.array(PlatformDumpsSchema),
   });
 
 export type StackGroupRow = z.infer<typeof StackGroupRowSchema>;
@@ -16,6 +46,11 @@ export type StackGroupRow = z.infer<typeof StackGroupRowSchema>;
 export const PropsSchema = z.object({
     githubLogin: z.string(),
     groups: z.array(StackGroupRowSchema),
+    statusFilter: z.string().optional(),
+    buildFilter: z.string().optional(),
+    builds: z.array(z.string()),
+    osFilter: z.string().optional(),
+    oses: z.array(z.string()),
   });
 
 export type Props = z.infer<typeof PropsSchema>;
@@ -1,20 +1,48 @@
-import type { Props } from "./.props";
+import type { PlatformDumps, Props, StackGroupStatus } from "./.props";
 
 export default function IndexPage(props: Props) {
     return (
         <div style={{ maxWidth: 1080, margin: "2rem auto", fontFamily: "system-ui" }}>
             <Header githubLogin={props.githubLogin} />
             <h1>Crashes</h1>
+            <form method="get" style={{ display: "flex", gap: 8, marginBottom: 16 }}>
+                <select name="status" defaultValue={props.statusFilter ?? ""}>
+                    <option value="">All statuses</option>
+                    <option value="open">Open (incl. regressed)</option>
+                    <option value="regressed">Regressed</option>
+                    <option value="resolved">Resolved</option>
+                    <option value="ignored">Ignored</option>
+                </select>
+                <select name="build" defaultValue={props.buildFilter ?? ""}>
+                    <option value="">All builds</option>
+                    {props.builds.map((b) => (
+                        <option key={b} value={b}>{b}</option>
+                    ))}
+                </select>
+                <select name="os" defaultValue={props.osFilter ?? ""}>
+                    <option value="">All platforms</option>
+                    {props.oses.map((os) => (
+                        <option key={os} value={os}>{os}</option>
+                    ))}
+                </select>
+                <button type="submit">Filter</button>
+            </form>
             {props.groups.length === 0 ? (
-                <p style={{ color: "#666" }}>No crashes received yet.</p>
+                <p style={{ color: "#666" }}>
+                    {props.statusFilter || props.buildFilter || props.osFilter
+                        ? "No crashes match the filter."
+                        : "No crashes received yet."}
+                </p>
             ) : (
                 <table style={{ width: "100%", borderCollapse: "collapse" }}>
                     <thead>
                         <tr>
                             <th style={cell}>Stack hash</th>
+                            <th style={cell}>Status</th>
                             <th style={cell}>Count</th>
                             <th style={cell}>Stored dumps</th>
                             <th style={cell}>Latest build</th>
+                            <th style={cell}>Platforms</th>
                             <th style={cell}>Last seen</th>
                             <th style={cell}>First seen</th>
                         </tr>
@@ -27,9 +55,11 @@ export default function IndexPage(props: Props) {
                                         <code>{shortHash(g.stackHash)}</code>
                                     </a>
                                 </td>
+                                <td style={cell}><StatusBadge status={g.status} /></td>
                                 <td style={cell}>{g.count}</td>
-                                <td style={cell}>{g.storedDumps} / 3</td>
+                                <td style={cell}>{g.storedDumps}</td>
                                 <td style={cell}><code>{g.latestBuildId}</code></td>
+                                <td style={cell}><Platforms platforms={g.platforms} /></td>
                                 <td style={cell}>{new Date(g.lastSeen).toLocaleString()}</td>
                                 <td style={cell}>{new Date(g.firstSeen).toLocaleString()}</td>
                             </tr>
@@ -41,6 +71,32 @@ export default function IndexPage(props: Props) {
     );
 }
 
+function StatusBadge({ status }: { status: StackGroupStatus }) {
+    switch (status.t) {
+        case "Open":
+            return <span>open</span>;
+        case "Resolved":
+            return <span style={{ color: "#2a7" }}>resolved in <code>{status.buildId}</code></span>;
+        case "Ignored":
+            return <span style={{ color: "#999" }}>ignored</span>;
+        case "Regressed":
+            return <span style={{ color: "crimson" }}>regressed in <code>{status.buildId}</code></span>;
+    }
+}
+
+function Platforms({ platforms }: { platforms: PlatformDumps[] }) {
+    if (platforms.length === 0) {
+        return <span style={{ color: "#999" }}>—</span>;
+    }
+    return (
+        <span>
+            {platforms
+                .map((p) => `${p.os} ${p.cpuArch} (${p.dumps})`)
+                .join(", ")}
+        </span>
+    );
+}
+
 function Header({ githubLogin }: { githubLogin: string }) {
     return (
         <nav style={{ display: "flex", gap: 16, marginBottom: 24, alignItems: "baseline" }}>
@@ -48,6 +104,7 @@ function Header({ githubLogin }: { githubLogin: string }) {
             <a href="/">Crashes</a>
             <a href="/builds">Builds</a>
             <a href="/tokens">Tokens</a>
+            <a href="/webhooks">Webhooks</a>
             <span style={{ marginLeft: "auto", color: "#666" }}>{githubLogin}</span>
         </nav>
     );
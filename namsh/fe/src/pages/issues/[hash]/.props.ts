@@ -2,24 +2,106 @@
 
 import { z } from "zod";
 
+export const CrashKindSchema = z.enum(["native", "panic"]);
+
+export type CrashKind = z.infer<typeof CrashKindSchema>;
+
+export const PanicDetailsSchema = z.object({
+    message: z.string(),
+    location: z.string(),
+  });
+
+export type PanicDetails = z.infer<typeof PanicDetailsSchema>;
+
+export const BreadcrumbSchema = z.object({
+    timestampUnixMs: z.number(),
+    category: z.string(),
+    message: z.string(),
+    data: z.record(z.string(), z.string()),
+  });
+
+export type Breadcrumb = z.infer<typeof BreadcrumbSchema>;
+
 export const CrashContextSchema = z.object({
     buildId: z.string(),
     installId: z.string(),
     sessionUptimeSec: z.number(),
     logTail: z.string().optional(),
+    kind: CrashKindSchema,
+    panic: PanicDetailsSchema.optional(),
+    breadcrumbs: z.array(BreadcrumbSchema),
   });
 
 export type CrashContext = z.infer<typeof CrashContextSchema>;
 
+export const StackGroupStatusSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Open"),
+  }),
+    z.object({
+    t: z.literal("Resolved"),
+    buildId: z.string(),
+  }),
+    z.object({
+    t: z.literal("Ignored"),
+  }),
+    z.object({
+    t: z.literal("Regressed"),
+    resolvedBuildId: z.string(),
+    buildId: z.string(),
+  })
+  ]);
+
+export type StackGroupStatus = z.infer<typeof StackGroupStatusSchema>;
+
+export const MinidumpInfoSchema = z.object({
+    os: z.string(),
+    osVersion: z.string(),
+    cpuArch: z.string(),
+    exceptionCode: z.number(),
+    exceptionName: z.string(),
+    crashingModule: z.string().optional(),
+  });
+
+export type MinidumpInfo = z.infer<typeof MinidumpInfoSchema>;
+
+export const DumpProcessingSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Pending"),
+  }),
+    z.object({
+    t: z.literal("Processed"),
+    info: MinidumpInfoSchema,
+  }),
+    z.object({
+    t: z.literal("Failed"),
+    reason: z.string(),
+  }),
+    z.object({
+    t: z.literal("NotMinidump"),
+  })
+  ]);
+
+export type DumpProcessing = z.infer<typeof DumpProcessingSchema>;
+
 export const DumpSummarySchema = z.object({
     dumpId: z.string(),
     buildId: z.string(),
     uploadedAt: z.coerce.date(),
     clientIp: z.string(),
+    processing: DumpProcessingSchema,
   });
 
 export type DumpSummary = z.infer<typeof DumpSummarySchema>;
 
+export const PlatformDumpsSchema = z.object({
+    os: z.string(),
+    cpuArch: z.string(),
+    dumps: z.number(),
+  });
+
+export type PlatformDumps = z.infer<typeof PlatformDumpsSchema>;
+
 export const PropsSchema = z.discriminatedUnion("t", [
     z.object({
     t: z.literal("Ok"),
@@ -29,6 +111,10 @@ export const PropsSchema = z.discriminatedUnion("t", [
     lastSeen: z.coerce.date(),
     count: z.number(),
     latestContext: CrashContextSchema,
+    status: StackGroupStatusSchema,
+    buildIds: z.array(z.string()),
+    platforms: z.array(PlatformDumpsSchema),
+    builds: z.array(z.string()),
     dumps: z.array(DumpSummarySchema),
   }),
     z.object({
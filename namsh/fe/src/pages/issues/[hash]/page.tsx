@@ -1,6 +1,7 @@
 import { useState } from "react";
-import type { Props } from "./.props";
+import type { DumpProcessing, Props, StackGroupStatus } from "./.props";
 import { requestDumpDownload } from "../../../actions/.generated/request_dump_download";
+import { setStackGroupStatus } from "../../../actions/.generated/set_stack_group_status";
 
 export default function IssuePage(props: Props) {
     if (props.t === "NotFound") {
@@ -22,6 +23,31 @@ type OkProps = Extract<Props, { t: "Ok" }>;
 function IssueDetail({ data }: { data: OkProps }) {
     const [error, setError] = useState<string | null>(null);
     const [busy, setBusy] = useState(false);
+    const [status, setStatus] = useState<StackGroupStatus>(data.status);
+    const [resolveBuildId, setResolveBuildId] = useState(data.builds[0] ?? "");
+
+    async function onSetStatus(next: "open" | "resolved" | "ignored") {
+        if (busy) return;
+        setBusy(true);
+        const res = await setStackGroupStatus({
+            stack_hash: data.stackHash,
+            status: next,
+            build_id: next === "resolved" ? resolveBuildId : undefined,
+        });
+        setBusy(false);
+        if (res.t === "Ok") {
+            setStatus(res.status);
+            setError(null);
+        } else if (res.t === "BuildNotFound") {
+            setError(`unknown build ${resolveBuildId}`);
+        } else if (res.t === "Error") {
+            setError(res.message);
+        } else if (res.t === "NotLoggedIn") {
+            setError("not signed in");
+        } else {
+            setError(res.t);
+        }
+    }
 
     async function onDownload(dumpId: string) {
         if (busy) return;
@@ -48,7 +74,52 @@ function IssueDetail({ data }: { data: OkProps }) {
                 last seen {new Date(data.lastSeen).toLocaleString()}
             </p>
 
-            <h2>Stored dumps ({data.dumps.length} / 3)</h2>
+            <h2>Status</h2>
+            <p><StatusLine status={status} /></p>
+            <div style={{ display: "flex", gap: 8, alignItems: "center" }}>
+                <select
+                    value={resolveBuildId}
+                    onChange={(e) => setResolveBuildId(e.target.value)}
+                    disabled={busy}
+                >
+                    {data.builds.map((b) => (
+                        <option key={b} value={b}>{b}</option>
+                    ))}
+                </select>
+                <button onClick={() => onSetStatus("resolved")} disabled={busy || !resolveBuildId}>
+                    Resolve in build
+                </button>
+                <button onClick={() => onSetStatus("ignored")} disabled={busy || status.t === "Ignored"}>
+                    Ignore
+                </button>
+                <button onClick={() => onSetStatus("open")} disabled={busy || status.t === "Open"}>
+                    Reopen
+                </button>
+            </div>
+            <p>
+                Seen in builds:{" "}
+                {(data.buildIds.length > 0 ? data.buildIds : [ctx.buildId]).map((b) => (
+                    <a key={b} href={`/?build=${encodeURIComponent(b)}`} style={{ marginRight: 8 }}>
+                        <code>{b}</code>
+                    </a>
+                ))}
+            </p>
+            {data.platforms.length > 0 && (
+                <p>
+                    Platforms:{" "}
+                    {data.platforms.map((p) => (
+                        <a
+                            key={`${p.os} ${p.cpuArch}`}
+                            href={`/?os=${encodeURIComponent(p.os)}`}
+                            style={{ marginRight: 8 }}
+                        >
+                            {p.os} {p.cpuArch} ({p.dumps})
+                        </a>
+                    ))}
+                </p>
+            )}
+
+            <h2>Stored dumps ({data.dumps.length})</h2>
             {data.dumps.length === 0 ? (
                 <p>No dumps stored.</p>
             ) : (
@@ -59,6 +130,7 @@ function IssueDetail({ data }: { data: OkProps }) {
                             <th style={cell}>Build</th>
                             <th style={cell}>Uploaded at</th>
                             <th style={cell}>Client IP</th>
+                            <th style={cell}>Minidump</th>
                             <th style={cell}></th>
                         </tr>
                     </thead>
@@ -69,9 +141,10 @@ function IssueDetail({ data }: { data: OkProps }) {
                                 <td style={cell}><code>{d.buildId}</code></td>
                                 <td style={cell}>{new Date(d.uploadedAt).toLocaleString()}</td>
                                 <td style={cell}>{d.clientIp}</td>
+                                <td style={cell}><ProcessingInfo processing={d.processing} /></td>
                                 <td style={cell}>
                                     <button onClick={() => onDownload(d.dumpId)} disabled={busy}>
-                                        Download .dmp
+                                        Download {ctx.kind === "panic" ? ".panic" : ".dmp"}
                                     </button>
                                 </td>
                             </tr>
@@ -85,10 +158,46 @@ function IssueDetail({ data }: { data: OkProps }) {
                 <tbody>
                     <Field label="build_id" value={ctx.buildId} />
                     <Field label="install_id" value={ctx.installId} />
+                    <Field label="kind" value={ctx.kind} />
                     <Field label="session_uptime_sec" value={String(ctx.sessionUptimeSec)} />
                 </tbody>
             </table>
 
+            {ctx.panic && (
+                <>
+                    <h2 style={{ marginTop: 24 }}>Panic</h2>
+                    <p><code>{ctx.panic.location}</code></p>
+                    <pre style={{
+                        background: "#f6f6f6",
+                        padding: 12,
+                        overflow: "auto",
+                        fontSize: 12,
+                    }}>{ctx.panic.message}</pre>
+                </>
+            )}
+
+            {ctx.breadcrumbs.length > 0 && (
+                <>
+                    <h2 style={{ marginTop: 24 }}>Breadcrumbs</h2>
+                    <table style={{ width: "100%", borderCollapse: "collapse" }}>
+                        <tbody>
+                            {ctx.breadcrumbs.map((b, i) => (
+                                <tr key={i}>
+                                    <td style={cell}>{new Date(b.timestampUnixMs).toLocaleTimeString()}</td>
+                                    <td style={cell}><code>{b.category}</code></td>
+                                    <td style={cell}>{b.message}</td>
+                                    <td style={cell}>
+                                        {Object.entries(b.data).map(([k, v]) => (
+                                            <code key={k} style={{ marginRight: 8 }}>{k}={v}</code>
+                                        ))}
+                                    </td>
+                                </tr>
+                            ))}
+                        </tbody>
+                    </table>
+                </>
+            )}
+
             {ctx.logTail && (
                 <>
                     <h2 style={{ marginTop: 24 }}>log_tail</h2>
@@ -109,6 +218,28 @@ function IssueDetail({ data }: { data: OkProps }) {
     );
 }
 
+function StatusLine({ status }: { status: StackGroupStatus }) {
+    switch (status.t) {
+        case "Open":
+            return <span>Open</span>;
+        case "Resolved":
+            return (
+                <span style={{ color: "#2a7" }}>
+                    Resolved in <code>{status.buildId}</code>; hits from later builds reopen it.
+                </span>
+            );
+        case "Ignored":
+            return <span style={{ color: "#999" }}>Ignored; hits are still counted.</span>;
+        case "Regressed":
+            return (
+                <span style={{ color: "crimson" }}>
+                    Regressed: resolved in <code>{status.resolvedBuildId}</code>, hit again
+                    by <code>{status.buildId}</code>.
+                </span>
+            );
+    }
+}
+
 function Field({ label, value }: { label: string; value: string }) {
     return (
         <tr>
@@ -118,6 +249,32 @@ function Field({ label, value }: { label: string; value: string }) {
     );
 }
 
+function ProcessingInfo({ processing }: { processing: DumpProcessing }) {
+    switch (processing.t) {
+        case "Pending":
+            return <span style={{ color: "#999" }}>not processed</span>;
+        case "NotMinidump":
+            return <span style={{ color: "#999" }}>panic report</span>;
+        case "Failed":
+            return <span style={{ color: "crimson" }}>{processing.reason}</span>;
+        case "Processed": {
+            const info = processing.info;
+            return (
+                <span>
+                    {info.os} {info.osVersion} {info.cpuArch}
+                    <br />
+                    <code>{info.exceptionName}</code> (0x{info.exceptionCode.toString(16)})
+                    {info.crashingModule && (
+                        <>
+                            {" "}in <code>{info.crashingModule}</code>
+                        </>
+                    )}
+                </span>
+            );
+        }
+    }
+}
+
 function Header({ githubLogin }: { githubLogin: string }) {
     return (
         <nav style={{ display: "flex", gap: 16, marginBottom: 24, alignItems: "baseline" }}>
@@ -125,6 +282,7 @@ function Header({ githubLogin }: { githubLogin: string }) {
             <a href="/">Crashes</a>
             <a href="/builds">Builds</a>
             <a href="/tokens">Tokens</a>
+            <a href="/webhooks">Webhooks</a>
             <span style={{ marginLeft: "auto", color: "#666" }}>{githubLogin}</span>
         </nav>
     );
@@ -162,6 +162,7 @@ function Header({ githubLogin }: { githubLogin: string }) {
             <a href="/">Crashes</a>
             <a href="/builds">Builds</a>
             <a href="/tokens">Tokens</a>
+            <a href="/webhooks">Webhooks</a>
             <span style={{ marginLeft: "auto", color: "#666" }}>{githubLogin}</span>
         </nav>
     );
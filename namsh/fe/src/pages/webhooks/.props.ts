@@ -0,0 +1,63 @@
+// Auto-generated from src/pages/webhooks/mod.rs
+
+import { z } from "zod";
+
+export const WebhookEventSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("NewGroup"),
+  }),
+    z.object({
+    t: z.literal("Regressed"),
+  }),
+    z.object({
+    t: z.literal("Spike"),
+  })
+  ]);
+
+export type WebhookEvent = z.infer<typeof WebhookEventSchema>;
+
+export const WebhookSummarySchema = z.object({
+    id: z.string(),
+    url: z.string(),
+    events: z.array(WebhookEventSchema),
+    spikeHitsPerHour: z.number().optional(),
+    createdAt: z.coerce.date(),
+  });
+
+export type WebhookSummary = z.infer<typeof WebhookSummarySchema>;
+
+export const DeliveryStatusSchema = z.discriminatedUnion("t", [
+    z.object({
+    t: z.literal("Pending"),
+  }),
+    z.object({
+    t: z.literal("Delivered"),
+  }),
+    z.object({
+    t: z.literal("Failed"),
+  })
+  ]);
+
+export type DeliveryStatus = z.infer<typeof DeliveryStatusSchema>;
+
+export const DeliverySummarySchema = z.object({
+    deliveryId: z.string(),
+    webhookId: z.string(),
+    event: WebhookEventSchema,
+    stackHash: z.string(),
+    createdAt: z.coerce.date(),
+    status: DeliveryStatusSchema,
+    attempts: z.number(),
+    nextAttemptAt: z.coerce.date(),
+    lastResponse: z.string().optional(),
+  });
+
+export type DeliverySummary = z.infer<typeof DeliverySummarySchema>;
+
+export const PropsSchema = z.object({
+    githubLogin: z.string(),
+    webhooks: z.array(WebhookSummarySchema),
+    deliveries: z.array(DeliverySummarySchema),
+  });
+
+export type Props = z.infer<typeof PropsSchema>;
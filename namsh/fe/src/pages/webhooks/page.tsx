@@ -0,0 +1,293 @@
+import { useState } from "react";
+import type { DeliverySummary, Props, WebhookEvent, WebhookSummary } from "./.props";
+import { createWebhook } from "../../actions/.generated/create_webhook";
+import { deleteWebhook } from "../../actions/.generated/delete_webhook";
+import { listWebhooks } from "../../actions/.generated/list_webhooks";
+
+const EVENTS: { t: WebhookEvent["t"]; label: string }[] = [
+    { t: "NewGroup", label: "New group" },
+    { t: "Regressed", label: "Regression" },
+    { t: "Spike", label: "Spike" },
+];
+
+type IssuedSecret = {
+    url: string;
+    secretHex: string;
+};
+
+export default function WebhooksPage(props: Props) {
+    const [webhooks, setWebhooks] = useState<WebhookSummary[]>(props.webhooks);
+    const [deliveries, setDeliveries] = useState<DeliverySummary[]>(props.deliveries);
+    const [url, setUrl] = useState("");
+    const [events, setEvents] = useState<WebhookEvent["t"][]>(["NewGroup", "Regressed"]);
+    const [spikeHitsPerHour, setSpikeHitsPerHour] = useState("100");
+    const [issued, setIssued] = useState<IssuedSecret | null>(null);
+    const [busy, setBusy] = useState(false);
+    const [error, setError] = useState<string | null>(null);
+
+    async function refresh() {
+        const res = await listWebhooks({});
+        if (res.t === "Ok") {
+            setWebhooks(res.webhooks);
+            setDeliveries(res.deliveries);
+            setError(null);
+        } else if (res.t === "Error") {
+            setError(res.message);
+        } else {
+            setError("Not signed in.");
+        }
+    }
+
+    function toggleEvent(t: WebhookEvent["t"]) {
+        setEvents((prev) => (prev.includes(t) ? prev.filter((e) => e !== t) : [...prev, t]));
+    }
+
+    async function onCreate(e: React.FormEvent) {
+        e.preventDefault();
+        if (!url.trim() || busy) return;
+        setBusy(true);
+        const res = await createWebhook({
+            url: url.trim(),
+            events: events.map((t) => ({ t })),
+            spikeHitsPerHour: events.includes("Spike") ? Number(spikeHitsPerHour) : undefined,
+        });
+        setBusy(false);
+        switch (res.t) {
+            case "Ok":
+                setIssued({ url: url.trim(), secretHex: res.secretHex });
+                setUrl("");
+                refresh();
+                break;
+            case "InvalidUrl":
+                setError("URL must start with http:// or https://.");
+                break;
+            case "NoEvents":
+                setError("Pick at least one event.");
+                break;
+            case "InvalidSpikeThreshold":
+                setError("Spike threshold must be a positive number of hits per hour.");
+                break;
+            case "Error":
+                setError(res.message);
+                break;
+            default:
+                setError("Not signed in.");
+        }
+    }
+
+    async function onDelete(id: string) {
+        if (busy) return;
+        if (!confirm("Delete this webhook? Its pending deliveries will fail.")) return;
+        setBusy(true);
+        const res = await deleteWebhook({ id });
+        setBusy(false);
+        if (res.t === "Ok" || res.t === "NotFound") {
+            refresh();
+        } else if (res.t === "Error") {
+            setError(res.message);
+        } else {
+            setError("Not signed in.");
+        }
+    }
+
+    const urlById = new Map(webhooks.map((w) => [w.id, w.url]));
+
+    return (
+        <div style={{ maxWidth: 960, margin: "2rem auto", fontFamily: "system-ui" }}>
+            <Header githubLogin={props.githubLogin} />
+            <h1>Webhooks</h1>
+            <p style={{ color: "#666" }}>
+                Each event is POSTed as JSON with an <code>X-Namsh-Signature</code> header:
+                hex HMAC-SHA256 of the body, keyed by the webhook's secret.
+            </p>
+
+            <h2>Add a webhook</h2>
+            <form onSubmit={onCreate} style={{ display: "grid", gap: 8 }}>
+                <input
+                    type="url"
+                    placeholder="https://example.com/namsh-hook"
+                    value={url}
+                    onChange={(e) => setUrl(e.target.value)}
+                    style={{ padding: 6 }}
+                    disabled={busy}
+                />
+                <div style={{ display: "flex", gap: 16, alignItems: "center" }}>
+                    {EVENTS.map((e) => (
+                        <label key={e.t}>
+                            <input
+                                type="checkbox"
+                                checked={events.includes(e.t)}
+                                onChange={() => toggleEvent(e.t)}
+                                disabled={busy}
+                            />{" "}
+                            {e.label}
+                        </label>
+                    ))}
+                    {events.includes("Spike") && (
+                        <label>
+                            at{" "}
+                            <input
+                                type="number"
+                                min={1}
+                                value={spikeHitsPerHour}
+                                onChange={(e) => setSpikeHitsPerHour(e.target.value)}
+                                style={{ width: 80, padding: 4 }}
+                                disabled={busy}
+                            />{" "}
+                            hits/hour
+                        </label>
+                    )}
+                    <button
+                        type="submit"
+                        disabled={busy || !url.trim() || events.length === 0}
+                        style={{ marginLeft: "auto" }}
+                    >
+                        Add
+                    </button>
+                </div>
+            </form>
+
+            {issued && (
+                <div
+                    style={{
+                        marginTop: 12,
+                        padding: 12,
+                        border: "1px solid #888",
+                        background: "#fffbe6",
+                    }}
+                >
+                    <p>
+                        <strong>Webhook added for {issued.url}.</strong> Configure the
+                        receiver with this secret — it will not be shown again.
+                    </p>
+                    <code style={{ wordBreak: "break-all" }}>{issued.secretHex}</code>
+                    <div style={{ marginTop: 8 }}>
+                        <button onClick={() => setIssued(null)}>Done</button>
+                    </div>
+                </div>
+            )}
+
+            <h2 style={{ marginTop: 32 }}>Webhooks</h2>
+            {webhooks.length === 0 ? (
+                <p>No webhooks yet.</p>
+            ) : (
+                <table style={{ width: "100%", borderCollapse: "collapse" }}>
+                    <thead>
+                        <tr>
+                            <th style={cell}>URL</th>
+                            <th style={cell}>Events</th>
+                            <th style={cell}>Created</th>
+                            <th style={cell}></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {webhooks.map((w) => (
+                            <tr key={w.id}>
+                                <td style={cell}>
+                                    <code>{w.url}</code>
+                                </td>
+                                <td style={cell}>
+                                    {w.events
+                                        .map((e) =>
+                                            e.t === "Spike"
+                                                ? `Spike (${w.spikeHitsPerHour}/h)`
+                                                : eventLabel(e.t),
+                                        )
+                                        .join(", ")}
+                                </td>
+                                <td style={cell}>{w.createdAt.toLocaleString()}</td>
+                                <td style={cell}>
+                                    <button onClick={() => onDelete(w.id)} disabled={busy}>
+                                        Delete
+                                    </button>
+                                </td>
+                            </tr>
+                        ))}
+                    </tbody>
+                </table>
+            )}
+
+            <h2 style={{ marginTop: 32 }}>Recent deliveries</h2>
+            {deliveries.length === 0 ? (
+                <p>Nothing delivered yet.</p>
+            ) : (
+                <table style={{ width: "100%", borderCollapse: "collapse" }}>
+                    <thead>
+                        <tr>
+                            <th style={cell}>Queued</th>
+                            <th style={cell}>Event</th>
+                            <th style={cell}>Group</th>
+                            <th style={cell}>Webhook</th>
+                            <th style={cell}>Status</th>
+                            <th style={cell}>Attempts</th>
+                            <th style={cell}>Last response</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {deliveries.map((d) => (
+                            <tr key={d.deliveryId}>
+                                <td style={cell}>{d.createdAt.toLocaleString()}</td>
+                                <td style={cell}>{eventLabel(d.event.t)}</td>
+                                <td style={cell}>
+                                    <a href={`/issues/${d.stackHash}`}>
+                                        <code>{d.stackHash.slice(0, 12)}</code>
+                                    </a>
+                                </td>
+                                <td style={cell}>
+                                    <code>{urlById.get(d.webhookId) ?? "(deleted)"}</code>
+                                </td>
+                                <td style={cell}>
+                                    <DeliveryStatusBadge delivery={d} />
+                                </td>
+                                <td style={cell}>{d.attempts}</td>
+                                <td style={cell}>{d.lastResponse ?? "—"}</td>
+                            </tr>
+                        ))}
+                    </tbody>
+                </table>
+            )}
+
+            {error && (
+                <p style={{ color: "crimson", marginTop: 16 }}>{error}</p>
+            )}
+        </div>
+    );
+}
+
+function eventLabel(t: WebhookEvent["t"]) {
+    return EVENTS.find((e) => e.t === t)?.label ?? t;
+}
+
+function DeliveryStatusBadge({ delivery }: { delivery: DeliverySummary }) {
+    switch (delivery.status.t) {
+        case "Delivered":
+            return <span style={{ color: "seagreen" }}>delivered</span>;
+        case "Failed":
+            return <span style={{ color: "crimson" }}>failed</span>;
+        case "Pending":
+            return (
+                <span style={{ color: "#b8860b" }}>
+                    pending, next {delivery.nextAttemptAt.toLocaleTimeString()}
+                </span>
+            );
+    }
+}
+
+function Header({ githubLogin }: { githubLogin: string }) {
+    return (
+        <nav style={{ display: "flex", gap: 16, marginBottom: 24, alignItems: "baseline" }}>
+            <a href="/"><strong>namsh</strong></a>
+            <a href="/">Crashes</a>
+            <a href="/builds">Builds</a>
+            <a href="/tokens">Tokens</a>
+            <a href="/webhooks">Webhooks</a>
+            <span style={{ marginLeft: "auto", color: "#666" }}>{githubLogin}</span>
+        </nav>
+    );
+}
+
+const cell: React.CSSProperties = {
+    padding: 8,
+    borderBottom: "1px solid #eee",
+    textAlign: "left",
+};
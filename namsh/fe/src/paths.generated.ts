@@ -4,8 +4,10 @@ export const paths = {
   "/oauth/cli/authorize": () => "/oauth/cli/authorize",
   "/oauth/github/callback": () => "/oauth/github/callback",
   "/builds": () => "/builds",
+  "/builds/:build_id": ({build_id}: {build_id: string}) => `/builds/${build_id}`,
   "/": () => "/",
   "/issues/:hash": ({hash}: {hash: string}) => `/issues/${hash}`,
   "/tokens": () => "/tokens",
+  "/webhooks": () => "/webhooks",
   "/login": () => "/login",
 } as const;
@@ -0,0 +1,781 @@
+//! In-process stand-in for namsh's crash intake, so the crash reporter's
+//! upload, retry and rate-limit handling can be tested without a deployment.
+//!
+//! [`Emulator::start`] serves two routes on `127.0.0.1`:
+//!
+//! - `POST /__forte_action/intake_crash` — the checks and group bookkeeping of
+//!   `rs/src/actions/intake_crash.rs`, in the same order, answered the way
+//!   forte serializes its `Output` (`"t"` tag, camelCase fields).
+//! - `PUT /r2/<key>?expires=<secs>` — the presigned R2 upload `intake_crash`
+//!   grants. The body lands on the matching [`Dump`].
+//!
+//! Everything lives in memory and dies with the [`Emulator`]. Time stands
+//! still until [`Emulator::advance`] moves it, so rate limits and URL expiry
+//! don't depend on how fast the test runs. Group status, `build_hits`,
+//! webhooks, dump processing and retention are not emulated.
+//!
+//! The client IP comes from the proxy headers namsh reads; without them —
+//! the crash reporter sends none, Cloudflare adds them in production — the
+//! connection's peer address stands in.
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const INTAKE_PATH: &str = "/__forte_action/intake_crash";
+const UPLOAD_PATH_PREFIX: &str = "/r2/";
+
+/// namsh's limits; see the constants at the top of `intake_crash.rs` and
+/// `RetentionPolicyDoc::default`.
+#[derive(Clone, Debug)]
+pub struct Limits {
+    pub max_dumps_per_group: usize,
+    pub rate_limit_per_minute: usize,
+    pub rate_limit_per_24h: usize,
+    pub log_tail_max_bytes: usize,
+    pub breadcrumbs_max_count: usize,
+    pub breadcrumbs_max_bytes: usize,
+    pub presigned_put_expires: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_dumps_per_group: 3,
+            rate_limit_per_minute: 1,
+            rate_limit_per_24h: 5,
+            log_tail_max_bytes: 64 * 1024,
+            breadcrumbs_max_count: 64,
+            breadcrumbs_max_bytes: 32 * 1024,
+            presigned_put_expires: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Mirrors namsh's `docs.rs::CrashContext`. The signature covers
+/// `serde_json::to_vec` of this, so a field the reporter adds without namsh
+/// shows up here as `InvalidSignature`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CrashContext {
+    pub build_id: String,
+    pub install_id: String,
+    pub session_uptime_sec: u64,
+    pub log_tail: Option<String>,
+    #[serde(default)]
+    pub kind: CrashKind,
+    #[serde(default)]
+    pub panic: Option<PanicDetails>,
+    #[serde(default)]
+    pub breadcrumbs: Vec<Breadcrumb>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum CrashKind {
+    #[default]
+    Native,
+    Panic,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PanicDetails {
+    pub message: String,
+    pub location: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Breadcrumb {
+    pub timestamp_unix_ms: u64,
+    pub category: String,
+    pub message: String,
+    pub data: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct IntakeInput {
+    build_id: String,
+    stack_hash: String,
+    context: CrashContext,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadGrant {
+    pub dump_id: String,
+    pub presigned_put_url: String,
+}
+
+/// `intake_crash`'s `Output`, minus `Error`: nothing here can fail that way.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+#[serde(tag = "t")]
+pub enum IntakeOutput {
+    Ok { upload: Option<UploadGrant> },
+    UnknownBuild,
+    InvalidSignature,
+    RateLimited,
+    PayloadTooLarge,
+}
+
+#[derive(Clone, Debug)]
+pub struct StackGroup {
+    pub stack_hash: String,
+    pub count: u64,
+    pub dump_ids: Vec<String>,
+    pub latest_context: CrashContext,
+}
+
+#[derive(Clone, Debug)]
+pub struct Dump {
+    pub dump_id: String,
+    pub stack_hash: String,
+    pub build_id: String,
+    pub r2_key: String,
+    pub context: CrashContext,
+    pub client_ip: String,
+    /// The uploaded payload; `None` until the presigned PUT arrives.
+    pub object: Option<Vec<u8>>,
+}
+
+#[derive(Default)]
+struct State {
+    limits: Limits,
+    /// Time since the emulator started, as far as it's concerned.
+    now: Duration,
+    /// Build id to hex HMAC key.
+    builds: BTreeMap<String, String>,
+    recent_requests: BTreeMap<String, Vec<Duration>>,
+    groups: BTreeMap<String, StackGroup>,
+    dumps: Vec<Dump>,
+    intake_outputs: Vec<IntakeOutput>,
+    connection_errors: Vec<String>,
+    failing_intakes: usize,
+    failing_uploads: usize,
+    next_dump_id: u64,
+}
+
+pub struct Emulator {
+    url: String,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+    addr: SocketAddr,
+    server: Option<JoinHandle<()>>,
+}
+
+impl Emulator {
+    pub fn start() -> io::Result<Self> {
+        Self::start_with(Limits::default())
+    }
+
+    pub fn start_with(limits: Limits) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let url = format!("http://{addr}");
+        let state = Arc::new(Mutex::new(State {
+            limits,
+            ..Default::default()
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let server = std::thread::spawn({
+            let url = url.clone();
+            let state = state.clone();
+            let shutdown = shutdown.clone();
+            move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let Ok(mut stream) = stream else { continue };
+                    // Recorded before `stream` drops, so a client that sees
+                    // the connection close also sees the error.
+                    if let Err(e) = serve(&mut stream, &url, &state) {
+                        state.lock().unwrap().connection_errors.push(e.to_string());
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            url,
+            state,
+            shutdown,
+            addr,
+            server: Some(server),
+        })
+    }
+
+    /// What to put in the crash reporter's `Config::namsh_url`.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Registers a build the way `request_pdb_upload` does and returns its HMAC key.
+    /// The key is derived from the build id, so runs are reproducible.
+    pub fn register_build(&self, build_id: &str) -> String {
+        let key_hex = hex::encode(Sha256::digest(format!("namsh-intake-emulator/{build_id}")));
+        self.state()
+            .builds
+            .insert(build_id.to_string(), key_hex.clone());
+        key_hex
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.state().now += by;
+    }
+
+    /// The next `count` intakes get an HTTP 500 before any check runs, as
+    /// when the forte runtime or the DB is down.
+    pub fn fail_next_intakes(&self, count: usize) {
+        self.state().failing_intakes = count;
+    }
+
+    /// The next `count` presigned PUTs get an HTTP 500 and store nothing.
+    pub fn fail_next_uploads(&self, count: usize) {
+        self.state().failing_uploads = count;
+    }
+
+    pub fn groups(&self) -> Vec<StackGroup> {
+        self.state().groups.values().cloned().collect()
+    }
+
+    pub fn group(&self, stack_hash: &str) -> Option<StackGroup> {
+        self.state().groups.get(stack_hash).cloned()
+    }
+
+    /// In intake order.
+    pub fn dumps(&self) -> Vec<Dump> {
+        self.state().dumps.clone()
+    }
+
+    /// Every answered intake in order. Injected failures never got an answer
+    /// and aren't listed.
+    pub fn intake_outputs(&self) -> Vec<IntakeOutput> {
+        self.state().intake_outputs.clone()
+    }
+
+    /// Connections that ended without a response, e.g. a malformed request or
+    /// a client that hung up mid-body, in order.
+    pub fn connection_errors(&self) -> Vec<String> {
+        self.state().connection_errors.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl Drop for Emulator {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wakes the blocked `accept` so the thread sees the flag.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(server) = self.server.take() {
+            let _ = server.join();
+        }
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: String,
+    /// Names lowercased.
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    peer: IpAddr,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn json(output: &IntakeOutput) -> Self {
+        Self {
+            status: 200,
+            content_type: "application/json",
+            body: serde_json::to_vec(output).expect("output serializes"),
+        }
+    }
+
+    fn text(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain",
+            body: body.as_bytes().to_vec(),
+        }
+    }
+}
+
+fn serve(stream: &mut TcpStream, url: &str, state: &Mutex<State>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let request = read_request(stream)?;
+    let mut state = state.lock().unwrap();
+    let response = match (request.method.as_str(), request.path.as_str()) {
+        ("POST", INTAKE_PATH) => intake(&mut state, url, &request),
+        ("PUT", path) if path.starts_with(UPLOAD_PATH_PREFIX) => upload(&mut state, &request),
+        _ => Response::text(404, "not found"),
+    };
+    drop(state);
+    write_response(stream, &response)
+}
+
+/// Only what reqwest sends for a byte body: one request with a
+/// `Content-Length`, no chunking, no keep-alive.
+fn read_request(stream: &TcpStream) -> io::Result<Request> {
+    let peer = stream.peer_addr()?.ip();
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "bad request line",
+        ));
+    };
+    let method = method.to_string();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let (path, query) = (path.to_string(), query.to_string());
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    let content_length = headers
+        .iter()
+        .find(|(n, _)| n == "content-length")
+        .and_then(|(_, v)| v.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method,
+        path,
+        query,
+        headers,
+        body,
+        peer,
+    })
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {reason}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+fn intake(state: &mut State, url: &str, request: &Request) -> Response {
+    if state.failing_intakes > 0 {
+        state.failing_intakes -= 1;
+        return Response::text(500, "injected failure");
+    }
+    // forte rejects a body that doesn't match `Input` before the handler runs.
+    let Ok(input) = serde_json::from_slice::<IntakeInput>(&request.body) else {
+        return Response::text(400, "invalid input");
+    };
+    let output = intake_output(state, url, request, input);
+    state.intake_outputs.push(output.clone());
+    Response::json(&output)
+}
+
+fn intake_output(
+    state: &mut State,
+    url: &str,
+    request: &Request,
+    input: IntakeInput,
+) -> IntakeOutput {
+    if request.header("x-namsh-build-id") != Some(input.build_id.as_str()) {
+        return IntakeOutput::InvalidSignature;
+    }
+    let Some(Ok(signature)) = request
+        .header("x-namsh-signature")
+        .map(|s| hex::decode(s.trim()))
+    else {
+        return IntakeOutput::InvalidSignature;
+    };
+
+    let limits = &state.limits;
+    if input
+        .context
+        .log_tail
+        .as_ref()
+        .is_some_and(|tail| tail.len() > limits.log_tail_max_bytes)
+    {
+        return IntakeOutput::PayloadTooLarge;
+    }
+    if !breadcrumbs_within_limit(&input.context.breadcrumbs, limits) {
+        return IntakeOutput::PayloadTooLarge;
+    }
+
+    let Some(key_hex) = state.builds.get(&input.build_id) else {
+        return IntakeOutput::UnknownBuild;
+    };
+    if !verify_hmac(
+        key_hex,
+        &input.build_id,
+        &input.stack_hash,
+        &input.context,
+        &signature,
+    ) {
+        return IntakeOutput::InvalidSignature;
+    }
+
+    let client_ip = client_ip(request);
+    let now = state.now;
+    let recent = state.recent_requests.entry(client_ip.clone()).or_default();
+    recent.retain(|t| within(now, *t, Duration::from_secs(24 * 3600)));
+    let recent_minute = recent
+        .iter()
+        .filter(|t| within(now, **t, Duration::from_secs(60)))
+        .count();
+    if recent_minute >= limits.rate_limit_per_minute || recent.len() >= limits.rate_limit_per_24h {
+        return IntakeOutput::RateLimited;
+    }
+    recent.push(now);
+
+    let dumps_in_group = state
+        .groups
+        .get(&input.stack_hash)
+        .map_or(0, |g| g.dump_ids.len());
+    let upload = if dumps_in_group < limits.max_dumps_per_group {
+        state.next_dump_id += 1;
+        let dump_id = format!("dump-{}", state.next_dump_id);
+        let extension = match input.context.kind {
+            CrashKind::Native => "dmp",
+            CrashKind::Panic => "panic",
+        };
+        let r2_key = format!("dump/{}/{dump_id}.{extension}", input.stack_hash);
+        let expires = (now + limits.presigned_put_expires).as_secs();
+        let presigned_put_url = format!("{url}{UPLOAD_PATH_PREFIX}{r2_key}?expires={expires}");
+        state.dumps.push(Dump {
+            dump_id: dump_id.clone(),
+            stack_hash: input.stack_hash.clone(),
+            build_id: input.build_id.clone(),
+            r2_key,
+            context: input.context.clone(),
+            client_ip,
+            object: None,
+        });
+        Some(UploadGrant {
+            dump_id,
+            presigned_put_url,
+        })
+    } else {
+        None
+    };
+
+    let group = state
+        .groups
+        .entry(input.stack_hash.clone())
+        .or_insert_with(|| StackGroup {
+            stack_hash: input.stack_hash,
+            count: 0,
+            dump_ids: Vec::new(),
+            latest_context: input.context.clone(),
+        });
+    group.count += 1;
+    group.latest_context = input.context;
+    if let Some(grant) = &upload {
+        group.dump_ids.push(grant.dump_id.clone());
+    }
+
+    IntakeOutput::Ok { upload }
+}
+
+fn upload(state: &mut State, request: &Request) -> Response {
+    if state.failing_uploads > 0 {
+        state.failing_uploads -= 1;
+        return Response::text(500, "injected failure");
+    }
+    let r2_key = &request.path[UPLOAD_PATH_PREFIX.len()..];
+    let expires = request
+        .query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("expires="))
+        .and_then(|secs| secs.parse::<u64>().ok());
+    match expires {
+        Some(expires) if state.now.as_secs() <= expires => {}
+        _ => return Response::text(403, "presigned URL expired"),
+    }
+    let Some(dump) = state.dumps.iter_mut().find(|d| d.r2_key == r2_key) else {
+        return Response::text(404, "no dump for key");
+    };
+    dump.object = Some(request.body.clone());
+    Response::text(200, "")
+}
+
+fn verify_hmac(
+    key_hex: &str,
+    build_id: &str,
+    stack_hash: &str,
+    context: &CrashContext,
+    signature: &[u8],
+) -> bool {
+    let Ok(key) = hex::decode(key_hex) else {
+        return false;
+    };
+    let Ok(context_json) = serde_json::to_vec(context) else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha256::new_from_slice(&key) else {
+        return false;
+    };
+    mac.update(build_id.as_bytes());
+    mac.update(stack_hash.as_bytes());
+    mac.update(&Sha256::digest(&context_json));
+    mac.verify_slice(signature).is_ok()
+}
+
+fn breadcrumbs_within_limit(breadcrumbs: &[Breadcrumb], limits: &Limits) -> bool {
+    let bytes: usize = breadcrumbs
+        .iter()
+        .map(|b| {
+            b.category.len()
+                + b.message.len()
+                + b.data.iter().map(|(k, v)| k.len() + v.len()).sum::<usize>()
+        })
+        .sum();
+    breadcrumbs.len() <= limits.breadcrumbs_max_count && bytes <= limits.breadcrumbs_max_bytes
+}
+
+fn client_ip(request: &Request) -> String {
+    ["cf-connecting-ip", "x-real-ip", "x-forwarded-for"]
+        .into_iter()
+        .filter_map(|name| request.header(name))
+        .map(|value| value.split(',').next().unwrap_or("").trim())
+        .find(|ip| !ip.is_empty())
+        .map_or_else(|| request.peer.to_string(), str::to_string)
+}
+
+/// Whether `t` falls in the `window` that ends at `now`; namsh's
+/// `t > now - window`.
+fn within(now: Duration, t: Duration, window: Duration) -> bool {
+    now.saturating_sub(t) < window
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(build_id: &str) -> CrashContext {
+        CrashContext {
+            build_id: build_id.to_string(),
+            install_id: "install".to_string(),
+            session_uptime_sec: 1,
+            log_tail: None,
+            kind: CrashKind::Native,
+            panic: None,
+            breadcrumbs: Vec::new(),
+        }
+    }
+
+    fn sign(key_hex: &str, build_id: &str, stack_hash: &str, context: &CrashContext) -> String {
+        let mut mac = HmacSha256::new_from_slice(&hex::decode(key_hex).unwrap()).unwrap();
+        mac.update(build_id.as_bytes());
+        mac.update(stack_hash.as_bytes());
+        mac.update(&Sha256::digest(serde_json::to_vec(context).unwrap()));
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    /// Sends one request and returns the status and body.
+    fn send(
+        url: &str,
+        method: &str,
+        target: &str,
+        headers: &[(&str, &str)],
+        body: &[u8],
+    ) -> (u16, String) {
+        let mut stream = TcpStream::connect(url.trim_start_matches("http://")).unwrap();
+        write!(
+            stream,
+            "{method} {target} HTTP/1.1\r\nContent-Length: {}\r\n",
+            body.len()
+        )
+        .unwrap();
+        for (name, value) in headers {
+            write!(stream, "{name}: {value}\r\n").unwrap();
+        }
+        stream.write_all(b"\r\n").unwrap();
+        stream.write_all(body).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response.split_once("\r\n\r\n").unwrap().1.to_string();
+        (status, body)
+    }
+
+    fn intake(emulator: &Emulator, key_hex: &str, stack_hash: &str) -> serde_json::Value {
+        let context = context("b1");
+        let signature = sign(key_hex, "b1", stack_hash, &context);
+        let body = serde_json::json!({
+            "build_id": "b1",
+            "stack_hash": stack_hash,
+            "context": context,
+        });
+        let (status, body) = send(
+            emulator.url(),
+            "POST",
+            INTAKE_PATH,
+            &[
+                ("x-namsh-build-id", "b1"),
+                ("x-namsh-signature", &signature),
+            ],
+            &serde_json::to_vec(&body).unwrap(),
+        );
+        assert_eq!(status, 200, "{body}");
+        serde_json::from_str(&body).unwrap()
+    }
+
+    fn upload_target(output: &serde_json::Value) -> String {
+        let url = output["upload"]["presignedPutUrl"].as_str().unwrap();
+        let authority_end = url["http://".len()..].find('/').unwrap() + "http://".len();
+        url[authority_end..].to_string()
+    }
+
+    #[test]
+    fn intake_then_upload_stores_the_dump() {
+        let emulator = Emulator::start().unwrap();
+        let key_hex = emulator.register_build("b1");
+
+        let output = intake(&emulator, &key_hex, "h1");
+        assert_eq!(output["t"], "Ok");
+        let (status, _) = send(emulator.url(), "PUT", &upload_target(&output), &[], b"MDMP");
+        assert_eq!(status, 200);
+
+        let dumps = emulator.dumps();
+        assert_eq!(dumps.len(), 1);
+        assert_eq!(dumps[0].r2_key, "dump/h1/dump-1.dmp");
+        assert_eq!(dumps[0].client_ip, "127.0.0.1");
+        assert_eq!(dumps[0].object.as_deref(), Some(&b"MDMP"[..]));
+        assert_eq!(emulator.group("h1").unwrap().dump_ids, ["dump-1"]);
+    }
+
+    #[test]
+    fn records_malformed_connections() {
+        let emulator = Emulator::start().unwrap();
+        let mut stream = TcpStream::connect(emulator.url().trim_start_matches("http://")).unwrap();
+        stream.write_all(b"\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.is_empty());
+        assert_eq!(emulator.connection_errors(), ["bad request line"]);
+        assert!(emulator.intake_outputs().is_empty());
+    }
+
+    #[test]
+    fn rejects_wrong_key_and_unknown_build() {
+        let emulator = Emulator::start().unwrap();
+        assert_eq!(
+            intake(&emulator, &"00".repeat(32), "h1")["t"],
+            "UnknownBuild"
+        );
+        emulator.register_build("b1");
+        assert_eq!(
+            intake(&emulator, &"00".repeat(32), "h1")["t"],
+            "InvalidSignature"
+        );
+        assert!(emulator.groups().is_empty());
+    }
+
+    #[test]
+    fn rate_limits_per_minute_and_day() {
+        let emulator = Emulator::start().unwrap();
+        let key_hex = emulator.register_build("b1");
+
+        assert_eq!(intake(&emulator, &key_hex, "h1")["t"], "Ok");
+        assert_eq!(intake(&emulator, &key_hex, "h2")["t"], "RateLimited");
+        for _ in 0..4 {
+            emulator.advance(Duration::from_secs(61));
+            assert_eq!(intake(&emulator, &key_hex, "h2")["t"], "Ok");
+        }
+        emulator.advance(Duration::from_secs(61));
+        assert_eq!(intake(&emulator, &key_hex, "h2")["t"], "RateLimited");
+        emulator.advance(Duration::from_secs(24 * 3600));
+        assert_eq!(intake(&emulator, &key_hex, "h2")["t"], "Ok");
+    }
+
+    #[test]
+    fn full_group_counts_without_upload() {
+        let emulator = Emulator::start_with(Limits {
+            max_dumps_per_group: 1,
+            rate_limit_per_minute: 10,
+            ..Default::default()
+        })
+        .unwrap();
+        let key_hex = emulator.register_build("b1");
+
+        assert!(intake(&emulator, &key_hex, "h1")["upload"].is_object());
+        assert!(intake(&emulator, &key_hex, "h1")["upload"].is_null());
+        let group = emulator.group("h1").unwrap();
+        assert_eq!((group.count, group.dump_ids.len()), (2, 1));
+    }
+
+    #[test]
+    fn expired_upload_is_refused() {
+        let emulator = Emulator::start().unwrap();
+        let key_hex = emulator.register_build("b1");
+        let output = intake(&emulator, &key_hex, "h1");
+
+        emulator.advance(Duration::from_secs(601));
+        let (status, _) = send(emulator.url(), "PUT", &upload_target(&output), &[], b"MDMP");
+        assert_eq!(status, 403);
+        assert!(emulator.dumps()[0].object.is_none());
+    }
+
+    #[test]
+    fn injected_failures_run_out() {
+        let emulator = Emulator::start().unwrap();
+        let key_hex = emulator.register_build("b1");
+        emulator.fail_next_intakes(1);
+
+        let (status, _) = send(emulator.url(), "POST", INTAKE_PATH, &[], b"{}");
+        assert_eq!(status, 500);
+        assert_eq!(intake(&emulator, &key_hex, "h1")["t"], "Ok");
+        assert_eq!(emulator.intake_outputs().len(), 1);
+    }
+}
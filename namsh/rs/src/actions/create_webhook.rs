@@ -0,0 +1,73 @@
+use crate::common::{auth, webhook};
+use crate::docs::*;
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Input {
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    /// Required iff `events` contains `spike`.
+    #[serde(default)]
+    pub spike_hits_per_hour: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok { id: String, secret_hex: String },
+    NotLoggedIn,
+    InvalidUrl,
+    NoEvents,
+    InvalidSpikeThreshold,
+    Error { message: String },
+}
+
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    let Some(user) = auth::session_or_bearer_user(req.jar, req.headers).await else {
+        return Output::NotLoggedIn;
+    };
+
+    let url = req.body.url.trim().to_string();
+    if !webhook::is_public_url(&url) {
+        return Output::InvalidUrl;
+    }
+    if req.body.events.is_empty() {
+        return Output::NoEvents;
+    }
+    let wants_spike = req.body.events.contains(&WebhookEvent::Spike);
+    let spike_hits_per_hour = match (wants_spike, req.body.spike_hits_per_hour) {
+        (true, Some(n)) if n > 0 => Some(n),
+        (false, _) => None,
+        _ => return Output::InvalidSpikeThreshold,
+    };
+
+    let id = match webhook::new_id() {
+        Ok(id) => id,
+        Err(e) => {
+            return Output::Error {
+                message: e.to_string(),
+            };
+        }
+    };
+    let secret_hex = hex::encode(rand::get_random_bytes(32));
+
+    let db = doc_db::turso();
+    if let Err(e) = WebhookDocPut(WebhookDoc {
+        id: id.clone(),
+        url,
+        secret_hex: secret_hex.clone(),
+        events: req.body.events.clone(),
+        spike_hits_per_hour,
+        created_by: user.github_id,
+        created_at: forte_sdk::now(),
+    })
+    .send_with(&db)
+    .await
+    {
+        tracing::error!(?e, "create_webhook WebhookDocPut");
+        return Output::Error {
+            message: format!("WebhookDocPut: {e}"),
+        };
+    }
+    Output::Ok { id, secret_hex }
+}
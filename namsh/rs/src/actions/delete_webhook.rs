@@ -0,0 +1,54 @@
+use crate::common::auth;
+use crate::docs::*;
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Input {
+    pub id: String,
+}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok,
+    NotLoggedIn,
+    NotFound,
+    Error { message: String },
+}
+
+/// Pending deliveries of the webhook fail on their next attempt.
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    let Some(_user) = auth::session_or_bearer_user(req.jar, req.headers).await else {
+        return Output::NotLoggedIn;
+    };
+
+    let db = doc_db::turso();
+    match (WebhookDocGet {
+        id: req.body.id.clone(),
+    })
+    .send_with(&db)
+    .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return Output::NotFound,
+        Err(e) => {
+            tracing::error!(?e, "delete_webhook WebhookDocGet");
+            return Output::Error {
+                message: format!("WebhookDocGet: {e}"),
+            };
+        }
+    }
+
+    if let Err(e) = (WebhookDocDelete {
+        id: req.body.id.clone(),
+    })
+    .send_with(&db)
+    .await
+    {
+        tracing::error!(?e, "delete_webhook WebhookDocDelete");
+        return Output::Error {
+            message: format!("WebhookDocDelete: {e}"),
+        };
+    }
+    Output::Ok
+}
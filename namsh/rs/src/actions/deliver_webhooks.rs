@@ -0,0 +1,30 @@
+use crate::common::webhook::DeliveryCounts;
+use crate::common::{admin, webhook};
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Input {}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok { counts: DeliveryCounts },
+    Unauthorized,
+    Error { message: String },
+}
+
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    if !admin::verify(req.headers) {
+        return Output::Unauthorized;
+    }
+
+    match webhook::deliver_due(forte_sdk::now()).await {
+        Ok(counts) => Output::Ok { counts },
+        Err(e) => {
+            tracing::error!(?e, "deliver_webhooks deliver_due");
+            Output::Error {
+                message: e.to_string(),
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::common::storage::R2;
+use crate::common::{admin, retention};
+use crate::docs::*;
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Input {
+    /// Report what would be deleted without deleting it.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok {
+        deleted_dump_ids: Vec<String>,
+        /// R2 keys that couldn't be deleted; their dumps are kept for the
+        /// next run.
+        failed_keys: Vec<String>,
+    },
+    Unauthorized,
+    Error {
+        message: String,
+    },
+}
+
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    if !admin::verify(req.headers) {
+        return Output::Unauthorized;
+    }
+
+    let policy = match retention::load_policy().await {
+        Ok(p) => p,
+        Err(e) => {
+            tracing::error!(?e, "gc_dumps load_policy");
+            return Output::Error {
+                message: format!("load_policy: {e}"),
+            };
+        }
+    };
+
+    let db = doc_db::turso();
+    let builds: Vec<BuildDoc> = match (BuildDocQuery {
+        build_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!(?e, "gc_dumps BuildDocQuery");
+            return Output::Error {
+                message: format!("BuildDocQuery: {e}"),
+            };
+        }
+    };
+    let dumps: Vec<DumpDoc> = match (DumpDocQuery {
+        dump_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!(?e, "gc_dumps DumpDocQuery");
+            return Output::Error {
+                message: format!("DumpDocQuery: {e}"),
+            };
+        }
+    };
+
+    let expired: Vec<DumpDoc> =
+        retention::expired_dumps(&policy, forte_sdk::now(), &builds, &dumps)
+            .into_iter()
+            .cloned()
+            .collect();
+    if req.body.dry_run {
+        return Output::Ok {
+            deleted_dump_ids: expired.into_iter().map(|d| d.dump_id).collect(),
+            failed_keys: Vec::new(),
+        };
+    }
+
+    let (deleted, failed_keys) = retention::delete_objects(&R2, expired).await;
+    if let Err(e) = retention::forget_dumps(&deleted).await {
+        tracing::error!(?e, "gc_dumps forget_dumps");
+        return Output::Error {
+            message: format!("forget_dumps: {e}"),
+        };
+    }
+    Output::Ok {
+        deleted_dump_ids: deleted.into_iter().map(|d| d.dump_id).collect(),
+        failed_keys,
+    }
+}
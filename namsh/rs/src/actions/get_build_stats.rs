@@ -0,0 +1,110 @@
+use crate::common::build_stats::{BuildDiff, BuildStats};
+use crate::common::{auth, build_stats};
+use crate::docs::*;
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+const TOP_GROUPS: usize = 20;
+
+#[derive(Deserialize)]
+pub struct Input {
+    pub build_id: String,
+    /// Also diff against this build's groups.
+    #[serde(default)]
+    pub base_build_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok {
+        /// `groups` cut to the top `TOP_GROUPS`.
+        stats: BuildStats,
+        diff: Option<BuildDiff>,
+    },
+    NotLoggedIn,
+    NotFound,
+    BaseNotFound,
+    Error {
+        message: String,
+    },
+}
+
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    let Some(_user) = auth::session_or_bearer_user(req.jar, req.headers).await else {
+        return Output::NotLoggedIn;
+    };
+
+    let db = doc_db::turso();
+    match (BuildDocGet {
+        build_id: req.body.build_id.clone(),
+    })
+    .send_with(&db)
+    .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return Output::NotFound,
+        Err(e) => {
+            tracing::error!(?e, "get_build_stats BuildDocGet");
+            return Output::Error {
+                message: format!("BuildDocGet: {e}"),
+            };
+        }
+    }
+    if let Some(base_build_id) = &req.body.base_build_id {
+        match (BuildDocGet {
+            build_id: base_build_id.clone(),
+        })
+        .send_with(&db)
+        .await
+        {
+            Ok(Some(_)) => {}
+            Ok(None) => return Output::BaseNotFound,
+            Err(e) => {
+                tracing::error!(?e, "get_build_stats BuildDocGet (base)");
+                return Output::Error {
+                    message: format!("BuildDocGet: {e}"),
+                };
+            }
+        }
+    }
+
+    match load(&req.body.build_id, req.body.base_build_id.as_deref()).await {
+        Ok((stats, diff)) => Output::Ok { stats, diff },
+        Err(e) => {
+            tracing::error!(?e, "get_build_stats load");
+            Output::Error {
+                message: e.to_string(),
+            }
+        }
+    }
+}
+
+/// `build_id`'s stats with `groups` cut to the top [`TOP_GROUPS`], diffed
+/// against `base_build_id`'s. Shared with the `/builds/[build_id]` page.
+pub async fn load(
+    build_id: &str,
+    base_build_id: Option<&str>,
+) -> anyhow::Result<(BuildStats, Option<BuildDiff>)> {
+    let db = doc_db::turso();
+    let groups: Vec<StackGroupDoc> = (StackGroupDocQuery {
+        stack_hash: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?;
+    let dumps: Vec<DumpDoc> = (DumpDocQuery {
+        dump_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?;
+    let mut stats = build_stats::compute(&groups, &dumps);
+
+    let diff = base_build_id
+        .map(|base_build_id| build_stats::diff(stats.get(base_build_id), stats.get(build_id)));
+    let mut stats = stats
+        .remove(build_id)
+        .unwrap_or_else(|| BuildStats::empty(build_id));
+    stats.groups.truncate(TOP_GROUPS);
+    Ok((stats, diff))
+}
@@ -14,6 +14,7 @@ pub struct DumpSummary {
     pub build_id: String,
     pub uploaded_at: DateTime,
     pub client_ip: String,
+    pub processing: DumpProcessing,
 }
 
 #[derive(Serialize)]
@@ -24,6 +25,9 @@ pub enum Output {
         last_seen: DateTime,
         count: u64,
         latest_context: CrashContext,
+        status: StackGroupStatus,
+        build_ids: Vec<String>,
+        platforms: Vec<PlatformDumps>,
         dumps: Vec<DumpSummary>,
     },
     NotLoggedIn,
@@ -68,6 +72,7 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
                 build_id: d.build_id,
                 uploaded_at: d.uploaded_at,
                 client_ip: d.client_ip,
+                processing: d.processing,
             }),
             Ok(None) => {
                 tracing::warn!(dump_id, "get_stack_group: missing DumpDoc");
@@ -87,6 +92,9 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
         last_seen: group.last_seen,
         count: group.count,
         latest_context: group.latest_context,
+        status: group.status,
+        build_ids: group.build_ids,
+        platforms: group.platforms,
         dumps,
     }
 }
@@ -1,3 +1,5 @@
+use crate::common::webhook::Occurrence;
+use crate::common::{retention, stack_group, webhook};
 use crate::docs::*;
 use forte_sdk::*;
 use hmac::{Hmac, Mac};
@@ -7,10 +9,12 @@ use std::time::Duration;
 
 type HmacSha256 = Hmac<Sha256>;
 
-const MAX_DUMPS_PER_GROUP: usize = 3;
+const MAX_INSTALLS_PER_BUILD: usize = 100;
 const RATE_LIMIT_PER_MINUTE: usize = 1;
 const RATE_LIMIT_PER_24H: usize = 5;
 const LOG_TAIL_MAX_BYTES: usize = 64 * 1024;
+const BREADCRUMBS_MAX_COUNT: usize = 64;
+const BREADCRUMBS_MAX_BYTES: usize = 32 * 1024;
 const PRESIGNED_PUT_EXPIRES_SECS: u64 = 600;
 
 #[derive(Deserialize)]
@@ -62,6 +66,9 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
     {
         return Output::PayloadTooLarge;
     }
+    if !breadcrumbs_within_limit(&req.body.context.breadcrumbs) {
+        return Output::PayloadTooLarge;
+    }
 
     let db = doc_db::turso();
     let build = match (BuildDocGet {
@@ -141,12 +148,95 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
         }
     };
 
-    let (first_seen, prev_count, mut dump_ids) = match existing {
-        Some(g) => (g.first_seen, g.count, g.dump_ids),
-        None => (now, 0, Vec::new()),
+    let mut build_hits = existing
+        .as_ref()
+        .map(|g| g.build_hits.clone())
+        .unwrap_or_default();
+    let platforms = existing
+        .as_ref()
+        .map(|g| g.platforms.clone())
+        .unwrap_or_default();
+    let hour = now.timestamp() / 3600;
+    let hour_hits = match existing.as_ref().and_then(|g| g.hour_hits.clone()) {
+        Some(h) if h.hour == hour => HourHits {
+            hour,
+            count: h.count + 1,
+        },
+        _ => HourHits { hour, count: 1 },
+    };
+    let (first_seen, prev_count, mut dump_ids, status, mut build_ids) = match existing {
+        Some(mut g) => {
+            if g.build_ids.is_empty() {
+                g.build_ids.push(g.latest_context.build_id);
+            }
+            (g.first_seen, g.count, g.dump_ids, g.status, g.build_ids)
+        }
+        None => (now, 0, Vec::new(), StackGroupStatus::Open, Vec::new()),
     };
+    if !build_ids.contains(&req.body.build_id) {
+        build_ids.push(req.body.build_id.clone());
+    }
 
-    let upload = if dump_ids.len() < MAX_DUMPS_PER_GROUP {
+    // Groups from before `build_hits` start from their stored dumps, the
+    // same fallback `build_stats` uses for groups that aren't hit again.
+    if build_hits.is_empty() {
+        for dump_id in &dump_ids {
+            match (DumpDocGet {
+                dump_id: dump_id.clone(),
+            })
+            .send_with(&db)
+            .await
+            {
+                Ok(Some(d)) => add_build_hit(&mut build_hits, &d.build_id, &d.context.install_id),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::error!(?e, "intake_crash DumpDocGet");
+                    return Output::Error {
+                        message: format!("DumpDocGet: {e}"),
+                    };
+                }
+            }
+        }
+    }
+    add_build_hit(
+        &mut build_hits,
+        &req.body.build_id,
+        &req.body.context.install_id,
+    );
+
+    let was_resolved = matches!(status, StackGroupStatus::Resolved { .. });
+    let resolved_build = match &status {
+        StackGroupStatus::Resolved { build_id } if *build_id != req.body.build_id => {
+            match (BuildDocGet {
+                build_id: build_id.clone(),
+            })
+            .send_with(&db)
+            .await
+            {
+                Ok(b) => b,
+                Err(e) => {
+                    tracing::error!(?e, "intake_crash BuildDocGet (resolved)");
+                    return Output::Error {
+                        message: format!("BuildDocGet: {e}"),
+                    };
+                }
+            }
+        }
+        _ => None,
+    };
+    let status = stack_group::status_after_hit(status, &build, resolved_build.as_ref());
+
+    let policy = match retention::load_policy().await {
+        Ok(p) => p,
+        Err(e) => {
+            tracing::error!(?e, "intake_crash load_policy");
+            return Output::Error {
+                message: format!("load_policy: {e}"),
+            };
+        }
+    };
+
+    let upload = if dump_ids.len() < policy.max_dumps_per_group {
         let bytes = rand::get_random_bytes(16);
         let Ok(uuid_bytes): Result<[u8; 16], _> = bytes.as_slice().try_into() else {
             return Output::Error {
@@ -154,7 +244,11 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
             };
         };
         let dump_id = Uuid::from_bytes(uuid_bytes).to_string();
-        let r2_key = format!("dump/{}/{}.dmp", req.body.stack_hash, dump_id);
+        let extension = match req.body.context.kind {
+            CrashKind::Native => "dmp",
+            CrashKind::Panic => "panic",
+        };
+        let r2_key = format!("dump/{}/{}.{extension}", req.body.stack_hash, dump_id);
 
         let bucket = object_storage::private::bucket();
         let presigned_put_url = match bucket
@@ -178,6 +272,10 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
             r2_key: r2_key.clone(),
             context: req.body.context.clone(),
             client_ip: client_ip.clone(),
+            processing: match req.body.context.kind {
+                CrashKind::Native => DumpProcessing::Pending,
+                CrashKind::Panic => DumpProcessing::NotMinidump,
+            },
         }))
         .send_with(&db)
         .await
@@ -204,14 +302,32 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
         count: prev_count + 1,
         dump_ids,
         latest_context: req.body.context,
+        status,
+        build_ids,
+        build_hits,
+        hour_hits: Some(hour_hits),
+        platforms,
     };
-    if let Err(e) = StackGroupDocPut(group).send_with(&db).await {
+    if let Err(e) = StackGroupDocPut(group.clone()).send_with(&db).await {
         tracing::error!(?e, "intake_crash StackGroupDocPut");
         return Output::Error {
             message: format!("StackGroupDocPut: {e}"),
         };
     }
 
+    // The crash is recorded either way, so a failure here is only logged.
+    if group.status != StackGroupStatus::Ignored {
+        let occurrence = Occurrence {
+            new_group: prev_count == 0,
+            regressed: was_resolved
+                && matches!(group.status, StackGroupStatus::Regressed { .. }),
+            hour_hits: group.hour_hits.as_ref().map_or(0, |h| h.count),
+        };
+        if let Err(e) = webhook::enqueue(&group, &occurrence).await {
+            tracing::error!(?e, "intake_crash webhook::enqueue");
+        }
+    }
+
     Output::Ok { upload }
 }
 
@@ -242,6 +358,39 @@ fn verify_hmac(
     mac.verify_slice(signature).is_ok()
 }
 
+fn add_build_hit(build_hits: &mut Vec<BuildHits>, build_id: &str, install_id: &str) {
+    let index = match build_hits.iter().position(|h| h.build_id == build_id) {
+        Some(index) => index,
+        None => {
+            build_hits.push(BuildHits {
+                build_id: build_id.to_string(),
+                count: 0,
+                install_ids: Vec::new(),
+            });
+            build_hits.len() - 1
+        }
+    };
+    let hits = &mut build_hits[index];
+    hits.count += 1;
+    if hits.install_ids.len() < MAX_INSTALLS_PER_BUILD
+        && !hits.install_ids.iter().any(|i| i == install_id)
+    {
+        hits.install_ids.push(install_id.to_string());
+    }
+}
+
+fn breadcrumbs_within_limit(breadcrumbs: &[Breadcrumb]) -> bool {
+    let bytes: usize = breadcrumbs
+        .iter()
+        .map(|b| {
+            b.category.len()
+                + b.message.len()
+                + b.data.iter().map(|(k, v)| k.len() + v.len()).sum::<usize>()
+        })
+        .sum();
+    breadcrumbs.len() <= BREADCRUMBS_MAX_COUNT && bytes <= BREADCRUMBS_MAX_BYTES
+}
+
 fn extract_client_ip(headers: &::http::HeaderMap) -> Option<String> {
     for name in ["cf-connecting-ip", "x-real-ip", "x-forwarded-for"] {
         let Some(v) = headers.get(name) else { continue };
@@ -1,4 +1,4 @@
-use crate::common::auth;
+use crate::common::{auth, build_stats};
 use crate::docs::*;
 use forte_sdk::*;
 use serde::{Deserialize, Serialize};
@@ -13,6 +13,9 @@ pub struct BuildSummary {
     pub uploaded_by: i64,
     pub pdb_uploaded: bool,
     pub pdb_size: Option<u64>,
+    /// See `build_stats::BuildStats`.
+    pub hits: u64,
+    pub installs: usize,
 }
 
 #[derive(Serialize)]
@@ -44,14 +47,51 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
         }
     };
 
+    let groups: Vec<StackGroupDoc> = match (StackGroupDocQuery {
+        stack_hash: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!(?e, "list_builds StackGroupDocQuery");
+            return Output::Error {
+                message: format!("StackGroupDocQuery: {e}"),
+            };
+        }
+    };
+    let dumps: Vec<DumpDoc> = match (DumpDocQuery {
+        dump_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!(?e, "list_builds DumpDocQuery");
+            return Output::Error {
+                message: format!("DumpDocQuery: {e}"),
+            };
+        }
+    };
+    let stats = build_stats::compute(&groups, &dumps);
+
     let mut builds: Vec<BuildSummary> = docs
         .into_iter()
-        .map(|b| BuildSummary {
-            build_id: b.build_id,
-            created_at: b.created_at,
-            uploaded_by: b.uploaded_by,
-            pdb_uploaded: b.pdb_uploaded,
-            pdb_size: b.pdb_size,
+        .map(|b| {
+            let stats = stats.get(&b.build_id);
+            BuildSummary {
+                build_id: b.build_id,
+                created_at: b.created_at,
+                uploaded_by: b.uploaded_by,
+                pdb_uploaded: b.pdb_uploaded,
+                pdb_size: b.pdb_size,
+                hits: stats.map_or(0, |s| s.hits),
+                installs: stats.map_or(0, |s| s.installs),
+            }
         })
         .collect();
     builds.sort_by(|a, b| b.created_at.cmp(&a.created_at));
@@ -1,10 +1,18 @@
-use crate::common::auth;
+use crate::common::{auth, stack_group};
 use crate::docs::*;
 use forte_sdk::*;
 use serde::{Deserialize, Serialize};
 
+/// See `stack_group::matches_filter`.
 #[derive(Deserialize)]
-pub struct Input {}
+pub struct Input {
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub build_id: Option<String>,
+    #[serde(default)]
+    pub os: Option<String>,
+}
 
 #[derive(Serialize)]
 pub struct StackGroupSummary {
@@ -14,12 +22,16 @@ pub struct StackGroupSummary {
     pub count: u64,
     pub stored_dumps: usize,
     pub latest_build_id: String,
+    pub status: StackGroupStatus,
+    pub build_ids: Vec<String>,
+    pub platforms: Vec<PlatformDumps>,
 }
 
 #[derive(Serialize)]
 pub enum Output {
     Ok { groups: Vec<StackGroupSummary> },
     NotLoggedIn,
+    InvalidStatus,
     Error { message: String },
 }
 
@@ -28,6 +40,10 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
         return Output::NotLoggedIn;
     };
 
+    if !stack_group::STATUS_FILTERS.contains(&req.body.status.as_deref().unwrap_or_default()) {
+        return Output::InvalidStatus;
+    }
+
     let db = doc_db::turso();
     let docs: Vec<StackGroupDoc> = match (StackGroupDocQuery {
         stack_hash: None,
@@ -47,6 +63,14 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
 
     let mut groups: Vec<StackGroupSummary> = docs
         .into_iter()
+        .filter(|g| {
+            stack_group::matches_filter(
+                g,
+                req.body.status.as_deref(),
+                req.body.build_id.as_deref(),
+                req.body.os.as_deref(),
+            )
+        })
         .map(|g| StackGroupSummary {
             stack_hash: g.stack_hash,
             first_seen: g.first_seen,
@@ -54,6 +78,9 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
             count: g.count,
             stored_dumps: g.dump_ids.len(),
             latest_build_id: g.latest_context.build_id,
+            status: g.status,
+            build_ids: g.build_ids,
+            platforms: g.platforms,
         })
         .collect();
     groups.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
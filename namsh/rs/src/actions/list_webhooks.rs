@@ -0,0 +1,108 @@
+use crate::common::auth;
+use crate::docs::*;
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+const RECENT_DELIVERIES: usize = 100;
+
+#[derive(Deserialize)]
+pub struct Input {}
+
+#[derive(Serialize)]
+pub struct WebhookSummary {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub spike_hits_per_hour: Option<u64>,
+    pub created_at: DateTime,
+}
+
+#[derive(Serialize)]
+pub struct DeliverySummary {
+    pub delivery_id: String,
+    pub webhook_id: String,
+    pub event: WebhookEvent,
+    pub stack_hash: String,
+    pub created_at: DateTime,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime,
+    pub last_response: Option<String>,
+}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok {
+        webhooks: Vec<WebhookSummary>,
+        /// Newest first, at most `RECENT_DELIVERIES`.
+        deliveries: Vec<DeliverySummary>,
+    },
+    NotLoggedIn,
+    Error {
+        message: String,
+    },
+}
+
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    let Some(_user) = auth::session_or_bearer_user(req.jar, req.headers).await else {
+        return Output::NotLoggedIn;
+    };
+
+    match load().await {
+        Ok((webhooks, deliveries)) => Output::Ok {
+            webhooks,
+            deliveries,
+        },
+        Err(e) => {
+            tracing::error!(?e, "list_webhooks load");
+            Output::Error {
+                message: e.to_string(),
+            }
+        }
+    }
+}
+
+/// Shared with the `/webhooks` page.
+pub async fn load() -> anyhow::Result<(Vec<WebhookSummary>, Vec<DeliverySummary>)> {
+    let db = doc_db::turso();
+    let mut webhooks: Vec<WebhookSummary> = (WebhookDocQuery {
+        id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?
+    .into_iter()
+    .map(|w| WebhookSummary {
+        id: w.id,
+        url: w.url,
+        events: w.events,
+        spike_hits_per_hour: w.spike_hits_per_hour,
+        created_at: w.created_at,
+    })
+    .collect();
+    webhooks.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    let mut deliveries: Vec<WebhookDeliveryDoc> = (WebhookDeliveryDocQuery {
+        delivery_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?;
+    deliveries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    let deliveries = deliveries
+        .into_iter()
+        .take(RECENT_DELIVERIES)
+        .map(|d| DeliverySummary {
+            delivery_id: d.delivery_id,
+            webhook_id: d.webhook_id,
+            event: d.event,
+            stack_hash: d.stack_hash,
+            created_at: d.created_at,
+            status: d.status,
+            attempts: d.attempts,
+            next_attempt_at: d.next_attempt_at,
+            last_response: d.last_response,
+        })
+        .collect();
+    Ok((webhooks, deliveries))
+}
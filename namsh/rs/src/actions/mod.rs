@@ -3,6 +3,11 @@
 pub mod add_user;
 pub mod approve_cli_authorization;
 pub mod confirm_pdb_uploaded;
+pub mod create_webhook;
+pub mod delete_webhook;
+pub mod deliver_webhooks;
+pub mod gc_dumps;
+pub mod get_build_stats;
 pub mod get_stack_group;
 pub mod intake_crash;
 pub mod issue_token;
@@ -10,9 +15,15 @@ pub mod list_builds;
 pub mod list_stack_groups;
 pub mod list_tokens;
 pub mod list_users;
+pub mod list_webhooks;
 pub mod oauth_cli_exchange;
+pub mod process_dumps;
+pub mod purge_build;
 pub mod remove_user;
 pub mod request_dump_download;
 pub mod request_pdb_download;
 pub mod request_pdb_upload;
+pub mod retire_build;
 pub mod revoke_token;
+pub mod set_retention_policy;
+pub mod set_stack_group_status;
@@ -0,0 +1,31 @@
+use crate::common::admin;
+use crate::common::dump_processing::{self, ProcessCounts};
+use crate::common::storage::R2;
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Input {}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok { counts: ProcessCounts },
+    Unauthorized,
+    Error { message: String },
+}
+
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    if !admin::verify(req.headers) {
+        return Output::Unauthorized;
+    }
+
+    match dump_processing::process_pending(&R2, forte_sdk::now()).await {
+        Ok(counts) => Output::Ok { counts },
+        Err(e) => {
+            tracing::error!(?e, "process_dumps process_pending");
+            Output::Error {
+                message: e.to_string(),
+            }
+        }
+    }
+}
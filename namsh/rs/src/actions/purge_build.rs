@@ -0,0 +1,109 @@
+use crate::common::storage::{ObjectStore, R2};
+use crate::common::{admin, retention};
+use crate::docs::*;
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Input {
+    pub build_id: String,
+}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok {
+        deleted_dumps: usize,
+    },
+    Unauthorized,
+    NotFound,
+    /// Some dump objects refused to be deleted, so the build and its PDB are
+    /// kept and the purge can be retried.
+    Incomplete {
+        deleted_dumps: usize,
+        failed_keys: Vec<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Deletes the build's dumps, its PDB and its `BuildDoc`. Groups keep their
+/// counts and `build_hits` for it.
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    if !admin::verify(req.headers) {
+        return Output::Unauthorized;
+    }
+
+    let db = doc_db::turso();
+    let build = match (BuildDocGet {
+        build_id: req.body.build_id.clone(),
+    })
+    .send_with(&db)
+    .await
+    {
+        Ok(Some(b)) => b,
+        Ok(None) => return Output::NotFound,
+        Err(e) => {
+            tracing::error!(?e, "purge_build BuildDocGet");
+            return Output::Error {
+                message: format!("BuildDocGet: {e}"),
+            };
+        }
+    };
+
+    let dumps: Vec<DumpDoc> = match (DumpDocQuery {
+        dump_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await
+    {
+        Ok(v) => v
+            .into_iter()
+            .filter(|d| d.build_id == build.build_id)
+            .collect(),
+        Err(e) => {
+            tracing::error!(?e, "purge_build DumpDocQuery");
+            return Output::Error {
+                message: format!("DumpDocQuery: {e}"),
+            };
+        }
+    };
+
+    let (deleted, failed_keys) = retention::delete_objects(&R2, dumps).await;
+    if let Err(e) = retention::forget_dumps(&deleted).await {
+        tracing::error!(?e, "purge_build forget_dumps");
+        return Output::Error {
+            message: format!("forget_dumps: {e}"),
+        };
+    }
+    if !failed_keys.is_empty() {
+        return Output::Incomplete {
+            deleted_dumps: deleted.len(),
+            failed_keys,
+        };
+    }
+
+    if let Some(pdb_r2_key) = &build.pdb_r2_key
+        && let Err(e) = R2.delete(pdb_r2_key).await
+    {
+        tracing::error!(?e, "purge_build delete pdb");
+        return Output::Error {
+            message: format!("delete {pdb_r2_key}: {e}"),
+        };
+    }
+    if let Err(e) = (BuildDocDelete {
+        build_id: build.build_id.clone(),
+    })
+    .send_with(&db)
+    .await
+    {
+        tracing::error!(?e, "purge_build BuildDocDelete");
+        return Output::Error {
+            message: format!("BuildDocDelete: {e}"),
+        };
+    }
+    Output::Ok {
+        deleted_dumps: deleted.len(),
+    }
+}
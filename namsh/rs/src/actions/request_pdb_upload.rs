@@ -66,6 +66,7 @@ pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
                 pdb_uploaded: false,
                 pdb_r2_key: Some(r2_key.clone()),
                 pdb_size: None,
+                retired_at: None,
             };
             if let Err(e) = BuildDocPut(fresh.clone()).send_with(&db).await {
                 tracing::error!(?e, "request_pdb_upload BuildDocPut");
@@ -0,0 +1,54 @@
+use crate::common::auth;
+use crate::docs::*;
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Input {
+    pub build_id: String,
+}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok { retired_at: DateTime },
+    NotLoggedIn,
+    NotFound,
+    Error { message: String },
+}
+
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    let Some(_user) = auth::session_or_bearer_user(req.jar, req.headers).await else {
+        return Output::NotLoggedIn;
+    };
+
+    let db = doc_db::turso();
+    let mut build = match (BuildDocGet {
+        build_id: req.body.build_id.clone(),
+    })
+    .send_with(&db)
+    .await
+    {
+        Ok(Some(b)) => b,
+        Ok(None) => return Output::NotFound,
+        Err(e) => {
+            tracing::error!(?e, "retire_build BuildDocGet");
+            return Output::Error {
+                message: format!("BuildDocGet: {e}"),
+            };
+        }
+    };
+
+    // Retiring again must not restart the clock.
+    if let Some(retired_at) = build.retired_at {
+        return Output::Ok { retired_at };
+    }
+    let retired_at = forte_sdk::now();
+    build.retired_at = Some(retired_at);
+    if let Err(e) = BuildDocPut(build).send_with(&db).await {
+        tracing::error!(?e, "retire_build BuildDocPut");
+        return Output::Error {
+            message: format!("BuildDocPut: {e}"),
+        };
+    }
+    Output::Ok { retired_at }
+}
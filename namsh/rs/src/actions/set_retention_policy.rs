@@ -0,0 +1,43 @@
+use crate::common::admin;
+use crate::docs::*;
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Input {
+    pub max_dumps_per_group: usize,
+    #[serde(default)]
+    pub max_dump_age_days: Option<u32>,
+    #[serde(default)]
+    pub retired_build_keep_days: Option<u32>,
+}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok,
+    Unauthorized,
+    Error { message: String },
+}
+
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    if !admin::verify(req.headers) {
+        return Output::Unauthorized;
+    }
+
+    let db = doc_db::turso();
+    if let Err(e) = (RetentionPolicyDocPut(RetentionPolicyDoc {
+        id: RETENTION_POLICY_ID.to_string(),
+        max_dumps_per_group: req.body.max_dumps_per_group,
+        max_dump_age_days: req.body.max_dump_age_days,
+        retired_build_keep_days: req.body.retired_build_keep_days,
+    }))
+    .send_with(&db)
+    .await
+    {
+        tracing::error!(?e, "set_retention_policy RetentionPolicyDocPut");
+        return Output::Error {
+            message: format!("RetentionPolicyDocPut: {e}"),
+        };
+    }
+    Output::Ok
+}
@@ -0,0 +1,79 @@
+use crate::common::auth;
+use crate::docs::*;
+use forte_sdk::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct Input {
+    pub stack_hash: String,
+    /// `open`, `resolved` or `ignored`.
+    pub status: String,
+    /// Required for `resolved`: the first build with the fix.
+    #[serde(default)]
+    pub build_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub enum Output {
+    Ok { status: StackGroupStatus },
+    NotLoggedIn,
+    NotFound,
+    BuildNotFound,
+    InvalidStatus,
+    Error { message: String },
+}
+
+pub async fn handler(req: ForteRequest<'_, Input>) -> Output {
+    let Some(_user) = auth::session_or_bearer_user(req.jar, req.headers).await else {
+        return Output::NotLoggedIn;
+    };
+
+    let db = doc_db::turso();
+    let status = match (req.body.status.as_str(), req.body.build_id.clone()) {
+        ("open", _) => StackGroupStatus::Open,
+        ("ignored", _) => StackGroupStatus::Ignored,
+        ("resolved", Some(build_id)) => {
+            match (BuildDocGet {
+                build_id: build_id.clone(),
+            })
+            .send_with(&db)
+            .await
+            {
+                Ok(Some(_)) => StackGroupStatus::Resolved { build_id },
+                Ok(None) => return Output::BuildNotFound,
+                Err(e) => {
+                    tracing::error!(?e, "set_stack_group_status BuildDocGet");
+                    return Output::Error {
+                        message: format!("BuildDocGet: {e}"),
+                    };
+                }
+            }
+        }
+        _ => return Output::InvalidStatus,
+    };
+
+    let mut group = match (StackGroupDocGet {
+        stack_hash: req.body.stack_hash.clone(),
+    })
+    .send_with(&db)
+    .await
+    {
+        Ok(Some(g)) => g,
+        Ok(None) => return Output::NotFound,
+        Err(e) => {
+            tracing::error!(?e, "set_stack_group_status StackGroupDocGet");
+            return Output::Error {
+                message: format!("StackGroupDocGet: {e}"),
+            };
+        }
+    };
+
+    group.status = status.clone();
+    if let Err(e) = StackGroupDocPut(group).send_with(&db).await {
+        tracing::error!(?e, "set_stack_group_status StackGroupDocPut");
+        return Output::Error {
+            message: format!("StackGroupDocPut: {e}"),
+        };
+    }
+    Output::Ok { status }
+}
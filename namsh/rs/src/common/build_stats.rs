@@ -0,0 +1,348 @@
+use crate::docs::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// namsh never hears about sessions that didn't crash, so there's no
+/// crash-free rate; installs affected is the closest it gets.
+#[derive(Serialize)]
+pub struct BuildStats {
+    pub build_id: String,
+    pub hits: u64,
+    pub installs: usize,
+    /// Most hits first.
+    pub groups: Vec<GroupHits>,
+}
+
+impl BuildStats {
+    /// For a build nothing has hit yet.
+    pub fn empty(build_id: &str) -> Self {
+        Self {
+            build_id: build_id.to_string(),
+            hits: 0,
+            installs: 0,
+            groups: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct GroupHits {
+    pub stack_hash: String,
+    pub hits: u64,
+    pub installs: usize,
+}
+
+#[derive(Serialize)]
+pub struct GroupChange {
+    pub stack_hash: String,
+    pub base_hits: u64,
+    pub hits: u64,
+}
+
+#[derive(Serialize)]
+pub struct BuildDiff {
+    /// Hit by the build but not by the base. Most hits first.
+    pub new: Vec<GroupChange>,
+    /// Hit by the base but not by the build. Most base hits first.
+    pub gone: Vec<GroupChange>,
+    /// Hit by both, more often by the build. Largest increase first.
+    pub increased: Vec<GroupChange>,
+}
+
+/// Stats for every build that hit a group. Groups from before `build_hits`
+/// fall back to their stored dumps, which retention caps and expires, so
+/// older builds undercount.
+pub fn compute(groups: &[StackGroupDoc], dumps: &[DumpDoc]) -> BTreeMap<String, BuildStats> {
+    let mut dumps_by_group: BTreeMap<&str, Vec<&DumpDoc>> = BTreeMap::new();
+    for dump in dumps {
+        dumps_by_group
+            .entry(dump.stack_hash.as_str())
+            .or_default()
+            .push(dump);
+    }
+
+    let mut hits_by_build: BTreeMap<String, Vec<(GroupHits, Vec<&str>)>> = BTreeMap::new();
+    for group in groups {
+        let dumps = dumps_by_group
+            .get(group.stack_hash.as_str())
+            .map_or(&[][..], Vec::as_slice);
+        for hits in group_build_hits(group, dumps) {
+            hits_by_build
+                .entry(hits.build_id.to_string())
+                .or_default()
+                .push((
+                    GroupHits {
+                        stack_hash: group.stack_hash.clone(),
+                        hits: hits.count,
+                        installs: hits.install_ids.len(),
+                    },
+                    hits.install_ids,
+                ));
+        }
+    }
+
+    hits_by_build
+        .into_iter()
+        .map(|(build_id, entries)| {
+            let installs: BTreeSet<&str> = entries
+                .iter()
+                .flat_map(|(_, install_ids)| install_ids.iter().copied())
+                .collect();
+            let mut groups: Vec<GroupHits> = entries.into_iter().map(|(g, _)| g).collect();
+            groups.sort_by(|a, b| b.hits.cmp(&a.hits));
+            let stats = BuildStats {
+                build_id: build_id.clone(),
+                hits: groups.iter().map(|g| g.hits).sum(),
+                installs: installs.len(),
+                groups,
+            };
+            (build_id, stats)
+        })
+        .collect()
+}
+
+/// A build nobody has hit yet is `None` on either side.
+pub fn diff(base: Option<&BuildStats>, build: Option<&BuildStats>) -> BuildDiff {
+    let base_hits = hits_by_group(base);
+    let build_hits = hits_by_group(build);
+
+    let mut new = Vec::new();
+    let mut increased = Vec::new();
+    for (stack_hash, &hits) in &build_hits {
+        let change = GroupChange {
+            stack_hash: stack_hash.to_string(),
+            base_hits: base_hits.get(stack_hash).copied().unwrap_or(0),
+            hits,
+        };
+        if change.base_hits == 0 {
+            new.push(change);
+        } else if change.hits > change.base_hits {
+            increased.push(change);
+        }
+    }
+    let mut gone: Vec<GroupChange> = base_hits
+        .iter()
+        .filter(|(stack_hash, _)| !build_hits.contains_key(*stack_hash))
+        .map(|(stack_hash, &base_hits)| GroupChange {
+            stack_hash: stack_hash.to_string(),
+            base_hits,
+            hits: 0,
+        })
+        .collect();
+
+    new.sort_by(|a, b| b.hits.cmp(&a.hits));
+    gone.sort_by(|a, b| b.base_hits.cmp(&a.base_hits));
+    increased.sort_by(|a, b| (b.hits - b.base_hits).cmp(&(a.hits - a.base_hits)));
+    BuildDiff {
+        new,
+        gone,
+        increased,
+    }
+}
+
+fn hits_by_group(stats: Option<&BuildStats>) -> BTreeMap<&str, u64> {
+    stats
+        .map(|s| {
+            s.groups
+                .iter()
+                .map(|g| (g.stack_hash.as_str(), g.hits))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `dumps` are the group's own.
+fn group_build_hits<'a>(group: &'a StackGroupDoc, dumps: &[&'a DumpDoc]) -> Vec<BorrowedHits<'a>> {
+    if !group.build_hits.is_empty() {
+        return group
+            .build_hits
+            .iter()
+            .map(|h| BorrowedHits {
+                build_id: &h.build_id,
+                count: h.count,
+                install_ids: h.install_ids.iter().map(String::as_str).collect(),
+            })
+            .collect();
+    }
+
+    let mut by_build: BTreeMap<&str, BorrowedHits> = BTreeMap::new();
+    for dump in dumps {
+        let hits = by_build
+            .entry(dump.build_id.as_str())
+            .or_insert_with(|| BorrowedHits {
+                build_id: &dump.build_id,
+                count: 0,
+                install_ids: Vec::new(),
+            });
+        hits.count += 1;
+        if !hits.install_ids.contains(&dump.context.install_id.as_str()) {
+            hits.install_ids.push(&dump.context.install_id);
+        }
+    }
+    by_build.into_values().collect()
+}
+
+struct BorrowedHits<'a> {
+    build_id: &'a str,
+    count: u64,
+    install_ids: Vec<&'a str>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time() -> DateTime {
+        chrono::DateTime::from_timestamp(0, 0).unwrap()
+    }
+
+    fn context(build_id: &str, install_id: &str) -> CrashContext {
+        CrashContext {
+            build_id: build_id.to_string(),
+            install_id: install_id.to_string(),
+            session_uptime_sec: 0,
+            log_tail: None,
+            kind: CrashKind::Native,
+            panic: None,
+            breadcrumbs: Vec::new(),
+        }
+    }
+
+    /// `hits` is `(build_id, count, install_ids)`; empty for a group from
+    /// before `build_hits`.
+    fn group(stack_hash: &str, hits: &[(&str, u64, &[&str])]) -> StackGroupDoc {
+        StackGroupDoc {
+            stack_hash: stack_hash.to_string(),
+            first_seen: time(),
+            last_seen: time(),
+            count: hits.iter().map(|(_, count, _)| count).sum(),
+            dump_ids: Vec::new(),
+            latest_context: context("", ""),
+            status: StackGroupStatus::Open,
+            build_ids: hits.iter().map(|(b, _, _)| b.to_string()).collect(),
+            build_hits: hits
+                .iter()
+                .map(|(build_id, count, install_ids)| BuildHits {
+                    build_id: build_id.to_string(),
+                    count: *count,
+                    install_ids: install_ids.iter().map(|i| i.to_string()).collect(),
+                })
+                .collect(),
+            hour_hits: None,
+            platforms: Vec::new(),
+        }
+    }
+
+    fn dump(dump_id: &str, stack_hash: &str, build_id: &str, install_id: &str) -> DumpDoc {
+        DumpDoc {
+            dump_id: dump_id.to_string(),
+            stack_hash: stack_hash.to_string(),
+            build_id: build_id.to_string(),
+            uploaded_at: time(),
+            r2_key: format!("dump/{stack_hash}/{dump_id}.dmp"),
+            context: context(build_id, install_id),
+            client_ip: String::new(),
+            processing: DumpProcessing::Pending,
+        }
+    }
+
+    fn changes(changes: &[GroupChange]) -> Vec<(&str, u64, u64)> {
+        changes
+            .iter()
+            .map(|c| (c.stack_hash.as_str(), c.base_hits, c.hits))
+            .collect()
+    }
+
+    #[test]
+    fn sums_hits_and_distinct_installs_per_build() {
+        let groups = [
+            group("g1", &[("a", 3, &["i1", "i2"]), ("b", 1, &["i1"])]),
+            group("g2", &[("a", 5, &["i2", "i3"])]),
+        ];
+        let stats = compute(&groups, &[]);
+
+        let a = &stats["a"];
+        assert_eq!(a.hits, 8);
+        assert_eq!(a.installs, 3);
+        let groups: Vec<_> = a
+            .groups
+            .iter()
+            .map(|g| (g.stack_hash.as_str(), g.hits, g.installs))
+            .collect();
+        assert_eq!(groups, [("g2", 5, 2), ("g1", 3, 2)]);
+
+        assert_eq!(stats["b"].hits, 1);
+        assert_eq!(stats["b"].installs, 1);
+    }
+
+    #[test]
+    fn legacy_groups_fall_back_to_their_dumps() {
+        let groups = [group("old", &[]), group("new", &[("a", 1, &["i9"])])];
+        let dumps = [
+            dump("d1", "old", "a", "i1"),
+            dump("d2", "old", "a", "i1"),
+            dump("d3", "old", "b", "i2"),
+            // `new` has `build_hits`, so its dumps don't count twice.
+            dump("d4", "new", "a", "i9"),
+        ];
+        let stats = compute(&groups, &dumps);
+
+        let a = &stats["a"];
+        assert_eq!(a.hits, 3);
+        assert_eq!(a.installs, 2);
+        assert_eq!(a.groups[0].stack_hash, "old");
+        assert_eq!(a.groups[0].hits, 2);
+        assert_eq!(a.groups[0].installs, 1);
+        assert_eq!(stats["b"].hits, 1);
+    }
+
+    /// Intake stops adding install ids at `MAX_INSTALLS_PER_BUILD`, so
+    /// installs undercount past it while hits keep counting.
+    #[test]
+    fn capped_install_ids_undercount_installs() {
+        let install_ids: Vec<String> = (0..100).map(|i| format!("i{i}")).collect();
+        let install_ids: Vec<&str> = install_ids.iter().map(String::as_str).collect();
+        let groups = [group("g1", &[("a", 250, &install_ids)])];
+        let stats = compute(&groups, &[]);
+
+        assert_eq!(stats["a"].hits, 250);
+        assert_eq!(stats["a"].installs, 100);
+        assert_eq!(stats["a"].groups[0].installs, 100);
+    }
+
+    #[test]
+    fn diff_sorts_new_gone_and_increased() {
+        let groups = [
+            group("same", &[("a", 2, &[]), ("b", 2, &[])]),
+            group("fewer", &[("a", 5, &[]), ("b", 1, &[])]),
+            group("more", &[("a", 1, &[]), ("b", 4, &[])]),
+            group("much-more", &[("a", 1, &[]), ("b", 9, &[])]),
+            group("gone", &[("a", 3, &[])]),
+            group("gone-big", &[("a", 7, &[])]),
+            group("new", &[("b", 2, &[])]),
+            group("new-big", &[("b", 6, &[])]),
+        ];
+        let stats = compute(&groups, &[]);
+        let diff = diff(stats.get("a"), stats.get("b"));
+
+        assert_eq!(changes(&diff.new), [("new-big", 0, 6), ("new", 0, 2)]);
+        assert_eq!(changes(&diff.gone), [("gone-big", 7, 0), ("gone", 3, 0)]);
+        assert_eq!(
+            changes(&diff.increased),
+            [("much-more", 1, 9), ("more", 1, 4)]
+        );
+    }
+
+    #[test]
+    fn diff_against_unhit_build() {
+        let stats = compute(&[group("g1", &[("a", 2, &[])])], &[]);
+
+        let diff_new = diff(None, stats.get("a"));
+        assert_eq!(changes(&diff_new.new), [("g1", 0, 2)]);
+        assert!(diff_new.gone.is_empty() && diff_new.increased.is_empty());
+
+        let diff_gone = diff(stats.get("a"), None);
+        assert_eq!(changes(&diff_gone.gone), [("g1", 2, 0)]);
+        assert!(diff_gone.new.is_empty() && diff_gone.increased.is_empty());
+    }
+}
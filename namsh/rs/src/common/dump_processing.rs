@@ -0,0 +1,239 @@
+use crate::common::storage::ObjectStore;
+use crate::docs::*;
+use forte_sdk::*;
+use minidump::{Minidump, MinidumpException, MinidumpModuleList, MinidumpSystemInfo, Module};
+use serde::Serialize;
+
+const DUMPS_PER_RUN: usize = 20;
+/// A dump whose object is still missing this long after intake was never
+/// uploaded; the presigned PUT expires after 10 minutes.
+const UPLOAD_GRACE_MINUTES: i64 = 15;
+
+pub fn extract(bytes: &[u8]) -> Result<MinidumpInfo, String> {
+    let dump = Minidump::read(bytes).map_err(|e| format!("not a minidump: {e}"))?;
+    let system_info: MinidumpSystemInfo = dump
+        .get_stream()
+        .map_err(|e| format!("system info stream: {e}"))?;
+    let exception: MinidumpException = dump
+        .get_stream()
+        .map_err(|e| format!("exception stream: {e}"))?;
+    let (os_version, _) = system_info.os_parts();
+
+    // Not `get_crash_address`, which for access violations is the address
+    // that was read or written rather than the faulting instruction.
+    let instruction = exception.raw.exception_record.exception_address;
+    let crashing_module = dump
+        .get_stream::<MinidumpModuleList>()
+        .ok()
+        .and_then(|modules| {
+            modules
+                .module_at_address(instruction)
+                .map(|m| module_basename(&m.code_file()))
+        });
+
+    Ok(MinidumpInfo {
+        os: system_info.os.long_name().into_owned(),
+        os_version,
+        cpu_arch: system_info.cpu.to_string(),
+        exception_code: exception.raw.exception_record.exception_code,
+        exception_name: exception
+            .get_crash_reason(system_info.os, system_info.cpu)
+            .to_string(),
+        crashing_module,
+    })
+}
+
+/// Matches the crash reporter's frame keys. Splits by hand because Windows
+/// dumps carry `\`-separated paths, which `Path` here doesn't split.
+fn module_basename(code_file: &str) -> String {
+    code_file
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(code_file)
+        .to_string()
+}
+
+/// What to record for a dump whose object is `object`, or `None` to try
+/// again next run.
+pub fn outcome(
+    object: Option<&[u8]>,
+    uploaded_at: DateTime,
+    now: DateTime,
+) -> Option<DumpProcessing> {
+    match object {
+        Some(bytes) => Some(match extract(bytes) {
+            Ok(info) => DumpProcessing::Processed { info },
+            Err(reason) => DumpProcessing::Failed { reason },
+        }),
+        None if now - uploaded_at > chrono::Duration::minutes(UPLOAD_GRACE_MINUTES) => {
+            Some(DumpProcessing::Failed {
+                reason: "never uploaded".to_string(),
+            })
+        }
+        None => None,
+    }
+}
+
+pub fn add_platform(platforms: &mut Vec<PlatformDumps>, info: &MinidumpInfo) {
+    match platforms
+        .iter_mut()
+        .find(|p| p.os == info.os && p.cpu_arch == info.cpu_arch)
+    {
+        Some(platform) => platform.dumps += 1,
+        None => platforms.push(PlatformDumps {
+            os: info.os.clone(),
+            cpu_arch: info.cpu_arch.clone(),
+            dumps: 1,
+        }),
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct ProcessCounts {
+    pub processed: usize,
+    pub failed: usize,
+    /// Not uploaded yet, or storage errors; retried next run.
+    pub waiting: usize,
+    /// Pending panic reports from before `NotMinidump`, marked as such.
+    pub not_minidump: usize,
+}
+
+/// Processes the oldest pending native dumps until `DUMPS_PER_RUN` of them
+/// are processed or failed, and adds each processed one to its group's
+/// `platforms`. Dumps still waiting for their upload don't count, so they
+/// can't hold up the ones behind them.
+pub(crate) async fn process_pending(
+    store: &impl ObjectStore,
+    now: DateTime,
+) -> anyhow::Result<ProcessCounts> {
+    let db = doc_db::turso();
+    let mut pending: Vec<DumpDoc> = (DumpDocQuery {
+        dump_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?
+    .into_iter()
+    .filter(|d| matches!(d.processing, DumpProcessing::Pending))
+    .collect();
+    pending.sort_by(|a, b| a.uploaded_at.cmp(&b.uploaded_at));
+
+    let mut counts = ProcessCounts::default();
+    for mut dump in pending {
+        if dump.context.kind == CrashKind::Panic {
+            dump.processing = DumpProcessing::NotMinidump;
+            DumpDocPut(dump).send_with(&db).await?;
+            counts.not_minidump += 1;
+            continue;
+        }
+        if counts.processed + counts.failed == DUMPS_PER_RUN {
+            break;
+        }
+        let object = match store.get(&dump.r2_key).await {
+            Ok(object) => object,
+            Err(e) => {
+                tracing::error!(?e, key = %dump.r2_key, "dump_processing get");
+                counts.waiting += 1;
+                continue;
+            }
+        };
+        let Some(processing) = outcome(object.as_deref(), dump.uploaded_at, now) else {
+            counts.waiting += 1;
+            continue;
+        };
+
+        if let DumpProcessing::Processed { info } = &processing {
+            counts.processed += 1;
+            if let Some(mut group) = (StackGroupDocGet {
+                stack_hash: dump.stack_hash.clone(),
+            })
+            .send_with(&db)
+            .await?
+            {
+                add_platform(&mut group.platforms, info);
+                StackGroupDocPut(group).send_with(&db).await?;
+            }
+        } else {
+            counts.failed += 1;
+        }
+        dump.processing = processing;
+        DumpDocPut(dump).send_with(&db).await?;
+    }
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hour(n: i64) -> DateTime {
+        chrono::DateTime::from_timestamp(n * 3600, 0).unwrap()
+    }
+
+    fn info(os: &str, cpu_arch: &str) -> MinidumpInfo {
+        MinidumpInfo {
+            os: os.to_string(),
+            os_version: String::new(),
+            cpu_arch: cpu_arch.to_string(),
+            exception_code: 0xc0000005,
+            exception_name: "EXCEPTION_ACCESS_VIOLATION_READ".to_string(),
+            crashing_module: Some("game.exe".to_string()),
+        }
+    }
+
+    /// `namui/crash-reporter/testdata/make_fixture.py`'s dump: Windows 10
+    /// amd64, a read access violation at 0x28 in `app.exe`.
+    #[test]
+    fn extracts_fixture_minidump() {
+        let bytes = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/crash.dmp"));
+        let info = extract(bytes).unwrap();
+        assert_eq!(info.os, "Windows NT");
+        assert_eq!(info.os_version, "10.0.19045");
+        assert_eq!(info.cpu_arch, "amd64");
+        assert_eq!(info.exception_code, 0xc0000005);
+        assert_eq!(info.exception_name, "EXCEPTION_ACCESS_VIOLATION_READ");
+        assert_eq!(info.crashing_module.as_deref(), Some("app.exe"));
+
+        let processing = outcome(Some(bytes), hour(0), hour(0));
+        assert!(matches!(processing, Some(DumpProcessing::Processed { .. })));
+    }
+
+    #[test]
+    fn garbage_fails_processing() {
+        let processing = outcome(Some(&b"MDMP but not really"[..]), hour(0), hour(1));
+        assert!(matches!(processing, Some(DumpProcessing::Failed { .. })));
+    }
+
+    #[test]
+    fn missing_object_waits_for_upload() {
+        let uploaded_at = hour(0);
+        let within_grace = uploaded_at + chrono::Duration::minutes(UPLOAD_GRACE_MINUTES);
+        assert!(outcome(None, uploaded_at, within_grace).is_none());
+        assert!(matches!(
+            outcome(None, uploaded_at, within_grace + chrono::Duration::minutes(1)),
+            Some(DumpProcessing::Failed { reason }) if reason == "never uploaded"
+        ));
+    }
+
+    #[test]
+    fn platforms_count_per_os_and_arch() {
+        let mut platforms = Vec::new();
+        add_platform(&mut platforms, &info("Windows NT", "amd64"));
+        add_platform(&mut platforms, &info("Windows NT", "amd64"));
+        add_platform(&mut platforms, &info("Windows NT", "x86"));
+        let counts: Vec<(&str, &str, u64)> = platforms
+            .iter()
+            .map(|p| (p.os.as_str(), p.cpu_arch.as_str(), p.dumps))
+            .collect();
+        assert_eq!(
+            counts,
+            [("Windows NT", "amd64", 2), ("Windows NT", "x86", 1)]
+        );
+    }
+
+    #[test]
+    fn module_basename_strips_directories() {
+        assert_eq!(module_basename(r"C:\Games\namui\game.exe"), "game.exe");
+        assert_eq!(module_basename("/usr/lib/libc.so.6"), "libc.so.6");
+    }
+}
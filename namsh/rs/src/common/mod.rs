@@ -1,3 +1,9 @@
 pub mod admin;
 pub mod auth;
+pub mod build_stats;
+pub mod dump_processing;
 pub mod github;
+pub mod retention;
+pub mod stack_group;
+pub mod storage;
+pub mod webhook;
@@ -0,0 +1,277 @@
+use crate::common::storage::ObjectStore;
+use crate::docs::*;
+use forte_sdk::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A dump this young may still have its presigned PUT outstanding (it expires
+/// after 10 minutes). Forgetting its doc now would strand the object.
+const UPLOAD_WINDOW_MINUTES: i64 = 15;
+
+pub async fn load_policy() -> anyhow::Result<RetentionPolicyDoc> {
+    let db = doc_db::turso();
+    let policy = (RetentionPolicyDocGet {
+        id: RETENTION_POLICY_ID.to_string(),
+    })
+    .send_with(&db)
+    .await?;
+    Ok(policy.unwrap_or_default())
+}
+
+/// Dumps the policy no longer keeps, oldest first. A group keeps its newest
+/// `max_dumps_per_group` of the dumps that survive the age rules. Dumps within
+/// [`UPLOAD_WINDOW_MINUTES`] of intake are never expired.
+pub fn expired_dumps<'a>(
+    policy: &RetentionPolicyDoc,
+    now: DateTime,
+    builds: &[BuildDoc],
+    dumps: &'a [DumpDoc],
+) -> Vec<&'a DumpDoc> {
+    let age_cutoff = policy
+        .max_dump_age_days
+        .map(|days| now - chrono::Duration::days(days.into()));
+    let retired_cutoff = policy
+        .retired_build_keep_days
+        .map(|days| now - chrono::Duration::days(days.into()));
+    let expired_builds: BTreeSet<&str> = builds
+        .iter()
+        .filter(|b| match (b.retired_at, retired_cutoff) {
+            (Some(retired_at), Some(cutoff)) => retired_at < cutoff,
+            _ => false,
+        })
+        .map(|b| b.build_id.as_str())
+        .collect();
+
+    let mut expired = Vec::new();
+    let mut kept_by_group: BTreeMap<&str, Vec<&DumpDoc>> = BTreeMap::new();
+    for dump in dumps {
+        let too_old = age_cutoff.is_some_and(|cutoff| dump.uploaded_at < cutoff);
+        if too_old || expired_builds.contains(dump.build_id.as_str()) {
+            expired.push(dump);
+        } else {
+            kept_by_group
+                .entry(&dump.stack_hash)
+                .or_default()
+                .push(dump);
+        }
+    }
+    for mut kept in kept_by_group.into_values() {
+        kept.sort_by(|a, b| b.uploaded_at.cmp(&a.uploaded_at));
+        expired.extend(kept.into_iter().skip(policy.max_dumps_per_group));
+    }
+    let upload_cutoff = now - chrono::Duration::minutes(UPLOAD_WINDOW_MINUTES);
+    expired.retain(|dump| dump.uploaded_at < upload_cutoff);
+    expired.sort_by(|a, b| a.uploaded_at.cmp(&b.uploaded_at));
+    expired
+}
+
+/// Deletes each dump's object, returning the dumps that are gone and the
+/// keys that refused. A refused dump must keep its `DumpDoc` so the next
+/// GC retries it.
+pub(crate) async fn delete_objects(
+    store: &impl ObjectStore,
+    dumps: Vec<DumpDoc>,
+) -> (Vec<DumpDoc>, Vec<String>) {
+    let mut deleted = Vec::new();
+    let mut failed = Vec::new();
+    for dump in dumps {
+        match store.delete(&dump.r2_key).await {
+            Ok(()) => deleted.push(dump),
+            Err(e) => {
+                tracing::error!(?e, key = %dump.r2_key, "retention delete_objects");
+                failed.push(dump.r2_key);
+            }
+        }
+    }
+    (deleted, failed)
+}
+
+/// Deletes the `DumpDoc`s of dumps whose objects are already gone and drops
+/// them from their groups' `dump_ids`, which frees the slots for new dumps.
+/// Group counts and `build_hits` are history and stay as they are.
+pub async fn forget_dumps(dumps: &[DumpDoc]) -> anyhow::Result<()> {
+    let db = doc_db::turso();
+    let mut by_group: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for dump in dumps {
+        (DumpDocDelete {
+            dump_id: dump.dump_id.clone(),
+        })
+        .send_with(&db)
+        .await?;
+        by_group
+            .entry(&dump.stack_hash)
+            .or_default()
+            .insert(&dump.dump_id);
+    }
+    for (stack_hash, dump_ids) in by_group {
+        let Some(mut group) = (StackGroupDocGet {
+            stack_hash: stack_hash.to_string(),
+        })
+        .send_with(&db)
+        .await?
+        else {
+            continue;
+        };
+        group.dump_ids.retain(|id| !dump_ids.contains(id.as_str()));
+        StackGroupDocPut(group).send_with(&db).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::storage::MemoryObjectStore;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    fn day(n: i64) -> DateTime {
+        chrono::DateTime::from_timestamp(n * 86_400, 0).unwrap()
+    }
+
+    fn build(build_id: &str, retired_at: Option<DateTime>) -> BuildDoc {
+        BuildDoc {
+            build_id: build_id.to_string(),
+            created_at: day(0),
+            uploaded_by: 0,
+            hmac_key_hex: String::new(),
+            pdb_uploaded: false,
+            pdb_r2_key: None,
+            pdb_size: None,
+            retired_at,
+        }
+    }
+
+    fn dump(dump_id: &str, stack_hash: &str, build_id: &str, uploaded_at: DateTime) -> DumpDoc {
+        DumpDoc {
+            dump_id: dump_id.to_string(),
+            stack_hash: stack_hash.to_string(),
+            build_id: build_id.to_string(),
+            uploaded_at,
+            r2_key: format!("dump/{stack_hash}/{dump_id}.dmp"),
+            context: CrashContext {
+                build_id: build_id.to_string(),
+                install_id: String::new(),
+                session_uptime_sec: 0,
+                log_tail: None,
+                kind: CrashKind::Native,
+                panic: None,
+                breadcrumbs: Vec::new(),
+            },
+            client_ip: String::new(),
+            processing: DumpProcessing::Pending,
+        }
+    }
+
+    fn ids(dumps: &[&DumpDoc]) -> Vec<String> {
+        dumps.iter().map(|d| d.dump_id.clone()).collect()
+    }
+
+    /// `MemoryObjectStore` never awaits anything, so one poll finishes.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future did not finish in one poll"),
+        }
+    }
+
+    #[test]
+    fn default_policy_only_caps_groups() {
+        let dumps = [
+            dump("a1", "a", "b1", day(1)),
+            dump("a2", "a", "b1", day(2)),
+            dump("a3", "a", "b1", day(3)),
+            dump("a4", "a", "b1", day(4)),
+            dump("b1", "b", "b1", day(1)),
+        ];
+        let expired = expired_dumps(
+            &RetentionPolicyDoc::default(),
+            day(1000),
+            &[build("b1", Some(day(5)))],
+            &dumps,
+        );
+        assert_eq!(ids(&expired), ["a1"]);
+    }
+
+    #[test]
+    fn age_and_retired_build_rules() {
+        let policy = RetentionPolicyDoc {
+            max_dump_age_days: Some(30),
+            retired_build_keep_days: Some(7),
+            ..Default::default()
+        };
+        let builds = [
+            build("old", Some(day(90))),
+            build("recent", Some(day(98))),
+            build("live", None),
+        ];
+        let dumps = [
+            dump("stale", "a", "live", day(60)),
+            dump("retired", "b", "old", day(95)),
+            dump("grace", "c", "recent", day(95)),
+            dump("fresh", "d", "live", day(95)),
+        ];
+        let expired = expired_dumps(&policy, day(100), &builds, &dumps);
+        assert_eq!(ids(&expired), ["stale", "retired"]);
+    }
+
+    #[test]
+    fn expired_dumps_free_group_slots() {
+        let policy = RetentionPolicyDoc {
+            max_dumps_per_group: 2,
+            max_dump_age_days: Some(30),
+            ..Default::default()
+        };
+        let dumps = [
+            dump("old", "a", "b1", day(10)),
+            dump("new1", "a", "b1", day(90)),
+            dump("new2", "a", "b1", day(95)),
+        ];
+        let expired = expired_dumps(&policy, day(100), &[], &dumps);
+        assert_eq!(ids(&expired), ["old"]);
+    }
+
+    #[test]
+    fn dumps_still_uploading_are_kept() {
+        let policy = RetentionPolicyDoc {
+            max_dumps_per_group: 0,
+            retired_build_keep_days: Some(0),
+            ..Default::default()
+        };
+        let now = day(100);
+        let dumps = [
+            dump("uploaded", "a", "b1", now - chrono::Duration::minutes(30)),
+            dump("uploading", "a", "b1", now - chrono::Duration::minutes(5)),
+            dump("retired", "b", "old", now - chrono::Duration::minutes(5)),
+        ];
+        let expired = expired_dumps(&policy, now, &[build("old", Some(day(90)))], &dumps);
+        assert_eq!(ids(&expired), ["uploaded"]);
+    }
+
+    #[test]
+    fn refused_deletes_are_reported() {
+        let mut store =
+            MemoryObjectStore::with_objects(["dump/a/1.dmp", "dump/a/2.dmp", "pdb/b1.pdb"]);
+        store.failing.push("dump/a/2.dmp".to_string());
+        let dumps = vec![
+            dump("1", "a", "b1", day(1)),
+            dump("2", "a", "b1", day(2)),
+            dump("3", "a", "b1", day(3)),
+        ];
+
+        let (deleted, failed) = block_on(delete_objects(&store, dumps));
+
+        assert_eq!(
+            deleted
+                .iter()
+                .map(|d| d.dump_id.as_str())
+                .collect::<Vec<_>>(),
+            ["1", "3"]
+        );
+        assert_eq!(failed, ["dump/a/2.dmp"]);
+        assert_eq!(store.keys(), ["dump/a/2.dmp", "pdb/b1.pdb"]);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::docs::*;
+
+/// The `status` filters [`matches_filter`] knows, plus the empty one.
+pub const STATUS_FILTERS: [&str; 5] = ["", "open", "regressed", "resolved", "ignored"];
+
+/// Filters shared by `list_stack_groups` and the `/` page. `status` is one of
+/// `open` (regressed groups included), `regressed`, `resolved` or `ignored`;
+/// `os` keeps groups with a processed dump from that OS. `None` or an empty
+/// string matches everything, for any filter.
+pub fn matches_filter(
+    group: &StackGroupDoc,
+    status: Option<&str>,
+    build_id: Option<&str>,
+    os: Option<&str>,
+) -> bool {
+    let status_matches = match status.unwrap_or_default() {
+        "" => true,
+        "open" => matches!(
+            group.status,
+            StackGroupStatus::Open | StackGroupStatus::Regressed { .. }
+        ),
+        "regressed" => matches!(group.status, StackGroupStatus::Regressed { .. }),
+        "resolved" => matches!(group.status, StackGroupStatus::Resolved { .. }),
+        "ignored" => matches!(group.status, StackGroupStatus::Ignored),
+        _ => false,
+    };
+    let build_matches = match build_id.unwrap_or_default() {
+        "" => true,
+        build_id => group.seen_in_build(build_id),
+    };
+    let os_matches = match os.unwrap_or_default() {
+        "" => true,
+        os => group.platforms.iter().any(|p| p.os == os),
+    };
+    status_matches && build_matches && os_matches
+}
+
+/// A group's status after a hit from `build`, SPEC §4.1. `resolved_build` is
+/// the `BuildDoc` of the build a `Resolved` group names, if it still exists.
+/// Build ids are free-form, so "later" means registered later; a resolving
+/// build that's gone can't be compared and leaves the group resolved.
+pub fn status_after_hit(
+    status: StackGroupStatus,
+    build: &BuildDoc,
+    resolved_build: Option<&BuildDoc>,
+) -> StackGroupStatus {
+    match status {
+        StackGroupStatus::Resolved {
+            build_id: resolved_build_id,
+        } if resolved_build_id != build.build_id
+            && resolved_build.is_some_and(|r| build.created_at > r.created_at) =>
+        {
+            StackGroupStatus::Regressed {
+                resolved_build_id,
+                build_id: build.build_id.clone(),
+            }
+        }
+        status => status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(build_id: &str, created_hour: i64) -> BuildDoc {
+        BuildDoc {
+            build_id: build_id.to_string(),
+            created_at: chrono::DateTime::from_timestamp(created_hour * 3600, 0).unwrap(),
+            uploaded_by: 0,
+            hmac_key_hex: String::new(),
+            pdb_uploaded: false,
+            pdb_r2_key: None,
+            pdb_size: None,
+            retired_at: None,
+        }
+    }
+
+    fn resolved(build_id: &str) -> StackGroupStatus {
+        StackGroupStatus::Resolved {
+            build_id: build_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn later_build_regresses_resolved_group() {
+        let status = status_after_hit(resolved("r"), &build("b", 2), Some(&build("r", 1)));
+        assert!(
+            status
+                == StackGroupStatus::Regressed {
+                    resolved_build_id: "r".to_string(),
+                    build_id: "b".to_string(),
+                }
+        );
+    }
+
+    #[test]
+    fn older_or_resolving_build_leaves_group_resolved() {
+        let r = build("r", 1);
+        assert!(status_after_hit(resolved("r"), &build("old", 0), Some(&r)) == resolved("r"));
+        assert!(status_after_hit(resolved("r"), &r, Some(&r)) == resolved("r"));
+    }
+
+    #[test]
+    fn missing_resolving_build_leaves_group_resolved() {
+        assert!(status_after_hit(resolved("r"), &build("b", 2), None) == resolved("r"));
+    }
+
+    #[test]
+    fn other_statuses_are_kept() {
+        let b = build("b", 2);
+        for status in [
+            StackGroupStatus::Open,
+            StackGroupStatus::Ignored,
+            StackGroupStatus::Regressed {
+                resolved_build_id: "r".to_string(),
+                build_id: "a".to_string(),
+            },
+        ] {
+            assert!(status_after_hit(status.clone(), &b, None) == status);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// The part of object storage that GC, purges and dump processing need, so
+/// they can run against `MemoryObjectStore` instead of R2.
+pub(crate) trait ObjectStore {
+    /// `None` for a missing key, e.g. a dump whose upload hasn't happened.
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>>;
+    /// Deleting a missing key succeeds; a dump whose upload never happened
+    /// has no object.
+    async fn delete(&self, key: &str) -> anyhow::Result<()>;
+}
+
+pub(crate) struct R2;
+
+impl ObjectStore for R2 {
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(object_storage::private::bucket().get(key).await?)
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        object_storage::private::bucket().delete(key).await?;
+        Ok(())
+    }
+}
+
+/// Local stand-in for R2. Keys listed in `failing` refuse to be deleted.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MemoryObjectStore {
+    pub objects: RefCell<BTreeMap<String, Vec<u8>>>,
+    pub failing: Vec<String>,
+}
+
+#[cfg(test)]
+impl MemoryObjectStore {
+    pub fn with_objects<'a>(keys: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            objects: RefCell::new(
+                keys.into_iter()
+                    .map(|k| (k.to_string(), Vec::new()))
+                    .collect(),
+            ),
+            failing: Vec::new(),
+        }
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        self.objects.borrow().keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+impl ObjectStore for MemoryObjectStore {
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.objects.borrow().get(key).cloned())
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        if self.failing.iter().any(|k| k == key) {
+            anyhow::bail!("delete refused: {key}");
+        }
+        self.objects.borrow_mut().remove(key);
+        Ok(())
+    }
+}
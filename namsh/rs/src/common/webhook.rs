@@ -0,0 +1,639 @@
+use crate::docs::*;
+use forte_sdk::*;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Waits before the second, third, ... attempt. A delivery that fails the
+/// attempt after the last wait is given up on.
+const RETRY_DELAY_MINUTES: [i64; 5] = [1, 5, 30, 2 * 60, 12 * 60];
+const DELIVERIES_PER_RUN: usize = 50;
+const FINISHED_DELIVERY_KEEP_DAYS: i64 = 30;
+/// How long a delivery being attempted stays out of other runs. Long enough
+/// for the POST; a run that dies mid-POST leaves it due again after this.
+const DELIVERY_LEASE_MINUTES: i64 = 5;
+
+/// What one intake did to its group.
+pub struct Occurrence {
+    pub new_group: bool,
+    pub regressed: bool,
+    pub hour_hits: u64,
+}
+
+pub fn events_for(webhook: &WebhookDoc, occurrence: &Occurrence) -> Vec<WebhookEvent> {
+    webhook
+        .events
+        .iter()
+        .copied()
+        .filter(|event| match event {
+            WebhookEvent::NewGroup => occurrence.new_group,
+            WebhookEvent::Regressed => occurrence.regressed,
+            // Only the hit that reaches the threshold, so a spike fires once
+            // an hour instead of on every hit past it.
+            WebhookEvent::Spike => webhook.spike_hits_per_hour == Some(occurrence.hour_hits),
+        })
+        .collect()
+}
+
+/// Whether `url` may be a webhook target: `http(s)` to a host that isn't
+/// loopback, link-local, private or otherwise internal, so webhooks can't be
+/// used to reach namsh's own network. Names are checked as written; one that
+/// resolves to an internal address is only caught by the network it runs on.
+pub fn is_public_url(url: &str) -> bool {
+    let Ok(uri) = url.parse::<::http::Uri>() else {
+        return false;
+    };
+    if !matches!(uri.scheme_str(), Some("https" | "http")) {
+        return false;
+    }
+    let Some(host) = uri.host() else {
+        return false;
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return is_public_ip(ip);
+    }
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    // Single labels are intranet names, and a numeric last label is an IPv4
+    // address in another notation (`0x7f.1`, `2130706433`).
+    let Some((_, tld)) = host.rsplit_once('.') else {
+        return false;
+    };
+    let numeric_tld = tld.starts_with("0x") || tld.bytes().all(|b| b.is_ascii_digit());
+    !numeric_tld && !matches!(tld, "localhost" | "local" | "internal")
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(ip),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    let shared = a == 100 && (64..128).contains(&b);
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || shared
+        || a == 0)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local())
+}
+
+pub fn event_name(event: WebhookEvent) -> &'static str {
+    match event {
+        WebhookEvent::NewGroup => "new_group",
+        WebhookEvent::Regressed => "regressed",
+        WebhookEvent::Spike => "spike",
+    }
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    event: &'static str,
+    delivery_id: &'a str,
+    stack_hash: &'a str,
+    /// `open`, `resolved`, `ignored` or `regressed`.
+    status: &'static str,
+    count: u64,
+    hour_hits: u64,
+    first_seen: DateTime,
+    last_seen: DateTime,
+    build_id: &'a str,
+    kind: CrashKind,
+    panic_message: Option<&'a str>,
+    issue_path: String,
+}
+
+pub fn payload(event: WebhookEvent, delivery_id: &str, group: &StackGroupDoc) -> String {
+    let context = &group.latest_context;
+    let payload = Payload {
+        event: event_name(event),
+        delivery_id,
+        stack_hash: &group.stack_hash,
+        status: match group.status {
+            StackGroupStatus::Open => "open",
+            StackGroupStatus::Resolved { .. } => "resolved",
+            StackGroupStatus::Ignored => "ignored",
+            StackGroupStatus::Regressed { .. } => "regressed",
+        },
+        count: group.count,
+        hour_hits: group.hour_hits.as_ref().map_or(0, |h| h.count),
+        first_seen: group.first_seen,
+        last_seen: group.last_seen,
+        build_id: &context.build_id,
+        kind: context.kind,
+        panic_message: context.panic.as_ref().map(|p| p.message.as_str()),
+        issue_path: format!("/issues/{}", group.stack_hash),
+    };
+    serde_json::to_string(&payload).expect("payload serializes")
+}
+
+/// Hex HMAC-SHA256 of the body, sent as `X-Namsh-Signature`.
+pub fn sign(secret_hex: &str, body: &[u8]) -> Option<String> {
+    let key = hex::decode(secret_hex).ok()?;
+    let mut mac = HmacSha256::new_from_slice(&key).ok()?;
+    mac.update(body);
+    Some(hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Records a failed attempt and schedules the next one, if any is left.
+pub fn record_failure(delivery: &mut WebhookDeliveryDoc, now: DateTime, response: String) {
+    delivery.attempts += 1;
+    delivery.last_response = Some(response);
+    match RETRY_DELAY_MINUTES.get(delivery.attempts as usize - 1) {
+        Some(minutes) => delivery.next_attempt_at = now + chrono::Duration::minutes(*minutes),
+        None => delivery.status = DeliveryStatus::Failed,
+    }
+}
+
+pub fn new_id() -> anyhow::Result<String> {
+    let bytes = rand::get_random_bytes(16);
+    let uuid_bytes: [u8; 16] = bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("rng returned wrong length"))?;
+    Ok(Uuid::from_bytes(uuid_bytes).to_string())
+}
+
+/// Queues a delivery for every event a webhook subscribes to. Nothing is sent
+/// here; `deliver_due` does that.
+pub async fn enqueue(group: &StackGroupDoc, occurrence: &Occurrence) -> anyhow::Result<()> {
+    let db = doc_db::turso();
+    let webhooks: Vec<WebhookDoc> = (WebhookDocQuery {
+        id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?;
+    let now = forte_sdk::now();
+    for webhook in webhooks {
+        for event in events_for(&webhook, occurrence) {
+            let delivery_id = new_id()?;
+            WebhookDeliveryDocPut(WebhookDeliveryDoc {
+                payload: payload(event, &delivery_id, group),
+                delivery_id,
+                webhook_id: webhook.id.clone(),
+                event,
+                stack_hash: group.stack_hash.clone(),
+                created_at: now,
+                status: DeliveryStatus::Pending,
+                attempts: 0,
+                next_attempt_at: now,
+                last_response: None,
+                leased_by: None,
+            })
+            .send_with(&db)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Default)]
+pub struct DeliveryCounts {
+    pub delivered: usize,
+    pub retrying: usize,
+    pub failed: usize,
+    pub pruned: usize,
+}
+
+/// Attempts the oldest due deliveries, at most `DELIVERIES_PER_RUN`, and
+/// deletes finished ones older than `FINISHED_DELIVERY_KEEP_DAYS`.
+pub async fn deliver_due(now: DateTime) -> anyhow::Result<DeliveryCounts> {
+    let db = doc_db::turso();
+    let deliveries: Vec<WebhookDeliveryDoc> = (WebhookDeliveryDocQuery {
+        delivery_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?;
+
+    let mut counts = DeliveryCounts::default();
+    let prune_cutoff = now - chrono::Duration::days(FINISHED_DELIVERY_KEEP_DAYS);
+    let mut due = Vec::new();
+    for delivery in deliveries {
+        if delivery.status == DeliveryStatus::Pending {
+            if delivery.next_attempt_at <= now {
+                due.push(delivery);
+            }
+        } else if delivery.created_at < prune_cutoff {
+            (WebhookDeliveryDocDelete {
+                delivery_id: delivery.delivery_id,
+            })
+            .send_with(&db)
+            .await?;
+            counts.pruned += 1;
+        }
+    }
+    due.sort_by(|a, b| a.next_attempt_at.cmp(&b.next_attempt_at));
+    due.truncate(DELIVERIES_PER_RUN);
+
+    // doc_db has no conditional write, so a lease is a put that is read back.
+    // Each delivery is re-read and skipped unless still due, leased to this
+    // run before the first POST, and read again right before its own POST;
+    // if another run's lease replaced ours, that run sends it. Two runs that
+    // interleave exactly between those reads can still both send it, which
+    // receivers can drop by `X-Namsh-Delivery`.
+    let run_id = new_id()?;
+    let lease_until = now + chrono::Duration::minutes(DELIVERY_LEASE_MINUTES);
+    let mut leased = Vec::new();
+    for delivery in due {
+        let Some(mut delivery) = (WebhookDeliveryDocGet {
+            delivery_id: delivery.delivery_id,
+        })
+        .send_with(&db)
+        .await?
+        else {
+            continue;
+        };
+        if delivery.status != DeliveryStatus::Pending || delivery.next_attempt_at > now {
+            continue;
+        }
+        delivery.next_attempt_at = lease_until;
+        delivery.leased_by = Some(run_id.clone());
+        WebhookDeliveryDocPut(delivery.clone())
+            .send_with(&db)
+            .await?;
+        leased.push(delivery);
+    }
+
+    let webhooks: BTreeMap<String, WebhookDoc> = (WebhookDocQuery {
+        id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?
+    .into_iter()
+    .map(|w| (w.id.clone(), w))
+    .collect();
+    for mut delivery in leased {
+        let current = (WebhookDeliveryDocGet {
+            delivery_id: delivery.delivery_id.clone(),
+        })
+        .send_with(&db)
+        .await?;
+        if current.is_none_or(|d| d.leased_by.as_ref() != Some(&run_id)) {
+            continue;
+        }
+        match webhooks.get(&delivery.webhook_id) {
+            None => {
+                delivery.status = DeliveryStatus::Failed;
+                delivery.last_response = Some("webhook deleted".to_string());
+            }
+            Some(webhook) if !is_public_url(&webhook.url) => {
+                delivery.status = DeliveryStatus::Failed;
+                delivery.last_response = Some("webhook url is not public".to_string());
+            }
+            Some(webhook) => attempt(&ForteHttp, webhook, &mut delivery, now).await,
+        }
+        match delivery.status {
+            DeliveryStatus::Pending => counts.retrying += 1,
+            DeliveryStatus::Delivered => counts.delivered += 1,
+            DeliveryStatus::Failed => counts.failed += 1,
+        }
+        WebhookDeliveryDocPut(delivery).send_with(&db).await?;
+    }
+    Ok(counts)
+}
+
+/// The part of HTTP that deliveries need, so `attempt` can run against a
+/// local listener instead of going through the host.
+pub(crate) trait HttpSend {
+    /// The response's status code; `Err` when there was no response.
+    async fn send(&self, request: http::Request<Vec<u8>>) -> Result<u16, String>;
+}
+
+pub(crate) struct ForteHttp;
+
+impl HttpSend for ForteHttp {
+    async fn send(&self, request: http::Request<Vec<u8>>) -> Result<u16, String> {
+        let resp = http::Client::new()
+            .send(request)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(resp.status().as_u16())
+    }
+}
+
+/// POSTs a delivery and records the outcome on it.
+async fn attempt(
+    client: &impl HttpSend,
+    webhook: &WebhookDoc,
+    delivery: &mut WebhookDeliveryDoc,
+    now: DateTime,
+) {
+    match post(client, webhook, delivery).await {
+        Ok(response) => {
+            delivery.attempts += 1;
+            delivery.status = DeliveryStatus::Delivered;
+            delivery.last_response = Some(response);
+        }
+        Err(response) => record_failure(delivery, now, response),
+    }
+}
+
+/// `Ok` and `Err` both carry what to show as the attempt's response.
+async fn post(
+    client: &impl HttpSend,
+    webhook: &WebhookDoc,
+    delivery: &WebhookDeliveryDoc,
+) -> Result<String, String> {
+    let signature = sign(&webhook.secret_hex, delivery.payload.as_bytes())
+        .ok_or_else(|| "invalid webhook secret".to_string())?;
+    let request = http::Request::builder()
+        .uri(&webhook.url)
+        .method("POST")
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "namsh")
+        .header("X-Namsh-Event", event_name(delivery.event))
+        .header("X-Namsh-Delivery", &delivery.delivery_id)
+        .header("X-Namsh-Signature", signature)
+        .body(delivery.payload.clone().into_bytes())
+        .map_err(|e| e.to_string())?;
+    let status = client.send(request).await?;
+    let response = format!("HTTP {status}");
+    if (200..300).contains(&status) {
+        Ok(response)
+    } else {
+        Err(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::pin::pin;
+    use std::sync::mpsc;
+    use std::task::{Context, Poll, Waker};
+    use std::thread;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        match future
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()))
+        {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future did not finish in one poll"),
+        }
+    }
+
+    /// Sends with blocking std IO, so `attempt` finishes in one poll.
+    struct StdHttp;
+
+    impl HttpSend for StdHttp {
+        async fn send(&self, request: http::Request<Vec<u8>>) -> Result<u16, String> {
+            let authority = request.uri().authority().ok_or("no host")?.to_string();
+            let mut stream = TcpStream::connect(&authority).map_err(|e| e.to_string())?;
+            let mut head = format!(
+                "{} {} HTTP/1.1\r\nHost: {authority}\r\nConnection: close\r\nContent-Length: {}\r\n",
+                request.method(),
+                request.uri().path(),
+                request.body().len()
+            );
+            for (name, value) in request.headers() {
+                head += &format!("{}: {}\r\n", name.as_str(), value.to_str().unwrap());
+            }
+            head += "\r\n";
+            stream
+                .write_all(head.as_bytes())
+                .and_then(|()| stream.write_all(request.body()))
+                .map_err(|e| e.to_string())?;
+            let mut response = String::new();
+            stream
+                .read_to_string(&mut response)
+                .map_err(|e| e.to_string())?;
+            response
+                .split(' ')
+                .nth(1)
+                .and_then(|code| code.parse().ok())
+                .ok_or_else(|| format!("bad response: {response}"))
+        }
+    }
+
+    /// A local webhook receiver answering one request per status, in order.
+    /// Hands back each request's headers, with lowercased names, and body.
+    fn receiver(
+        statuses: Vec<u16>,
+    ) -> (String, mpsc::Receiver<(BTreeMap<String, String>, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut headers = BTreeMap::new();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    let Some((name, value)) = line.trim_end().split_once(": ") else {
+                        break;
+                    };
+                    headers.insert(name.to_ascii_lowercase(), value.to_string());
+                }
+                let mut body = vec![0; headers["content-length"].parse().unwrap()];
+                reader.read_exact(&mut body).unwrap();
+                reader
+                    .get_mut()
+                    .write_all(
+                        format!(
+                            "HTTP/1.1 {status} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        )
+                        .as_bytes(),
+                    )
+                    .unwrap();
+                tx.send((headers, body)).unwrap();
+            }
+        });
+        (url, rx)
+    }
+
+    fn day(n: i64) -> DateTime {
+        chrono::DateTime::from_timestamp(n * 86_400, 0).unwrap()
+    }
+
+    fn webhook(events: Vec<WebhookEvent>, spike_hits_per_hour: Option<u64>) -> WebhookDoc {
+        WebhookDoc {
+            id: "w".to_string(),
+            url: "http://127.0.0.1:9000/hook".to_string(),
+            secret_hex: "00".repeat(32),
+            events,
+            spike_hits_per_hour,
+            created_by: 0,
+            created_at: day(0),
+        }
+    }
+
+    fn delivery() -> WebhookDeliveryDoc {
+        WebhookDeliveryDoc {
+            delivery_id: "d".to_string(),
+            webhook_id: "w".to_string(),
+            event: WebhookEvent::NewGroup,
+            stack_hash: "abc".to_string(),
+            payload: "{}".to_string(),
+            created_at: day(0),
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: day(0),
+            last_response: None,
+            leased_by: None,
+        }
+    }
+
+    #[test]
+    fn events_follow_subscription() {
+        let hook = webhook(vec![WebhookEvent::NewGroup, WebhookEvent::Spike], Some(10));
+        let new_group = Occurrence {
+            new_group: true,
+            regressed: false,
+            hour_hits: 1,
+        };
+        assert!(events_for(&hook, &new_group) == [WebhookEvent::NewGroup]);
+
+        let regressed = Occurrence {
+            new_group: false,
+            regressed: true,
+            hour_hits: 2,
+        };
+        assert!(events_for(&hook, &regressed).is_empty());
+    }
+
+    #[test]
+    fn spike_fires_once_at_threshold() {
+        let hook = webhook(vec![WebhookEvent::Spike], Some(10));
+        let fired: Vec<u64> = (1..=20)
+            .filter(|&hour_hits| {
+                let occurrence = Occurrence {
+                    new_group: false,
+                    regressed: false,
+                    hour_hits,
+                };
+                !events_for(&hook, &occurrence).is_empty()
+            })
+            .collect();
+        assert_eq!(fired, [10]);
+    }
+
+    #[test]
+    fn signature_matches_hmac_of_body() {
+        let body = br#"{"event":"new_group"}"#;
+        let signature = sign(&"00".repeat(32), body).unwrap();
+
+        let mut mac = HmacSha256::new_from_slice(&[0; 32]).unwrap();
+        mac.update(body);
+        assert!(mac.verify_slice(&hex::decode(signature).unwrap()).is_ok());
+        assert!(sign("not hex", body).is_none());
+    }
+
+    #[test]
+    fn public_urls_only() {
+        for url in [
+            "https://hooks.slack.com/services/x",
+            "http://example.com:8080/hook",
+            "https://8.8.8.8/hook",
+            "https://[2001:4860::8888]/hook",
+        ] {
+            assert!(is_public_url(url), "{url}");
+        }
+        for url in [
+            "ftp://example.com/hook",
+            "not a url",
+            "http://127.0.0.1:9000/hook",
+            "http://localhost/hook",
+            "http://api.localhost/hook",
+            "http://metadata/computeMetadata",
+            "http://169.254.169.254/latest/meta-data",
+            "http://10.0.0.1/hook",
+            "http://172.16.0.1/hook",
+            "http://192.168.1.1/hook",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://2130706433/hook",
+            "http://0x7f.1/hook",
+            "http://printer.local/hook",
+            "http://[::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(!is_public_url(url), "{url}");
+        }
+    }
+
+    #[test]
+    fn failures_back_off_then_give_up() {
+        let mut d = delivery();
+        for minutes in RETRY_DELAY_MINUTES {
+            record_failure(&mut d, day(1), "HTTP 500".to_string());
+            assert!(d.status == DeliveryStatus::Pending);
+            assert_eq!(
+                d.next_attempt_at,
+                day(1) + chrono::Duration::minutes(minutes)
+            );
+        }
+        record_failure(&mut d, day(1), "HTTP 500".to_string());
+        assert!(d.status == DeliveryStatus::Failed);
+        assert_eq!(d.attempts, RETRY_DELAY_MINUTES.len() as u32 + 1);
+        assert_eq!(d.last_response.as_deref(), Some("HTTP 500"));
+    }
+
+    #[test]
+    fn attempt_posts_signed_payload_and_retries_after_500() {
+        let (url, requests) = receiver(vec![500, 200]);
+        let hook = WebhookDoc {
+            url,
+            ..webhook(vec![WebhookEvent::NewGroup], None)
+        };
+        let mut d = WebhookDeliveryDoc {
+            payload: r#"{"event":"new_group"}"#.to_string(),
+            ..delivery()
+        };
+
+        block_on(attempt(&StdHttp, &hook, &mut d, day(1)));
+        let (headers, body) = requests.recv().unwrap();
+        assert_eq!(body, d.payload.as_bytes());
+        assert_eq!(
+            headers["x-namsh-signature"],
+            sign(&hook.secret_hex, &body).unwrap()
+        );
+        assert_eq!(headers["x-namsh-delivery"], "d");
+        assert_eq!(headers["x-namsh-event"], "new_group");
+        assert!(d.status == DeliveryStatus::Pending);
+        assert_eq!(d.attempts, 1);
+        assert_eq!(d.next_attempt_at, day(1) + chrono::Duration::minutes(1));
+        assert_eq!(d.last_response.as_deref(), Some("HTTP 500"));
+
+        let retry_at = d.next_attempt_at;
+        block_on(attempt(&StdHttp, &hook, &mut d, retry_at));
+        let (headers, _) = requests.recv().unwrap();
+        assert_eq!(headers["x-namsh-delivery"], "d");
+        assert!(d.status == DeliveryStatus::Delivered);
+        assert_eq!(d.attempts, 2);
+        assert_eq!(d.last_response.as_deref(), Some("HTTP 200"));
+    }
+}
@@ -1,5 +1,6 @@
 use forte_sdk::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 pub use doc_db::DbRequest;
 
@@ -22,6 +23,35 @@ pub struct CrashContext {
     pub install_id: String,
     pub session_uptime_sec: u64,
     pub log_tail: Option<String>,
+    #[serde(default)]
+    pub kind: CrashKind,
+    #[serde(default)]
+    pub panic: Option<PanicDetails>,
+    #[serde(default)]
+    pub breadcrumbs: Vec<Breadcrumb>,
+}
+
+/// Reports from before `kind` existed are all minidumps, hence the default.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CrashKind {
+    #[default]
+    Native,
+    Panic,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PanicDetails {
+    pub message: String,
+    pub location: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Breadcrumb {
+    pub timestamp_unix_ms: u64,
+    pub category: String,
+    pub message: String,
+    pub data: BTreeMap<String, String>,
 }
 
 #[forte_doc]
@@ -44,6 +74,28 @@ pub struct BuildDoc {
     pub pdb_uploaded: bool,
     pub pdb_r2_key: Option<String>,
     pub pdb_size: Option<u64>,
+    /// Set once nobody should be running the build any more; its dumps then
+    /// expire per `RetentionPolicyDoc::retired_build_keep_days`.
+    #[serde(default)]
+    pub retired_at: Option<DateTime>,
+}
+
+/// Groups from before `status` existed are all open, hence the default.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub enum StackGroupStatus {
+    #[default]
+    Open,
+    /// Fixed as of `build_id`. A hit from a build created after it reopens the
+    /// group as `Regressed`.
+    Resolved {
+        build_id: String,
+    },
+    Ignored,
+    /// Was resolved in `resolved_build_id`, then hit again by `build_id`.
+    Regressed {
+        resolved_build_id: String,
+        build_id: String,
+    },
 }
 
 #[forte_doc]
@@ -55,6 +107,51 @@ pub struct StackGroupDoc {
     pub count: u64,
     pub dump_ids: Vec<String>,
     pub latest_context: CrashContext,
+    #[serde(default)]
+    pub status: StackGroupStatus,
+    /// Every build that hit the group, oldest first. Groups from before this
+    /// field only know `latest_context.build_id`.
+    #[serde(default)]
+    pub build_ids: Vec<String>,
+    /// Per-build hits. Groups from before this field only have their stored
+    /// dumps to go on; see `common::build_stats`.
+    #[serde(default)]
+    pub build_hits: Vec<BuildHits>,
+    /// Hits within the current hour, for `WebhookEvent::Spike`.
+    #[serde(default)]
+    pub hour_hits: Option<HourHits>,
+    /// Processed dumps per OS and CPU arch. Only stored dumps get processed,
+    /// so these are samples, not hit counts.
+    #[serde(default)]
+    pub platforms: Vec<PlatformDumps>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PlatformDumps {
+    pub os: String,
+    pub cpu_arch: String,
+    pub dumps: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HourHits {
+    /// Hours since the Unix epoch.
+    pub hour: i64,
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BuildHits {
+    pub build_id: String,
+    pub count: u64,
+    /// Distinct `install_id`s, bounded to `MAX_INSTALLS_PER_BUILD`.
+    pub install_ids: Vec<String>,
+}
+
+impl StackGroupDoc {
+    pub fn seen_in_build(&self, build_id: &str) -> bool {
+        self.build_ids.iter().any(|b| b == build_id) || self.latest_context.build_id == build_id
+    }
 }
 
 #[forte_doc]
@@ -67,6 +164,116 @@ pub struct DumpDoc {
     pub r2_key: String,
     pub context: CrashContext,
     pub client_ip: String,
+    #[serde(default)]
+    pub processing: DumpProcessing,
+}
+
+/// Dumps from before processing existed are pending, hence the default.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub enum DumpProcessing {
+    #[default]
+    Pending,
+    Processed {
+        info: MinidumpInfo,
+    },
+    Failed {
+        reason: String,
+    },
+    /// A panic report; there's nothing to process.
+    NotMinidump,
+}
+
+/// Read from the minidump's system-info and exception streams.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MinidumpInfo {
+    /// e.g. `Windows NT`, `Linux`.
+    pub os: String,
+    pub os_version: String,
+    /// e.g. `amd64`, `arm64`.
+    pub cpu_arch: String,
+    pub exception_code: u32,
+    /// e.g. `EXCEPTION_ACCESS_VIOLATION_READ`.
+    pub exception_name: String,
+    /// Basename of the module holding the crashing instruction, if any.
+    pub crashing_module: Option<String>,
+}
+
+pub const RETENTION_POLICY_ID: &str = "default";
+
+/// The single policy, at `RETENTION_POLICY_ID`. Until an admin sets one, the
+/// default keeps everything but caps each group at 3 dumps.
+#[forte_doc]
+pub struct RetentionPolicyDoc {
+    #[sk]
+    pub id: String,
+    pub max_dumps_per_group: usize,
+    /// Dumps uploaded longer ago than this are deleted.
+    pub max_dump_age_days: Option<u32>,
+    /// Dumps of a build retired longer ago than this are deleted.
+    pub retired_build_keep_days: Option<u32>,
+}
+
+impl Default for RetentionPolicyDoc {
+    fn default() -> Self {
+        Self {
+            id: RETENTION_POLICY_ID.to_string(),
+            max_dumps_per_group: 3,
+            max_dump_age_days: None,
+            retired_build_keep_days: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    /// A stack group's first hit.
+    NewGroup,
+    /// A resolved group hit by a later build; see `StackGroupStatus::Regressed`.
+    Regressed,
+    /// A group's hits within the hour reached `WebhookDoc::spike_hits_per_hour`.
+    Spike,
+}
+
+#[forte_doc]
+pub struct WebhookDoc {
+    #[sk]
+    pub id: String,
+    pub url: String,
+    /// Key for the `X-Namsh-Signature` HMAC; only shown when created.
+    pub secret_hex: String,
+    pub events: Vec<WebhookEvent>,
+    pub spike_hits_per_hour: Option<u64>,
+    pub created_by: i64,
+    pub created_at: DateTime,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    /// Out of attempts, or the webhook was deleted.
+    Failed,
+}
+
+#[forte_doc]
+pub struct WebhookDeliveryDoc {
+    #[sk]
+    pub delivery_id: String,
+    pub webhook_id: String,
+    pub event: WebhookEvent,
+    pub stack_hash: String,
+    /// Exactly the bytes that are signed and POSTed.
+    pub payload: String,
+    pub created_at: DateTime,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime,
+    /// Status code or error of the latest attempt.
+    pub last_response: Option<String>,
+    /// `deliver_due` run that last leased it, to tell whether a lease it
+    /// wrote survived.
+    #[serde(default)]
+    pub leased_by: Option<String>,
 }
 
 #[forte_doc]
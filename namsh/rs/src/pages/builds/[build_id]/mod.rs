@@ -0,0 +1,79 @@
+use crate::actions::get_build_stats;
+use crate::common::auth;
+use crate::common::build_stats::{BuildDiff, BuildStats};
+use crate::docs::*;
+use crate::route_generated::Redirect;
+use forte_sdk::*;
+use serde::Serialize;
+
+pub struct PathParams {
+    pub build_id: String,
+}
+
+pub struct SearchParams {
+    /// Build to diff against. Ignored unless registered.
+    pub base: Option<String>,
+}
+
+#[derive(Serialize)]
+pub enum Props {
+    Ok {
+        github_login: String,
+        created_at: DateTime,
+        /// `groups` cut to the top `get_build_stats::TOP_GROUPS`.
+        stats: BuildStats,
+        base_build_id: Option<String>,
+        diff: Option<BuildDiff>,
+        /// For the base picker, newest first, this build excluded.
+        builds: Vec<String>,
+    },
+    NotFound {
+        github_login: String,
+        build_id: String,
+    },
+}
+
+pub async fn handler(
+    req: ForteRequest<'_>,
+    path_params: PathParams,
+    search_params: SearchParams,
+) -> anyhow::Result<Props> {
+    let Some(user) = auth::current_user(req.jar).await else {
+        return Err(Redirect::Login.into());
+    };
+
+    let db = doc_db::turso();
+    let mut builds: Vec<BuildDoc> = (BuildDocQuery {
+        build_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?;
+    builds.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    let Some(build) = builds.iter().find(|b| b.build_id == path_params.build_id) else {
+        return Ok(Props::NotFound {
+            github_login: user.github_login,
+            build_id: path_params.build_id,
+        });
+    };
+    let created_at = build.created_at;
+    let base_build_id = search_params
+        .base
+        .filter(|base| base != &path_params.build_id && builds.iter().any(|b| &b.build_id == base));
+
+    let (stats, diff) =
+        get_build_stats::load(&path_params.build_id, base_build_id.as_deref()).await?;
+
+    Ok(Props::Ok {
+        github_login: user.github_login,
+        created_at,
+        stats,
+        base_build_id,
+        diff,
+        builds: builds
+            .into_iter()
+            .map(|b| b.build_id)
+            .filter(|b| b != &path_params.build_id)
+            .collect(),
+    })
+}
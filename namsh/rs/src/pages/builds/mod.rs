@@ -1,5 +1,5 @@
 use crate::actions::list_builds::BuildSummary;
-use crate::common::auth;
+use crate::common::{auth, build_stats};
 use crate::docs::*;
 use crate::route_generated::Redirect;
 use forte_sdk::*;
@@ -24,14 +24,33 @@ pub async fn handler(req: ForteRequest<'_>) -> anyhow::Result<Props> {
     .send_with(&db)
     .await?;
 
+    let groups: Vec<StackGroupDoc> = (StackGroupDocQuery {
+        stack_hash: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?;
+    let dumps: Vec<DumpDoc> = (DumpDocQuery {
+        dump_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?;
+    let stats = build_stats::compute(&groups, &dumps);
+
     let mut builds: Vec<BuildSummary> = docs
         .into_iter()
-        .map(|b| BuildSummary {
-            build_id: b.build_id,
-            created_at: b.created_at,
-            uploaded_by: b.uploaded_by,
-            pdb_uploaded: b.pdb_uploaded,
-            pdb_size: b.pdb_size,
+        .map(|b| {
+            let stats = stats.get(&b.build_id);
+            BuildSummary {
+                build_id: b.build_id,
+                created_at: b.created_at,
+                uploaded_by: b.uploaded_by,
+                pdb_uploaded: b.pdb_uploaded,
+                pdb_size: b.pdb_size,
+                hits: stats.map_or(0, |s| s.hits),
+                installs: stats.map_or(0, |s| s.installs),
+            }
         })
         .collect();
     builds.sort_by(|a, b| b.created_at.cmp(&a.created_at));
@@ -1,8 +1,16 @@
-use crate::common::auth;
+use crate::common::{auth, stack_group};
 use crate::docs::*;
 use crate::route_generated::Redirect;
 use forte_sdk::*;
 use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// See `stack_group::matches_filter`.
+pub struct SearchParams {
+    pub status: Option<String>,
+    pub build: Option<String>,
+    pub os: Option<String>,
+}
 
 #[derive(Serialize)]
 pub struct StackGroupRow {
@@ -12,15 +20,27 @@ pub struct StackGroupRow {
     pub count: u64,
     pub stored_dumps: usize,
     pub latest_build_id: String,
+    pub status: StackGroupStatus,
+    pub platforms: Vec<PlatformDumps>,
 }
 
 #[derive(Serialize)]
 pub struct Props {
     pub github_login: String,
     pub groups: Vec<StackGroupRow>,
+    pub status_filter: Option<String>,
+    pub build_filter: Option<String>,
+    /// For the build filter, newest first.
+    pub builds: Vec<String>,
+    pub os_filter: Option<String>,
+    /// For the OS filter: every OS a processed dump came from.
+    pub oses: Vec<String>,
 }
 
-pub async fn handler(req: ForteRequest<'_>) -> anyhow::Result<Props> {
+pub async fn handler(
+    req: ForteRequest<'_>,
+    search_params: SearchParams,
+) -> anyhow::Result<Props> {
     let Some(user) = auth::current_user(req.jar).await else {
         return Err(Redirect::Login.into());
     };
@@ -32,9 +52,21 @@ pub async fn handler(req: ForteRequest<'_>) -> anyhow::Result<Props> {
     })
     .send_with(&db)
     .await?;
+    let oses: BTreeSet<String> = docs
+        .iter()
+        .flat_map(|g| g.platforms.iter().map(|p| p.os.clone()))
+        .collect();
 
     let mut groups: Vec<StackGroupRow> = docs
         .into_iter()
+        .filter(|g| {
+            stack_group::matches_filter(
+                g,
+                search_params.status.as_deref(),
+                search_params.build.as_deref(),
+                search_params.os.as_deref(),
+            )
+        })
         .map(|g| StackGroupRow {
             stack_hash: g.stack_hash,
             first_seen: g.first_seen,
@@ -42,12 +74,27 @@ pub async fn handler(req: ForteRequest<'_>) -> anyhow::Result<Props> {
             count: g.count,
             stored_dumps: g.dump_ids.len(),
             latest_build_id: g.latest_context.build_id,
+            status: g.status,
+            platforms: g.platforms,
         })
         .collect();
     groups.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
 
+    let mut builds: Vec<BuildDoc> = (BuildDocQuery {
+        build_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?;
+    builds.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
     Ok(Props {
         github_login: user.github_login,
         groups,
+        status_filter: search_params.status,
+        build_filter: search_params.build,
+        builds: builds.into_iter().map(|b| b.build_id).collect(),
+        os_filter: search_params.os,
+        oses: oses.into_iter().collect(),
     })
 }
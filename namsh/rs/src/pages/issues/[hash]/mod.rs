@@ -18,6 +18,11 @@ pub enum Props {
         last_seen: DateTime,
         count: u64,
         latest_context: CrashContext,
+        status: StackGroupStatus,
+        build_ids: Vec<String>,
+        platforms: Vec<PlatformDumps>,
+        /// For the "resolved in" picker, newest first.
+        builds: Vec<String>,
         dumps: Vec<DumpSummary>,
     },
     NotFound {
@@ -60,10 +65,19 @@ pub async fn handler(
                 build_id: d.build_id,
                 uploaded_at: d.uploaded_at,
                 client_ip: d.client_ip,
+                processing: d.processing,
             });
         }
     }
 
+    let mut builds: Vec<BuildDoc> = (BuildDocQuery {
+        build_id: None,
+        limit: None,
+    })
+    .send_with(&db)
+    .await?;
+    builds.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
     Ok(Props::Ok {
         github_login: user.github_login,
         stack_hash: group.stack_hash,
@@ -71,6 +85,10 @@ pub async fn handler(
         last_seen: group.last_seen,
         count: group.count,
         latest_context: group.latest_context,
+        status: group.status,
+        build_ids: group.build_ids,
+        platforms: group.platforms,
+        builds: builds.into_iter().map(|b| b.build_id).collect(),
         dumps,
     })
 }
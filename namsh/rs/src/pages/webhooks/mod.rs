@@ -0,0 +1,25 @@
+use crate::actions::list_webhooks::{self, DeliverySummary, WebhookSummary};
+use crate::common::auth;
+use crate::route_generated::Redirect;
+use forte_sdk::*;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct Props {
+    pub github_login: String,
+    pub webhooks: Vec<WebhookSummary>,
+    pub deliveries: Vec<DeliverySummary>,
+}
+
+pub async fn handler(req: ForteRequest<'_>) -> anyhow::Result<Props> {
+    let Some(user) = auth::current_user(req.jar).await else {
+        return Err(Redirect::Login.into());
+    };
+
+    let (webhooks, deliveries) = list_webhooks::load().await?;
+    Ok(Props {
+        github_login: user.github_login,
+        webhooks,
+        deliveries,
+    })
+}
@@ -0,0 +1,352 @@
+use image::{GenericImage, RgbaImage};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Images under a directory with this file are packed into atlas pages.
+///
+/// `register_assets!` expands again when this file or a packed image changes. Stable
+/// proc macros can't track a directory, so `namui start` touches this file when an
+/// image is added to or removed from the directory. With plain cargo, touch it yourself.
+///
+/// ```toml
+/// max_size = 2048 # optional, page width and height limit
+/// padding = 2     # optional, transparent pixels around each image
+/// ```
+pub const ATLAS_CONFIG_FILE_NAME: &str = "atlas.toml";
+/// Generated pages are written here, relative to the project root. Build output,
+/// like namui-cli's `target/namui/audio`; namui-cli bundles them under `asset/.atlas`.
+pub const ATLAS_PAGE_DIR: &str = "target/namui/atlas";
+/// Where pages are found in the bundle, relative to the asset directory.
+pub const ATLAS_PAGE_BUNDLE_DIR_NAME: &str = ".atlas";
+
+const DEFAULT_MAX_SIZE: u32 = 2048;
+const DEFAULT_PADDING: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AtlasConfig {
+    max_size: u32,
+    padding: u32,
+}
+
+#[derive(Debug)]
+pub struct AtlasSprite {
+    pub source_path: PathBuf,
+    pub page_path: PathBuf,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Placement {
+    page: usize,
+    x: u32,
+    y: u32,
+}
+
+/// Returns the nearest directory with `atlas.toml` which contains `file_path`.
+pub fn atlas_dir_of(asset_dir: &Path, file_path: &Path) -> Option<PathBuf> {
+    file_path
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(asset_dir))
+        .find(|dir| dir.join(ATLAS_CONFIG_FILE_NAME).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Packs every atlas directory and writes pages into `page_dir`.
+/// Pages are rewritten only when their content changes, and stale pages are removed.
+pub fn pack_atlases(
+    asset_dir: &Path,
+    page_dir: &Path,
+    image_files: &[PathBuf],
+) -> Vec<AtlasSprite> {
+    let mut sources_by_atlas_dir = BTreeMap::<PathBuf, Vec<PathBuf>>::new();
+    for file_path in image_files {
+        if let Some(atlas_dir) = atlas_dir_of(asset_dir, file_path) {
+            sources_by_atlas_dir
+                .entry(atlas_dir)
+                .or_default()
+                .push(file_path.clone());
+        }
+    }
+
+    let mut page_name_prefixes = BTreeMap::<String, PathBuf>::new();
+    let mut written_pages = BTreeSet::new();
+    let mut sprites = Vec::new();
+
+    for (atlas_dir, mut source_paths) in sources_by_atlas_dir {
+        source_paths.sort();
+        let config = read_config(&atlas_dir);
+        let images = source_paths
+            .iter()
+            .map(|path| {
+                image::open(path)
+                    .unwrap_or_else(|e| panic!("Failed to open atlas image {path:?}: {e}"))
+                    .to_rgba8()
+            })
+            .collect::<Vec<_>>();
+        let sizes = images
+            .iter()
+            .map(|image| (image.width(), image.height()))
+            .collect::<Vec<_>>();
+        let placements = pack(&sizes, config)
+            .unwrap_or_else(|e| panic!("Failed to pack atlas {atlas_dir:?}: {e}"));
+
+        let page_count = placements.iter().map(|p| p.page + 1).max().unwrap_or(0);
+        let page_name_prefix = page_name_prefix(atlas_dir.strip_prefix(asset_dir).unwrap());
+        if let Some(other_dir) =
+            page_name_prefixes.insert(page_name_prefix.clone(), atlas_dir.clone())
+        {
+            panic!("Atlas directories {other_dir:?} and {atlas_dir:?} would share page names");
+        }
+
+        for page in 0..page_count {
+            let (page_width, page_height) = placements
+                .iter()
+                .zip(&sizes)
+                .filter(|(placement, _)| placement.page == page)
+                .fold((0, 0), |(w, h), (placement, (width, height))| {
+                    (
+                        w.max(placement.x + width + config.padding),
+                        h.max(placement.y + height + config.padding),
+                    )
+                });
+            let mut page_image = RgbaImage::new(page_width, page_height);
+            for ((placement, image), source_path) in
+                placements.iter().zip(&images).zip(&source_paths)
+            {
+                if placement.page != page {
+                    continue;
+                }
+                page_image
+                    .copy_from(image, placement.x, placement.y)
+                    .unwrap_or_else(|e| panic!("Failed to copy {source_path:?} into atlas: {e}"));
+            }
+
+            let page_path = page_dir.join(format!("{page_name_prefix}_{page}.png"));
+            write_page_if_changed(&page_path, &page_image);
+            written_pages.insert(page_path.clone());
+
+            for ((placement, (width, height)), source_path) in
+                placements.iter().zip(&sizes).zip(&source_paths)
+            {
+                if placement.page != page {
+                    continue;
+                }
+                sprites.push(AtlasSprite {
+                    source_path: source_path.clone(),
+                    page_path: page_path.clone(),
+                    x: placement.x,
+                    y: placement.y,
+                    width: *width,
+                    height: *height,
+                });
+            }
+        }
+    }
+
+    remove_stale_pages(page_dir, &written_pages);
+    sprites
+}
+
+/// Pages of every atlas share one directory. The readable part alone is ambiguous
+/// (`a_b/` and `a/b/`), so a hash of the relative path follows it.
+pub(crate) fn page_name_prefix(relative_dir: &Path) -> String {
+    let components = relative_dir
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect::<Vec<_>>();
+    let readable = if components.is_empty() {
+        "root".to_string()
+    } else {
+        components.join("_")
+    };
+    format!("{readable}-{:08x}", fnv1a(components.join("/").as_bytes()))
+}
+
+/// Stable across builds and platforms, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ *byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
+fn read_config(atlas_dir: &Path) -> AtlasConfig {
+    let config_path = atlas_dir.join(ATLAS_CONFIG_FILE_NAME);
+    let text = fs::read_to_string(&config_path)
+        .unwrap_or_else(|e| panic!("Failed to read {config_path:?}: {e}"));
+    let table = text
+        .parse::<toml::Table>()
+        .unwrap_or_else(|e| panic!("Failed to parse {config_path:?}: {e}"));
+    let read_u32 = |key: &str, default: u32| match table.get(key) {
+        Some(value) => value
+            .as_integer()
+            .and_then(|value| u32::try_from(value).ok())
+            .unwrap_or_else(|| panic!("`{key}` in {config_path:?} should be a positive integer")),
+        None => default,
+    };
+    AtlasConfig {
+        max_size: read_u32("max_size", DEFAULT_MAX_SIZE),
+        padding: read_u32("padding", DEFAULT_PADDING),
+    }
+}
+
+/// Shelf packing, tallest first. Deterministic for the same input order.
+fn pack(sizes: &[(u32, u32)], config: AtlasConfig) -> Result<Vec<Placement>, String> {
+    let AtlasConfig { max_size, padding } = config;
+    let mut order = (0..sizes.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&index| {
+        let (width, height) = sizes[index];
+        (std::cmp::Reverse(height), std::cmp::Reverse(width), index)
+    });
+
+    let mut placements = vec![Placement::default(); sizes.len()];
+    let mut page = 0;
+    let mut cursor_x = padding;
+    let mut shelf_y = padding;
+    let mut shelf_height = 0;
+
+    for index in order {
+        let (width, height) = sizes[index];
+        if width + padding * 2 > max_size || height + padding * 2 > max_size {
+            return Err(format!(
+                "image #{index} ({width}x{height}) doesn't fit in max_size {max_size}"
+            ));
+        }
+        if cursor_x + width + padding > max_size {
+            shelf_y += shelf_height + padding;
+            cursor_x = padding;
+            shelf_height = 0;
+        }
+        if shelf_y + height + padding > max_size {
+            page += 1;
+            cursor_x = padding;
+            shelf_y = padding;
+            shelf_height = 0;
+        }
+        placements[index] = Placement {
+            page,
+            x: cursor_x,
+            y: shelf_y,
+        };
+        cursor_x += width + padding;
+        shelf_height = shelf_height.max(height);
+    }
+
+    Ok(placements)
+}
+
+fn write_page_if_changed(page_path: &Path, page_image: &RgbaImage) {
+    let mut bytes = Vec::new();
+    page_image
+        .write_to(
+            &mut std::io::Cursor::new(&mut bytes),
+            image::ImageFormat::Png,
+        )
+        .unwrap_or_else(|e| panic!("Failed to encode atlas page {page_path:?}: {e}"));
+
+    if fs::read(page_path).is_ok_and(|existing| existing == bytes) {
+        return;
+    }
+
+    let page_dir = page_path.parent().unwrap();
+    fs::create_dir_all(page_dir).unwrap_or_else(|e| panic!("Failed to create {page_dir:?}: {e}"));
+
+    // cargo check and rust-analyzer can expand the macro at the same time.
+    let temp_path = page_path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&temp_path, &bytes)
+        .unwrap_or_else(|e| panic!("Failed to write atlas page {temp_path:?}: {e}"));
+    fs::rename(&temp_path, page_path)
+        .unwrap_or_else(|e| panic!("Failed to write atlas page {page_path:?}: {e}"));
+}
+
+fn remove_stale_pages(page_dir: &Path, written_pages: &BTreeSet<PathBuf>) {
+    let Ok(entries) = fs::read_dir(page_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_png = path.extension().is_some_and(|ext| ext == "png");
+        if is_png && !written_pages.contains(&path) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: AtlasConfig = AtlasConfig {
+        max_size: 16,
+        padding: 1,
+    };
+
+    #[test]
+    fn packs_tallest_first_on_shelves() {
+        let placements = pack(&[(4, 2), (4, 6), (4, 4)], CONFIG).unwrap();
+        assert_eq!(
+            placements[1],
+            Placement {
+                page: 0,
+                x: 1,
+                y: 1
+            }
+        );
+        assert_eq!(
+            placements[2],
+            Placement {
+                page: 0,
+                x: 6,
+                y: 1
+            }
+        );
+        assert_eq!(
+            placements[0],
+            Placement {
+                page: 0,
+                x: 11,
+                y: 1
+            }
+        );
+    }
+
+    #[test]
+    fn overflows_to_next_page() {
+        let placements = pack(&[(14, 14), (14, 14)], CONFIG).unwrap();
+        assert_eq!(
+            placements[0],
+            Placement {
+                page: 0,
+                x: 1,
+                y: 1
+            }
+        );
+        assert_eq!(
+            placements[1],
+            Placement {
+                page: 1,
+                x: 1,
+                y: 1
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_image_larger_than_page() {
+        assert!(pack(&[(15, 1)], CONFIG).is_err());
+    }
+
+    #[test]
+    fn page_name_prefixes_are_unambiguous() {
+        let nested = page_name_prefix(Path::new("a/b"));
+        let underscored = page_name_prefix(Path::new("a_b"));
+        assert!(nested.starts_with("a_b-"));
+        assert!(underscored.starts_with("a_b-"));
+        assert_ne!(nested, underscored);
+        assert!(page_name_prefix(Path::new("")).starts_with("root-"));
+    }
+}
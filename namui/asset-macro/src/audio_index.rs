@@ -0,0 +1,55 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Written by namui-cli's bundle audio step, relative to the project root.
+const AUDIO_INDEX_PATH: &str = "target/namui/audio/index.json";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessedAudio {
+    pub duration_millis: i64,
+    pub lufs: Option<f64>,
+    pub peak_dbtp: Option<f64>,
+    pub loop_region_millis: Option<(i64, i64)>,
+}
+
+/// Processed audio by asset relative path, `/` separated.
+/// Entries made from an older version of the source file are left out,
+/// so the macro falls back to the source file until the next bundle.
+pub fn read_audio_index(manifest_dir: &Path, asset_dir: &Path) -> BTreeMap<String, ProcessedAudio> {
+    let Ok(json) = std::fs::read(manifest_dir.join(AUDIO_INDEX_PATH)) else {
+        return BTreeMap::new();
+    };
+    let Ok(serde_json::Value::Object(index)) = serde_json::from_slice(&json) else {
+        return BTreeMap::new();
+    };
+
+    index
+        .into_iter()
+        .filter_map(|(relative_path, entry)| {
+            let source_metadata = asset_dir.join(&relative_path).metadata().ok()?;
+            let source_modified = source_metadata
+                .modified()
+                .ok()?
+                .duration_since(UNIX_EPOCH)
+                .ok()?
+                .as_secs();
+            if entry["source_size"].as_u64()? != source_metadata.len()
+                || entry["source_modified"].as_u64()? != source_modified
+            {
+                return None;
+            }
+
+            let metadata = &entry["metadata"];
+            let processed = ProcessedAudio {
+                duration_millis: metadata["duration_ms"].as_i64()?,
+                lufs: metadata["lufs"].as_f64(),
+                peak_dbtp: metadata["peak_dbtp"].as_f64(),
+                loop_region_millis: metadata["loop_start_ms"]
+                    .as_i64()
+                    .zip(metadata["loop_end_ms"].as_i64()),
+            };
+            Some((relative_path, processed))
+        })
+        .collect()
+}
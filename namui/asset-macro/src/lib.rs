@@ -1,6 +1,10 @@
+mod atlas;
+mod audio_index;
+
+use audio_index::ProcessedAudio;
 use proc_macro::TokenStream;
 use quote::quote;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -8,7 +12,8 @@ use std::path::{Path, PathBuf};
 struct ModuleNode {
     children: BTreeMap<String, ModuleNode>,
     images: Vec<(String, usize)>,
-    audios: Vec<(String, usize, i64)>,
+    atlas_images: Vec<(String, usize, [u32; 4])>,
+    audios: Vec<(String, usize, ProcessedAudio)>,
 }
 
 impl ModuleNode {
@@ -16,6 +21,7 @@ impl ModuleNode {
         Self {
             children: BTreeMap::new(),
             images: Vec::new(),
+            atlas_images: Vec::new(),
             audios: Vec::new(),
         }
     }
@@ -27,22 +33,41 @@ impl ModuleNode {
             child.add_image(&path_parts[1..], file_name, id);
         }
     }
+    fn add_atlas_image(
+        &mut self,
+        path_parts: &[String],
+        file_name: String,
+        page_id: usize,
+        rect: [u32; 4],
+    ) {
+        if path_parts.is_empty() {
+            self.atlas_images.push((file_name, page_id, rect));
+        } else {
+            let child = self.children.entry(path_parts[0].clone()).or_default();
+            child.add_atlas_image(&path_parts[1..], file_name, page_id, rect);
+        }
+    }
     fn add_audio(
         &mut self,
         path_parts: &[String],
         file_name: String,
         id: usize,
-        duration_millis: i64,
+        audio: ProcessedAudio,
     ) {
         if path_parts.is_empty() {
-            self.audios.push((file_name, id, duration_millis));
+            self.audios.push((file_name, id, audio));
         } else {
             let child = self.children.entry(path_parts[0].clone()).or_default();
-            child.add_audio(&path_parts[1..], file_name, id, duration_millis);
+            child.add_audio(&path_parts[1..], file_name, id, audio);
         }
     }
     fn has_images(&self) -> bool {
-        !self.images.is_empty() || self.children.values().any(|c| c.has_images())
+        !self.images.is_empty()
+            || !self.atlas_images.is_empty()
+            || self.children.values().any(|c| c.has_images())
+    }
+    fn has_atlas_images(&self) -> bool {
+        !self.atlas_images.is_empty() || self.children.values().any(|c| c.has_atlas_images())
     }
     fn has_audios(&self) -> bool {
         !self.audios.is_empty() || self.children.values().any(|c| c.has_audios())
@@ -58,6 +83,11 @@ impl ModuleNode {
             } else {
                 quote! {}
             };
+            let atlas_image_use = if child.has_atlas_images() {
+                quote! { use super::AtlasImage; }
+            } else {
+                quote! {}
+            };
             let audio_use = if child.has_audios() {
                 quote! { use super::AudioAsset; use super::Duration; }
             } else {
@@ -66,6 +96,7 @@ impl ModuleNode {
             modules.push(quote! {
                 pub mod #mod_ident {
                     #image_use
+                    #atlas_image_use
                     #audio_use
                     #child_tokens
                 }
@@ -80,11 +111,35 @@ impl ModuleNode {
             });
         }
 
+        for (name, page_id, [x, y, width, height]) in &self.atlas_images {
+            let const_name = quote::format_ident!("{}", name);
+            images.push(quote! {
+                pub static #const_name: AtlasImage =
+                    AtlasImage::new(Image::new(#page_id), #x, #y, #width, #height);
+            });
+        }
+
         let mut audios = Vec::new();
-        for (name, id, duration_millis) in &self.audios {
+        for (name, id, audio) in &self.audios {
             let const_name = quote::format_ident!("{}", name);
+            let duration_millis = audio.duration_millis;
+            let loudness = match (audio.lufs, audio.peak_dbtp) {
+                (Some(lufs), Some(peak_dbtp)) => {
+                    let (lufs, peak_dbtp) = (lufs as f32, peak_dbtp as f32);
+                    quote! { .with_loudness(#lufs, #peak_dbtp) }
+                }
+                _ => quote! {},
+            };
+            let loop_region = match audio.loop_region_millis {
+                Some((start, end)) => quote! {
+                    .with_loop_region(Duration::from_millis(#start), Duration::from_millis(#end))
+                },
+                None => quote! {},
+            };
             audios.push(quote! {
-                pub static #const_name: AudioAsset = AudioAsset::new(#id, Duration::from_millis(#duration_millis));
+                pub static #const_name: AudioAsset = AudioAsset::new(#id, Duration::from_millis(#duration_millis))
+                    #loudness
+                    #loop_region;
             });
         }
 
@@ -113,7 +168,7 @@ fn collect_image_files(asset_dir: &Path) -> Vec<PathBuf> {
 }
 
 fn collect_audio_files(asset_dir: &Path) -> Vec<PathBuf> {
-    collect_files_by_extensions(asset_dir, &["mp3", "wav", "ogg", "opus"])
+    collect_files_by_extensions(asset_dir, &["mp3", "wav", "ogg", "opus", "flac"])
 }
 
 fn collect_files_by_extensions(asset_dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
@@ -206,27 +261,134 @@ fn get_audio_duration_millis(path: &Path) -> i64 {
     }
 }
 
+/// An image the runtimes load by id. Ids are indexes into the list sorted by `key`.
+#[derive(Debug)]
+struct ImageEntry {
+    /// Relative to the asset directory, `/`-separated. Sorted bytewise, the same way
+    /// namui-cli's `assetCollectorPlugin.ts` sorts, so both sides agree on ids.
+    key: String,
+    /// Relative to the asset directory, as stored in the bundle.
+    bundle_path: PathBuf,
+    /// `None` for atlas pages, which have no constant of their own.
+    source_path: Option<PathBuf>,
+}
+
+fn image_key(relative_path: &Path) -> String {
+    relative_path.to_string_lossy().replace('\\', "/")
+}
+
+fn image_entries(
+    asset_dir: &Path,
+    image_files: &[PathBuf],
+    atlas_sprites: &[atlas::AtlasSprite],
+) -> Vec<ImageEntry> {
+    let mut entries = image_files
+        .iter()
+        .filter(|file_path| atlas::atlas_dir_of(asset_dir, file_path).is_none())
+        .map(|file_path| {
+            let bundle_path = file_path.strip_prefix(asset_dir).unwrap().to_path_buf();
+            ImageEntry {
+                key: image_key(&bundle_path),
+                bundle_path,
+                source_path: Some(file_path.clone()),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let page_paths = atlas_sprites
+        .iter()
+        .map(|sprite| sprite.page_path.clone())
+        .collect::<BTreeSet<_>>();
+    for page_path in page_paths {
+        let bundle_path =
+            Path::new(atlas::ATLAS_PAGE_BUNDLE_DIR_NAME).join(page_path.file_name().unwrap());
+        entries.push(ImageEntry {
+            key: image_key(&bundle_path),
+            bundle_path,
+            source_path: None,
+        });
+    }
+
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    entries
+}
+
 #[proc_macro]
 pub fn register_assets(_input: TokenStream) -> TokenStream {
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
         .expect("CARGO_MANIFEST_DIR environment variable not set");
-    let asset_dir = PathBuf::from(&manifest_dir).join("asset");
+    TokenStream::from(expand(Path::new(&manifest_dir)))
+}
+
+fn expand(manifest_dir: &Path) -> proc_macro2::TokenStream {
+    let asset_dir = manifest_dir.join("asset");
+    let page_dir = manifest_dir.join(atlas::ATLAS_PAGE_DIR);
 
     let mut root = ModuleNode::new();
 
-    let mut image_files = collect_image_files(&asset_dir);
-    image_files.sort();
-    for (id, file_path) in image_files.iter().enumerate() {
-        let (components, const_name) = path_to_parts(&asset_dir, file_path);
+    let image_files = collect_image_files(&asset_dir);
+    let mut atlas_sprites = atlas::pack_atlases(&asset_dir, &page_dir, &image_files);
+    atlas_sprites.sort_by(|a, b| a.source_path.cmp(&b.source_path));
+
+    let image_entries = image_entries(&asset_dir, &image_files, &atlas_sprites);
+    for (id, entry) in image_entries.iter().enumerate() {
+        let Some(source_path) = &entry.source_path else {
+            continue;
+        };
+        let (components, const_name) = path_to_parts(&asset_dir, source_path);
         root.add_image(&components, const_name, id);
     }
+    for sprite in &atlas_sprites {
+        let page_file_name = sprite.page_path.file_name().unwrap();
+        let page_id = image_entries
+            .iter()
+            .position(|entry| {
+                entry.source_path.is_none() && entry.bundle_path.file_name() == Some(page_file_name)
+            })
+            .expect("Atlas page should be collected");
+        let (components, const_name) = path_to_parts(&asset_dir, &sprite.source_path);
+        root.add_atlas_image(
+            &components,
+            const_name,
+            page_id,
+            [sprite.x, sprite.y, sprite.width, sprite.height],
+        );
+    }
+
+    // Pages and rects depend on the pixels and `atlas.toml`, so expand again when they change.
+    let tracked_paths = atlas_sprites
+        .iter()
+        .flat_map(|sprite| {
+            let config_path = atlas::atlas_dir_of(&asset_dir, &sprite.source_path)
+                .unwrap()
+                .join(atlas::ATLAS_CONFIG_FILE_NAME);
+            [sprite.source_path.clone(), config_path]
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|path| path.to_str().unwrap().to_string());
 
+    // Trimming changes duration, so prefer what the bundle audio step measured.
+    let audio_index = audio_index::read_audio_index(manifest_dir, &asset_dir);
     let mut audio_files = collect_audio_files(&asset_dir);
     audio_files.sort();
     for (id, file_path) in audio_files.iter().enumerate() {
         let (components, const_name) = path_to_parts(&asset_dir, file_path);
-        let duration_millis = get_audio_duration_millis(file_path);
-        root.add_audio(&components, const_name, id, duration_millis);
+        let relative_path = file_path
+            .strip_prefix(&asset_dir)
+            .unwrap()
+            .to_string_lossy()
+            .replace('\\', "/");
+        let audio = audio_index
+            .get(&relative_path)
+            .copied()
+            .unwrap_or_else(|| ProcessedAudio {
+                duration_millis: get_audio_duration_millis(file_path),
+                lufs: None,
+                peak_dbtp: None,
+                loop_region_millis: None,
+            });
+        root.add_audio(&components, const_name, id, audio);
     }
 
     let module_tree = root.to_tokens();
@@ -236,6 +398,11 @@ pub fn register_assets(_input: TokenStream) -> TokenStream {
     } else {
         quote! {}
     };
+    let atlas_image_use = if root.has_atlas_images() {
+        quote! { use super::AtlasImage; }
+    } else {
+        quote! {}
+    };
     let audio_use = if root.has_audios() {
         quote! { use super::AudioAsset; use super::Duration; }
     } else {
@@ -245,13 +412,8 @@ pub fn register_assets(_input: TokenStream) -> TokenStream {
     // Generate native asset initialization function
     let native_init = {
         let mut image_init_calls = Vec::new();
-        for (id, file_path) in image_files.iter().enumerate() {
-            let relative_path = file_path
-                .strip_prefix(&asset_dir)
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_string();
+        for (id, entry) in image_entries.iter().enumerate() {
+            let relative_path = entry.bundle_path.to_str().unwrap().to_string();
             image_init_calls.push(quote! {
                 register_image(#id, #relative_path, &read_asset);
             });
@@ -292,14 +454,87 @@ pub fn register_assets(_input: TokenStream) -> TokenStream {
         }
     };
 
-    let expanded = quote! {
+    quote! {
         pub mod asset {
             #image_use
+            #atlas_image_use
             #audio_use
+            #(const _: &[u8] = include_bytes!(#tracked_paths);)*
             #module_tree
             #native_init
         }
-    };
+    }
+}
 
-    TokenStream::from(expanded)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_png(path: &Path, width: u32, height: u32) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        image::RgbaImage::new(width, height).save(path).unwrap();
+    }
+
+    /// Whitespace in `quote!` output isn't stable, so compare without it.
+    fn squash(tokens: impl ToString) -> String {
+        tokens.to_string().split_whitespace().collect()
+    }
+
+    #[test]
+    fn image_ids_follow_web_collector_order() {
+        let project = tempfile::tempdir().unwrap();
+        let asset_dir = project.path().join("asset");
+        write_png(&asset_dir.join("b.png"), 1, 1);
+        // Bytewise, `a.png` < `a/x.png`. Path components would put the directory first.
+        write_png(&asset_dir.join("a.png"), 1, 1);
+        write_png(&asset_dir.join("a/x.png"), 1, 1);
+        write_png(&asset_dir.join("ui/button.png"), 4, 4);
+        write_png(&asset_dir.join("ui/icon.png"), 2, 2);
+        fs::write(asset_dir.join("ui/atlas.toml"), "").unwrap();
+
+        let expanded = squash(expand(project.path()));
+
+        // What assetCollectorPlugin.ts lists, in the same order.
+        for (id, name) in ["A", "X", "B"].iter().enumerate() {
+            let id = id + 1;
+            let expected = squash(format!("pub static {name}: Image = Image::new({id}usize);"));
+            assert!(expanded.contains(&expected), "{expected} not in {expanded}");
+        }
+        let page_path = format!(".atlas/{}_0.png", atlas::page_name_prefix(Path::new("ui")));
+        let expected_reads = [page_path.as_str(), "a.png", "a/x.png", "b.png"]
+            .iter()
+            .enumerate()
+            .map(|(id, path)| {
+                squash(format!(
+                    "register_image({id}usize, \"{path}\", &read_asset);"
+                ))
+            })
+            .collect::<String>();
+        assert!(expanded.contains(&expected_reads), "{expanded}");
+        assert!(expanded.contains(&squash(
+            "pub static BUTTON: AtlasImage = AtlasImage::new(Image::new(0usize), 2u32, 2u32, 4u32, 4u32);"
+        )));
+    }
+
+    #[test]
+    fn atlas_pages_stay_out_of_asset_dir() {
+        let project = tempfile::tempdir().unwrap();
+        let asset_dir = project.path().join("asset");
+        let sprite_path = asset_dir.join("ui/icon.png");
+        write_png(&sprite_path, 2, 2);
+        fs::write(asset_dir.join("ui/atlas.toml"), "").unwrap();
+
+        let expanded = expand(project.path()).to_string();
+
+        assert!(project
+            .path()
+            .join(atlas::ATLAS_PAGE_DIR)
+            .join(format!(
+                "{}_0.png",
+                atlas::page_name_prefix(Path::new("ui"))
+            ))
+            .is_file());
+        assert!(!asset_dir.join(atlas::ATLAS_PAGE_BUNDLE_DIR_NAME).exists());
+        assert!(expanded.contains(&format!("include_bytes ! ({:?})", sprite_path)));
+    }
 }
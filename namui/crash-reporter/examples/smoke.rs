@@ -10,6 +10,10 @@
 //! Parent → spawns self as `--namui-crash-server` → installs crash handler →
 //! sleeps briefly → null-deref triggers SIGSEGV → child writes the minidump,
 //! parses out `stack_hash`, and POSTs `intake_crash` (+ R2 PUT).
+//!
+//! `SMOKE_MODE=panic` panics instead: the panic hook queues a `kind: panic`
+//! report in-process. The process usually exits before the immediate upload
+//! finishes, so run it once more to flush the queue.
 
 fn config() -> namui_crash_reporter::Config {
     namui_crash_reporter::Config {
@@ -20,6 +24,9 @@ fn config() -> namui_crash_reporter::Config {
             .trim_end_matches('/')
             .to_string(),
         app_name: "namui-crash-reporter-smoke".to_string(),
+        require_consent: false,
+        record_input: false,
+        scrub_rules: namui_crash_reporter::default_scrub_rules(),
     }
 }
 
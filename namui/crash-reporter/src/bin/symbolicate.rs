@@ -0,0 +1,151 @@
+//! `namui-crash-symbolicate` — symbolicates a minidump downloaded from namsh.
+//!
+//! ```text
+//! cargo install --path namui/crash-reporter --features symbolicate
+//! namui-crash-symbolicate --dmp crash.dmp --debug-file target/release/tower-defense
+//! namui-crash-symbolicate --dmp crash.dmp --exe app.exe --pdb app.pdb --json
+//! ```
+//!
+//! PDBs go through `dump_syms`, which has to be on `PATH`.
+
+use namui_crash_reporter::{SymbolSources, symbolicate};
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+const USAGE: &str = "\
+Usage:
+    namui-crash-symbolicate --dmp <crash.dmp> [options]
+
+Options:
+    --debug-file <path>    ELF or Mach-O file with DWARF, matched by build-id (repeatable)
+    --symbols <dir>        Breakpad symbol store root (repeatable)
+    --exe <path> --pdb <path>
+                           Windows executable and its PDB, converted with dump_syms
+    --json                 Print JSON instead of text
+    -h, --help             Print this help
+";
+
+struct Args {
+    dmp: PathBuf,
+    sources: SymbolSources,
+    exe_pdb: Option<(PathBuf, PathBuf)>,
+    json: bool,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(mut args: Args) -> Result<(), String> {
+    let work_dir =
+        std::env::temp_dir().join(format!("namui-crash-symbolicate-{}", std::process::id()));
+    if let Some((exe, pdb)) = &args.exe_pdb {
+        let store = work_dir.join("symbols");
+        pdb_to_breakpad(exe, pdb, &store)?;
+        args.sources.breakpad_paths.push(store);
+    }
+
+    let report = symbolicate(&args.dmp, &args.sources);
+    let _ = std::fs::remove_dir_all(&work_dir);
+    let report = report.map_err(|e| format!("{}: {e}", args.dmp.display()))?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+        println!("{json}");
+    } else {
+        print!("{report}");
+    }
+    Ok(())
+}
+
+fn parse_args(mut argv: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut dmp = None;
+    let mut exe = None;
+    let mut pdb = None;
+    let mut sources = SymbolSources::default();
+    let mut json = false;
+
+    while let Some(arg) = argv.next() {
+        let mut value = || {
+            argv.next()
+                .map(PathBuf::from)
+                .ok_or_else(|| format!("{arg} needs a value"))
+        };
+        match arg.as_str() {
+            "--dmp" => dmp = Some(value()?),
+            "--debug-file" => sources.debug_files.push(value()?),
+            "--symbols" => sources.breakpad_paths.push(value()?),
+            "--exe" => exe = Some(value()?),
+            "--pdb" => pdb = Some(value()?),
+            "--json" => json = true,
+            "-h" | "--help" => return Ok(None),
+            _ => return Err(format!("unknown argument: {arg}")),
+        }
+    }
+
+    let dmp = dmp.ok_or("--dmp is required")?;
+    let exe_pdb = match (exe, pdb) {
+        (Some(exe), Some(pdb)) => Some((exe, pdb)),
+        (None, None) => None,
+        // `dump_syms` needs both for the x64 unwind info (STACK CFI).
+        _ => return Err("--exe and --pdb go together".to_string()),
+    };
+    Ok(Some(Args {
+        dmp,
+        sources,
+        exe_pdb,
+        json,
+    }))
+}
+
+/// Writes `<store>/<pdb file name>/<debug_id>/<pdb stem>.sym`, where the
+/// Breakpad symbolizer looks for a Windows module's symbols.
+fn pdb_to_breakpad(exe: &Path, pdb: &Path, store: &Path) -> Result<(), String> {
+    let output = Command::new("dump_syms")
+        .arg(exe)
+        .arg(pdb)
+        .output()
+        .map_err(|e| format!("failed to run dump_syms: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "dump_syms failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    // `MODULE <os> <arch> <debug_id> <debug_file>`
+    let sym = output.stdout;
+    let header = sym.split(|b| *b == b'\n').next().unwrap_or_default();
+    let header = String::from_utf8_lossy(header);
+    let debug_id = match header.split_whitespace().collect::<Vec<_>>()[..] {
+        ["MODULE", _, _, debug_id, _, ..] => debug_id.to_string(),
+        _ => return Err(format!("unexpected dump_syms header: {header}")),
+    };
+
+    let pdb_name = pdb
+        .file_name()
+        .ok_or_else(|| format!("not a file: {}", pdb.display()))?;
+    let pdb_stem = Path::new(pdb_name).with_extension("");
+    let dir = store.join(pdb_name).join(debug_id);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(pdb_stem).with_extension("sym"), &sym).map_err(|e| e.to_string())
+}
@@ -0,0 +1,393 @@
+//! Breadcrumbs — the last [`SLOT_COUNT`] things the app said it was doing,
+//! like "stage 12 started" or "opened shop", sent as
+//! `CrashContext::breadcrumbs`.
+//!
+//! Same sharing scheme as `log_capture.rs`: the parent creates an mmap-backed
+//! file and points `NAMUI_CRASH_BREADCRUMBS` at it, [`add`] writes to it from
+//! anywhere in the process (including the app dylib, which links its own copy
+//! of this crate), and the crash child reads it back after the crash.
+//!
+//! Layout: `[ 8 bytes magic | 8 bytes LE u64 claimed | SLOT_COUNT × SLOT_SIZE ]`,
+//! each slot `[ u64 LE stamp | u32 LE len | JSON ]`. Fields are cut to the
+//! `*_MAX_BYTES` limits before JSON escaping, which can make a control
+//! character six bytes, so a breadcrumb that still doesn't fit its slot loses
+//! its data, then the end of its message.
+//!
+//! A writer claims the next index with a `fetch_add` on `claimed`, so the
+//! copies of this crate never write the same slot without a lock between them.
+//! The slot is stamped with `index + 1` once its JSON is in; a reader skips
+//! slots whose stamp doesn't match, which are still being written or torn by
+//! the crash.
+
+use crate::{Error, queue};
+use memmap2::{Mmap, MmapMut};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    path::Path,
+    sync::{
+        OnceLock,
+        atomic::{AtomicU64, Ordering, fence},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const MAGIC: &[u8; 8] = b"NAMSHBR2";
+const HEADER_LEN: usize = 16;
+const CLAIMED_OFFSET: usize = 8;
+const STAMP_LEN: usize = 8;
+pub const SLOT_COUNT: usize = 64;
+const SLOT_SIZE: usize = 512;
+const FILE_SIZE: u64 = (HEADER_LEN + SLOT_COUNT * SLOT_SIZE) as u64;
+const ENV_BREADCRUMBS: &str = "NAMUI_CRASH_BREADCRUMBS";
+
+pub const CATEGORY_MAX_BYTES: usize = 32;
+pub const MESSAGE_MAX_BYTES: usize = 160;
+pub const DATA_MAX_ENTRIES: usize = 8;
+pub const DATA_MAX_BYTES: usize = 32;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Breadcrumb {
+    pub timestamp_unix_ms: u64,
+    pub category: String,
+    pub message: String,
+    pub data: BTreeMap<String, String>,
+}
+
+/// Called by the parent, before the child and the app start.
+pub fn set_path(app_name: &str) -> Result<(), Error> {
+    let path = queue::root_dir(app_name)?.join("breadcrumbs");
+    let file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .write(true)
+        .truncate(true)
+        .open(&path)?;
+    file.set_len(FILE_SIZE)?;
+    let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+    mmap[0..8].copy_from_slice(MAGIC);
+    mmap.flush()?;
+
+    // SAFETY: see `log_capture::start` — called early in `main` before any
+    // other threads touch the environment.
+    unsafe {
+        std::env::set_var(ENV_BREADCRUMBS, &path);
+    }
+    Ok(())
+}
+
+/// Leaves a breadcrumb. A no-op when the crash reporter isn't running.
+///
+/// ```ignore
+/// namui_crash_reporter::breadcrumb("shop", "opened shop", [("gold", gold.to_string())]);
+/// ```
+pub fn add(
+    category: &str,
+    message: impl AsRef<str>,
+    data: impl IntoIterator<Item = (&'static str, String)>,
+) {
+    let Some(writer) = writer() else {
+        return;
+    };
+    let breadcrumb = Breadcrumb {
+        timestamp_unix_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        category: truncate(category, CATEGORY_MAX_BYTES),
+        message: truncate(message.as_ref(), MESSAGE_MAX_BYTES),
+        data: data
+            .into_iter()
+            .take(DATA_MAX_ENTRIES)
+            .map(|(key, value)| {
+                (
+                    truncate(key, DATA_MAX_BYTES),
+                    truncate(&value, DATA_MAX_BYTES),
+                )
+            })
+            .collect(),
+    };
+    writer.ring.write(&breadcrumb);
+}
+
+/// Oldest first.
+pub fn read() -> Vec<Breadcrumb> {
+    let Some(path) = std::env::var_os(ENV_BREADCRUMBS) else {
+        return Vec::new();
+    };
+    let Ok(file) = File::open(Path::new(&path)) else {
+        return Vec::new();
+    };
+    if file.metadata().map(|m| m.len()).unwrap_or(0) < FILE_SIZE {
+        return Vec::new();
+    }
+    let Ok(mmap) = (unsafe { Mmap::map(&file) }) else {
+        return Vec::new();
+    };
+    read_ring(&mmap)
+}
+
+struct Writer {
+    ring: Ring,
+    _mmap: MmapMut,
+}
+
+fn writer() -> Option<&'static Writer> {
+    static WRITER: OnceLock<Option<Writer>> = OnceLock::new();
+    WRITER
+        .get_or_init(|| {
+            let path = std::env::var_os(ENV_BREADCRUMBS)?;
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(Path::new(&path))
+                .ok()?;
+            if file.metadata().ok()?.len() < FILE_SIZE {
+                return None;
+            }
+            let mut mmap = unsafe { MmapMut::map_mut(&file).ok()? };
+            if &mmap[0..8] != MAGIC {
+                return None;
+            }
+            Some(Writer {
+                ring: Ring(mmap.as_mut_ptr()),
+                _mmap: mmap,
+            })
+        })
+        .as_ref()
+}
+
+/// Start of [`FILE_SIZE`] bytes laid out as in the module doc, aligned to 8.
+/// Shared by threads and by the copies of this crate, so it is only written
+/// through atomics and claimed slots.
+struct Ring(*mut u8);
+
+// SAFETY: see `Ring`.
+unsafe impl Send for Ring {}
+unsafe impl Sync for Ring {}
+
+impl Ring {
+    fn atomic(&self, offset: usize) -> &AtomicU64 {
+        debug_assert!(offset.is_multiple_of(8) && offset + 8 <= FILE_SIZE as usize);
+        // SAFETY: in bounds and aligned, and `AtomicU64` has the layout of `u64`.
+        unsafe { AtomicU64::from_ptr(self.0.add(offset).cast()) }
+    }
+
+    fn slot_start(index: u64) -> usize {
+        HEADER_LEN + (index as usize % SLOT_COUNT) * SLOT_SIZE
+    }
+
+    fn write(&self, breadcrumb: &Breadcrumb) {
+        let Some(json) = encode(breadcrumb) else {
+            return;
+        };
+
+        let index = self.atomic(CLAIMED_OFFSET).fetch_add(1, Ordering::Relaxed);
+        let start = Self::slot_start(index);
+        let stamp = self.atomic(start);
+        stamp.store(0, Ordering::Relaxed);
+        fence(Ordering::Release);
+        let mut body = (json.len() as u32).to_le_bytes().to_vec();
+        body.extend_from_slice(&json);
+        // SAFETY: `encode` keeps the body within the claimed slot.
+        unsafe {
+            std::ptr::copy_nonoverlapping(body.as_ptr(), self.0.add(start + STAMP_LEN), body.len());
+        }
+        stamp.store(index + 1, Ordering::Release);
+    }
+
+    fn read_slot(&self, index: u64) -> Option<Breadcrumb> {
+        let start = Self::slot_start(index);
+        let stamp = self.atomic(start);
+        if stamp.load(Ordering::Acquire) != index + 1 {
+            return None;
+        }
+        let mut body = [0; SLOT_SIZE - STAMP_LEN];
+        // SAFETY: in bounds. A writer may race the copy, then the stamp changes.
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                self.0.add(start + STAMP_LEN),
+                body.as_mut_ptr(),
+                body.len(),
+            );
+        }
+        fence(Ordering::Acquire);
+        if stamp.load(Ordering::Relaxed) != index + 1 {
+            return None;
+        }
+        let len = u32::from_le_bytes(body[0..4].try_into().unwrap()) as usize;
+        serde_json::from_slice(body.get(4..4 + len)?).ok()
+    }
+}
+
+/// JSON that fits a slot, or `None` if even the message can't be cut to fit.
+fn encode(breadcrumb: &Breadcrumb) -> Option<Vec<u8>> {
+    let fits = |json: &[u8]| STAMP_LEN + 4 + json.len() <= SLOT_SIZE;
+    let json = serde_json::to_vec(breadcrumb).ok()?;
+    if fits(&json) {
+        return Some(json);
+    }
+    let mut breadcrumb = Breadcrumb {
+        data: BTreeMap::new(),
+        ..breadcrumb.clone()
+    };
+    loop {
+        let json = serde_json::to_vec(&breadcrumb).ok()?;
+        if fits(&json) {
+            return Some(json);
+        }
+        breadcrumb.message.pop()?;
+    }
+}
+
+fn read_ring(bytes: &[u8]) -> Vec<Breadcrumb> {
+    if bytes.len() < FILE_SIZE as usize
+        || &bytes[0..8] != MAGIC
+        || bytes.as_ptr().align_offset(8) != 0
+    {
+        return Vec::new();
+    }
+    // Only read through.
+    let ring = Ring(bytes.as_ptr().cast_mut());
+    let claimed = ring.atomic(CLAIMED_OFFSET).load(Ordering::Acquire);
+    (claimed.saturating_sub(SLOT_COUNT as u64)..claimed)
+        .filter_map(|index| ring.read_slot(index))
+        .collect()
+}
+
+fn truncate(s: &str, max_bytes: usize) -> String {
+    let mut end = s.len().min(max_bytes);
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    s[..end].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `u64`s, for the alignment of a mapping.
+    fn empty_ring() -> Vec<u64> {
+        let mut ring = vec![0; FILE_SIZE as usize / 8];
+        ring[0] = u64::from_ne_bytes(*MAGIC);
+        ring
+    }
+
+    fn as_bytes(ring: &[u64]) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(ring.as_ptr().cast(), FILE_SIZE as usize) }
+    }
+
+    fn breadcrumb(message: String) -> Breadcrumb {
+        Breadcrumb {
+            timestamp_unix_ms: 0,
+            category: "test".to_string(),
+            message,
+            data: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn keeps_last_slot_count_in_order() {
+        let mut ring = empty_ring();
+        let writer = Ring(ring.as_mut_ptr().cast());
+        for i in 0..SLOT_COUNT + 3 {
+            writer.write(&breadcrumb(i.to_string()));
+        }
+        let messages: Vec<_> = read_ring(as_bytes(&ring))
+            .into_iter()
+            .map(|b| b.message)
+            .collect();
+        let expected: Vec<_> = (3..SLOT_COUNT + 3).map(|i| i.to_string()).collect();
+        assert_eq!(messages, expected);
+    }
+
+    #[test]
+    fn concurrent_writers_take_their_own_slots() {
+        let mut ring = empty_ring();
+        let writer = Ring(ring.as_mut_ptr().cast());
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let writer = &writer;
+                scope.spawn(move || {
+                    for i in 0..SLOT_COUNT / 4 {
+                        writer.write(&breadcrumb(format!("{thread}-{i}")));
+                    }
+                });
+            }
+        });
+        let mut messages: Vec<_> = read_ring(as_bytes(&ring))
+            .into_iter()
+            .map(|b| b.message)
+            .collect();
+        messages.sort();
+        messages.dedup();
+        assert_eq!(messages.len(), SLOT_COUNT);
+    }
+
+    #[test]
+    fn skips_slot_being_written() {
+        let mut ring = empty_ring();
+        let writer = Ring(ring.as_mut_ptr().cast());
+        writer.write(&breadcrumb("done".to_string()));
+        writer.write(&breadcrumb("cut".to_string()));
+        // As if the crash came before the second stamp.
+        writer
+            .atomic(Ring::slot_start(1))
+            .store(0, Ordering::Relaxed);
+        let messages: Vec<_> = read_ring(as_bytes(&ring))
+            .into_iter()
+            .map(|b| b.message)
+            .collect();
+        assert_eq!(messages, ["done"]);
+    }
+
+    #[test]
+    fn drops_data_that_does_not_fit_the_slot() {
+        let mut ring = empty_ring();
+        let writer = Ring(ring.as_mut_ptr().cast());
+        let mut oversized = breadcrumb("m".repeat(MESSAGE_MAX_BYTES));
+        for i in 0..DATA_MAX_ENTRIES {
+            oversized.data.insert(
+                format!("{i}{}", "k".repeat(DATA_MAX_BYTES - 1)),
+                "\u{1}".repeat(DATA_MAX_BYTES),
+            );
+        }
+        writer.write(&oversized);
+        let read = read_ring(as_bytes(&ring));
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].message, oversized.message);
+        assert!(read[0].data.is_empty());
+    }
+
+    #[test]
+    fn cuts_escaped_message_to_fit_last_slot() {
+        const GUARD: u64 = 0xdead_beef_dead_beef;
+        let mut ring = empty_ring();
+        ring.extend([GUARD; 16]);
+        let writer = Ring(ring.as_mut_ptr().cast());
+        writer
+            .atomic(CLAIMED_OFFSET)
+            .store(SLOT_COUNT as u64 - 1, Ordering::Relaxed);
+
+        writer.write(&breadcrumb("\u{1}".repeat(MESSAGE_MAX_BYTES)));
+
+        assert!(
+            ring[FILE_SIZE as usize / 8..]
+                .iter()
+                .all(|&word| word == GUARD)
+        );
+        let read = read_ring(as_bytes(&ring));
+        assert_eq!(read.len(), 1);
+        assert!(!read[0].message.is_empty());
+        assert!(read[0].message.len() < MESSAGE_MAX_BYTES);
+        assert!(read[0].message.chars().all(|c| c == '\u{1}'));
+    }
+
+    #[test]
+    fn truncates_on_char_boundary() {
+        assert_eq!(truncate("가나다", 4), "가");
+        assert_eq!(truncate("abc", 8), "abc");
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{Config, Error, context, log_capture, namsh, queue, stack_hash};
+use crate::{
+    Config, Error, breadcrumb, consent, context, input_recording, log_capture, namsh, queue,
+    stack_hash,
+};
 use minidumper::{LoopAction, MinidumpBinary, Server, ServerHandler, SocketName};
 use std::{
     fs::File,
@@ -82,17 +85,22 @@ impl ServerHandler for Handler {
 
 impl Handler {
     fn process_dump(&self, dump_path: &Path) -> Result<(), Error> {
+        // minidumper has already written it by now.
+        if !consent::allows_recording(&self.config) {
+            return queue::delete_entry(dump_path);
+        }
         let stack_hash = stack_hash::compute(dump_path)?;
         let session_uptime_sec = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_secs().saturating_sub(self.parent_start_unix))
             .unwrap_or(0);
-        let log_tail = log_capture::read_tail();
         let ctx = context::collect(context::CollectArgs {
             config: &self.config,
             install_id: &self.install_id,
             session_uptime_sec,
-            log_tail,
+            log_tail: log_capture::read_tail(),
+            panic: None,
+            breadcrumbs: breadcrumb::read(),
         });
         queue::write_sidecar(
             dump_path,
@@ -101,6 +109,14 @@ impl Handler {
                 context: ctx,
             },
         )?;
+        if let Err(e) =
+            input_recording::save_tail(&self.config.app_name, &queue::report_id(dump_path))
+        {
+            eprintln!("[crash-reporter:child] failed to save input recording: {e}");
+        }
+        if !consent::allows_upload(&self.config) {
+            return Ok(());
+        }
         if let Err(e) = namsh::upload_single(&self.config, dump_path) {
             eprintln!("[crash-reporter:child] immediate upload failed (queued): {e}");
         }
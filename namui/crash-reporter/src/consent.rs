@@ -0,0 +1,115 @@
+//! Whether the player agreed to send crash reports, persisted per install as
+//! `<root>/consent` next to `install_id`.
+//!
+//! - [`Consent::Granted`]: reports upload as soon as they're recorded.
+//! - [`Consent::Denied`]: nothing is recorded, and the queue and the saved
+//!   input recordings are dropped.
+//! - [`Consent::Unknown`]: the player hasn't been asked. Reports upload as
+//!   before unless [`Config::require_consent`] is set, in which case they stay
+//!   queued until the game asks (see `pending.rs`).
+
+use crate::{Config, Error, input_recording, queue};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum Consent {
+    #[default]
+    Unknown,
+    Granted,
+    Denied,
+}
+
+pub fn get(app_name: &str) -> Consent {
+    let Ok(path) = queue::root_dir(app_name).map(|root| root.join("consent")) else {
+        return Consent::Unknown;
+    };
+    match std::fs::read_to_string(path).as_deref().map(str::trim) {
+        Ok("granted") => Consent::Granted,
+        Ok("denied") => Consent::Denied,
+        _ => Consent::Unknown,
+    }
+}
+
+/// Denying also deletes whatever is still queued.
+pub fn set(app_name: &str, consent: Consent) -> Result<(), Error> {
+    let root = queue::root_dir(app_name)?;
+    std::fs::create_dir_all(&root)?;
+    let path = root.join("consent");
+    match consent {
+        Consent::Unknown => {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+        }
+        Consent::Granted => std::fs::write(&path, "granted")?,
+        Consent::Denied => {
+            std::fs::write(&path, "denied")?;
+            for payload in queue::list_pending(app_name)? {
+                queue::delete_entry(&payload)?;
+            }
+            input_recording::delete_all(app_name)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn allows_recording(config: &Config) -> bool {
+    get(&config.app_name) != Consent::Denied
+}
+
+pub(crate) fn allows_upload(config: &Config) -> bool {
+    match get(&config.app_name) {
+        Consent::Granted => true,
+        Consent::Denied => false,
+        Consent::Unknown => !config.require_consent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        context::CrashKind,
+        test_queue::{TestQueue, context},
+    };
+    use namsh_intake_emulator::Emulator;
+
+    #[test]
+    fn unknown_follows_require_consent() {
+        let emulator = Emulator::start().unwrap();
+        let mut queue = TestQueue::new("consent-unknown", &emulator);
+        assert_eq!(get(&queue.config.app_name), Consent::Unknown);
+        assert!(allows_recording(&queue.config));
+        assert!(allows_upload(&queue.config));
+
+        queue.config.require_consent = true;
+        assert!(allows_recording(&queue.config));
+        assert!(!allows_upload(&queue.config));
+
+        set(&queue.config.app_name, Consent::Granted).unwrap();
+        assert_eq!(get(&queue.config.app_name), Consent::Granted);
+        assert!(allows_upload(&queue.config));
+
+        set(&queue.config.app_name, Consent::Unknown).unwrap();
+        assert_eq!(get(&queue.config.app_name), Consent::Unknown);
+        assert!(!allows_upload(&queue.config));
+    }
+
+    #[test]
+    fn denying_purges_queue_and_recordings() {
+        let emulator = Emulator::start().unwrap();
+        let queue = TestQueue::new("consent-denied", &emulator);
+        queue.push("h1", context(CrashKind::Native), b"MDMP");
+        queue.push("h2", context(CrashKind::Panic), b"panic");
+        let recording = input_recording::path(&queue.config.app_name, "h1").unwrap();
+        std::fs::create_dir_all(recording.parent().unwrap()).unwrap();
+        std::fs::write(&recording, b"recording").unwrap();
+
+        set(&queue.config.app_name, Consent::Denied).unwrap();
+
+        assert_eq!(get(&queue.config.app_name), Consent::Denied);
+        assert!(!allows_recording(&queue.config));
+        assert!(!allows_upload(&queue.config));
+        assert_eq!(queue.pending(), 0);
+        assert!(!recording.exists());
+    }
+}
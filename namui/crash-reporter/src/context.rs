@@ -4,8 +4,10 @@
 //!
 //! Minimal by design: anything the server can derive by parsing the uploaded
 //! minidump (OS, arch, CPU, exception code, etc.) lives there, not here.
+//! Panics have no minidump, so their message and location travel in
+//! [`CrashContext::panic`].
 
-use crate::Config;
+use crate::{Config, breadcrumb::Breadcrumb, scrub};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -14,6 +16,29 @@ pub struct CrashContext {
     pub install_id: String,
     pub session_uptime_sec: u64,
     pub log_tail: Option<String>,
+    #[serde(default)]
+    pub kind: CrashKind,
+    #[serde(default)]
+    pub panic: Option<PanicDetails>,
+    #[serde(default)]
+    pub breadcrumbs: Vec<Breadcrumb>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum CrashKind {
+    /// Minidump captured by the out-of-process child.
+    #[default]
+    Native,
+    /// Rust panic recorded in-process by [`crate::panic`].
+    Panic,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PanicDetails {
+    pub message: String,
+    /// `file:line:column`.
+    pub location: String,
 }
 
 pub struct CollectArgs<'a> {
@@ -21,13 +46,23 @@ pub struct CollectArgs<'a> {
     pub install_id: &'a str,
     pub session_uptime_sec: u64,
     pub log_tail: Option<String>,
+    pub panic: Option<PanicDetails>,
+    pub breadcrumbs: Vec<Breadcrumb>,
 }
 
 pub fn collect(args: CollectArgs<'_>) -> CrashContext {
-    CrashContext {
+    let mut context = CrashContext {
         build_id: args.config.build_id.clone(),
         install_id: args.install_id.to_string(),
         session_uptime_sec: args.session_uptime_sec,
         log_tail: args.log_tail,
-    }
+        kind: match args.panic {
+            Some(_) => CrashKind::Panic,
+            None => CrashKind::Native,
+        },
+        panic: args.panic,
+        breadcrumbs: args.breadcrumbs,
+    };
+    scrub::apply(&args.config.scrub_rules, &mut context);
+    context
 }
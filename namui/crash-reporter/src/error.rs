@@ -14,6 +14,14 @@ pub enum Error {
     #[error("minidump parse: {0}")]
     MinidumpParse(#[from] minidump::Error),
 
+    #[cfg(feature = "symbolicate")]
+    #[error("debug file: {0}")]
+    DebugFile(#[from] object::Error),
+
+    #[cfg(feature = "symbolicate")]
+    #[error("dwarf: {0}")]
+    Dwarf(#[from] gimli::Error),
+
     #[error("hex decode: {0}")]
     Hex(#[from] hex::FromHexError),
 
@@ -37,6 +45,9 @@ pub enum Error {
 
     #[error("crashing thread context missing")]
     MissingCrashingContext,
+
+    #[error("no queued report with id {0}")]
+    UnknownReport(String),
 }
 
 impl From<minidumper::Error> for Error {
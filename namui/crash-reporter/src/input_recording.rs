@@ -0,0 +1,161 @@
+//! Keeps the tail of namui's input recording (`namui::recording`) with a
+//! crash report, so the last seconds before the crash can be replayed.
+//!
+//! Only with [`crate::Config::record_input`] and unless the player denied
+//! consent, the parent points namui at `<root>/input_recording` via
+//! `NAMUI_CRASH_INPUT_RECORDING` before spawning the child. namui flushes the
+//! file after every frame and keeps only the last frames in it, without the
+//! text input and dropped files (see `namui::recording`).
+//!
+//! The recording is `[20 bytes header][len u32 LE | frame]*`, and any run of
+//! whole frames after the header is still a valid recording. At crash time the
+//! header plus the last frames that fit in [`TAIL_BUDGET`] go to
+//! `<root>/recordings/<report id>.bin`, and only the last [`KEEP`] are kept.
+//! They stay on this machine — key presses can't be scrubbed, so they're never
+//! uploaded — and outlive the upload, for whoever reproduces the crash there.
+//! [`crate::PendingReport::input_recording`] points at a queued report's one.
+
+use crate::queue;
+use std::path::{Path, PathBuf};
+
+const HEADER_LEN: usize = 20;
+const TAIL_BUDGET: usize = 12 * 1024;
+const KEEP: usize = 5;
+const ENV_INPUT_RECORDING: &str = "NAMUI_CRASH_INPUT_RECORDING";
+
+/// Called by the parent, before the child and the app start.
+pub fn set_path(app_name: &str) -> Result<(), crate::Error> {
+    let path = queue::root_dir(app_name)?.join("input_recording");
+    // SAFETY: see `log_capture::start` — called early in `main` before any
+    // other threads touch the environment.
+    unsafe {
+        std::env::set_var(ENV_INPUT_RECORDING, &path);
+    }
+    Ok(())
+}
+
+fn dir(app_name: &str) -> Result<PathBuf, crate::Error> {
+    Ok(queue::root_dir(app_name)?.join("recordings"))
+}
+
+pub fn path(app_name: &str, report_id: &str) -> Result<PathBuf, crate::Error> {
+    Ok(dir(app_name)?.join(format!("{report_id}.bin")))
+}
+
+/// Saves the tail for `report_id`, if namui is recording.
+pub fn save_tail(app_name: &str, report_id: &str) -> Result<(), crate::Error> {
+    let Some(recording) = std::env::var_os(ENV_INPUT_RECORDING) else {
+        return Ok(());
+    };
+    let Ok(bytes) = std::fs::read(Path::new(&recording)) else {
+        return Ok(());
+    };
+    let Some(tail) = tail(&bytes) else {
+        return Ok(());
+    };
+    std::fs::create_dir_all(dir(app_name)?)?;
+    std::fs::write(path(app_name, report_id)?, tail)?;
+    prune(&dir(app_name)?, KEEP)
+}
+
+/// Deletes every saved tail, for when the player denies consent.
+pub fn delete_all(app_name: &str) -> Result<(), crate::Error> {
+    let dir = dir(app_name)?;
+    if dir.exists() {
+        std::fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+fn prune(dir: &Path, keep: usize) -> Result<(), crate::Error> {
+    let mut saved = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        saved.push((entry.metadata()?.modified()?, entry.path()));
+    }
+    saved.sort();
+    for (_, path) in saved.iter().rev().skip(keep) {
+        let _ = std::fs::remove_file(path);
+    }
+    Ok(())
+}
+
+fn tail(bytes: &[u8]) -> Option<Vec<u8>> {
+    if bytes.len() <= HEADER_LEN {
+        return None;
+    }
+
+    let mut frame_starts = vec![];
+    let mut end = HEADER_LEN;
+    while let Some(len_bytes) = bytes.get(end..end + 4) {
+        let len = u32::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
+        let frame_end = end + 4 + len;
+        if frame_end > bytes.len() {
+            break;
+        }
+        frame_starts.push(end);
+        end = frame_end;
+    }
+
+    let start = frame_starts
+        .into_iter()
+        .find(|start| end - start <= TAIL_BUDGET)?;
+
+    let mut tail = bytes[..HEADER_LEN].to_vec();
+    tail.extend_from_slice(&bytes[start..end]);
+    Some(tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(len: usize) -> Vec<u8> {
+        let mut frame = (len as u32).to_le_bytes().to_vec();
+        frame.extend(std::iter::repeat_n(7, len));
+        frame
+    }
+
+    #[test]
+    fn keeps_last_whole_frames_within_budget() {
+        let mut bytes = vec![1; HEADER_LEN];
+        bytes.extend(frame(TAIL_BUDGET));
+        bytes.extend(frame(100));
+        bytes.extend(frame(200));
+        // Cut by the crash in the middle of a frame.
+        bytes.extend(&frame(300)[..50]);
+
+        let tail = tail(&bytes).unwrap();
+
+        let mut expected = vec![1; HEADER_LEN];
+        expected.extend(frame(100));
+        expected.extend(frame(200));
+        assert_eq!(tail, expected);
+    }
+
+    #[test]
+    fn prune_keeps_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let now = std::time::SystemTime::now();
+        for index in 0..4u64 {
+            let file = std::fs::File::create(dir.join(format!("{index}.bin"))).unwrap();
+            file.set_modified(now - std::time::Duration::from_secs(100 - index))
+                .unwrap();
+        }
+
+        prune(dir, 2).unwrap();
+
+        let mut left: Vec<_> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        left.sort();
+        assert_eq!(left, ["2.bin", "3.bin"]);
+    }
+
+    #[test]
+    fn no_tail_without_frames() {
+        assert!(tail(&[1; HEADER_LEN]).is_none());
+    }
+}
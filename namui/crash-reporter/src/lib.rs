@@ -8,27 +8,71 @@
 //! `--namui-crash-server <socket-name>`, call [`server_main`]. Otherwise, call
 //! [`init`] once before running the rest of the program, and hold the returned
 //! [`CrashGuard`] for the lifetime of the process.
+//!
+//! Rust panics don't produce a minidump; [`init`] also installs a panic hook
+//! that queues them as `kind: panic` reports and lets the panic continue.
+//!
+//! Call [`breadcrumb`] to leave a trail of what the app was doing; the last
+//! ones ride along in every report.
+//!
+//! Nothing is recorded once the player denies [`set_consent`], and with
+//! [`Config::require_consent`] nothing is sent until they grant it. Reports
+//! waiting in the queue can be listed with [`pending_reports`] for a
+//! "send crash report?" dialog. `log_tail`, the panic message and
+//! breadcrumbs go through [`Config::scrub_rules`] before they're queued.
 
+mod breadcrumb;
 mod child;
+mod consent;
 mod context;
 mod error;
+mod input_recording;
 mod install_id;
 mod log_capture;
 mod namsh;
+mod panic;
 mod parent;
+mod pending;
 mod queue;
+mod scrub;
 mod stack_hash;
+#[cfg(feature = "symbolicate")]
+mod symbolicate;
+#[cfg(test)]
+mod test_queue;
 
+pub use breadcrumb::{Breadcrumb, add as breadcrumb};
+pub use consent::Consent;
+pub use context::{CrashContext, CrashKind, PanicDetails};
 pub use log_capture::{LogCapture, start as start_log_capture};
+pub use pending::{
+    PendingReport, consent, discard as discard_pending_report, list as pending_reports,
+    send as send_pending_report, set_consent,
+};
+pub use scrub::{ScrubRule, default_rules as default_scrub_rules};
 
 pub use child::server_main;
 pub use error::Error;
 pub use parent::{CrashGuard, init};
+#[cfg(feature = "symbolicate")]
+pub use symbolicate::{
+    InlinedFrame, SymbolSources, SymbolicatedFrame, SymbolicatedReport, SymbolicatedThread,
+    symbolicate,
+};
 
-#[derive(Clone)]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Config {
     pub build_id: String,
     pub hmac_key_hex: String,
     pub namsh_url: String,
     pub app_name: String,
+    /// Hold reports until the player grants [`set_consent`].
+    pub require_consent: bool,
+    /// Keep the tail of the player's input with reports, on this machine only.
+    /// Off unless the game opts in.
+    #[serde(default)]
+    pub record_input: bool,
+    /// Applied in order; [`default_scrub_rules`] is a good start.
+    #[serde(skip)]
+    pub scrub_rules: Vec<ScrubRule>,
 }
@@ -1,4 +1,5 @@
 //! namsh wire protocol — `intake_crash` HMAC envelope + presigned R2 PUT.
+//! The PUT body is the queued payload: a minidump, or a `.panic` text report.
 //!
 //! Request body uses snake_case keys (forte_json reads them as-is). Response is
 //! internally-tagged with `"t"` discriminator and camelCase fields (forte_json's
@@ -162,3 +163,130 @@ pub fn flush_queue(config: &Config) -> Result<(), Error> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::breadcrumb::Breadcrumb;
+    use crate::context::{CrashKind, PanicDetails};
+    use crate::test_queue::{TestQueue, context};
+    use namsh_intake_emulator::{Emulator, IntakeOutput, Limits};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn flush_uploads_and_clears_queue() {
+        let emulator = Emulator::start().unwrap();
+        let queue = TestQueue::new("flush", &emulator);
+        let mut context = context(CrashKind::Panic);
+        context.panic = Some(PanicDetails {
+            message: "index out of bounds".to_string(),
+            location: "src/main.rs:1:1".to_string(),
+        });
+        context.breadcrumbs.push(Breadcrumb {
+            timestamp_unix_ms: 1,
+            category: "scene".to_string(),
+            message: "entered stage 3".to_string(),
+            data: BTreeMap::from([("seed".to_string(), "7".to_string())]),
+        });
+        queue.push("h1", context, b"panic at src/main.rs:1:1");
+
+        flush_queue(&queue.config).unwrap();
+
+        assert_eq!(queue.pending(), 0);
+        let dumps = emulator.dumps();
+        assert_eq!(dumps.len(), 1);
+        assert_eq!(dumps[0].r2_key, "dump/h1/dump-1.panic");
+        assert_eq!(
+            dumps[0].object.as_deref(),
+            Some(&b"panic at src/main.rs:1:1"[..])
+        );
+        assert_eq!(dumps[0].context.breadcrumbs[0].data["seed"], "7");
+    }
+
+    #[test]
+    fn rate_limited_entries_wait_for_next_flush() {
+        let emulator = Emulator::start().unwrap();
+        let queue = TestQueue::new("rate-limit", &emulator);
+        queue.push("h1", context(CrashKind::Native), b"MDMP1");
+        queue.push("h2", context(CrashKind::Native), b"MDMP2");
+
+        flush_queue(&queue.config).unwrap();
+        assert_eq!(queue.pending(), 1);
+        assert!(matches!(
+            emulator.intake_outputs()[..],
+            [IntakeOutput::Ok { .. }, IntakeOutput::RateLimited]
+        ));
+
+        emulator.advance(Duration::from_secs(61));
+        flush_queue(&queue.config).unwrap();
+        assert_eq!(queue.pending(), 0);
+        assert_eq!(emulator.groups().len(), 2);
+        assert!(emulator.dumps().iter().all(|d| d.object.is_some()));
+    }
+
+    #[test]
+    fn server_error_keeps_entry_for_retry() {
+        let emulator = Emulator::start().unwrap();
+        let queue = TestQueue::new("server-error", &emulator);
+        queue.push("h1", context(CrashKind::Native), b"MDMP");
+        emulator.fail_next_intakes(1);
+
+        flush_queue(&queue.config).unwrap();
+        assert_eq!(queue.pending(), 1);
+        assert!(emulator.groups().is_empty());
+
+        flush_queue(&queue.config).unwrap();
+        assert_eq!(queue.pending(), 0);
+        assert_eq!(emulator.group("h1").unwrap().count, 1);
+    }
+
+    /// The retry goes through intake again, so namsh counts the crash twice.
+    #[test]
+    fn failed_upload_retries_from_intake() {
+        let emulator = Emulator::start().unwrap();
+        let queue = TestQueue::new("upload-error", &emulator);
+        queue.push("h1", context(CrashKind::Native), b"MDMP");
+        emulator.fail_next_uploads(1);
+
+        flush_queue(&queue.config).unwrap();
+        assert_eq!(queue.pending(), 1);
+
+        emulator.advance(Duration::from_secs(61));
+        flush_queue(&queue.config).unwrap();
+        assert_eq!(queue.pending(), 0);
+        assert_eq!(emulator.group("h1").unwrap().count, 2);
+        let objects: Vec<_> = emulator.dumps().into_iter().map(|d| d.object).collect();
+        assert_eq!(objects, [None, Some(b"MDMP".to_vec())]);
+    }
+
+    #[test]
+    fn full_group_clears_queue_without_upload() {
+        let emulator = Emulator::start_with(Limits {
+            max_dumps_per_group: 0,
+            ..Default::default()
+        })
+        .unwrap();
+        let queue = TestQueue::new("full-group", &emulator);
+        queue.push("h1", context(CrashKind::Native), b"MDMP");
+
+        flush_queue(&queue.config).unwrap();
+
+        assert_eq!(queue.pending(), 0);
+        assert_eq!(emulator.group("h1").unwrap().count, 1);
+        assert!(emulator.dumps().is_empty());
+    }
+
+    #[test]
+    fn rejected_signature_keeps_entry() {
+        let emulator = Emulator::start().unwrap();
+        let mut queue = TestQueue::new("bad-key", &emulator);
+        queue.config.hmac_key_hex = "00".repeat(32);
+        queue.push("h1", context(CrashKind::Native), b"MDMP");
+
+        flush_queue(&queue.config).unwrap();
+
+        assert_eq!(queue.pending(), 1);
+        assert_eq!(emulator.intake_outputs(), [IntakeOutput::InvalidSignature]);
+        assert!(emulator.groups().is_empty());
+    }
+}
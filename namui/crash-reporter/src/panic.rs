@@ -0,0 +1,182 @@
+//! Records Rust panics as `kind: panic` crash reports.
+//!
+//! A panic never reaches the minidump child: forcing it into a native crash
+//! groups every panic by the abort frame, so the hook writes its own queue
+//! entry instead — `<id>.panic` plus the usual `.json` sidecar — and lets the
+//! panic continue. The `.panic` file takes the minidump's place in the upload:
+//! message and location, scrubbed like the context, and the raw frames.
+//!
+//! `stack_hash` is symbol-free like `stack_hash.rs`: SHA-256 hex over the
+//! location followed by the top [`TOP_N`] frames in
+//! `module_basename!0x<offset_from_module_base>` form, joined by `\n`. The first
+//! frames are always the hook and std's panic machinery, so [`TOP_N`] is larger
+//! than the native one and the location keeps unrelated panics apart.
+
+use crate::{
+    Config, Error, breadcrumb, consent,
+    context::{self, PanicDetails},
+    input_recording, install_id, log_capture, namsh, queue, scrub,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Write,
+    panic::PanicHookInfo,
+    path::{Path, PathBuf},
+    thread,
+    time::Instant,
+};
+
+const TOP_N: usize = 32;
+
+pub fn install_hook(config: &Config) {
+    let config = config.clone();
+    let started_at = Instant::now();
+    let prev = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if consent::allows_recording(&config) {
+            match record(&config, started_at, info) {
+                Ok(path) if consent::allows_upload(&config) => {
+                    let config = config.clone();
+                    // Best effort; whatever doesn't make it is flushed on the next launch.
+                    let _ = thread::Builder::new()
+                        .name("crash-reporter-panic-uploader".into())
+                        .spawn(move || {
+                            if let Err(e) = namsh::upload_single(&config, &path) {
+                                eprintln!("[crash-reporter] panic upload failed (queued): {e}");
+                            }
+                        });
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("[crash-reporter] failed to record panic: {e}"),
+            }
+        }
+        prev(info);
+    }));
+}
+
+fn record(config: &Config, started_at: Instant, info: &PanicHookInfo) -> Result<PathBuf, Error> {
+    let mut details = details(info);
+    scrub::apply_to_panic(&config.scrub_rules, &mut details);
+    let frames = frames();
+    let stack_hash = stack_hash(&details.location, &frames);
+
+    let queue_dir = queue::queue_dir(&config.app_name)?;
+    std::fs::create_dir_all(&queue_dir)?;
+    let path = queue_dir.join(format!("{}.panic", uuid::Uuid::new_v4()));
+    std::fs::write(&path, report(&details, &frames))?;
+
+    let install_id = install_id::get_or_create(&config.app_name)?;
+    let context = context::collect(context::CollectArgs {
+        config,
+        install_id: &install_id,
+        session_uptime_sec: started_at.elapsed().as_secs(),
+        log_tail: log_capture::read_tail(),
+        panic: Some(details),
+        breadcrumbs: breadcrumb::read(),
+    });
+    queue::write_sidecar(
+        &path,
+        &queue::PendingEntry {
+            stack_hash,
+            context,
+        },
+    )?;
+    if let Err(e) = input_recording::save_tail(&config.app_name, &queue::report_id(&path)) {
+        eprintln!("[crash-reporter] failed to save input recording: {e}");
+    }
+    Ok(path)
+}
+
+fn details(info: &PanicHookInfo) -> PanicDetails {
+    let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = info.payload().downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    };
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_else(|| "<unknown>".to_string());
+    PanicDetails { message, location }
+}
+
+fn frames() -> Vec<String> {
+    let mut frames = Vec::with_capacity(TOP_N);
+    backtrace::trace(|frame| {
+        frames.push(format_frame(frame));
+        frames.len() < TOP_N
+    });
+    frames
+}
+
+#[cfg(unix)]
+fn format_frame(frame: &backtrace::Frame) -> String {
+    let ip = frame.ip();
+    let mut dl_info: libc::Dl_info = unsafe { std::mem::zeroed() };
+    // SAFETY: `dladdr` only reads the loader's module list and fills `dl_info`.
+    if unsafe { libc::dladdr(ip, &mut dl_info) } == 0 || dl_info.dli_fname.is_null() {
+        return format!("0x{:x}", ip as usize);
+    }
+    // SAFETY: non-null `dli_fname` points to the loader's NUL-terminated path.
+    let raw = unsafe { std::ffi::CStr::from_ptr(dl_info.dli_fname) }.to_string_lossy();
+    let name = Path::new(raw.as_ref())
+        .file_name()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| raw.clone().into_owned());
+    let offset = (ip as usize).saturating_sub(dl_info.dli_fbase as usize);
+    format!("{name}!0x{offset:x}")
+}
+
+/// Without `dladdr` only the module base is known, so the name is left out.
+#[cfg(not(unix))]
+fn format_frame(frame: &backtrace::Frame) -> String {
+    let ip = frame.ip() as usize;
+    match frame.module_base_address() {
+        Some(base) => format!("!0x{:x}", ip.saturating_sub(base as usize)),
+        None => format!("0x{ip:x}"),
+    }
+}
+
+fn stack_hash(location: &str, frames: &[String]) -> String {
+    let mut digest = Sha256::new();
+    digest.update(location.as_bytes());
+    for frame in frames {
+        digest.update(b"\n");
+        digest.update(frame.as_bytes());
+    }
+    hex::encode(digest.finalize())
+}
+
+fn report(details: &PanicDetails, frames: &[String]) -> String {
+    let mut report = format!("panic at {}: {}\n\n", details.location, details.message);
+    for (index, frame) in frames.iter().enumerate() {
+        let _ = writeln!(report, "{index:>3}: {frame}");
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stack_hash_is_location_then_frames() {
+        let frames = vec!["app!0x10".to_string(), "app!0x20".to_string()];
+        let expected = hex::encode(Sha256::digest(b"src/main.rs:1:1\napp!0x10\napp!0x20"));
+        assert_eq!(stack_hash("src/main.rs:1:1", &frames), expected);
+        assert_ne!(stack_hash("src/main.rs:2:1", &frames), expected);
+    }
+
+    #[test]
+    fn frames_are_symbol_free() {
+        let frames = frames();
+        assert!(!frames.is_empty());
+        assert!(frames.len() <= TOP_N);
+        for frame in frames {
+            let offset = frame.rsplit_once('!').map_or(frame.as_str(), |(_, o)| o);
+            assert!(offset.starts_with("0x"), "{frame}");
+        }
+    }
+}
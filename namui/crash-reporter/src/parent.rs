@@ -1,4 +1,6 @@
-use crate::{Config, Error, install_id, namsh, queue};
+use crate::{
+    Config, Error, breadcrumb, consent, input_recording, install_id, namsh, panic, pending, queue,
+};
 use crash_handler::CrashHandler;
 use minidumper::{Client, SocketName};
 use std::{
@@ -17,15 +19,22 @@ pub fn init(config: &Config) -> Result<CrashGuard, Error> {
     let root = queue::root_dir(&config.app_name)?;
     std::fs::create_dir_all(&root)?;
     let _ = install_id::get_or_create(&config.app_name)?;
+    if config.record_input && consent::allows_recording(config) {
+        input_recording::set_path(&config.app_name)?;
+    }
+    breadcrumb::set_path(&config.app_name)?;
+    pending::export_config(config)?;
 
-    let pending_config = config.clone();
-    thread::Builder::new()
-        .name("crash-reporter-uploader".into())
-        .spawn(move || {
-            if let Err(e) = namsh::flush_queue(&pending_config) {
-                eprintln!("[crash-reporter] flush_queue error: {e}");
-            }
-        })?;
+    if consent::allows_upload(config) {
+        let pending_config = config.clone();
+        thread::Builder::new()
+            .name("crash-reporter-uploader".into())
+            .spawn(move || {
+                if let Err(e) = namsh::flush_queue(&pending_config) {
+                    eprintln!("[crash-reporter] flush_queue error: {e}");
+                }
+            })?;
+    }
 
     let pid = std::process::id();
     // Use the OS temp dir rather than hard-coding `/tmp/…`: on Windows `/tmp`
@@ -70,7 +79,7 @@ pub fn init(config: &Config) -> Result<CrashGuard, Error> {
     #[cfg(any(target_os = "linux", target_os = "android"))]
     handler.set_ptracer(Some(child.id()));
 
-    install_panic_hook();
+    panic::install_hook(config);
 
     Ok(CrashGuard {
         _handler: handler,
@@ -78,26 +87,6 @@ pub fn init(config: &Config) -> Result<CrashGuard, Error> {
     })
 }
 
-/// Translate a Rust `panic!()` into a signal `CrashHandler` can capture.
-///
-/// Without this, panic unwinds and drops `CrashGuard` (handler + minidumper
-/// child) before the abort signal fires, so the signal lands with no handler
-/// installed. We instead force an immediate abort from the hook.
-///
-/// `libc::abort()` (not `std::process::abort()`) is required on Windows:
-/// Rust's `process::abort()` uses `__fastfail` which bypasses SEH and any
-/// `signal(SIGABRT, …)` handler — including the one `crash_handler` installs
-/// to translate SIGABRT into a captureable exception. The CRT `abort()`
-/// raises SIGABRT first, so the handler runs.
-fn install_panic_hook() {
-    let prev = std::panic::take_hook();
-    std::panic::set_hook(Box::new(move |info| {
-        prev(info);
-        // SAFETY: `abort` is signal-safe and never returns.
-        unsafe { libc::abort() };
-    }));
-}
-
 fn connect_with_retry(
     socket_path: &std::path::Path,
     timeout: Duration,
@@ -0,0 +1,165 @@
+//! Queued reports and consent, for a "send crash report?" dialog.
+//!
+//! The dialog lives in game code — in dev that's the app dylib, which links its
+//! own copy of this crate and never sees the runner's [`Config`]. So [`init`]
+//! leaves the config in `NAMUI_CRASH_CONFIG`, the same way `log_capture.rs`
+//! shares its ring, and everything here reads it from there. Without a running
+//! crash reporter the list is empty and the rest is a no-op.
+//!
+//! [`init`]: crate::init
+
+use crate::{
+    Config, Error,
+    consent::{self, Consent},
+    context::CrashContext,
+    input_recording, namsh, queue,
+};
+use std::{path::PathBuf, time::SystemTime};
+
+const ENV_CONFIG: &str = "NAMUI_CRASH_CONFIG";
+
+pub struct PendingReport {
+    /// Pass to [`send`] or [`discard`].
+    pub id: String,
+    pub recorded_at: SystemTime,
+    pub stack_hash: String,
+    /// Exactly what will be sent, scrubbed.
+    pub context: CrashContext,
+    /// Size of the minidump or panic report uploaded with it.
+    pub payload_bytes: u64,
+    /// The input recording's tail, with [`Config::record_input`]. Never
+    /// uploaded; it stays on disk after the report is sent or discarded.
+    pub input_recording: Option<PathBuf>,
+}
+
+/// Called by the parent, before the app starts.
+pub(crate) fn export_config(config: &Config) -> Result<(), Error> {
+    let json = serde_json::to_string(config)?;
+    // SAFETY: see `log_capture::start` — called early in `main` before any
+    // other threads touch the environment.
+    unsafe {
+        std::env::set_var(ENV_CONFIG, json);
+    }
+    Ok(())
+}
+
+fn config() -> Option<Config> {
+    serde_json::from_str(&std::env::var(ENV_CONFIG).ok()?).ok()
+}
+
+/// Oldest first.
+pub fn list() -> Result<Vec<PendingReport>, Error> {
+    config().map_or(Ok(Vec::new()), |config| list_for(&config))
+}
+
+/// Uploads one report regardless of consent — the player just said yes to it.
+/// Blocks on the network, so keep it off the UI thread.
+pub fn send(id: &str) -> Result<(), Error> {
+    config().map_or(Ok(()), |config| send_for(&config, id))
+}
+
+pub fn discard(id: &str) -> Result<(), Error> {
+    config().map_or(Ok(()), |config| discard_for(&config, id))
+}
+
+pub fn consent() -> Consent {
+    config().map_or(Consent::Unknown, |config| consent::get(&config.app_name))
+}
+
+/// See [`Consent`]. Takes effect for the next crash, including ones the crash
+/// child records, since both read it from disk.
+pub fn set_consent(consent: Consent) -> Result<(), Error> {
+    config().map_or(Ok(()), |config| consent::set(&config.app_name, consent))
+}
+
+fn list_for(config: &Config) -> Result<Vec<PendingReport>, Error> {
+    let mut reports = Vec::new();
+    for path in queue::list_pending(&config.app_name)? {
+        let entry = queue::load_sidecar(&path)?;
+        let metadata = std::fs::metadata(&path)?;
+        let id = queue::report_id(&path);
+        let input_recording =
+            Some(input_recording::path(&config.app_name, &id)?).filter(|path| path.exists());
+        reports.push(PendingReport {
+            id,
+            recorded_at: metadata.modified()?,
+            stack_hash: entry.stack_hash,
+            context: entry.context,
+            payload_bytes: metadata.len(),
+            input_recording,
+        });
+    }
+    reports.sort_by_key(|report| report.recorded_at);
+    Ok(reports)
+}
+
+fn send_for(config: &Config, id: &str) -> Result<(), Error> {
+    namsh::upload_single(config, &find(config, id)?)
+}
+
+fn discard_for(config: &Config, id: &str) -> Result<(), Error> {
+    queue::delete_entry(&find(config, id)?)
+}
+
+fn find(config: &Config, id: &str) -> Result<PathBuf, Error> {
+    queue::list_pending(&config.app_name)?
+        .into_iter()
+        .find(|path| queue::report_id(path) == id)
+        .ok_or_else(|| Error::UnknownReport(id.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        context::CrashKind,
+        test_queue::{TestQueue, context},
+    };
+    use namsh_intake_emulator::Emulator;
+
+    #[test]
+    fn lists_sends_and_discards() {
+        let emulator = Emulator::start().unwrap();
+        let queue = TestQueue::new("pending", &emulator);
+        queue.push("h1", context(CrashKind::Native), b"MDMP1");
+        queue.push("h2", context(CrashKind::Panic), b"panic");
+
+        let mut reports = list_for(&queue.config).unwrap();
+        reports.sort_by(|a, b| a.id.cmp(&b.id));
+        let ids: Vec<_> = reports.iter().map(|report| report.id.as_str()).collect();
+        assert_eq!(ids, ["h1", "h2"]);
+        assert_eq!(reports[0].stack_hash, "h1");
+        assert_eq!(reports[0].payload_bytes, 5);
+        assert_eq!(reports[1].context.kind, CrashKind::Panic);
+        assert!(reports[0].input_recording.is_none());
+
+        send_for(&queue.config, "h1").unwrap();
+        assert_eq!(queue.pending(), 1);
+        assert_eq!(emulator.group("h1").unwrap().count, 1);
+
+        discard_for(&queue.config, "h2").unwrap();
+        assert_eq!(queue.pending(), 0);
+        assert!(emulator.group("h2").is_none());
+
+        assert!(matches!(
+            send_for(&queue.config, "h2"),
+            Err(Error::UnknownReport(id)) if id == "h2"
+        ));
+    }
+
+    #[test]
+    fn lists_saved_input_recording() {
+        let emulator = Emulator::start().unwrap();
+        let queue = TestQueue::new("pending-recording", &emulator);
+        queue.push("h1", context(CrashKind::Native), b"MDMP");
+        let recording = input_recording::path(&queue.config.app_name, "h1").unwrap();
+        std::fs::create_dir_all(recording.parent().unwrap()).unwrap();
+        std::fs::write(&recording, b"recording").unwrap();
+
+        let reports = list_for(&queue.config).unwrap();
+        assert_eq!(reports[0].input_recording.as_ref(), Some(&recording));
+
+        send_for(&queue.config, "h1").unwrap();
+        assert!(recording.exists());
+    }
+}
@@ -2,11 +2,24 @@ use crate::{Error, context::CrashContext};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+/// Overrides the user data dir, for tests and portable installs. The crash
+/// child inherits it with the rest of the environment.
+pub const ENV_DATA_DIR: &str = "NAMUI_CRASH_DATA_DIR";
+
+/// `<user data dir>/<app_name>/crashes`, where the queue, consent, install id
+/// and the files shared with the child live.
 pub fn root_dir(app_name: &str) -> Result<PathBuf, Error> {
-    let base = dirs::data_local_dir().ok_or(Error::NoUserDataDir)?;
+    let base = match std::env::var_os(ENV_DATA_DIR) {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::data_local_dir().ok_or(Error::NoUserDataDir)?,
+    };
     Ok(base.join(app_name).join("crashes"))
 }
 
+/// Native crashes queue a minidump, panics a text report (see `panic.rs`).
+/// Either way the payload has a `.json` sidecar next to it.
+const PAYLOAD_EXTENSIONS: [&str; 2] = ["dmp", "panic"];
+
 pub fn queue_dir(app_name: &str) -> Result<PathBuf, Error> {
     Ok(root_dir(app_name)?.join("queue"))
 }
@@ -17,6 +30,15 @@ pub struct PendingEntry {
     pub context: CrashContext,
 }
 
+/// The payload's file stem — what [`crate::PendingReport::id`] and the saved
+/// input recording go by.
+pub fn report_id(payload_path: &Path) -> String {
+    payload_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
 pub fn sidecar_path(dump_path: &Path) -> PathBuf {
     dump_path.with_extension("json")
 }
@@ -32,17 +54,19 @@ pub fn list_pending(app_name: &str) -> Result<Vec<PathBuf>, Error> {
     if !dir.exists() {
         return Ok(Vec::new());
     }
-    let mut dumps = Vec::new();
+    let mut payloads = Vec::new();
     for entry in std::fs::read_dir(&dir)? {
         let entry = entry?;
         let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) == Some("dmp")
-            && sidecar_path(&path).exists()
-        {
-            dumps.push(path);
+        let is_payload = path
+            .extension()
+            .and_then(|s| s.to_str())
+            .is_some_and(|ext| PAYLOAD_EXTENSIONS.contains(&ext));
+        if is_payload && sidecar_path(&path).exists() {
+            payloads.push(path);
         }
     }
-    Ok(dumps)
+    Ok(payloads)
 }
 
 pub fn load_sidecar(dump_path: &Path) -> Result<PendingEntry, Error> {
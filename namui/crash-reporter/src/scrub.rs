@@ -0,0 +1,154 @@
+//! PII scrubbing for the free-text parts of a report — `log_tail`, the panic
+//! message and location, and the breadcrumbs' messages and data values —
+//! applied in `context::collect`, so what's queued, previewed and signed is
+//! already scrubbed.
+
+use crate::context::{CrashContext, PanicDetails};
+use regex::Regex;
+use std::borrow::Cow;
+
+#[derive(Clone, Debug)]
+pub struct ScrubRule {
+    pattern: Regex,
+    replacement: String,
+}
+
+impl ScrubRule {
+    /// `replacement` may refer to capture groups as `$1` or `${name}`.
+    pub fn new(pattern: &str, replacement: impl Into<String>) -> Result<Self, regex::Error> {
+        Ok(Self {
+            pattern: Regex::new(pattern)?,
+            replacement: replacement.into(),
+        })
+    }
+
+    pub fn literal(text: &str, replacement: impl Into<String>) -> Self {
+        Self {
+            pattern: Regex::new(&regex::escape(text)).expect("escaped text is a valid regex"),
+            replacement: replacement.into(),
+        }
+    }
+
+    fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        self.pattern.replace_all(text, self.replacement.as_str())
+    }
+}
+
+/// The home directory becomes `~`, the OS user name `<user>` and email
+/// addresses `<email>`.
+pub fn default_rules() -> Vec<ScrubRule> {
+    let mut rules = Vec::new();
+    if let Some(home) = dirs::home_dir().and_then(|home| home.to_str().map(str::to_string)) {
+        if cfg!(windows) {
+            rules.push(ScrubRule::literal(&home.replace('\\', "/"), "~"));
+        }
+        rules.push(ScrubRule::literal(&home, "~"));
+    }
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok();
+    // Too short a name would match all over the log.
+    if let Some(user) = user.filter(|user| user.chars().count() >= 3) {
+        rules.push(
+            ScrubRule::new(&format!(r"\b{}\b", regex::escape(&user)), "<user>")
+                .expect("escaped text is a valid regex"),
+        );
+    }
+    rules
+        .push(ScrubRule::new(r"[\w.+-]+@[\w-]+(\.[\w-]+)+", "<email>").expect("valid email regex"));
+    rules
+}
+
+pub(crate) fn apply(rules: &[ScrubRule], context: &mut CrashContext) {
+    if rules.is_empty() {
+        return;
+    }
+    if let Some(log_tail) = &mut context.log_tail {
+        *log_tail = scrub(rules, log_tail);
+    }
+    if let Some(panic) = &mut context.panic {
+        apply_to_panic(rules, panic);
+    }
+    for breadcrumb in &mut context.breadcrumbs {
+        breadcrumb.message = scrub(rules, &breadcrumb.message);
+        for value in breadcrumb.data.values_mut() {
+            *value = scrub(rules, value);
+        }
+    }
+}
+
+/// Also used on its own for the `.panic` payload, which repeats both.
+pub(crate) fn apply_to_panic(rules: &[ScrubRule], panic: &mut PanicDetails) {
+    panic.message = scrub(rules, &panic.message);
+    panic.location = scrub(rules, &panic.location);
+}
+
+fn scrub(rules: &[ScrubRule], text: &str) -> String {
+    rules.iter().fold(text.to_string(), |text, rule| {
+        rule.apply(&text).into_owned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{breadcrumb::Breadcrumb, context::CrashKind};
+
+    fn context(log_tail: &str, message: &str) -> CrashContext {
+        CrashContext {
+            build_id: "b".to_string(),
+            install_id: "i".to_string(),
+            session_uptime_sec: 0,
+            log_tail: Some(log_tail.to_string()),
+            kind: CrashKind::Native,
+            panic: None,
+            breadcrumbs: vec![Breadcrumb {
+                timestamp_unix_ms: 0,
+                category: "save".to_string(),
+                message: message.to_string(),
+                data: [("path".to_string(), "/home/alice/save.bin".to_string())].into(),
+            }],
+        }
+    }
+
+    #[test]
+    fn scrubs_log_tail_and_breadcrumbs() {
+        let rules = vec![
+            ScrubRule::literal("/home/alice", "~"),
+            ScrubRule::new(r"\balice\b", "<user>").unwrap(),
+        ];
+        let mut context = context(
+            "loading /home/alice/save.bin\nhello alice\n",
+            "saved by alice",
+        );
+        apply(&rules, &mut context);
+        assert_eq!(
+            context.log_tail.as_deref(),
+            Some("loading ~/save.bin\nhello <user>\n")
+        );
+        assert_eq!(context.breadcrumbs[0].message, "saved by <user>");
+        assert_eq!(context.breadcrumbs[0].data["path"], "~/save.bin");
+    }
+
+    #[test]
+    fn scrubs_panic_details() {
+        let rules = vec![ScrubRule::literal("/home/alice", "~")];
+        let mut context = context("", "");
+        context.kind = CrashKind::Panic;
+        context.panic = Some(PanicDetails {
+            message: "no such file: /home/alice/save.bin".to_string(),
+            location: "/home/alice/.cargo/registry/src/lib.rs:1:1".to_string(),
+        });
+        apply(&rules, &mut context);
+        let panic = context.panic.unwrap();
+        assert_eq!(panic.message, "no such file: ~/save.bin");
+        assert_eq!(panic.location, "~/.cargo/registry/src/lib.rs:1:1");
+    }
+
+    #[test]
+    fn default_rules_scrub_emails() {
+        let mut context = context("contact: someone@example.com\n", "");
+        apply(&default_rules(), &mut context);
+        assert_eq!(context.log_tail.as_deref(), Some("contact: <email>\n"));
+    }
+}
@@ -9,7 +9,8 @@ use minidump::{
     Module,
 };
 use minidump_unwind::{
-    CallStack, SystemInfo as UnwindSystemInfo, Symbolizer, simple_symbol_supplier, walk_stack,
+    CallStack, StackFrame, Symbolizer, SystemInfo as UnwindSystemInfo, simple_symbol_supplier,
+    walk_stack,
 };
 use sha2::{Digest, Sha256};
 use std::path::Path;
@@ -36,16 +37,7 @@ pub fn compute(dump_path: &Path) -> Result<String, Error> {
         .ok_or(Error::MissingCrashingContext)?
         .into_owned();
 
-    let (os_version, os_build) = mdsi.os_parts();
-    let unwind_system_info = UnwindSystemInfo {
-        os: mdsi.os,
-        os_version: Some(os_version),
-        os_build,
-        cpu: mdsi.cpu,
-        cpu_info: mdsi.cpu_info().map(|c| c.into_owned()),
-        cpu_microcode_version: None,
-        cpu_count: 1,
-    };
+    let unwind_system_info = unwind_system_info(&mdsi);
 
     let mut stack = CallStack::with_context(context);
     let symbolizer = Symbolizer::new(simple_symbol_supplier(vec![]));
@@ -63,21 +55,39 @@ pub fn compute(dump_path: &Path) -> Result<String, Error> {
         &symbolizer,
     ));
 
-    let mut lines = Vec::with_capacity(TOP_N);
-    for frame in stack.frames.iter().take(TOP_N) {
-        match &frame.module {
-            Some(module) => {
-                let raw = module.code_file();
-                let name = Path::new(raw.as_ref())
-                    .file_name()
-                    .map(|s| s.to_string_lossy().into_owned())
-                    .unwrap_or_else(|| raw.clone().into_owned());
-                let offset = frame.instruction.saturating_sub(module.base_address());
-                lines.push(format!("{name}!0x{offset:x}"));
-            }
-            None => lines.push(format!("0x{:x}", frame.instruction)),
+    let lines: Vec<String> = stack.frames.iter().take(TOP_N).map(frame_key).collect();
+
+    Ok(hex::encode(Sha256::digest(lines.join("\n").as_bytes())))
+}
+
+pub(crate) fn unwind_system_info(mdsi: &MinidumpSystemInfo) -> UnwindSystemInfo {
+    let (os_version, os_build) = mdsi.os_parts();
+    UnwindSystemInfo {
+        os: mdsi.os,
+        os_version: Some(os_version),
+        os_build,
+        cpu: mdsi.cpu,
+        cpu_info: mdsi.cpu_info().map(|c| c.into_owned()),
+        cpu_microcode_version: None,
+        cpu_count: 1,
+    }
+}
+
+/// `module_basename!0x<offset>`, or the bare address outside any module.
+pub(crate) fn frame_key(frame: &StackFrame) -> String {
+    match &frame.module {
+        Some(module) => {
+            let offset = frame.instruction.saturating_sub(module.base_address());
+            format!("{}!0x{offset:x}", module_basename(module))
         }
+        None => format!("0x{:x}", frame.instruction),
     }
+}
 
-    Ok(hex::encode(Sha256::digest(lines.join("\n").as_bytes())))
+pub(crate) fn module_basename(module: &(impl Module + ?Sized)) -> String {
+    let raw = module.code_file();
+    Path::new(raw.as_ref())
+        .file_name()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| raw.clone().into_owned())
 }
@@ -0,0 +1,504 @@
+//! Offline symbolication of a minidump, for the `namui-crash-symbolicate` CLI.
+//!
+//! Walks every thread with `minidump-unwind` and names the frames from local
+//! debug files:
+//! - Breakpad `.sym` stores (`<debug_file>/<debug_id>/<name>.sym`), which is
+//!   how PDBs come in — the CLI converts them with `dump_syms`.
+//! - ELF or Mach-O files carrying DWARF, matched to the dump's modules by
+//!   GNU build-id or Mach-O UUID and read with `addr2line`.
+//!
+//! The report leads with [`stack_hash::compute`]'s hash, so it can be looked up
+//! as-is in namsh.
+
+use crate::{Error, stack_hash};
+use async_trait::async_trait;
+use gimli::{EndianArcSlice, RunTimeEndian};
+use minidump::{
+    Minidump, MinidumpException, MinidumpModuleList, MinidumpSystemInfo, MinidumpThreadList,
+    MinidumpThreadNames, Module,
+};
+use minidump_unwind::{
+    CallStack, FileError, FileKind, FillSymbolError, FrameSymbolizer, FrameWalker, StackFrame,
+    SymbolProvider, Symbolizer, simple_symbol_supplier, walk_stack,
+};
+use object::{Object, ObjectSection, ObjectSymbol, SymbolKind};
+use serde::Serialize;
+use std::{
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+#[derive(Default)]
+pub struct SymbolSources {
+    /// Breakpad symbol store roots.
+    pub breakpad_paths: Vec<PathBuf>,
+    /// ELF or Mach-O files with DWARF.
+    pub debug_files: Vec<PathBuf>,
+}
+
+#[derive(Serialize)]
+pub struct SymbolicatedReport {
+    pub stack_hash: String,
+    pub crash_reason: Option<String>,
+    pub crash_address: Option<u64>,
+    pub threads: Vec<SymbolicatedThread>,
+}
+
+#[derive(Serialize)]
+pub struct SymbolicatedThread {
+    pub thread_id: u32,
+    pub name: Option<String>,
+    pub crashed: bool,
+    pub frames: Vec<SymbolicatedFrame>,
+}
+
+#[derive(Serialize)]
+pub struct SymbolicatedFrame {
+    /// `module_basename!0x<offset>`, the form `stack_hash` hashes.
+    pub address: String,
+    pub function: Option<String>,
+    pub function_offset: Option<u64>,
+    pub source_file: Option<String>,
+    pub source_line: Option<u32>,
+    /// Outermost first; the last one is where the instruction actually is.
+    pub inlined: Vec<InlinedFrame>,
+    /// How the unwinder found the frame: `context`, `cfi`, `frame_pointer`, `scan`, ...
+    pub trust: String,
+}
+
+#[derive(Serialize)]
+pub struct InlinedFrame {
+    pub function: String,
+    pub source_file: Option<String>,
+    pub source_line: Option<u32>,
+}
+
+pub fn symbolicate(dump_path: &Path, sources: &SymbolSources) -> Result<SymbolicatedReport, Error> {
+    let stack_hash = stack_hash::compute(dump_path)?;
+
+    let dump = Minidump::read_path(dump_path)?;
+    let threads: MinidumpThreadList = dump.get_stream()?;
+    let modules: MinidumpModuleList = dump.get_stream()?;
+    let mdsi: MinidumpSystemInfo = dump.get_stream()?;
+    let exception = dump.get_stream::<MinidumpException>().ok();
+    let thread_names = dump.get_stream::<MinidumpThreadNames>().unwrap_or_default();
+    let memory_list = dump.get_memory().unwrap_or_default();
+    let unwind_system_info = stack_hash::unwind_system_info(&mdsi);
+
+    let provider = Provider {
+        breakpad: Symbolizer::new(simple_symbol_supplier(sources.breakpad_paths.clone())),
+        debug_files: sources
+            .debug_files
+            .iter()
+            .map(|path| DebugFile::load(path))
+            .collect::<Result<_, _>>()?,
+    };
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let crashing_tid = exception.as_ref().map(|e| e.get_crashing_thread_id());
+    let mut symbolicated_threads = Vec::with_capacity(threads.threads.len());
+    for (index, thread) in threads.threads.iter().enumerate() {
+        let thread_id = thread.raw.thread_id;
+        let crashed = crashing_tid == Some(thread_id);
+        let context = exception
+            .as_ref()
+            .filter(|_| crashed)
+            .and_then(|e| e.context(&mdsi, None))
+            .or_else(|| thread.context(&mdsi, None));
+
+        let mut frames = Vec::new();
+        if let Some(context) = context {
+            let mut stack = CallStack::with_context(context.into_owned());
+            rt.block_on(walk_stack(
+                index,
+                (),
+                &mut stack,
+                thread.stack_memory(&memory_list),
+                &modules,
+                &unwind_system_info,
+                &provider,
+            ));
+            frames = stack.frames.iter().map(symbolicated_frame).collect();
+        }
+
+        symbolicated_threads.push(SymbolicatedThread {
+            thread_id,
+            name: thread_names.get_name(thread_id).map(|n| n.into_owned()),
+            crashed,
+            frames,
+        });
+    }
+    // Crashed thread first, like minidump-stackwalk.
+    symbolicated_threads.sort_by_key(|t| !t.crashed);
+
+    Ok(SymbolicatedReport {
+        stack_hash,
+        crash_reason: exception
+            .as_ref()
+            .map(|e| e.get_crash_reason(mdsi.os, mdsi.cpu).to_string()),
+        crash_address: exception
+            .as_ref()
+            .map(|e| e.get_crash_address(mdsi.os, mdsi.cpu)),
+        threads: symbolicated_threads,
+    })
+}
+
+fn symbolicated_frame(frame: &StackFrame) -> SymbolicatedFrame {
+    SymbolicatedFrame {
+        address: stack_hash::frame_key(frame),
+        function: frame.function_name.clone(),
+        function_offset: frame
+            .function_base
+            .map(|base| frame.instruction.saturating_sub(base)),
+        source_file: frame.source_file_name.clone(),
+        source_line: frame.source_line,
+        inlined: frame
+            .inlines
+            .iter()
+            .map(|inline| InlinedFrame {
+                function: inline.function_name.clone(),
+                source_file: inline.source_file_name.clone(),
+                source_line: inline.source_line,
+            })
+            .collect(),
+        trust: frame.trust.as_str().to_string(),
+    }
+}
+
+impl Display for SymbolicatedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "stack_hash: {}", self.stack_hash)?;
+        if let Some(reason) = &self.crash_reason {
+            write!(f, "crash: {reason}")?;
+            if let Some(address) = self.crash_address {
+                write!(f, " at 0x{address:x}")?;
+            }
+            writeln!(f)?;
+        }
+        for thread in &self.threads {
+            writeln!(f)?;
+            write!(f, "Thread {}", thread.thread_id)?;
+            if let Some(name) = &thread.name {
+                write!(f, " \"{name}\"")?;
+            }
+            writeln!(f, "{}", if thread.crashed { " (crashed)" } else { "" })?;
+            for (index, frame) in thread.frames.iter().enumerate() {
+                write!(f, "{index:>4}  {}", frame.address)?;
+                if let Some(function) = &frame.function {
+                    write!(f, "  {function}")?;
+                    if let Some(offset) = frame.function_offset {
+                        write!(f, " + 0x{offset:x}")?;
+                    }
+                }
+                write_source(f, &frame.source_file, frame.source_line)?;
+                writeln!(f, "  ({})", frame.trust)?;
+                for inline in &frame.inlined {
+                    write!(f, "          inlined {}", inline.function)?;
+                    write_source(f, &inline.source_file, inline.source_line)?;
+                    writeln!(f)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_source(
+    f: &mut fmt::Formatter<'_>,
+    file: &Option<String>,
+    line: Option<u32>,
+) -> fmt::Result {
+    match (file, line) {
+        (Some(file), Some(line)) => write!(f, "  [{file}:{line}]"),
+        (Some(file), None) => write!(f, "  [{file}]"),
+        _ => Ok(()),
+    }
+}
+
+struct Provider {
+    breakpad: Symbolizer,
+    debug_files: Vec<DebugFile>,
+}
+
+impl Provider {
+    fn debug_file(&self, module: &(dyn Module + Sync)) -> Option<&DebugFile> {
+        let ids = module_ids(module);
+        self.debug_files
+            .iter()
+            .find(|file| file.ids.iter().any(|id| ids.contains(id)))
+    }
+}
+
+#[async_trait]
+impl SymbolProvider for Provider {
+    async fn fill_symbol(
+        &self,
+        module: &(dyn Module + Sync),
+        frame: &mut (dyn FrameSymbolizer + Send),
+    ) -> Result<(), FillSymbolError> {
+        match self.debug_file(module) {
+            Some(debug_file) => {
+                debug_file.fill_symbol(module, frame);
+                Ok(())
+            }
+            None => self.breakpad.fill_symbol(module, frame).await,
+        }
+    }
+
+    /// DWARF files are only used for names; their modules unwind by frame
+    /// pointers and stack scanning.
+    async fn walk_frame(
+        &self,
+        module: &(dyn Module + Sync),
+        walker: &mut (dyn FrameWalker + Send),
+    ) -> Option<()> {
+        self.breakpad.walk_frame(module, walker).await
+    }
+
+    async fn get_file_path(
+        &self,
+        module: &(dyn Module + Sync),
+        file_kind: FileKind,
+    ) -> Result<PathBuf, FileError> {
+        self.breakpad.get_file_path(module, file_kind).await
+    }
+}
+
+/// Lowercase hex of the module's code id (the full GNU build-id on Linux) and
+/// of its debug id's UUID (the Mach-O UUID on macOS).
+fn module_ids(module: &(dyn Module + Sync)) -> Vec<String> {
+    let mut ids = Vec::with_capacity(2);
+    if let Some(code_id) = module.code_identifier() {
+        ids.push(code_id.as_str().to_lowercase());
+    }
+    if let Some(debug_id) = module.debug_identifier() {
+        ids.push(debug_id.uuid().simple().to_string());
+    }
+    ids
+}
+
+type Dwarf = addr2line::Context<EndianArcSlice<RunTimeEndian>>;
+
+struct DebugFile {
+    ids: Vec<String>,
+    /// Added to a module offset to get the address the debug info uses.
+    relative_address_base: u64,
+    /// `(address, demangled name)`, sorted; for functions without DWARF.
+    symbols: Vec<(u64, String)>,
+    // `addr2line::Context` caches lazily and isn't `Sync`.
+    dwarf: Mutex<Dwarf>,
+}
+
+impl DebugFile {
+    fn load(path: &Path) -> Result<Self, Error> {
+        let data = std::fs::read(path)?;
+        let file = object::File::parse(data.as_slice())?;
+
+        let mut ids = Vec::new();
+        if let Some(build_id) = file.build_id()? {
+            ids.push(hex::encode(build_id));
+        }
+        if let Some(uuid) = file.mach_uuid()? {
+            ids.push(hex::encode(uuid));
+        }
+
+        let mut symbols: Vec<(u64, String)> = file
+            .symbols()
+            .filter(|symbol| symbol.kind() == SymbolKind::Text && symbol.address() != 0)
+            .filter_map(|symbol| {
+                let name = symbol.name().ok()?;
+                Some((
+                    symbol.address(),
+                    format!("{:#}", rustc_demangle::demangle(name)),
+                ))
+            })
+            .collect();
+        symbols.sort_by_key(|(address, _)| *address);
+
+        let endian = if file.is_little_endian() {
+            RunTimeEndian::Little
+        } else {
+            RunTimeEndian::Big
+        };
+        let dwarf = gimli::Dwarf::load(|id| -> Result<_, gimli::Error> {
+            let data = file
+                .section_by_name(id.name())
+                .and_then(|section| section.uncompressed_data().ok())
+                .unwrap_or_default();
+            Ok(EndianArcSlice::new(Arc::from(&*data), endian))
+        })?;
+
+        Ok(Self {
+            ids,
+            relative_address_base: file.relative_address_base(),
+            symbols,
+            dwarf: Mutex::new(addr2line::Context::from_dwarf(dwarf)?),
+        })
+    }
+
+    fn fill_symbol(&self, module: &(dyn Module + Sync), frame: &mut (dyn FrameSymbolizer + Send)) {
+        let instruction = frame.get_instruction();
+        let address =
+            self.relative_address_base + instruction.saturating_sub(module.base_address());
+        let symbol = self.symbol(address);
+        let function_base = match symbol {
+            Some((symbol_address, _)) => {
+                module.base_address() + (symbol_address - self.relative_address_base)
+            }
+            None => instruction,
+        };
+
+        // Innermost first; the last one is the function the code belongs to.
+        let mut locations = self.locations(address);
+        let outermost = locations.pop();
+
+        let name = outermost
+            .as_ref()
+            .and_then(|location| location.function.clone())
+            .or_else(|| symbol.map(|(_, name)| name.to_string()));
+        let Some(name) = name else {
+            return;
+        };
+        frame.set_function(&name, function_base, 0);
+        if let Some(Location {
+            file: Some(file),
+            line: Some(line),
+            ..
+        }) = &outermost
+        {
+            frame.set_source_file(file, *line, instruction);
+        }
+        for location in locations.iter().rev() {
+            frame.add_inline_frame(
+                location.function.as_deref().unwrap_or("<unknown>"),
+                location.file.as_deref(),
+                location.line,
+            );
+        }
+    }
+
+    fn symbol(&self, address: u64) -> Option<(u64, &str)> {
+        let index = self
+            .symbols
+            .partition_point(|(symbol_address, _)| *symbol_address <= address);
+        let (symbol_address, name) = self.symbols.get(index.checked_sub(1)?)?;
+        Some((*symbol_address, name.as_str()))
+    }
+
+    fn locations(&self, address: u64) -> Vec<Location> {
+        let dwarf = self.dwarf.lock().unwrap_or_else(|e| e.into_inner());
+        let mut locations = Vec::new();
+        let Ok(mut frames) = dwarf.find_frames(address).skip_all_loads() else {
+            return locations;
+        };
+        while let Ok(Some(frame)) = frames.next() {
+            locations.push(Location {
+                function: frame
+                    .function
+                    .as_ref()
+                    .and_then(|function| function.demangle().ok())
+                    .map(|name| name.into_owned()),
+                file: frame
+                    .location
+                    .as_ref()
+                    .and_then(|location| location.file)
+                    .map(str::to_string),
+                line: frame.location.as_ref().and_then(|location| location.line),
+            });
+        }
+        locations
+    }
+}
+
+struct Location {
+    function: Option<String>,
+    file: Option<String>,
+    line: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `testdata/crash.dmp` and its symbols come from `testdata/make_fixture.py`.
+    #[test]
+    fn symbolicates_fixture_dump_with_breakpad_symbols() {
+        let testdata = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata");
+        let report = symbolicate(
+            &testdata.join("crash.dmp"),
+            &SymbolSources {
+                breakpad_paths: vec![testdata.join("symbols")],
+                debug_files: vec![],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(report.stack_hash.len(), 64);
+        assert!(
+            report
+                .crash_reason
+                .as_deref()
+                .is_some_and(|reason| reason.starts_with("EXCEPTION_ACCESS_VIOLATION")),
+            "{:?}",
+            report.crash_reason
+        );
+        assert_eq!(report.crash_address, Some(0x28));
+
+        let thread = &report.threads[0];
+        assert_eq!(thread.thread_id, 0x1234);
+        assert!(thread.crashed);
+
+        let crashed = &thread.frames[0];
+        assert_eq!(crashed.address, "app.exe!0x1010");
+        assert_eq!(crashed.function.as_deref(), Some("crash_here"));
+        assert_eq!(crashed.function_offset, Some(0x10));
+        assert_eq!(crashed.source_file.as_deref(), Some("src\\main.rs"));
+        assert_eq!(crashed.source_line, Some(12));
+        assert_eq!(crashed.trust, "context");
+
+        let caller = &thread.frames[1];
+        assert_eq!(caller.function.as_deref(), Some("main"));
+        assert_eq!(caller.source_line, Some(30));
+        assert_eq!(caller.trust, "cfi");
+    }
+
+    #[test]
+    fn text_report_lists_crashed_thread_with_sources() {
+        let report = SymbolicatedReport {
+            stack_hash: "abc".to_string(),
+            crash_reason: Some("SIGSEGV".to_string()),
+            crash_address: Some(0),
+            threads: vec![SymbolicatedThread {
+                thread_id: 7,
+                name: Some("main".to_string()),
+                crashed: true,
+                frames: vec![SymbolicatedFrame {
+                    address: "app!0x10".to_string(),
+                    function: Some("app::run".to_string()),
+                    function_offset: Some(4),
+                    source_file: Some("src/lib.rs".to_string()),
+                    source_line: Some(12),
+                    inlined: vec![InlinedFrame {
+                        function: "app::step".to_string(),
+                        source_file: Some("src/step.rs".to_string()),
+                        source_line: Some(3),
+                    }],
+                    trust: "context".to_string(),
+                }],
+            }],
+        };
+        assert_eq!(
+            report.to_string(),
+            "stack_hash: abc\n\
+             crash: SIGSEGV at 0x0\n\
+             \n\
+             Thread 7 \"main\" (crashed)\n   \
+             0  app!0x10  app::run + 0x4  [src/lib.rs:12]  (context)\n          \
+             inlined app::step  [src/step.rs:3]\n"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! A crash queue of its own per test, shared by the tests that go through
+//! the queue.
+
+use crate::{
+    Config,
+    context::{CrashContext, CrashKind},
+    queue,
+};
+use namsh_intake_emulator::Emulator;
+use std::sync::OnceLock;
+use tempfile::TempDir;
+
+/// Every test's data lives under one temp dir instead of the real user data
+/// dir. It's shared because [`queue::ENV_DATA_DIR`] is process-wide; the app
+/// names keep the tests apart. Statics aren't dropped, so the dir itself stays
+/// behind, empty once every [`TestQueue`] is gone.
+fn data_dir() -> &'static TempDir {
+    static DATA_DIR: OnceLock<TempDir> = OnceLock::new();
+    DATA_DIR.get_or_init(|| {
+        let dir = tempfile::Builder::new()
+            .prefix("namui-crash-reporter-test-")
+            .tempdir()
+            .unwrap();
+        // SAFETY: only std reads the environment in these tests, and std
+        // serializes access to it.
+        unsafe {
+            std::env::set_var(queue::ENV_DATA_DIR, dir.path());
+        }
+        dir
+    })
+}
+
+/// A queue of its own under [`data_dir`], removed on drop.
+pub struct TestQueue {
+    pub config: Config,
+}
+
+impl TestQueue {
+    pub fn new(name: &str, emulator: &Emulator) -> Self {
+        data_dir();
+        let hmac_key_hex = emulator.register_build("b1");
+        Self {
+            config: Config {
+                build_id: "b1".to_string(),
+                hmac_key_hex,
+                namsh_url: emulator.url().to_string(),
+                app_name: name.to_string(),
+                require_consent: false,
+                record_input: false,
+                scrub_rules: Vec::new(),
+            },
+        }
+    }
+
+    pub fn push(&self, stack_hash: &str, context: CrashContext, payload: &[u8]) {
+        let extension = match context.kind {
+            CrashKind::Native => "dmp",
+            CrashKind::Panic => "panic",
+        };
+        let dir = queue::queue_dir(&self.config.app_name).unwrap();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{stack_hash}.{extension}"));
+        std::fs::write(&path, payload).unwrap();
+        let entry = queue::PendingEntry {
+            stack_hash: stack_hash.to_string(),
+            context,
+        };
+        queue::write_sidecar(&path, &entry).unwrap();
+    }
+
+    pub fn pending(&self) -> usize {
+        queue::list_pending(&self.config.app_name).unwrap().len()
+    }
+}
+
+impl Drop for TestQueue {
+    fn drop(&mut self) {
+        let root = queue::root_dir(&self.config.app_name).unwrap();
+        let _ = std::fs::remove_dir_all(root.parent().unwrap());
+    }
+}
+
+pub fn context(kind: CrashKind) -> CrashContext {
+    CrashContext {
+        build_id: "b1".to_string(),
+        install_id: "install".to_string(),
+        session_uptime_sec: 42,
+        log_tail: Some("last log line\n".to_string()),
+        kind,
+        panic: None,
+        breadcrumbs: Vec::new(),
+    }
+}
@@ -0,0 +1,148 @@
+"""Writes crash.dmp and its Breakpad symbols for symbolicate's end-to-end test.
+
+One Windows x86_64 thread, crashed in `crash_here` (app.exe+0x1010) with a
+read access violation at 0x28, called from `main`. The stack holds just the
+return address into `main`, and the .sym carries the CFI to unwind to it.
+
+    python3 make_fixture.py
+"""
+
+import os
+import struct
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+
+THREAD_ID = 0x1234
+MODULE_BASE = 0x140000000
+MODULE_SIZE = 0x10000
+STACK_BASE = 0x10000
+CRASH_RIP = MODULE_BASE + 0x1010
+RETURN_ADDRESS = MODULE_BASE + 0x1105
+FAULT_ADDRESS = 0x28
+
+GUID = struct.pack("<IHH8s", 0x12345678, 0x9ABC, 0xDEF0, bytes(range(1, 9)))
+AGE = 1
+DEBUG_ID = "123456789ABCDEF00102030405060708%X" % AGE
+
+CONTEXT_AMD64_FULL = 0x0010000B
+CONTEXT_AMD64_SIZE = 1232
+
+
+def context():
+    buf = bytearray(CONTEXT_AMD64_SIZE)
+    struct.pack_into("<I", buf, 0x30, CONTEXT_AMD64_FULL)
+    struct.pack_into("<Q", buf, 0x98, STACK_BASE)  # rsp
+    struct.pack_into("<Q", buf, 0xA0, STACK_BASE + 0x40)  # rbp
+    struct.pack_into("<Q", buf, 0xF8, CRASH_RIP)  # rip
+    return bytes(buf)
+
+
+def minidump_string(text):
+    data = text.encode("utf-16-le")
+    return struct.pack("<I", len(data)) + data + b"\0\0"
+
+
+class Writer:
+    def __init__(self):
+        self.buf = bytearray()
+
+    def add(self, data):
+        while len(self.buf) % 8:
+            self.buf.append(0)
+        rva = len(self.buf)
+        self.buf += data
+        return rva
+
+
+def build_dump():
+    w = Writer()
+    stream_count = 6
+    w.add(bytes(32))
+    directory_rva = w.add(bytes(12 * stream_count))
+
+    context_rva = w.add(context())
+    stack = bytearray(0x80)
+    struct.pack_into("<Q", stack, 0, RETURN_ADDRESS)
+    stack_rva = w.add(bytes(stack))
+    module_name_rva = w.add(minidump_string("app.exe"))
+    cv_rva = w.add(b"RSDS" + GUID + struct.pack("<I", AGE) + b"app.pdb\0")
+    cv_size = len(w.buf) - cv_rva
+    csd_rva = w.add(minidump_string(""))
+
+    streams = []
+
+    thread = struct.pack(
+        "<IIIIQQIIII",
+        THREAD_ID, 0, 0, 0, 0,
+        STACK_BASE, len(stack), stack_rva,
+        CONTEXT_AMD64_SIZE, context_rva,
+    )
+    data = struct.pack("<I", 1) + thread
+    streams.append((3, len(data), w.add(data)))  # ThreadListStream
+
+    module = struct.pack(
+        "<QIIII52sIIIIQQ",
+        MODULE_BASE, MODULE_SIZE, 0, 0, module_name_rva,
+        bytes(52),
+        cv_size, cv_rva,
+        0, 0,
+        0, 0,
+    )
+    data = struct.pack("<I", 1) + module
+    streams.append((4, len(data), w.add(data)))  # ModuleListStream
+
+    data = struct.pack("<IQII", 1, STACK_BASE, len(stack), stack_rva)
+    streams.append((5, len(data), w.add(data)))  # MemoryListStream
+
+    information = [0, FAULT_ADDRESS] + [0] * 13
+    exception = struct.pack(
+        "<IIQQII15Q",
+        0xC0000005, 0, 0, CRASH_RIP, 2, 0, *information,
+    )
+    data = (
+        struct.pack("<II", THREAD_ID, 0)
+        + exception
+        + struct.pack("<II", CONTEXT_AMD64_SIZE, context_rva)
+    )
+    streams.append((6, len(data), w.add(data)))  # ExceptionStream
+
+    data = struct.pack(
+        "<HHHBBIIIIIHH24s",
+        9, 6, 0, 1, 1,  # AMD64, one processor, workstation
+        10, 0, 19045, 2,  # Windows 10.0.19045, VER_PLATFORM_WIN32_NT
+        csd_rva, 0, 0,
+        bytes(24),
+    )
+    streams.append((7, len(data), w.add(data)))  # SystemInfoStream
+
+    data = b"".join(struct.pack("<III", *s) for s in streams)
+    data += bytes(12 * (stream_count - len(streams)))  # UnusedStream
+    w.buf[directory_rva:directory_rva + len(data)] = data
+    struct.pack_into("<IIIIIIQ", w.buf, 0, 0x504D444D, 0xA793, stream_count,
+                     directory_rva, 0, 0, 0)
+    return bytes(w.buf)
+
+
+SYM = f"""\
+MODULE windows x86_64 {DEBUG_ID} app.pdb
+FILE 0 src\\main.rs
+FUNC 1000 20 0 crash_here
+1000 20 12 0
+FUNC 1100 40 0 main
+1100 40 30 0
+STACK CFI INIT 1000 20 .cfa: $rsp 8 + .ra: .cfa -8 + ^
+STACK CFI INIT 1100 40 .cfa: $rsp 8 + .ra: .cfa -8 + ^
+"""
+
+
+def main():
+    with open(os.path.join(HERE, "crash.dmp"), "wb") as f:
+        f.write(build_dump())
+    sym_dir = os.path.join(HERE, "symbols", "app.pdb", DEBUG_ID)
+    os.makedirs(sym_dir, exist_ok=True)
+    with open(os.path.join(sym_dir, "app.sym"), "w", newline="\n") as f:
+        f.write(SYM)
+
+
+if __name__ == "__main__":
+    main()
@@ -63,6 +63,38 @@ pub enum Commands {
         #[arg(short, long, value_hint = ValueHint::FilePath)]
         manifest_path: Option<PathBuf>,
     },
+    /// Build in release mode and pack the output into a distributable directory and archive.
+    Package {
+        #[arg(value_enum)]
+        target: Target,
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        manifest_path: Option<PathBuf>,
+        /// Defaults to the package version in Cargo.toml.
+        #[arg(long)]
+        version: Option<String>,
+        /// Defaults to `target/namui/package`.
+        #[arg(short, long, value_hint = ValueHint::DirPath)]
+        out: Option<PathBuf>,
+        /// Package the existing build output without building again.
+        #[arg(long)]
+        skip_build: bool,
+    },
+    /// Write a bundle which holds only entries changed from `from` to `to`.
+    BundleDelta {
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        from: PathBuf,
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        to: PathBuf,
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        out: PathBuf,
+    },
+    /// Apply a delta made by `bundle-delta` to the `from` bundle in place.
+    BundleApplyDelta {
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        base: PathBuf,
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        delta: PathBuf,
+    },
 }
 
 #[allow(clippy::enum_variant_names)]
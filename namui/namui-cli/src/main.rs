@@ -41,6 +41,28 @@ async fn main() -> Result<()> {
                 manifest_path.unwrap_or_else(|| project_path.join("Cargo.toml"));
             procedures::build(target, manifest_path, release).await?;
         }
+        Commands::Package {
+            target,
+            manifest_path,
+            version,
+            out,
+            skip_build,
+        } => {
+            let manifest_path =
+                manifest_path.unwrap_or_else(|| project_path.join("Cargo.toml"));
+            procedures::package(target, manifest_path, version, out, skip_build).await?;
+        }
+        Commands::BundleDelta { from, to, out } => {
+            let summary = services::bundle::create_delta(&from, &to, &out)?;
+            println!(
+                "INFO: bundle delta {} -> {}: {} changed, {} removed",
+                summary.from_bundle_hash, summary.to_bundle_hash, summary.changed, summary.removed
+            );
+        }
+        Commands::BundleApplyDelta { base, delta } => {
+            let manifest = services::bundle::apply_delta(&base, &delta)?;
+            println!("INFO: bundle is now {}", manifest.bundle_hash);
+        }
         _ => {
             eprintln!("Command not yet supported");
             std::process::exit(1);
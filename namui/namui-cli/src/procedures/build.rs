@@ -16,7 +16,7 @@ pub async fn build(target: Target, manifest_path: PathBuf, release: bool) -> Res
                 Target::X86_64PcWindowsMsvc => {
                     linux::x86_64_pc_windows_msvc::build(&manifest_path, release).await?
                 }
-                _ => unimplemented!(),
+                _ => bail!("{} is unsupported target", target),
             }
         }
     } else if cfg!(target_os = "macos") {
@@ -36,7 +36,7 @@ pub async fn build(target: Target, manifest_path: PathBuf, release: bool) -> Res
                 Target::Aarch64PcWindowsMsvc => {
                     macos::aarch64_pc_windows_msvc::build(&manifest_path, release).await?
                 }
-                _ => unimplemented!(),
+                _ => bail!("{} is unsupported target", target),
             }
         }
     } else if cfg!(target_os = "windows") {
@@ -50,7 +50,7 @@ pub async fn build(target: Target, manifest_path: PathBuf, release: bool) -> Res
                 Target::X86_64PcWindowsMsvc => {
                     windows::x86_64_pc_windows_msvc::build(&manifest_path).await?;
                 }
-                _ => unimplemented!(),
+                _ => bail!("{} is unsupported target", target),
             }
         }
     } else {
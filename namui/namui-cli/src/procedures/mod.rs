@@ -5,6 +5,7 @@ mod clippy;
 pub mod linux;
 #[cfg(target_os = "macos")]
 pub mod macos;
+mod package;
 mod start;
 mod test;
 #[cfg(target_os = "windows")]
@@ -15,6 +16,7 @@ pub use build::*;
 pub use check::*;
 #[allow(unused_imports)]
 pub use clippy::*;
+pub use package::*;
 #[allow(unused_imports)]
 pub use start::*;
 #[allow(unused_imports)]
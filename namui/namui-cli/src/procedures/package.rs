@@ -0,0 +1,34 @@
+use crate::cli::Target;
+use crate::services::package_service::{self, PackageArgs};
+use crate::*;
+use std::path::PathBuf;
+
+pub async fn package(
+    target: Target,
+    manifest_path: PathBuf,
+    version: Option<String>,
+    out_dir: Option<PathBuf>,
+    skip_build: bool,
+) -> Result<()> {
+    let manifest_path = std::fs::canonicalize(manifest_path)?;
+    if !skip_build {
+        super::build(target, manifest_path.clone(), true).await?;
+    }
+
+    let out_dir =
+        out_dir.unwrap_or_else(|| manifest_path.parent().unwrap().join("target/namui/package"));
+    let output = package_service::package(PackageArgs {
+        manifest_path,
+        target,
+        version,
+        out_dir,
+    })?;
+
+    println!("Package directory: {}", output.dir.display());
+    println!("Package archive: {}", output.archive.display());
+    if let Some(symbols_dir) = output.symbols_dir {
+        println!("Debug symbols: {}", symbols_dir.display());
+    }
+
+    Ok(())
+}
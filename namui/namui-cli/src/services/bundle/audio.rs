@@ -0,0 +1,644 @@
+use super::compression::content_hash;
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Mutex,
+    time::UNIX_EPOCH,
+};
+
+/// Opt-in. Without this file in the project root, audio is bundled as-is.
+///
+/// ```toml
+/// lufs = -16.0         # default target loudness
+/// true_peak = -1.0     # dBTP ceiling
+/// trim_silence = true
+///
+/// [groups.bgm]
+/// path = "audio/bgm"   # relative to asset directory
+/// lufs = -20.0
+/// ```
+pub const AUDIO_CONFIG_FILE_NAME: &str = "audio.toml";
+/// Processed files and `index.json`, relative to the project root.
+/// `register_assets!` and the web asset collector read the index.
+pub const AUDIO_CACHE_DIR: &str = "target/namui/audio";
+const AUDIO_INDEX_FILE_NAME: &str = "index.json";
+/// Bump when the ffmpeg pipeline changes so cached outputs are made again.
+const PIPELINE_VERSION: u32 = 1;
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "wav", "ogg", "opus", "flac"];
+
+#[derive(Debug, Clone, Deserialize)]
+struct AudioConfig {
+    #[serde(default = "default_lufs")]
+    lufs: f64,
+    #[serde(default = "default_true_peak")]
+    true_peak: f64,
+    #[serde(default = "default_trim_silence")]
+    trim_silence: bool,
+    #[serde(default = "default_silence_threshold_db")]
+    silence_threshold_db: f64,
+    #[serde(default)]
+    groups: BTreeMap<String, AudioGroup>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AudioGroup {
+    path: String,
+    lufs: Option<f64>,
+    true_peak: Option<f64>,
+    trim_silence: Option<bool>,
+}
+
+fn default_lufs() -> f64 {
+    -16.0
+}
+fn default_true_peak() -> f64 {
+    -1.0
+}
+fn default_trim_silence() -> bool {
+    true
+}
+fn default_silence_threshold_db() -> f64 {
+    -60.0
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct AudioSettings {
+    lufs: f64,
+    true_peak: f64,
+    trim_silence: bool,
+    silence_threshold_db: f64,
+}
+
+impl AudioSettings {
+    fn key(&self) -> String {
+        format!("{PIPELINE_VERSION}:{self:?}")
+    }
+
+    fn cache_key(&self, source: &[u8]) -> String {
+        let mut buffer = format!("{}:", self.key()).into_bytes();
+        buffer.extend_from_slice(source);
+        content_hash(&buffer)
+    }
+}
+
+impl AudioConfig {
+    /// The group with the longest matching path wins.
+    fn settings_for(&self, relative_path: &str) -> AudioSettings {
+        let group = self
+            .groups
+            .values()
+            .filter(|group| {
+                let prefix = group.path.trim_matches('/');
+                relative_path == prefix
+                    || relative_path
+                        .strip_prefix(prefix)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .max_by_key(|group| group.path.trim_matches('/').len());
+
+        AudioSettings {
+            lufs: group.and_then(|group| group.lufs).unwrap_or(self.lufs),
+            true_peak: group
+                .and_then(|group| group.true_peak)
+                .unwrap_or(self.true_peak),
+            trim_silence: group
+                .and_then(|group| group.trim_silence)
+                .unwrap_or(self.trim_silence),
+            silence_threshold_db: self.silence_threshold_db,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioMetadata {
+    pub duration_ms: u64,
+    /// `None` if the audio is silent.
+    pub lufs: Option<f64>,
+    pub peak_dbtp: Option<f64>,
+    pub loop_start_ms: Option<u64>,
+    pub loop_end_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct AudioIndexEntry {
+    /// Lets `register_assets!` tell whether the entry is for the current source file
+    /// without hashing it.
+    source_size: u64,
+    source_modified: u64,
+    /// [`AudioSettings::key`] the output was made with.
+    #[serde(default)]
+    settings_key: String,
+    cache_key: String,
+    /// Content hash of the processed output, so the bundler can skip unchanged audio without
+    /// reading it.
+    #[serde(default)]
+    output_hash: String,
+    metadata: AudioMetadata,
+}
+
+impl AudioIndexEntry {
+    fn is_fresh(&self, source_size: u64, source_modified: u64, settings_key: &str) -> bool {
+        self.source_size == source_size
+            && self.source_modified == source_modified
+            && self.settings_key == settings_key
+            && !self.output_hash.is_empty()
+    }
+}
+
+pub struct ProcessedAudio {
+    pub path: PathBuf,
+    pub hash: String,
+}
+
+/// Transcodes to Ogg Vorbis, trims silence and normalizes loudness with ffmpeg.
+/// Opus would be smaller, but native playback decodes with symphonia, which has no Opus decoder.
+pub struct AudioPreprocessor {
+    asset_dir: PathBuf,
+    cache_dir: PathBuf,
+    config: Option<AudioConfig>,
+    /// `index.json` of the last run. Sources whose size, mtime and settings match are not read
+    /// again.
+    previous_index: BTreeMap<String, AudioIndexEntry>,
+    index: Mutex<BTreeMap<String, AudioIndexEntry>>,
+}
+
+impl AudioPreprocessor {
+    pub fn load(project_root_path: impl AsRef<Path>) -> Result<Self> {
+        let project_root_path = project_root_path.as_ref();
+        let config_path = project_root_path.join(AUDIO_CONFIG_FILE_NAME);
+        let config = match config_path.exists() {
+            true => Some(
+                toml::from_str::<AudioConfig>(&std::fs::read_to_string(&config_path)?)
+                    .map_err(|error| anyhow!("{config_path:?} parse error: {error}"))?,
+            ),
+            false => None,
+        };
+
+        let cache_dir = project_root_path.join(AUDIO_CACHE_DIR);
+        let previous_index = std::fs::read(cache_dir.join(AUDIO_INDEX_FILE_NAME))
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .unwrap_or_default();
+
+        Ok(Self {
+            asset_dir: project_root_path.join("asset"),
+            cache_dir,
+            config,
+            previous_index,
+            index: Default::default(),
+        })
+    }
+
+    /// Returns the processed file, or `None` for files this step doesn't touch.
+    pub fn process(&self, src_path: &Path) -> Result<Option<ProcessedAudio>> {
+        let Some(config) = &self.config else {
+            return Ok(None);
+        };
+        let is_audio = src_path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                AUDIO_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+            });
+        let Some(relative_path) = src_path
+            .strip_prefix(&self.asset_dir)
+            .ok()
+            .filter(|_| is_audio)
+            .and_then(|relative_path| relative_path.to_str())
+            .map(|relative_path| relative_path.replace('\\', "/"))
+        else {
+            return Ok(None);
+        };
+
+        let source_metadata = src_path.metadata()?;
+        let source_size = source_metadata.len();
+        let source_modified = source_metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)?
+            .as_secs();
+        let settings = config.settings_for(&relative_path);
+        let settings_key = settings.key();
+
+        let entry = match self
+            .previous_index
+            .get(&relative_path)
+            .filter(|entry| entry.is_fresh(source_size, source_modified, &settings_key))
+            .filter(|entry| self.output_path(&entry.cache_key).exists())
+        {
+            Some(entry) => entry.clone(),
+            None => {
+                let source = std::fs::read(src_path)?;
+                let cache_key = settings.cache_key(&source);
+                let output_path = self.output_path(&cache_key);
+                let metadata_path = self.cache_dir.join(format!("{cache_key}.json"));
+
+                let metadata = match std::fs::read(&metadata_path)
+                    .ok()
+                    .and_then(|json| serde_json::from_slice::<AudioMetadata>(&json).ok())
+                    .filter(|_| output_path.exists())
+                {
+                    Some(metadata) => metadata,
+                    None => {
+                        std::fs::create_dir_all(&self.cache_dir)?;
+                        let loop_points = read_wav_loop_points(&source);
+                        let metadata = run_ffmpeg(src_path, &output_path, &settings, loop_points)?;
+                        std::fs::write(&metadata_path, serde_json::to_vec_pretty(&metadata)?)?;
+                        metadata
+                    }
+                };
+
+                AudioIndexEntry {
+                    source_size,
+                    source_modified,
+                    settings_key,
+                    output_hash: content_hash(&std::fs::read(&output_path)?),
+                    cache_key,
+                    metadata,
+                }
+            }
+        };
+
+        let processed = ProcessedAudio {
+            path: self.output_path(&entry.cache_key),
+            hash: entry.output_hash.clone(),
+        };
+        self.index.lock().unwrap().insert(relative_path, entry);
+
+        Ok(Some(processed))
+    }
+
+    fn output_path(&self, cache_key: &str) -> PathBuf {
+        self.cache_dir.join(format!("{cache_key}.ogg"))
+    }
+
+    /// Writes `index.json` and removes cached outputs no entry refers to.
+    pub fn finish(&self) -> Result<()> {
+        if self.config.is_none() {
+            return Ok(());
+        }
+        let index = self.index.lock().unwrap();
+        std::fs::create_dir_all(&self.cache_dir)?;
+        std::fs::write(
+            self.cache_dir.join(AUDIO_INDEX_FILE_NAME),
+            serde_json::to_string_pretty(&*index)?,
+        )?;
+
+        for entry in std::fs::read_dir(&self.cache_dir)?.flatten() {
+            let path = entry.path();
+            let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let is_used = index.values().any(|entry| entry.cache_key == stem);
+            if path.file_name().unwrap() != AUDIO_INDEX_FILE_NAME && !is_used {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn run_ffmpeg(
+    src_path: &Path,
+    output_path: &Path,
+    settings: &AudioSettings,
+    loop_points: Option<LoopPoints>,
+) -> Result<AudioMetadata> {
+    // Trimming would move authored loop points, so looped audio keeps its silence.
+    let trim_filter = if settings.trim_silence && loop_points.is_none() {
+        let threshold = settings.silence_threshold_db;
+        format!(
+            "silenceremove=start_periods=1:start_threshold={threshold}dB,\
+            areverse,silenceremove=start_periods=1:start_threshold={threshold}dB,areverse,"
+        )
+    } else {
+        String::new()
+    };
+    let loudnorm = format!(
+        "loudnorm=I={}:TP={}:LRA=11:print_format=json",
+        settings.lufs, settings.true_peak
+    );
+
+    let measured = parse_loudnorm_json(&ffmpeg(&[
+        "-i",
+        src_path.to_str().unwrap(),
+        "-af",
+        &format!("{trim_filter}{loudnorm}"),
+        "-f",
+        "null",
+        "-",
+    ])?)?;
+
+    let filter = match measured.get("input_i") {
+        Some(input_i) if input_i.is_finite() => format!(
+            "{trim_filter}{loudnorm}:linear=true:measured_I={input_i}:measured_TP={}\
+            :measured_LRA={}:measured_thresh={}:offset={}",
+            measured["input_tp"],
+            measured["input_lra"],
+            measured["input_thresh"],
+            measured["target_offset"],
+        ),
+        // Silence. Nothing to normalize.
+        _ => format!("{trim_filter}anull"),
+    };
+
+    let temp_path = output_path.with_extension("ogg.tmp");
+    let stderr = ffmpeg(&[
+        "-i",
+        src_path.to_str().unwrap(),
+        "-af",
+        &filter,
+        // loudnorm resamples to 192kHz internally.
+        "-ar",
+        "48000",
+        "-c:a",
+        "libvorbis",
+        "-q:a",
+        "6",
+        "-f",
+        "ogg",
+        "-y",
+        temp_path.to_str().unwrap(),
+    ])?;
+    std::fs::rename(&temp_path, output_path)?;
+    let output = parse_loudnorm_json(&stderr).unwrap_or_default();
+
+    Ok(AudioMetadata {
+        duration_ms: probe_duration_ms(output_path)?,
+        lufs: output
+            .get("output_i")
+            .copied()
+            .filter(|value| value.is_finite()),
+        peak_dbtp: output
+            .get("output_tp")
+            .copied()
+            .filter(|value| value.is_finite()),
+        loop_start_ms: loop_points.map(|points| points.start_ms()),
+        loop_end_ms: loop_points.map(|points| points.end_ms()),
+    })
+}
+
+/// Returns stderr, where ffmpeg prints the loudnorm report.
+fn ffmpeg(args: &[&str]) -> Result<String> {
+    let output = Command::new("ffmpeg")
+        .args(["-hide_banner", "-nostdin"])
+        .args(args)
+        .output()
+        .map_err(|error| anyhow!("ffmpeg is required to preprocess audio: {error}"))?;
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if !output.status.success() {
+        bail!("ffmpeg failed: {stderr}");
+    }
+    Ok(stderr)
+}
+
+fn probe_duration_ms(path: &Path) -> Result<u64> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "format=duration",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(path)
+        .output()?;
+    let seconds = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<f64>()
+        .map_err(|error| anyhow!("ffprobe duration parse error for {path:?}: {error}"))?;
+    Ok((seconds * 1000.0).round() as u64)
+}
+
+/// loudnorm prints a JSON object with numbers as strings at the end of stderr.
+fn parse_loudnorm_json(stderr: &str) -> Result<BTreeMap<String, f64>> {
+    let start = stderr
+        .rfind('{')
+        .ok_or_else(|| anyhow!("loudnorm report not found"))?;
+    let end = stderr[start..]
+        .find('}')
+        .ok_or_else(|| anyhow!("loudnorm report not closed"))?;
+    let report: BTreeMap<String, String> = serde_json::from_str(&stderr[start..start + end + 1])?;
+    Ok(report
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value.parse::<f64>().ok()?)))
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LoopPoints {
+    sample_rate: u32,
+    start_sample: u32,
+    /// Inclusive, as in the `smpl` chunk.
+    end_sample: u32,
+}
+
+impl LoopPoints {
+    fn start_ms(&self) -> u64 {
+        self.start_sample as u64 * 1000 / self.sample_rate as u64
+    }
+    fn end_ms(&self) -> u64 {
+        (self.end_sample as u64 + 1) * 1000 / self.sample_rate as u64
+    }
+}
+
+/// Reads the first loop of the `smpl` chunk, which audio editors write for looped wav files.
+fn read_wav_loop_points(bytes: &[u8]) -> Option<LoopPoints> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return None;
+    }
+    let u32_at = |offset: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(
+            bytes.get(offset..offset + 4)?.try_into().ok()?,
+        ))
+    };
+
+    let mut sample_rate = None;
+    let mut loop_range = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let chunk_id = &bytes[offset..offset + 4];
+        let chunk_size = u32_at(offset + 4)? as usize;
+        let body = offset + 8;
+        match chunk_id {
+            b"fmt " => sample_rate = u32_at(body + 4),
+            b"smpl" => {
+                let loop_count = u32_at(body + 28)?;
+                if loop_count > 0 {
+                    // Each loop: cue id, type, start, end, fraction, play count.
+                    let first_loop = body + 36;
+                    loop_range = Some((u32_at(first_loop + 8)?, u32_at(first_loop + 12)?));
+                }
+            }
+            _ => {}
+        }
+        // Chunks are padded to even size.
+        offset = body + chunk_size + (chunk_size & 1);
+    }
+
+    let (start_sample, end_sample) = loop_range?;
+    Some(LoopPoints {
+        sample_rate: sample_rate.filter(|rate| *rate > 0)?,
+        start_sample,
+        end_sample,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AudioConfig {
+        toml::from_str(
+            r#"
+            lufs = -16.0
+
+            [groups.bgm]
+            path = "audio/bgm"
+            lufs = -20.0
+
+            [groups.boss_bgm]
+            path = "audio/bgm/boss/"
+            trim_silence = false
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn longest_group_path_wins() {
+        let config = config();
+        assert_eq!(config.settings_for("audio/sfx/hit.wav").lufs, -16.0);
+        assert_eq!(config.settings_for("audio/bgm/title.wav").lufs, -20.0);
+        assert_eq!(config.settings_for("audio/bgmx/title.wav").lufs, -16.0);
+
+        let boss = config.settings_for("audio/bgm/boss/phase1.wav");
+        assert_eq!(boss.lufs, -16.0);
+        assert!(!boss.trim_silence);
+    }
+
+    #[test]
+    fn cache_key_changes_with_settings() {
+        let config = config();
+        let sfx = config.settings_for("audio/sfx/hit.wav");
+        let bgm = config.settings_for("audio/bgm/title.wav");
+        assert_eq!(sfx.cache_key(b"a"), sfx.cache_key(b"a"));
+        assert_ne!(sfx.cache_key(b"a"), sfx.cache_key(b"b"));
+        assert_ne!(sfx.cache_key(b"a"), bgm.cache_key(b"a"));
+    }
+
+    #[test]
+    fn unchanged_source_reuses_last_output_without_reading_it() {
+        let project_root =
+            std::env::temp_dir().join(format!("namui-audio-cache-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&project_root);
+        std::fs::create_dir_all(project_root.join("asset/audio")).unwrap();
+        std::fs::write(project_root.join(AUDIO_CONFIG_FILE_NAME), "").unwrap();
+        // Not audio at all, so any attempt to run ffmpeg on it fails the test.
+        let src_path = project_root.join("asset/audio/hit.wav");
+        std::fs::write(&src_path, b"not audio").unwrap();
+
+        let preprocessor = AudioPreprocessor::load(&project_root).unwrap();
+        let source_metadata = src_path.metadata().unwrap();
+        let entry = AudioIndexEntry {
+            source_size: source_metadata.len(),
+            source_modified: source_metadata
+                .modified()
+                .unwrap()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            settings_key: preprocessor
+                .config
+                .as_ref()
+                .unwrap()
+                .settings_for("audio/hit.wav")
+                .key(),
+            cache_key: "cached".to_string(),
+            output_hash: "output-hash".to_string(),
+            metadata: AudioMetadata {
+                duration_ms: 1,
+                lufs: None,
+                peak_dbtp: None,
+                loop_start_ms: None,
+                loop_end_ms: None,
+            },
+        };
+        std::fs::create_dir_all(&preprocessor.cache_dir).unwrap();
+        std::fs::write(preprocessor.output_path("cached"), b"ogg").unwrap();
+        std::fs::write(
+            preprocessor.cache_dir.join(AUDIO_INDEX_FILE_NAME),
+            serde_json::to_vec(&BTreeMap::from([("audio/hit.wav", &entry)])).unwrap(),
+        )
+        .unwrap();
+
+        let preprocessor = AudioPreprocessor::load(&project_root).unwrap();
+        let processed = preprocessor.process(&src_path).unwrap().unwrap();
+        assert_eq!(processed.path, preprocessor.output_path("cached"));
+        assert_eq!(processed.hash, "output-hash");
+        assert_eq!(preprocessor.index.lock().unwrap()["audio/hit.wav"], entry);
+
+        std::fs::remove_dir_all(&project_root).unwrap();
+    }
+
+    #[test]
+    fn parses_loudnorm_report() {
+        let stderr = r#"
+[Parsed_loudnorm_0 @ 0x0]
+{
+	"input_i" : "-27.61",
+	"input_tp" : "-4.47",
+	"input_lra" : "18.06",
+	"input_thresh" : "-39.20",
+	"output_i" : "-inf",
+	"normalization_type" : "dynamic",
+	"target_offset" : "0.58"
+}
+"#;
+        let report = parse_loudnorm_json(stderr).unwrap();
+        assert_eq!(report["input_i"], -27.61);
+        assert_eq!(report["target_offset"], 0.58);
+        assert!(report["output_i"].is_infinite());
+        assert!(!report.contains_key("normalization_type"));
+    }
+
+    fn wav_with_loop(sample_rate: u32, start: u32, end: u32) -> Vec<u8> {
+        let mut fmt = vec![1, 0, 1, 0];
+        fmt.extend(sample_rate.to_le_bytes());
+        fmt.extend((sample_rate * 2).to_le_bytes());
+        fmt.extend([2, 0, 16, 0]);
+
+        let mut smpl = vec![0; 28];
+        smpl.extend(1u32.to_le_bytes());
+        smpl.extend(0u32.to_le_bytes());
+        for value in [0, 0, start, end, 0, 0u32] {
+            smpl.extend(value.to_le_bytes());
+        }
+
+        let mut body = b"WAVE".to_vec();
+        for (id, chunk) in [(b"fmt ", fmt), (b"data", vec![0; 3]), (b"smpl", smpl)] {
+            body.extend(id);
+            body.extend((chunk.len() as u32).to_le_bytes());
+            let padded = chunk.len() % 2 == 1;
+            body.extend(chunk);
+            if padded {
+                body.push(0);
+            }
+        }
+        let mut bytes = b"RIFF".to_vec();
+        bytes.extend((body.len() as u32).to_le_bytes());
+        bytes.extend(body);
+        bytes
+    }
+
+    #[test]
+    fn reads_wav_loop_points() {
+        let loop_points = read_wav_loop_points(&wav_with_loop(48000, 24000, 95999)).unwrap();
+        assert_eq!(loop_points.start_ms(), 500);
+        assert_eq!(loop_points.end_ms(), 2000);
+        assert_eq!(read_wav_loop_points(b"OggS not a wav file"), None);
+    }
+}
@@ -1,11 +1,13 @@
+use super::audio::AudioPreprocessor;
+use super::compression::{Compression, content_hash};
+use super::manifest::{BUNDLE_MANIFEST_FILE_NAME, BundleContentManifest};
 use super::*;
 use anyhow::Result;
 use rayon::prelude::*;
-use rusqlite::{Connection, DatabaseName, OptionalExtension};
+use rusqlite::{Connection, OptionalExtension};
 use std::{
     collections::HashSet,
     fs::create_dir_all,
-    io,
     sync::{
         Arc,
         atomic::{AtomicBool, Ordering},
@@ -13,9 +15,19 @@ use std::{
     time::UNIX_EPOCH,
 };
 
+pub(crate) const CREATE_BUNDLE_TABLE_SQL: &str = "CREATE TABLE IF NOT EXISTS bundle (
+        path TEXT PRIMARY KEY NOT NULL,
+        data BLOB,
+        modified INTEGER,
+        compression TEXT NOT NULL DEFAULT 'none',
+        size INTEGER NOT NULL DEFAULT 0,
+        hash TEXT NOT NULL DEFAULT ''
+    )";
+
 pub fn bundle_to_sqlite(
     sqlite_path: impl AsRef<std::path::Path>,
     collect_operations: Vec<CollectOperation>,
+    audio_preprocessor: &AudioPreprocessor,
 ) -> Result<()> {
     let sqlite_path = sqlite_path.as_ref().to_path_buf();
     create_dir_all(sqlite_path.parent().unwrap())?;
@@ -28,14 +40,11 @@ pub fn bundle_to_sqlite(
     let conn = create_conn();
     let has_changes = Arc::new(AtomicBool::new(false));
 
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS bundle (
-                path TEXT PRIMARY KEY NOT NULL,
-                data BLOB,
-                modified INTEGER
-            )",
-        (),
-    )?;
+    if !has_column(&conn, "bundle", "hash")? {
+        // Bundle made by older cli. It's only a cache of source files, so just rebuild it.
+        conn.execute("DROP TABLE IF EXISTS bundle", ())?;
+    }
+    conn.execute(CREATE_BUNDLE_TABLE_SQL, ())?;
 
     let bundle_dest_list = collect_operations
         .iter()
@@ -60,47 +69,103 @@ pub fn bundle_to_sqlite(
             assert!(metadata.is_file());
 
             let modified = metadata.modified()?.duration_since(UNIX_EPOCH)?.as_secs();
+            let dest_path = operation.dest_path();
+            let dest_path = dest_path.to_str().unwrap();
 
-            let stored_modified = conn
+            let stored = conn
                 .query_row(
-                    "SELECT modified FROM bundle WHERE path = ?",
-                    [&operation.dest_path().to_str().unwrap()],
-                    |row| row.get::<usize, u64>(0),
+                    "SELECT modified, hash FROM bundle WHERE path = ?",
+                    [dest_path],
+                    |row| Ok((row.get::<usize, u64>(0)?, row.get::<usize, String>(1)?)),
                 )
                 .optional()?;
 
-            if stored_modified == Some(modified) {
+            // Processed audio also depends on audio.toml, so it can't be skipped by mtime.
+            // The preprocessor has its own cache and knows the output hash without reading it.
+            let processed_audio = audio_preprocessor.process(&operation.src_path)?;
+
+            match (&processed_audio, &stored) {
+                (None, Some((stored_modified, _))) if *stored_modified == modified => {
+                    return Ok(());
+                }
+                (Some(processed_audio), Some((stored_modified, stored_hash)))
+                    if *stored_hash == processed_audio.hash =>
+                {
+                    if *stored_modified != modified {
+                        conn.execute(
+                            "UPDATE bundle SET modified = ? WHERE path = ?",
+                            (modified, dest_path),
+                        )?;
+                    }
+                    return Ok(());
+                }
+                _ => {}
+            }
+
+            let (data, compression, hash) = match processed_audio {
+                Some(processed_audio) => (
+                    std::fs::read(&processed_audio.path)?,
+                    Compression::None,
+                    processed_audio.hash,
+                ),
+                None => {
+                    let data = std::fs::read(&operation.src_path)?;
+                    let hash = content_hash(&data);
+                    (data, Compression::for_path(dest_path), hash)
+                }
+            };
+
+            if let Some((_, stored_hash)) = &stored
+                && *stored_hash == hash
+            {
+                // Touched but not changed. Don't pay for compression again.
+                conn.execute(
+                    "UPDATE bundle SET modified = ? WHERE path = ?",
+                    (modified, dest_path),
+                )?;
                 return Ok(());
             }
 
             has_changes.store(true, Ordering::Relaxed);
 
+            let size = data.len() as u64;
+            let compressed = compression.compress(data)?;
+
             conn.execute(
-                "INSERT OR REPLACE INTO bundle (path, data, modified) VALUES (?, ZEROBLOB(?), ?)",
+                "INSERT OR REPLACE INTO bundle (path, data, modified, compression, size, hash)
+                    VALUES (?, ?, ?, ?, ?, ?)",
                 (
-                    operation.dest_path().to_str().unwrap(),
-                    metadata.len(),
+                    dest_path,
+                    compressed,
                     modified,
+                    compression.as_str(),
+                    size,
+                    hash,
                 ),
             )?;
 
-            let rowid = conn.last_insert_rowid();
-
-            let mut blob = conn.blob_open(DatabaseName::Main, "bundle", "data", rowid, false)?;
-
-            let mut file = std::fs::File::open(&operation.src_path)?;
-
-            io::copy(&mut file, &mut blob)?;
-
-            blob.close()?;
-
             Ok(())
         },
     )?;
 
+    audio_preprocessor.finish()?;
+
     if has_changes.load(Ordering::Relaxed) {
         conn.execute("VACUUM", ())?;
     }
 
+    BundleContentManifest::read_from_sqlite(&conn)?
+        .write(sqlite_path.with_file_name(BUNDLE_MANIFEST_FILE_NAME))?;
+
     Ok(())
 }
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let found = stmt
+        .query_map([], |row| row.get::<usize, String>(1))?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+        .iter()
+        .any(|name| name == column);
+    Ok(found)
+}
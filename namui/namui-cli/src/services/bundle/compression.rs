@@ -0,0 +1,85 @@
+use anyhow::Result;
+use std::path::Path;
+
+/// Stored in `bundle.compression` column as text. Runtime readers match on the same strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Zstd { level: i32 },
+}
+
+impl Compression {
+    /// Already compressed formats are stored as-is, because zstd can't shrink them
+    /// but still costs decompression time on every load.
+    pub fn for_path(path: impl AsRef<Path>) -> Self {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some(
+                "png" | "jpg" | "jpeg" | "webp" | "gif" | "avif" | "ogg" | "opus" | "mp3" | "m4a"
+                | "aac" | "flac" | "mp4" | "webm" | "woff2" | "zst" | "gz" | "zip",
+            ) => Compression::None,
+            Some("wav" | "psd" | "bmp" | "tga") => Compression::Zstd { level: 9 },
+            _ => Compression::Zstd { level: 19 },
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Compression::None => "none",
+            Compression::Zstd { .. } => "zstd",
+        }
+    }
+
+    pub fn compress(&self, data: Vec<u8>) -> Result<Vec<u8>> {
+        match self {
+            Compression::None => Ok(data),
+            Compression::Zstd { level } => Ok(zstd::encode_all(data.as_slice(), *level)?),
+        }
+    }
+}
+
+pub fn decompress(compression: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+    match compression {
+        "none" => Ok(data),
+        "zstd" => Ok(zstd::decode_all(data.as_slice())?),
+        other => Err(anyhow::anyhow!("unknown bundle compression: {other}")),
+    }
+}
+
+pub fn content_hash(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_already_compressed_formats() {
+        assert_eq!(Compression::for_path("a/b.PNG"), Compression::None);
+        assert_eq!(Compression::for_path("bgm.ogg"), Compression::None);
+        assert_eq!(
+            Compression::for_path("hit.wav"),
+            Compression::Zstd { level: 9 }
+        );
+        assert_eq!(
+            Compression::for_path("font.ttf"),
+            Compression::Zstd { level: 19 }
+        );
+    }
+
+    #[test]
+    fn zstd_round_trip() {
+        let data = b"namui namui namui namui namui".repeat(100);
+        let compression = Compression::for_path("a.json");
+        let compressed = compression.compress(data.clone()).unwrap();
+        assert!(compressed.len() < data.len());
+        assert_eq!(decompress(compression.as_str(), compressed).unwrap(), data);
+    }
+}
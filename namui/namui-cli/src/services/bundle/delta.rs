@@ -0,0 +1,212 @@
+use super::bundle_to_sqlite::CREATE_BUNDLE_TABLE_SQL;
+use super::manifest::{BUNDLE_MANIFEST_FILE_NAME, BundleContentManifest};
+use anyhow::{Result, bail};
+use rusqlite::Connection;
+use std::path::Path;
+
+/// A delta bundle is a `bundle.sqlite` which holds only added or changed entries,
+/// plus `bundle_removed` for deleted paths and `bundle_delta` for the versions it connects.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DeltaSummary {
+    pub from_bundle_hash: String,
+    pub to_bundle_hash: String,
+    pub changed: usize,
+    pub removed: usize,
+}
+
+pub fn create_delta(
+    from_sqlite_path: impl AsRef<Path>,
+    to_sqlite_path: impl AsRef<Path>,
+    delta_sqlite_path: impl AsRef<Path>,
+) -> Result<DeltaSummary> {
+    let from_manifest =
+        BundleContentManifest::read_from_sqlite(&Connection::open(from_sqlite_path.as_ref())?)?;
+    let to_manifest =
+        BundleContentManifest::read_from_sqlite(&Connection::open(to_sqlite_path.as_ref())?)?;
+
+    let delta_sqlite_path = delta_sqlite_path.as_ref();
+    if delta_sqlite_path.exists() {
+        std::fs::remove_file(delta_sqlite_path)?;
+    }
+    let conn = Connection::open(delta_sqlite_path)?;
+    conn.execute(CREATE_BUNDLE_TABLE_SQL, ())?;
+    conn.execute(
+        "CREATE TABLE bundle_removed (path TEXT PRIMARY KEY NOT NULL)",
+        (),
+    )?;
+    conn.execute(
+        "CREATE TABLE bundle_delta (
+            from_bundle_hash TEXT NOT NULL,
+            to_bundle_hash TEXT NOT NULL
+        )",
+        (),
+    )?;
+
+    conn.execute(
+        "ATTACH DATABASE ? AS old",
+        [from_sqlite_path.as_ref().to_str().unwrap()],
+    )?;
+    conn.execute(
+        "ATTACH DATABASE ? AS new",
+        [to_sqlite_path.as_ref().to_str().unwrap()],
+    )?;
+
+    let changed = conn.execute(
+        "INSERT INTO main.bundle (path, data, modified, compression, size, hash)
+            SELECT n.path, n.data, n.modified, n.compression, n.size, n.hash
+            FROM new.bundle n LEFT JOIN old.bundle o ON o.path = n.path
+            WHERE o.path IS NULL OR o.hash != n.hash",
+        (),
+    )?;
+    let removed = conn.execute(
+        "INSERT INTO main.bundle_removed (path)
+            SELECT path FROM old.bundle WHERE path NOT IN (SELECT path FROM new.bundle)",
+        (),
+    )?;
+    conn.execute(
+        "INSERT INTO main.bundle_delta (from_bundle_hash, to_bundle_hash) VALUES (?, ?)",
+        (&from_manifest.bundle_hash, &to_manifest.bundle_hash),
+    )?;
+
+    conn.execute("DETACH DATABASE old", ())?;
+    conn.execute("DETACH DATABASE new", ())?;
+    conn.execute("VACUUM", ())?;
+
+    Ok(DeltaSummary {
+        from_bundle_hash: from_manifest.bundle_hash,
+        to_bundle_hash: to_manifest.bundle_hash,
+        changed,
+        removed,
+    })
+}
+
+/// Updates `base_sqlite_path` in place. Fails without touching it if the delta was made
+/// from another version.
+pub fn apply_delta(
+    base_sqlite_path: impl AsRef<Path>,
+    delta_sqlite_path: impl AsRef<Path>,
+) -> Result<BundleContentManifest> {
+    let (from_bundle_hash, to_bundle_hash) = Connection::open(delta_sqlite_path.as_ref())?
+        .query_row(
+            "SELECT from_bundle_hash, to_bundle_hash FROM bundle_delta",
+            [],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )?;
+
+    let base_sqlite_path = base_sqlite_path.as_ref();
+    let mut conn = Connection::open(base_sqlite_path)?;
+    let base_manifest = BundleContentManifest::read_from_sqlite(&conn)?;
+    if base_manifest.bundle_hash != from_bundle_hash {
+        bail!(
+            "delta is for bundle {from_bundle_hash}, but base is {}",
+            base_manifest.bundle_hash
+        );
+    }
+
+    conn.execute(
+        "ATTACH DATABASE ? AS delta",
+        [delta_sqlite_path.as_ref().to_str().unwrap()],
+    )?;
+    {
+        let transaction = conn.transaction()?;
+        transaction.execute(
+            "DELETE FROM main.bundle WHERE path IN (SELECT path FROM delta.bundle_removed)",
+            (),
+        )?;
+        transaction.execute(
+            "INSERT OR REPLACE INTO main.bundle (path, data, modified, compression, size, hash)
+                SELECT path, data, modified, compression, size, hash FROM delta.bundle",
+            (),
+        )?;
+
+        let applied_manifest = BundleContentManifest::read_from_sqlite(&transaction)?;
+        if applied_manifest.bundle_hash != to_bundle_hash {
+            bail!(
+                "bundle after applying delta is {}, expected {to_bundle_hash}",
+                applied_manifest.bundle_hash
+            );
+        }
+        transaction.commit()?;
+    }
+    conn.execute("DETACH DATABASE delta", ())?;
+
+    let manifest = BundleContentManifest::read_from_sqlite(&conn)?;
+    manifest.write(base_sqlite_path.with_file_name(BUNDLE_MANIFEST_FILE_NAME))?;
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::compression::content_hash;
+    use super::*;
+    use std::path::PathBuf;
+
+    fn write_bundle(path: &Path, entries: &[(&str, &[u8])]) {
+        let _ = std::fs::remove_file(path);
+        let conn = Connection::open(path).unwrap();
+        conn.execute(CREATE_BUNDLE_TABLE_SQL, ()).unwrap();
+        for (entry_path, data) in entries {
+            conn.execute(
+                "INSERT INTO bundle (path, data, modified, compression, size, hash)
+                    VALUES (?, ?, 0, 'none', ?, ?)",
+                (entry_path, data, data.len() as u64, content_hash(data)),
+            )
+            .unwrap();
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("namui_cli_bundle_delta_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn delta_contains_only_changes_and_reproduces_new_bundle() {
+        let dir = temp_dir("reproduce");
+        let old = dir.join("old.sqlite");
+        let new = dir.join("new.sqlite");
+        let delta = dir.join("delta.sqlite");
+        write_bundle(
+            &old,
+            &[
+                ("asset/same.png", b"same"),
+                ("asset/changed.json", b"before"),
+                ("asset/removed.wav", b"bye"),
+            ],
+        );
+        write_bundle(
+            &new,
+            &[
+                ("asset/same.png", b"same"),
+                ("asset/changed.json", b"after"),
+                ("asset/added.ttf", b"hello"),
+            ],
+        );
+
+        let summary = create_delta(&old, &new, &delta).unwrap();
+        assert_eq!(summary.changed, 2);
+        assert_eq!(summary.removed, 1);
+
+        let applied = apply_delta(&old, &delta).unwrap();
+        let expected =
+            BundleContentManifest::read_from_sqlite(&Connection::open(&new).unwrap()).unwrap();
+        assert_eq!(applied, expected);
+    }
+
+    #[test]
+    fn refuses_delta_for_other_base() {
+        let dir = temp_dir("other_base");
+        let old = dir.join("old.sqlite");
+        let new = dir.join("new.sqlite");
+        let other = dir.join("other.sqlite");
+        let delta = dir.join("delta.sqlite");
+        write_bundle(&old, &[("asset/a.png", b"a")]);
+        write_bundle(&new, &[("asset/a.png", b"b")]);
+        write_bundle(&other, &[("asset/a.png", b"c")]);
+
+        create_delta(&old, &new, &delta).unwrap();
+        assert!(apply_delta(&other, &delta).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use super::compression::content_hash;
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+pub const BUNDLE_MANIFEST_FILE_NAME: &str = "bundle_manifest.json";
+
+/// Describes the content of a `bundle.sqlite` so two bundle versions can be compared
+/// without reading blobs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleContentManifest {
+    /// Hash over every `(path, hash)` pair. Same content, same bundle hash.
+    pub bundle_hash: String,
+    pub entries: BTreeMap<String, BundleManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifestEntry {
+    pub hash: String,
+    pub size: u64,
+    pub stored_size: u64,
+    pub compression: String,
+}
+
+impl BundleContentManifest {
+    pub fn read_from_sqlite(conn: &Connection) -> Result<Self> {
+        let mut stmt = conn.prepare(
+            "SELECT path, hash, size, LENGTH(data), compression FROM bundle ORDER BY path",
+        )?;
+        let entries = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    BundleManifestEntry {
+                        hash: row.get(1)?,
+                        size: row.get(2)?,
+                        stored_size: row.get(3)?,
+                        compression: row.get(4)?,
+                    },
+                ))
+            })?
+            .collect::<rusqlite::Result<BTreeMap<_, _>>>()?;
+
+        Ok(Self {
+            bundle_hash: bundle_hash(&entries),
+            entries,
+        })
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+fn bundle_hash(entries: &BTreeMap<String, BundleManifestEntry>) -> String {
+    let mut buffer = Vec::new();
+    for (path, entry) in entries {
+        buffer.extend_from_slice(path.as_bytes());
+        buffer.push(0);
+        buffer.extend_from_slice(entry.hash.as_bytes());
+        buffer.push(0);
+    }
+    content_hash(&buffer)
+}
@@ -1,11 +1,18 @@
+mod audio;
 mod bundle_to_sqlite;
+mod compression;
+mod delta;
 mod lexicon;
+mod manifest;
 mod token;
 use crate::*;
 
 use super::resource_collect_service::CollectOperation;
 use crate::util::get_cli_root_path;
+use audio::AudioPreprocessor;
 use bundle_to_sqlite::bundle_to_sqlite;
+pub use delta::{DeltaSummary, apply_delta, create_delta};
+pub use manifest::BundleContentManifest;
 use lexicon::*;
 use std::{
     collections::HashMap,
@@ -13,6 +20,11 @@ use std::{
 };
 use token::*;
 
+/// `register_assets!` writes atlas pages here, relative to the project root.
+pub const ATLAS_PAGE_DIR: &str = "target/namui/atlas";
+/// Where the runtimes read atlas pages, as `register_assets!` registers them.
+const ATLAS_PAGE_BUNDLE_DIR: &str = "asset/.atlas";
+
 #[derive(Debug)]
 pub struct NamuiBundleManifest {
     project_bundle: Bundle,
@@ -40,6 +52,7 @@ impl NamuiBundleManifest {
                 let mut merged_query = HashMap::new();
                 merged_query.extend(project_bundle_query);
                 merged_query.extend(system_bundle_query);
+                merged_query.extend(atlas_page_query(&project_root_path, PathBuf::new())?);
                 merged_query
             };
             convert_src_dest_path_map_to_url_src_path_map(merged_query)
@@ -75,6 +88,7 @@ impl NamuiBundleManifest {
         let mut merged_query = HashMap::new();
         merged_query.extend(project_bundle_query);
         merged_query.extend(system_bundle_query);
+        merged_query.extend(atlas_page_query(&self.project_root_path, &dest_root_path)?);
         Ok(merged_query)
     }
 
@@ -108,8 +122,9 @@ impl NamuiBundleManifest {
 
     pub(crate) fn bundle_to_sqlite(&self, sqlite_path: impl AsRef<std::path::Path>) -> Result<()> {
         let ops = self.get_collect_operations(Path::new(""))?;
+        let audio_preprocessor = AudioPreprocessor::load(&self.project_root_path)?;
         let now = std::time::Instant::now();
-        bundle_to_sqlite(sqlite_path, ops)?;
+        bundle_to_sqlite(sqlite_path, ops, &audio_preprocessor)?;
         println!("INFO: bundle_to_sqlite took: {:?}", now.elapsed());
         Ok(())
     }
@@ -134,6 +149,31 @@ fn parse_bundle(root_path: impl AsRef<std::path::Path>) -> Result<Bundle> {
     })
 }
 
+/// Atlas pages are build output, so no `.namuibundle` line covers them.
+fn atlas_page_query(
+    project_root_path: impl AsRef<std::path::Path>,
+    dest_root_path: impl AsRef<std::path::Path>,
+) -> Result<HashMap<PathBuf, PathBuf>> {
+    let page_dir = project_root_path.as_ref().join(ATLAS_PAGE_DIR);
+    let mut src_dest_path_map = HashMap::new();
+    if !page_dir.exists() {
+        return Ok(src_dest_path_map);
+    }
+    for entry in std::fs::read_dir(&page_dir)? {
+        let src_path = entry?.path();
+        if src_path
+            .extension()
+            .is_some_and(|extension| extension == "png")
+        {
+            src_dest_path_map.insert(
+                src_path,
+                dest_root_path.as_ref().join(ATLAS_PAGE_BUNDLE_DIR),
+            );
+        }
+    }
+    Ok(src_dest_path_map)
+}
+
 fn convert_src_dest_path_map_to_url_src_path_map(
     src_dest_path_map: HashMap<PathBuf, PathBuf>,
 ) -> HashMap<PathBuf, PathBuf> {
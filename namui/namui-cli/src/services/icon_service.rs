@@ -74,6 +74,16 @@ pub fn generate_ico(src_image: &ImageBuffer<Rgba<u8>, Vec<u8>>, dst: &Path) -> R
     Ok(())
 }
 
+pub fn generate_png(src_image: &ImageBuffer<Rgba<u8>, Vec<u8>>, size: u32, dst: &Path) -> Result<()> {
+    if let Some(parent) = dst.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    image::imageops::resize(src_image, size, size, FilterType::Lanczos3)
+        .save_with_format(dst, image::ImageFormat::Png)
+        .map_err(|e| anyhow!("save png icon {:?}: {e}", dst))?;
+    Ok(())
+}
+
 pub fn generate_steam_assets(
     src_image: &ImageBuffer<Rgba<u8>, Vec<u8>>,
     dst_dir: &Path,
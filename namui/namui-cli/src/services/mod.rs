@@ -3,6 +3,7 @@ pub mod bundle;
 pub mod deep_link_manifest_service;
 pub mod icon_service;
 pub mod node_project_watch_service;
+pub mod package_service;
 pub mod resource_collect_service;
 pub mod runtime_project;
 pub mod rust_build_service;
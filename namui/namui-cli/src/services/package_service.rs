@@ -0,0 +1,406 @@
+use super::{deep_link_manifest_service::DeepLinkManifest, icon_service};
+use crate::cli::Target;
+use anyhow::{Result, anyhow, bail};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+const CHECKSUM_FILE_NAME: &str = "SHA256SUMS";
+const LINUX_ICON_SIZE: u32 = 256;
+const MACOS_ICON_SIZE: u32 = 1024;
+
+pub struct PackageArgs {
+    pub manifest_path: PathBuf,
+    pub target: Target,
+    /// Defaults to the package version in Cargo.toml.
+    pub version: Option<String>,
+    pub out_dir: PathBuf,
+}
+
+pub struct PackageOutput {
+    pub dir: PathBuf,
+    pub archive: PathBuf,
+    /// Debug symbols are kept out of the package, but next to it for crash symbolication.
+    pub symbols_dir: Option<PathBuf>,
+}
+
+/// Turns the release build output into a self-contained directory and a `.tar.gz` of it.
+/// Nothing in the package needs network at runtime; assets are all in `bundle.sqlite`.
+///
+/// - Windows: flat directory, `icon.ico` and `register-deep-links.cmd`.
+/// - Linux: AppImage-style AppDir with `AppRun` and a `.desktop` entry.
+/// - macOS: `<name>.app` bundle.
+/// - Web: the static `dist` directory.
+pub fn package(args: PackageArgs) -> Result<PackageOutput> {
+    let manifest_path = std::fs::canonicalize(&args.manifest_path)?;
+    let project_root_path = manifest_path.parent().unwrap();
+    let (name, cargo_version) = read_package_name_and_version(&manifest_path)?;
+    let version = args.version.unwrap_or(cargo_version);
+    let target = args.target;
+
+    let build_output_dir = build_output_dir(project_root_path, target);
+    if !build_output_dir.exists() {
+        bail!("build output not found at {build_output_dir:?}");
+    }
+
+    let package_name = format!("{name}-{version}-{target}");
+    let package_dir = args.out_dir.join(&package_name);
+    if package_dir.exists() {
+        std::fs::remove_dir_all(&package_dir)?;
+    }
+
+    let content_dir = match target {
+        Target::Aarch64AppleDarwin => package_dir.join(format!("{name}.app/Contents/MacOS")),
+        _ => package_dir.clone(),
+    };
+    copy_dir_recursive(&build_output_dir, &content_dir)?;
+
+    let symbols_dir = args.out_dir.join(format!("{package_name}-symbols"));
+    let symbols_dir = move_debug_symbols(&content_dir, &symbols_dir)?.then_some(symbols_dir);
+
+    let icon = icon_service::read_icon_path(&manifest_path)?
+        .map(|icon_path| icon_service::validate_source(&icon_path))
+        .transpose()?;
+    let deep_link_schemes = DeepLinkManifest::try_load(project_root_path)?
+        .map(|manifest| manifest.deep_link_schemes().clone())
+        .unwrap_or_default();
+
+    match target {
+        Target::X86_64PcWindowsMsvc | Target::Aarch64PcWindowsMsvc => {
+            if let Some(icon) = &icon {
+                icon_service::generate_ico(icon, &package_dir.join("icon.ico"))?;
+            }
+            if !deep_link_schemes.is_empty() {
+                std::fs::write(
+                    package_dir.join("register-deep-links.cmd"),
+                    windows_deep_link_script(&runner_file_name(target), &deep_link_schemes),
+                )?;
+            }
+        }
+        Target::X86_64UnknownLinuxGnu => {
+            write_executable(
+                &package_dir.join("AppRun"),
+                &linux_app_run(&runner_file_name(target)),
+            )?;
+            std::fs::write(
+                package_dir.join(format!("{name}.desktop")),
+                linux_desktop_entry(&name, &deep_link_schemes),
+            )?;
+            if let Some(icon) = &icon {
+                icon_service::generate_png(
+                    icon,
+                    LINUX_ICON_SIZE,
+                    &package_dir.join(format!("{name}.png")),
+                )?;
+            }
+        }
+        Target::Aarch64AppleDarwin => {
+            let contents_dir = package_dir.join(format!("{name}.app/Contents"));
+            std::fs::write(
+                contents_dir.join("Info.plist"),
+                macos_info_plist(
+                    &name,
+                    &version,
+                    &runner_file_name(target),
+                    icon.is_some(),
+                    &deep_link_schemes,
+                ),
+            )?;
+            if let Some(icon) = &icon {
+                icon_service::generate_png(
+                    icon,
+                    MACOS_ICON_SIZE,
+                    &contents_dir.join("Resources/icon.png"),
+                )?;
+            }
+        }
+        Target::Wasm32WasiWeb => {}
+    }
+
+    write_checksums(&package_dir)?;
+
+    let archive = args.out_dir.join(format!("{package_name}.tar.gz"));
+    write_tar_gz(&package_dir, &package_name, &archive)?;
+
+    Ok(PackageOutput {
+        dir: package_dir,
+        archive,
+        symbols_dir,
+    })
+}
+
+fn read_package_name_and_version(manifest_path: &Path) -> Result<(String, String)> {
+    let metadata = cargo_metadata::MetadataCommand::new()
+        .manifest_path(manifest_path)
+        .no_deps()
+        .exec()?;
+    let package = metadata
+        .packages
+        .iter()
+        .find(|package| Path::new(&package.manifest_path) == manifest_path)
+        .ok_or_else(|| anyhow!("package not found for manifest {:?}", manifest_path))?;
+    Ok((package.name.clone(), package.version.to_string()))
+}
+
+/// Where `namui build <target> --release` leaves its output.
+fn build_output_dir(project_root_path: &Path, target: Target) -> PathBuf {
+    match target {
+        Target::Wasm32WasiWeb => {
+            project_root_path.join("target/namui/target/wasm32-wasip1-threads/release/dist")
+        }
+        _ => project_root_path
+            .join("target/namui")
+            .join(target.to_string()),
+    }
+}
+
+fn runner_file_name(target: Target) -> String {
+    match target {
+        Target::X86_64PcWindowsMsvc | Target::Aarch64PcWindowsMsvc => {
+            format!("namui-runtime-{target}.exe")
+        }
+        _ => format!("namui-runtime-{target}"),
+    }
+}
+
+fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let dst_path = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&entry.path(), &dst_path)?;
+        } else {
+            // std::fs::copy keeps permissions, so runner binaries stay executable.
+            std::fs::copy(entry.path(), &dst_path)
+                .map_err(|e| anyhow!("copy {:?} -> {:?}: {e}", entry.path(), dst_path))?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether any symbol file was moved.
+fn move_debug_symbols(content_dir: &Path, symbols_dir: &Path) -> Result<bool> {
+    if symbols_dir.exists() {
+        std::fs::remove_dir_all(symbols_dir)?;
+    }
+    let mut moved = false;
+    for entry in std::fs::read_dir(content_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "pdb") {
+            std::fs::create_dir_all(symbols_dir)?;
+            std::fs::rename(&path, symbols_dir.join(path.file_name().unwrap()))?;
+            moved = true;
+        }
+    }
+    Ok(moved)
+}
+
+fn write_executable(path: &Path, content: &str) -> Result<()> {
+    std::fs::write(path, content)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+fn windows_deep_link_script(runner_file_name: &str, schemes: &[String]) -> String {
+    let mut script = format!(
+        "@echo off\r\n\
+        rem Registers deep link schemes for the current user to this directory's executable.\r\n\
+        set \"EXE=%~dp0{runner_file_name}\"\r\n"
+    );
+    for scheme in schemes {
+        let key = format!("HKCU\\Software\\Classes\\{scheme}");
+        script.push_str(&format!(
+            "reg add \"{key}\" /ve /d \"URL:{scheme}\" /f >nul\r\n\
+            reg add \"{key}\" /v \"URL Protocol\" /d \"\" /f >nul\r\n\
+            reg add \"{key}\\shell\\open\\command\" /ve /d \"\\\"%EXE%\\\" \\\"%%1\\\"\" /f >nul\r\n"
+        ));
+    }
+    script
+}
+
+fn linux_app_run(runner_file_name: &str) -> String {
+    format!(
+        "#!/bin/sh\n\
+        HERE=\"$(dirname \"$(readlink -f \"$0\")\")\"\n\
+        cd \"$HERE\"\n\
+        exec \"$HERE/{runner_file_name}\" \"$@\"\n"
+    )
+}
+
+fn linux_desktop_entry(name: &str, schemes: &[String]) -> String {
+    let mut entry = format!(
+        "[Desktop Entry]\n\
+        Type=Application\n\
+        Name={name}\n\
+        Exec=AppRun %u\n\
+        Icon={name}\n\
+        Categories=Game;\n"
+    );
+    if !schemes.is_empty() {
+        let mime_types = schemes
+            .iter()
+            .map(|scheme| format!("x-scheme-handler/{scheme};"))
+            .collect::<String>();
+        entry.push_str(&format!("MimeType={mime_types}\n"));
+    }
+    entry
+}
+
+fn macos_info_plist(
+    name: &str,
+    version: &str,
+    runner_file_name: &str,
+    has_icon: bool,
+    schemes: &[String],
+) -> String {
+    let icon = if has_icon {
+        "    <key>CFBundleIconFile</key>\n    <string>icon.png</string>\n"
+    } else {
+        ""
+    };
+    let url_types = if schemes.is_empty() {
+        String::new()
+    } else {
+        let schemes = schemes
+            .iter()
+            .map(|scheme| format!("                <string>{scheme}</string>\n"))
+            .collect::<String>();
+        format!(
+            "    <key>CFBundleURLTypes</key>\n    <array>\n        <dict>\n\
+            \x20           <key>CFBundleURLName</key>\n            <string>{name}</string>\n\
+            \x20           <key>CFBundleURLSchemes</key>\n            <array>\n{schemes}\
+            \x20           </array>\n        </dict>\n    </array>\n"
+        )
+    };
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleName</key>
+    <string>{name}</string>
+    <key>CFBundleIdentifier</key>
+    <string>com.namseent.{name}</string>
+    <key>CFBundleVersion</key>
+    <string>{version}</string>
+    <key>CFBundleShortVersionString</key>
+    <string>{version}</string>
+    <key>CFBundleExecutable</key>
+    <string>{runner_file_name}</string>
+    <key>CFBundlePackageType</key>
+    <string>APPL</string>
+{icon}{url_types}</dict>
+</plist>
+"#
+    )
+}
+
+/// Same format as `sha256sum`, so `sha256sum -c SHA256SUMS` verifies the package offline.
+fn write_checksums(package_dir: &Path) -> Result<()> {
+    let mut files = Vec::new();
+    collect_files(package_dir, &mut files)?;
+    files.sort();
+
+    let mut checksums = String::new();
+    for file in files {
+        let relative_path = file
+            .strip_prefix(package_dir)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        if relative_path == CHECKSUM_FILE_NAME {
+            continue;
+        }
+        let hash = hex::encode(Sha256::digest(std::fs::read(&file)?));
+        checksums.push_str(&format!("{hash}  {relative_path}\n"));
+    }
+    std::fs::write(package_dir.join(CHECKSUM_FILE_NAME), checksums)?;
+    Ok(())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+fn write_tar_gz(package_dir: &Path, package_name: &str, archive_path: &Path) -> Result<()> {
+    let file = std::fs::File::create(archive_path)?;
+    let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder.append_dir_all(package_name, package_dir)?;
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("namui_cli_package_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn checksums_cover_every_file_but_itself() {
+        let dir = temp_dir("checksums");
+        std::fs::create_dir_all(dir.join("__system__/font")).unwrap();
+        std::fs::write(dir.join("bundle.sqlite"), b"bundle").unwrap();
+        std::fs::write(dir.join("__system__/font/a.woff2"), b"font").unwrap();
+
+        write_checksums(&dir).unwrap();
+        write_checksums(&dir).unwrap();
+
+        let checksums = std::fs::read_to_string(dir.join(CHECKSUM_FILE_NAME)).unwrap();
+        let lines = checksums.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("  __system__/font/a.woff2"));
+        assert_eq!(
+            lines[1],
+            format!("{}  bundle.sqlite", hex::encode(Sha256::digest(b"bundle")))
+        );
+    }
+
+    #[test]
+    fn moves_pdb_out_of_package() {
+        let dir = temp_dir("symbols");
+        let content = dir.join("content");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(content.join("runtime.exe"), b"exe").unwrap();
+        std::fs::write(content.join("runtime.pdb"), b"pdb").unwrap();
+
+        assert!(move_debug_symbols(&content, &dir.join("symbols")).unwrap());
+        assert!(content.join("runtime.exe").exists());
+        assert!(!content.join("runtime.pdb").exists());
+        assert!(dir.join("symbols/runtime.pdb").exists());
+    }
+
+    #[test]
+    fn registers_deep_link_schemes() {
+        let schemes = vec!["boxbox".to_string()];
+
+        let desktop_entry = linux_desktop_entry("tower-defense", &schemes);
+        assert!(desktop_entry.contains("MimeType=x-scheme-handler/boxbox;\n"));
+        assert!(!linux_desktop_entry("tower-defense", &[]).contains("MimeType"));
+
+        let plist = macos_info_plist("tower-defense", "0.1.0", "runner", false, &schemes);
+        assert!(plist.contains("<string>boxbox</string>"));
+        assert!(!plist.contains("CFBundleIconFile"));
+
+        let script = windows_deep_link_script("runner.exe", &schemes);
+        assert!(script.contains(r#"reg add "HKCU\Software\Classes\boxbox" /v "URL Protocol""#));
+        assert!(script.contains(r#"/ve /d "\"%EXE%\" \"%%1\"" /f"#));
+    }
+}
@@ -161,6 +161,7 @@ build = "build.rs"
 {kv_store_native_dep}
 mimalloc = "0.1.39"
 rusqlite = {{ version = "0.31.0", features = ["blob", "bundled"] }}
+zstd = "0.13"
 
 {build_deps_section}
 [profile.release]
@@ -207,10 +208,10 @@ fn main() {{
             &bundle_path,
             rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
         ).unwrap_or_else(|e| panic!("Failed to open bundle.sqlite: {{e}}"));
-        let rowid: i64 = conn.query_row(
-            "SELECT rowid FROM bundle WHERE path = ?",
+        let (rowid, compression): (i64, String) = conn.query_row(
+            "SELECT rowid, compression FROM bundle WHERE path = ?",
             [&asset_path],
-            |row| row.get(0),
+            |row| Ok((row.get(0)?, row.get(1)?)),
         ).unwrap_or_else(|e| panic!("Asset not found in bundle '{{}}': {{e}}", asset_path));
         let mut blob = conn.blob_open(
             rusqlite::DatabaseName::Main,
@@ -222,7 +223,11 @@ fn main() {{
         let mut data = vec![0u8; blob.len()];
         blob.read_exact(&mut data)
             .unwrap_or_else(|e| panic!("Failed to read blob for '{{}}': {{e}}", asset_path));
-        data
+        match compression.as_str() {{
+            "zstd" => zstd::decode_all(data.as_slice())
+                .unwrap_or_else(|e| panic!("Failed to decompress '{{}}': {{e}}", asset_path)),
+            _ => data,
+        }}
     }});
     {project_name_underscored}::main();
     native_runner::entry();
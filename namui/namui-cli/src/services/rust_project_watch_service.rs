@@ -2,7 +2,11 @@ use crate::*;
 use cargo_metadata::MetadataCommand;
 use notify::{Config, RecommendedWatcher, Watcher};
 use regex::Regex;
-use std::{collections::HashSet, path::PathBuf, str::FromStr};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 pub struct RustProjectWatchService {
     manifest_path: PathBuf,
@@ -11,7 +15,9 @@ pub struct RustProjectWatchService {
     watching_paths: HashSet<PathBuf>,
 }
 
-const WATCHING_ITEMS_IN_PROJECT: [&str; 3] = ["src", "Cargo.toml", ".namuibundle"];
+const WATCHING_ITEMS_IN_PROJECT: [&str; 4] = ["src", "Cargo.toml", ".namuibundle", "asset"];
+/// Same as `ATLAS_CONFIG_FILE_NAME` in namui-asset-macro.
+const ATLAS_CONFIG_FILE_NAME: &str = "atlas.toml";
 
 impl RustProjectWatchService {
     pub(crate) fn new(manifest_path: impl AsRef<std::path::Path>) -> Result<Self> {
@@ -36,6 +42,13 @@ impl RustProjectWatchService {
             self.update_watching_paths().await?;
 
             let event = self.watcher_receiver.recv().await.unwrap().unwrap();
+            if event.paths.iter().any(|path| self.is_asset_path(path)) {
+                // Only a file added to or removed from an atlas directory changes what
+                // `register_assets!` expands to without changing a file it tracks.
+                if !self.touch_atlas_configs(&event)? {
+                    continue;
+                }
+            }
             match event.kind {
                 notify::EventKind::Create(_)
                 | notify::EventKind::Modify(_)
@@ -58,6 +71,44 @@ impl RustProjectWatchService {
         }
     }
 
+    fn is_asset_path(&self, path: &Path) -> bool {
+        self.watching_paths.iter().any(|watching_path| {
+            watching_path.ends_with("asset") && path.starts_with(watching_path)
+        })
+    }
+
+    /// `register_assets!` tracks `atlas.toml`, so touching it makes cargo expand the macro again.
+    /// Returns whether any was touched.
+    fn touch_atlas_configs(&self, event: &notify::Event) -> Result<bool> {
+        let is_membership_change = matches!(
+            event.kind,
+            notify::EventKind::Create(_)
+                | notify::EventKind::Remove(_)
+                | notify::EventKind::Modify(notify::event::ModifyKind::Name(_))
+        );
+        if !is_membership_change {
+            return Ok(false);
+        }
+        let mut touched = false;
+        for path in &event.paths {
+            let Some(config_path) = path
+                .ancestors()
+                .skip(1)
+                .take_while(|dir| self.is_asset_path(dir))
+                .map(|dir| dir.join(ATLAS_CONFIG_FILE_NAME))
+                .find(|config_path| config_path.is_file())
+            else {
+                continue;
+            };
+            std::fs::File::options()
+                .append(true)
+                .open(&config_path)?
+                .set_modified(std::time::SystemTime::now())?;
+            touched = true;
+        }
+        Ok(touched)
+    }
+
     async fn update_watching_paths(&mut self) -> Result<()> {
         let local_path_in_repr = Regex::new(r"path\+file://([^#]+)")?;
         let project_root_path = self.manifest_path.parent().unwrap();
@@ -1,4 +1,7 @@
-use crate::{services::bundle::NamuiBundleManifest, util::get_cli_root_path};
+use crate::{
+    services::bundle::{ATLAS_PAGE_DIR, NamuiBundleManifest},
+    util::get_cli_root_path,
+};
 use anyhow::Result;
 use std::path::Path;
 use tokio::fs::{create_dir_all, remove_dir_all};
@@ -51,6 +54,11 @@ pub async fn prepare_vite_env(config: &ViteConfig<'_>) -> Result<ViteEnvVars> {
     let server_fs_allow = serde_json::json!([
         "./",
         asset_dir.to_string_lossy().to_string(),
+        config
+            .project_root_path
+            .join(ATLAS_PAGE_DIR)
+            .to_string_lossy()
+            .to_string(),
         target_project_path.to_string_lossy().to_string(),
         format!("{}/system_bundle", cli_root.to_string_lossy()),
     ]);
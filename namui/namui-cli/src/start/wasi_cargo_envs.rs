@@ -127,8 +127,10 @@ pub fn wasi_cargo_envs(wasi_type: WasiType) -> [(&'static str, String); 10] {
                         "_on_key_up",
                         "_on_screen_resize",
                         "_on_animation_frame",
+                        "_request_full_frame",
                         "_on_blur",
                         "_on_visibility_change",
+                        "_on_file_drop",
                         "_on_text_input",
                         "_on_text_input_key_down",
                         "_on_text_input_selection_change",
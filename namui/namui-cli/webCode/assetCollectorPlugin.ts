@@ -3,12 +3,29 @@ import * as fs from "fs";
 import * as path from "path";
 
 export function assetCollectorPlugin(assetDir: string): Plugin {
-    const imageFiles = collectImageFiles(assetDir);
-    imageFiles.sort();
+    // Must match the ids `register_assets!` gives: sorted bytewise by the `/`-separated
+    // path under the asset directory (`image_entries` in asset-macro). Images in atlas
+    // directories are served through the pages it writes to `target/namui/atlas`,
+    // which the bundle puts under `asset/.atlas`.
+    const atlasPageDir = path.join(assetDir, "../target/namui/atlas");
+    const imageFiles = [
+        ...collectImageFiles(assetDir)
+            .filter((file) => !isInAtlasDir(assetDir, file))
+            .map((file) => ({
+                path: file,
+                relativePath: path.relative(assetDir, file).split(path.sep).join("/"),
+            })),
+        ...collectFilesByExtensions(atlasPageDir, [".png"]).map((file) => ({
+            path: file,
+            relativePath: `.atlas/${path.basename(file)}`,
+        })),
+    ];
+    imageFiles.sort((a, b) =>
+        Buffer.compare(Buffer.from(a.relativePath), Buffer.from(b.relativePath)),
+    );
 
     const imageInfos: ImageInfo[] = imageFiles.map((file, id) => ({
-        path: file,
-        relativePath: path.relative(assetDir, file),
+        ...file,
         id,
     }));
 
@@ -28,11 +45,15 @@ export function assetCollectorPlugin(assetDir: string): Plugin {
     const audioFiles = collectAudioFiles(assetDir);
     audioFiles.sort();
 
-    const audioInfos: AudioInfo[] = audioFiles.map((file, id) => ({
-        path: file,
-        relativePath: path.relative(assetDir, file),
-        id,
-    }));
+    const processedAudioPaths = readProcessedAudioPaths(assetDir);
+    const audioInfos: AudioInfo[] = audioFiles.map((file, id) => {
+        const relativePath = path.relative(assetDir, file);
+        return {
+            path: processedAudioPaths.get(relativePath) ?? file,
+            relativePath,
+            id,
+        };
+    });
 
     console.log(`Collected ${audioInfos.length} audio files from ${assetDir}`);
 
@@ -142,8 +163,60 @@ function collectImageFiles(assetDir: string): string[] {
     return collectFilesByExtensions(assetDir, [".jpg", ".jpeg", ".png"]);
 }
 
+function isInAtlasDir(assetDir: string, file: string): boolean {
+    let dir = path.dirname(file);
+    while (dir.startsWith(assetDir)) {
+        if (fs.existsSync(path.join(dir, "atlas.toml"))) {
+            return true;
+        }
+        const parent = path.dirname(dir);
+        if (parent === dir) {
+            break;
+        }
+        dir = parent;
+    }
+    return false;
+}
+
+// Written by namui-cli's bundle audio step when the project has audio.toml.
+// Entries made from an older version of the source file are ignored, same as register_assets!.
+function readProcessedAudioPaths(assetDir: string): Map<string, string> {
+    const processedAudioPaths = new Map<string, string>();
+    const audioCacheDir = path.join(assetDir, "../target/namui/audio");
+    const indexPath = path.join(audioCacheDir, "index.json");
+    if (!fs.existsSync(indexPath)) {
+        return processedAudioPaths;
+    }
+
+    const index: Record<
+        string,
+        { source_size: number; source_modified: number; cache_key: string }
+    > = JSON.parse(fs.readFileSync(indexPath, "utf-8"));
+
+    for (const [relativePath, entry] of Object.entries(index)) {
+        const sourcePath = path.join(assetDir, relativePath);
+        const processedPath = path.join(
+            audioCacheDir,
+            `${entry.cache_key}.ogg`,
+        );
+        if (!fs.existsSync(sourcePath) || !fs.existsSync(processedPath)) {
+            continue;
+        }
+        const stat = fs.statSync(sourcePath);
+        if (
+            stat.size !== entry.source_size ||
+            Math.floor(stat.mtimeMs / 1000) !== entry.source_modified
+        ) {
+            continue;
+        }
+        processedAudioPaths.set(path.normalize(relativePath), processedPath);
+    }
+
+    return processedAudioPaths;
+}
+
 function collectAudioFiles(assetDir: string): string[] {
-    return collectFilesByExtensions(assetDir, [".mp3", ".wav", ".ogg", ".opus"]);
+    return collectFilesByExtensions(assetDir, [".mp3", ".wav", ".ogg", ".opus", ".flac"]);
 }
 
 function collectFilesByExtensions(
@@ -50,12 +50,15 @@ export function startEventSystem({
                 ),
             );
 
-            drawer.exports._draw_rendering_tree(
+            const isDecoded = drawer.exports._draw_rendering_tree(
                 renderingTreePtrOnDrawer,
                 renderingTreeLen,
                 mouseX,
                 mouseY,
             );
+            if (!isDecoded) {
+                exports._request_full_frame();
+            }
         } finally {
             drawer.exports.free(renderingTreePtrOnDrawer);
         }
@@ -155,6 +158,45 @@ export function startEventSystem({
     }
     document.addEventListener("visibilitychange", onVisibilityChange);
 
+    function onDragOver(event: DragEvent) {
+        event.preventDefault();
+    }
+    document.addEventListener("dragover", onDragOver);
+
+    async function onDrop(event: DragEvent) {
+        event.preventDefault();
+
+        const files = Array.from(event.dataTransfer?.files ?? []);
+        for (const file of files) {
+            const bytes = new Uint8Array(await file.arrayBuffer());
+            const nameBytes = new TextEncoder().encode(file.name);
+
+            const bytesPtr = exports.malloc(bytes.length);
+            new Uint8Array(memory.buffer, bytesPtr, bytes.length).set(bytes);
+            const namePtr = exports.malloc(nameBytes.length);
+            new Uint8Array(memory.buffer, namePtr, nameBytes.length).set(
+                nameBytes,
+            );
+
+            onEventHandlerReturn(
+                exports._on_file_drop(
+                    event.clientX,
+                    event.clientY,
+                    namePtr,
+                    nameBytes.length,
+                    0,
+                    0,
+                    bytesPtr,
+                    bytes.length,
+                ),
+            );
+
+            exports.free(bytesPtr);
+            exports.free(namePtr);
+        }
+    }
+    document.addEventListener("drop", onDrop);
+
     function onContextMenu(event: PointerEvent) {
         event.preventDefault();
     }
@@ -174,6 +216,8 @@ export function startEventSystem({
         document.removeEventListener("wheel", onWheel);
         window.removeEventListener("blur", onBlur);
         document.removeEventListener("visibilitychange", onVisibilityChange);
+        document.removeEventListener("dragover", onDragOver);
+        document.removeEventListener("drop", onDrop);
         document.removeEventListener("contextmenu", onContextMenu);
     }
 
@@ -29,8 +29,19 @@ export type Exports = CommonExports & {
     _on_key_up: (code: number) => number;
     _on_blur: () => number;
     _on_visibility_change: () => number;
+    _on_file_drop: (
+        x: number,
+        y: number,
+        name_ptr: number,
+        name_len: number,
+        path_ptr: number,
+        path_len: number,
+        bytes_ptr: number,
+        bytes_len: number,
+    ) => number;
     _on_screen_resize: (width: number, height: number) => number;
     _on_animation_frame: () => number;
+    _request_full_frame: () => void;
     _on_text_input: (
         text_ptr: number,
         text_len: number,
@@ -92,7 +103,7 @@ export type DrawerExports = CommonExports & {
         renderingTreeBytesLen: number,
         mouseX: number,
         mouseY: number,
-    ) => void;
+    ) => number;
     _redraw(mouseX: number, mouseY: number): void;
     _on_window_resize: (windowWidth: number, windowHeight: number) => void;
 };
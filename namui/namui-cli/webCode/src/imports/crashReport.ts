@@ -0,0 +1,250 @@
+// Reports Rust panics to namsh as `kind: panic` crash reports, the web
+// counterpart of `namui-crash-reporter`'s panic hook, with the same consent
+// check and scrubbing before anything is hashed or signed. Wire format, hash
+// and HMAC must stay in sync with `crash-reporter/src/{context,namsh,panic}.rs`.
+
+const namsh =
+    __NAMSH_BUILD_ID__ && __NAMSH_HMAC_KEY__ && __NAMSH_URL__
+        ? {
+              buildId: __NAMSH_BUILD_ID__,
+              hmacKeyHex: __NAMSH_HMAC_KEY__,
+              url: __NAMSH_URL__.replace(/\/+$/, ""),
+          }
+        : null;
+
+const TOP_N = 32;
+
+const INSTALL_ID_KEY = "namui-crash-install-id";
+// "granted" or "denied", set by the page that embeds the game. Unset follows
+// `NAMSH_REQUIRE_CONSENT`, like `Consent::Unknown` in `crash-reporter/src/consent.rs`.
+const CONSENT_KEY = "namui-crash-consent";
+
+// Workers have no localStorage, so the page reads it and hands it to the
+// threads. `undefined` when the player's consent doesn't allow reports, which
+// turns reporting off for the page load.
+export function crashReportInstallId(): string | undefined {
+    return allowsUpload() ? getOrCreateInstallId() : undefined;
+}
+
+function allowsUpload(): boolean {
+    let consent: string | null;
+    try {
+        consent = localStorage.getItem(CONSENT_KEY);
+    } catch {
+        consent = null;
+    }
+    switch (consent) {
+        case "granted":
+            return true;
+        case "denied":
+            return false;
+        default:
+            return !__NAMSH_REQUIRE_CONSENT__;
+    }
+}
+
+// Without storage (private mode), it lasts one page load.
+function getOrCreateInstallId(): string {
+    try {
+        const saved = localStorage.getItem(INSTALL_ID_KEY);
+        if (saved) {
+            return saved;
+        }
+        const installId = crypto.randomUUID();
+        localStorage.setItem(INSTALL_ID_KEY, installId);
+        return installId;
+    } catch {
+        return crypto.randomUUID();
+    }
+}
+
+export function crashReportImports({
+    memory,
+    installId,
+}: {
+    memory: WebAssembly.Memory;
+    // Missing for the drawer and font threads, which don't report panics, and
+    // when the player's consent doesn't allow reports.
+    installId: string | undefined;
+}) {
+    function readString(ptr: number, len: number): string {
+        return new TextDecoder().decode(
+            new Uint8Array(memory.buffer, ptr, len).slice(),
+        );
+    }
+
+    return {
+        _namui_report_panic(
+            messagePtr: number,
+            messageLen: number,
+            locationPtr: number,
+            locationLen: number,
+        ) {
+            if (!namsh || !installId) {
+                return;
+            }
+            const message = scrub(readString(messagePtr, messageLen));
+            const location = scrub(readString(locationPtr, locationLen));
+            // Taken synchronously, while the panicking wasm frames are still on the stack.
+            const frames = wasmFrames(new Error().stack ?? "");
+
+            reportPanic(namsh, {
+                installId,
+                message,
+                location,
+                frames,
+            }).catch((error) => {
+                console.error("[crash-report] panic upload failed", error);
+            });
+        },
+    };
+}
+
+// The browser has no home directory or user name to scrub, but the build
+// machine's paths in panic locations do, so these stand in for
+// `scrub::default_rules`: home directories become `~` and email addresses
+// `<email>`.
+const SCRUB_RULES: [RegExp, string][] = [
+    [/(?:\/home|\/Users)\/[^/\s:]+/g, "~"],
+    [/[A-Za-z]:[\\/]Users[\\/][^\\/\s:]+/g, "~"],
+    [/[\w.+-]+@[\w-]+(?:\.[\w-]+)+/g, "<email>"],
+];
+
+function scrub(text: string): string {
+    return SCRUB_RULES.reduce(
+        (text, [pattern, replacement]) => text.replace(pattern, replacement),
+        text,
+    );
+}
+
+// `wasm-function[<index>]:0x<offset>` is symbol-free and stable per build,
+// like `module!0x<offset>` on native.
+function wasmFrames(stack: string): string[] {
+    const frames: string[] = [];
+    for (const match of stack.matchAll(/wasm-function\[\d+\]:0x[0-9a-f]+/g)) {
+        frames.push(match[0]);
+        if (frames.length === TOP_N) {
+            break;
+        }
+    }
+    return frames;
+}
+
+async function reportPanic(
+    config: NonNullable<typeof namsh>,
+    {
+        installId,
+        message,
+        location,
+        frames,
+    }: {
+        installId: string;
+        message: string;
+        location: string;
+        frames: string[];
+    },
+) {
+    const encoder = new TextEncoder();
+    const stackHash = toHex(
+        await crypto.subtle.digest(
+            "SHA-256",
+            encoder.encode([location, ...frames].join("\n")),
+        ),
+    );
+
+    // Key order matches the Rust struct, so this is the same bytes
+    // `serde_json::to_vec` gives namsh when it verifies the signature.
+    const context = {
+        build_id: config.buildId,
+        install_id: installId,
+        session_uptime_sec: Math.floor(
+            (Date.now() - performance.timeOrigin) / 1000,
+        ),
+        log_tail: null,
+        kind: "panic",
+        panic: { message, location },
+        breadcrumbs: [],
+    };
+    const contextJson = encoder.encode(JSON.stringify(context));
+
+    const key = await crypto.subtle.importKey(
+        "raw",
+        fromHex(config.hmacKeyHex),
+        { name: "HMAC", hash: "SHA-256" },
+        false,
+        ["sign"],
+    );
+    const signature = toHex(
+        await crypto.subtle.sign(
+            "HMAC",
+            key,
+            concat([
+                encoder.encode(config.buildId),
+                encoder.encode(stackHash),
+                new Uint8Array(await crypto.subtle.digest("SHA-256", contextJson)),
+            ]),
+        ),
+    );
+
+    const response = await fetch(`${config.url}/__forte_action/intake_crash`, {
+        method: "POST",
+        headers: {
+            "content-type": "application/json",
+            "x-namsh-build-id": config.buildId,
+            "x-namsh-signature": signature,
+        },
+        body: JSON.stringify({
+            build_id: config.buildId,
+            stack_hash: stackHash,
+            context,
+        }),
+    });
+    if (!response.ok) {
+        throw new Error(`intake_crash http ${response.status}`);
+    }
+    const output = await response.json();
+    if (output.t !== "Ok") {
+        throw new Error(`intake_crash rejected: ${output.t}`);
+    }
+    if (!output.upload) {
+        return;
+    }
+
+    const report = [
+        `panic at ${location}: ${message}`,
+        "",
+        ...frames.map((frame, index) => `${String(index).padStart(3)}: ${frame}`),
+    ].join("\n");
+    const putResponse = await fetch(output.upload.presignedPutUrl, {
+        method: "PUT",
+        headers: { "content-type": "application/octet-stream" },
+        body: report,
+    });
+    if (!putResponse.ok) {
+        throw new Error(`r2 put http ${putResponse.status}`);
+    }
+}
+
+function toHex(buffer: ArrayBuffer): string {
+    return Array.from(new Uint8Array(buffer))
+        .map((byte) => byte.toString(16).padStart(2, "0"))
+        .join("");
+}
+
+function fromHex(hex: string): Uint8Array {
+    const bytes = new Uint8Array(hex.length / 2);
+    for (let i = 0; i < bytes.length; i++) {
+        bytes[i] = parseInt(hex.slice(i * 2, i * 2 + 2), 16);
+    }
+    return bytes;
+}
+
+function concat(chunks: Uint8Array[]): Uint8Array {
+    const bytes = new Uint8Array(chunks.reduce((sum, chunk) => sum + chunk.length, 0));
+    let offset = 0;
+    for (const chunk of chunks) {
+        bytes.set(chunk, offset);
+        offset += chunk.length;
+    }
+    return bytes;
+}
@@ -3,6 +3,7 @@ import { textInputImports } from "./textInput";
 import { type DrawerExports, type Exports } from "@/exports";
 import { kvStoreImports } from "@/storage/imports";
 import { createAudioImports } from "@/audio";
+import { crashReportImports } from "./crashReport";
 import { ThreadStartSupplies } from "@/thread/startThread";
 
 export function createImportObject({
@@ -70,6 +71,13 @@ export function createImportObject({
             ...createAudioImports({
                 memory,
             }),
+            ...crashReportImports({
+                memory,
+                installId:
+                    supplies.type === "main" || supplies.type === "sub"
+                        ? supplies.installId
+                        : undefined,
+            }),
             _hardware_concurrency: () => navigator.hardwareConcurrency,
             _namui_console_log: (level: number, ptr: number, len: number) => {
                 const bytes = new Uint8Array(memory.buffer, ptr, len).slice();
@@ -8,6 +8,7 @@ import { assetList } from "virtual:asset-list";
 import { audioAssetList } from "virtual:audio-asset-list";
 import { loadFonts } from "@/font/loadFont";
 import { loadAudioAssets } from "@/audio";
+import { crashReportInstallId } from "@/imports/crashReport";
 
 console.debug("crossOriginIsolated", crossOriginIsolated);
 
@@ -165,6 +166,7 @@ async function startMainThread() {
                 module,
                 nextTid,
                 initialWindowWh: (window.innerWidth << 16) | window.innerHeight,
+                installId: crashReportInstallId(),
                 imageCount: drawer.imageCount,
                 imageInfoBytes: drawer.imageInfoBytes,
                 spawnPort: spawnChannel.port2,
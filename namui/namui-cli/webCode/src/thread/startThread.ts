@@ -18,6 +18,7 @@ export type ThreadStartSupplies = {
 } & (
     | {
           type: "main";
+          installId: string | undefined;
           imageInfoBytes: Uint8Array;
           imageCount: number;
           spawnPort: MessagePort;
@@ -25,6 +26,7 @@ export type ThreadStartSupplies = {
       }
     | {
           type: "sub";
+          installId: string | undefined;
           startArgPtr: number;
           tid: number;
           imageInfoBytes: Uint8Array;
@@ -27,3 +27,7 @@ declare module "virtual:audio-asset-list" {
 
 declare const __NAMUI_RUST_LOG__: string | null;
 declare const __NAMUI_RUST_BACKTRACE__: string | null;
+declare const __NAMSH_BUILD_ID__: string | null;
+declare const __NAMSH_HMAC_KEY__: string | null;
+declare const __NAMSH_URL__: string | null;
+declare const __NAMSH_REQUIRE_CONSENT__: boolean;
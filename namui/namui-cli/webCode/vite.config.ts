@@ -23,6 +23,12 @@ export default defineConfig({
         __NAMUI_RUST_BACKTRACE__: JSON.stringify(
             process.env.RUST_BACKTRACE ?? null,
         ),
+        __NAMSH_BUILD_ID__: JSON.stringify(process.env.NAMSH_BUILD_ID ?? null),
+        __NAMSH_HMAC_KEY__: JSON.stringify(process.env.NAMSH_HMAC_KEY ?? null),
+        __NAMSH_URL__: JSON.stringify(process.env.NAMSH_URL ?? null),
+        __NAMSH_REQUIRE_CONSENT__: JSON.stringify(
+            ["1", "true"].includes(process.env.NAMSH_REQUIRE_CONSENT ?? ""),
+        ),
     },
     server: {
         host: true,
@@ -0,0 +1,47 @@
+use crate::*;
+use std::{cell::RefCell, collections::HashMap};
+
+struct CachedPicture {
+    picture: NativePicture,
+    used: bool,
+}
+
+thread_local! {
+    static PICTURE_CACHE: RefCell<HashMap<u64, CachedPicture>> = RefCell::default();
+}
+
+pub(super) fn draw_cache_node(canvas: &dyn SkCanvas, cache: &CacheNode) {
+    let picture = PICTURE_CACHE.with_borrow_mut(|picture_cache| {
+        if let Some(cached) = picture_cache.get_mut(&cache.key) {
+            cached.used = true;
+            return Some(cached.picture.clone());
+        }
+
+        let picture = record_picture(cache.rendering_tree)?;
+        picture_cache.insert(
+            cache.key,
+            CachedPicture {
+                picture: picture.clone(),
+                used: true,
+            },
+        );
+        Some(picture)
+    });
+
+    if let Some(picture) = picture {
+        canvas.draw_picture(&picture);
+    }
+}
+
+fn record_picture(rendering_tree: &RenderingTree) -> Option<NativePicture> {
+    let bounding_box = rendering_tree.bounding_box()?;
+    NativePicture::record(bounding_box, |canvas| (*rendering_tree).draw(canvas))
+}
+
+/// Drops recordings which were not drawn since the last call.
+/// Call this after drawing a new rendering tree, not after redrawing the same one.
+pub fn retain_used_pictures() {
+    PICTURE_CACHE.with_borrow_mut(|picture_cache| {
+        picture_cache.retain(|_, cached| std::mem::take(&mut cached.used));
+    });
+}
@@ -1,7 +1,7 @@
 use crate::*;
 
 impl Draw for &ImageDrawCommand {
-    fn draw(self, skia: &mut NativeSkia) {
+    fn draw(self, canvas: &dyn SkCanvas) {
         if self.sprites.is_empty() {
             return;
         }
@@ -24,7 +24,7 @@ impl Draw for &ImageDrawCommand {
             None
         };
 
-        skia.surface().canvas().draw_atlas(
+        canvas.draw_atlas(
             &self.image,
             &xforms,
             &tex_rects,
@@ -1,20 +1,23 @@
+mod cache;
 mod image;
 mod path;
 mod text;
+mod z_layer;
 
 use crate::*;
+pub use cache::retain_used_pictures;
 
 pub trait Draw {
-    fn draw(self, skia: &mut NativeSkia);
+    fn draw(self, canvas: &dyn SkCanvas);
 }
 
 impl Draw for RenderingTree {
-    fn draw(self, skia: &mut NativeSkia) {
+    fn draw(self, canvas: &dyn SkCanvas) {
         struct RenderingTreeDrawContext {
             on_top_node_matrix_tuples: Vec<(OnTopNode, TransformMatrix)>,
         }
         fn draw_internal(
-            skia: &mut NativeSkia,
+            canvas: &dyn SkCanvas,
             rendering_tree: &RenderingTree,
             rendering_tree_draw_context: &mut RenderingTreeDrawContext,
         ) {
@@ -22,70 +25,86 @@ impl Draw for RenderingTree {
                 RenderingTree::Children(children) => {
                     // NOTE: Children are drawn in reverse order. First(Left) child is drawn at the front.
                     for child in children.iter().rev() {
-                        draw_internal(skia, child, rendering_tree_draw_context);
+                        draw_internal(canvas, child, rendering_tree_draw_context);
                     }
                 }
                 RenderingTree::Node(draw_command) => {
-                    draw_command.draw(skia);
+                    draw_command.draw(canvas);
                 }
                 RenderingTree::Special(special) => match special {
                     SpecialRenderingNode::Translate(translate) => {
-                        skia.surface().canvas().save();
-                        skia.surface().canvas().translate(translate.x, translate.y);
+                        canvas.save();
+                        canvas.translate(translate.x, translate.y);
 
-                        draw_internal(skia, translate.rendering_tree, rendering_tree_draw_context);
-                        skia.surface().canvas().restore();
+                        draw_internal(
+                            canvas,
+                            translate.rendering_tree,
+                            rendering_tree_draw_context,
+                        );
+                        canvas.restore();
                     }
                     SpecialRenderingNode::Clip(clip) => {
-                        skia.surface().canvas().save();
-                        skia.surface()
-                            .canvas()
-                            .clip_path(clip.path, clip.clip_op, true);
-                        draw_internal(skia, clip.rendering_tree, rendering_tree_draw_context);
-                        skia.surface().canvas().restore();
+                        canvas.save();
+                        canvas.clip_path(clip.path, clip.clip_op, true);
+                        draw_internal(canvas, clip.rendering_tree, rendering_tree_draw_context);
+                        canvas.restore();
                     }
                     SpecialRenderingNode::Absolute(absolute) => {
-                        skia.surface().canvas().save();
-                        skia.surface()
-                            .canvas()
-                            .set_matrix(TransformMatrix::from_slice([
-                                [1.0, 0.0, absolute.x.as_f32()],
-                                [0.0, 1.0, absolute.y.as_f32()],
-                            ]));
-                        draw_internal(skia, absolute.rendering_tree, rendering_tree_draw_context);
-                        skia.surface().canvas().restore();
+                        canvas.save();
+                        canvas.set_matrix(TransformMatrix::from_slice([
+                            [1.0, 0.0, absolute.x.as_f32()],
+                            [0.0, 1.0, absolute.y.as_f32()],
+                        ]));
+                        draw_internal(canvas, absolute.rendering_tree, rendering_tree_draw_context);
+                        canvas.restore();
                     }
                     SpecialRenderingNode::Rotate(rotate) => {
-                        skia.surface().canvas().save();
-                        skia.surface().canvas().rotate(rotate.angle);
-                        draw_internal(skia, rotate.rendering_tree, rendering_tree_draw_context);
-                        skia.surface().canvas().restore();
+                        canvas.save();
+                        canvas.rotate(rotate.angle);
+                        draw_internal(canvas, rotate.rendering_tree, rendering_tree_draw_context);
+                        canvas.restore();
                     }
                     SpecialRenderingNode::Scale(scale) => {
-                        skia.surface().canvas().save();
-                        skia.surface().canvas().scale(*scale.x, *scale.y);
-                        draw_internal(skia, scale.rendering_tree, rendering_tree_draw_context);
-                        skia.surface().canvas().restore();
+                        canvas.save();
+                        canvas.scale(*scale.x, *scale.y);
+                        draw_internal(canvas, scale.rendering_tree, rendering_tree_draw_context);
+                        canvas.restore();
                     }
                     SpecialRenderingNode::Transform(transform) => {
-                        skia.surface().canvas().save();
-                        skia.surface().canvas().transform(transform.matrix);
-                        draw_internal(skia, transform.rendering_tree, rendering_tree_draw_context);
-                        skia.surface().canvas().restore();
+                        canvas.save();
+                        canvas.transform(transform.matrix);
+                        draw_internal(
+                            canvas,
+                            transform.rendering_tree,
+                            rendering_tree_draw_context,
+                        );
+                        canvas.restore();
                     }
                     SpecialRenderingNode::OnTop(on_top) => {
-                        let matrix = skia.surface().canvas().get_matrix();
+                        let matrix = canvas.get_matrix();
                         rendering_tree_draw_context
                             .on_top_node_matrix_tuples
                             .push((*on_top, matrix));
                     }
                     SpecialRenderingNode::MouseCursor(_) => {
                         draw_internal(
-                            skia,
+                            canvas,
                             special.inner_rendering_tree_ref(),
                             rendering_tree_draw_context,
                         );
                     }
+                    SpecialRenderingNode::Cache(cache) => {
+                        cache::draw_cache_node(canvas, cache);
+                    }
+                    SpecialRenderingNode::Layer(layer) => {
+                        // NOTE: OnTop and ZLayer nodes inside are drawn later, out of the layer.
+                        canvas.save_layer(layer.layer);
+                        draw_internal(canvas, layer.rendering_tree, rendering_tree_draw_context);
+                        canvas.restore();
+                    }
+                    SpecialRenderingNode::ZLayer(_) => {
+                        // NOTE: Drawn later, as an entry of `z_layer::z_layer_entries`.
+                    }
                 },
                 RenderingTree::Empty => {}
             }
@@ -94,23 +113,33 @@ impl Draw for RenderingTree {
         let mut draw_context = RenderingTreeDrawContext {
             on_top_node_matrix_tuples: Vec::new(),
         };
-        draw_internal(skia, &self, &mut draw_context);
+        let z_layer_entries = z_layer::z_layer_entries(&self, canvas.get_matrix());
+        if let [entry] = z_layer_entries.as_slice() {
+            draw_internal(canvas, entry.rendering_tree, &mut draw_context);
+        } else {
+            for entry in z_layer_entries {
+                canvas.save();
+                canvas.set_matrix(entry.matrix);
+                draw_internal(canvas, entry.rendering_tree, &mut draw_context);
+                canvas.restore();
+            }
+        }
 
         for (node, matrix) in draw_context.on_top_node_matrix_tuples {
-            skia.surface().canvas().save();
-            skia.surface().canvas().set_matrix(matrix);
-            node.rendering_tree.draw(skia);
-            skia.surface().canvas().restore();
+            canvas.save();
+            canvas.set_matrix(matrix);
+            node.rendering_tree.draw(canvas);
+            canvas.restore();
         }
     }
 }
 
 impl Draw for &DrawCommand {
-    fn draw(self, skia: &mut NativeSkia) {
+    fn draw(self, canvas: &dyn SkCanvas) {
         match self {
-            DrawCommand::Path { command } => command.draw(skia),
-            DrawCommand::Text { command } => command.draw(skia),
-            DrawCommand::Image { command } => command.draw(skia),
+            DrawCommand::Path { command } => command.draw(canvas),
+            DrawCommand::Text { command } => command.draw(canvas),
+            DrawCommand::Image { command } => command.draw(canvas),
         }
     }
 }
@@ -121,8 +150,9 @@ pub fn draw_mouse_cursor(
     mouse_cursor: MouseCursor,
     sprite_set: &StandardCursorSpriteSet,
 ) {
-    skia.surface().canvas().save();
-    skia.surface().canvas().translate(mouse_xy.x, mouse_xy.y);
+    let canvas = skia.surface().canvas();
+    canvas.save();
+    canvas.translate(mouse_xy.x, mouse_xy.y);
 
     'draw: {
         match mouse_cursor {
@@ -167,13 +197,13 @@ pub fn draw_mouse_cursor(
                     paint: None,
                     sprite_colors_blend_mode: BlendMode::SrcOver,
                 }
-                .draw(skia);
+                .draw(canvas);
             }
             MouseCursor::Custom(rendering_tree) => {
-                rendering_tree.draw(skia);
+                rendering_tree.draw(canvas);
             }
         }
     }
 
-    skia.surface().canvas().restore();
+    canvas.restore();
 }
@@ -1,9 +1,7 @@
 use crate::*;
 
 impl Draw for &PathDrawCommand {
-    fn draw(self, skia: &mut NativeSkia) {
-        let canvas = skia.surface().canvas();
-
+    fn draw(self, canvas: &dyn SkCanvas) {
         let is_stroke = self.paint.paint_style == Some(PaintStyle::Stroke);
         if !is_stroke || self.paint.stroke_width <= 0.px() {
             canvas.draw_path(&self.path, &self.paint);
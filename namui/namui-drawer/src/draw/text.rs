@@ -1,7 +1,7 @@
 use crate::*;
 
 impl Draw for &TextDrawCommand {
-    fn draw(self, skia: &mut NativeSkia) {
+    fn draw(self, canvas: &dyn SkCanvas) {
         if self.text.is_empty() {
             return;
         }
@@ -52,19 +52,14 @@ impl Draw for &TextDrawCommand {
                     let bottom = y + get_bottom_of_baseline(self.baseline, font_metrics);
 
                     if let Some(underline_paint) = &self.underline {
-                        skia.surface().canvas().draw_line(
+                        canvas.draw_line(
                             Xy::new(x, bottom + 2.px()),
                             Xy::new(x + width, bottom + 2.px()),
                             underline_paint,
                         );
                     }
 
-                    skia.surface().canvas().draw_text_blob(
-                        glyph_ids,
-                        Xy::new(x, bottom),
-                        &font,
-                        &self.paint,
-                    );
+                    canvas.draw_text_blob(glyph_ids, Xy::new(x, bottom), &font, &self.paint);
 
                     x += width;
                 }
@@ -0,0 +1,67 @@
+use crate::*;
+
+pub(super) struct ZLayerEntry<'a> {
+    pub(super) matrix: TransformMatrix,
+    pub(super) rendering_tree: &'a RenderingTree,
+}
+
+/// Splits `rendering_tree` into the subtrees of each z-layer, in drawing order.
+/// The first subtree at layer 0 is `rendering_tree` itself.
+/// Z-layer nodes should be skipped while drawing an entry because they are entries on their own.
+pub(super) fn z_layer_entries(
+    rendering_tree: &RenderingTree,
+    matrix: TransformMatrix,
+) -> Vec<ZLayerEntry<'_>> {
+    let mut z_entries = vec![(
+        0,
+        ZLayerEntry {
+            matrix,
+            rendering_tree,
+        },
+    )];
+    collect(rendering_tree, matrix, &mut z_entries);
+
+    // NOTE: Stable sort keeps the traversal order in the same layer.
+    z_entries.sort_by_key(|(z, _)| *z);
+    z_entries.into_iter().map(|(_, entry)| entry).collect()
+}
+
+fn collect<'a>(
+    rendering_tree: &'a RenderingTree,
+    matrix: TransformMatrix,
+    z_entries: &mut Vec<(i32, ZLayerEntry<'a>)>,
+) {
+    match rendering_tree {
+        RenderingTree::Children(children) => {
+            for child in children.iter().rev() {
+                collect(child, matrix, z_entries);
+            }
+        }
+        RenderingTree::Special(special) => {
+            let matrix = match special {
+                SpecialRenderingNode::Translate(translate) => matrix * translate.get_matrix(),
+                SpecialRenderingNode::Absolute(absolute) => absolute.get_matrix(),
+                SpecialRenderingNode::Rotate(rotate) => matrix * rotate.get_matrix(),
+                SpecialRenderingNode::Scale(scale) => matrix * scale.get_matrix(),
+                SpecialRenderingNode::Transform(transform) => matrix * transform.matrix,
+                SpecialRenderingNode::ZLayer(z_layer) => {
+                    z_entries.push((
+                        z_layer.z,
+                        ZLayerEntry {
+                            matrix,
+                            rendering_tree: z_layer.rendering_tree,
+                        },
+                    ));
+                    matrix
+                }
+                // NOTE: Z-layers inside are drawn in their own pass, relative to the node.
+                SpecialRenderingNode::OnTop(_) | SpecialRenderingNode::Cache(_) => return,
+                SpecialRenderingNode::Clip(_)
+                | SpecialRenderingNode::MouseCursor(_)
+                | SpecialRenderingNode::Layer(_) => matrix,
+            };
+            collect(special.inner_rendering_tree_ref(), matrix, z_entries);
+        }
+        RenderingTree::Node(_) | RenderingTree::Empty => {}
+    }
+}
@@ -28,7 +28,9 @@ pub fn draw_rendering_tree(
     LAST_RENDERING_TREE.with(|cell| {
         *cell.borrow_mut() = Some(rendering_tree);
     });
-    redraw(skia, mouse_x, mouse_y, sprite_set)
+    let mouse_cursor = redraw(skia, mouse_x, mouse_y, sprite_set);
+    draw::retain_used_pictures();
+    mouse_cursor
 }
 
 /// Redraw the last rendering tree (for native targets).
@@ -47,7 +49,7 @@ pub fn redraw(
 
         let mouse_cursor = calculate_mouse_cursor(rendering_tree, mouse_xy);
 
-        (*rendering_tree).draw(skia);
+        (*rendering_tree).draw(skia.surface().canvas());
 
         if let Some(sprite_set) = sprite_set {
             draw::draw_mouse_cursor(skia, mouse_xy, mouse_cursor, sprite_set);
@@ -59,6 +59,8 @@ mod wasi_ffi {
         static RENDERING_TREE: RefCell<Option<RenderingTree>> = const { RefCell::new(None) };
     }
 
+    /// Returns 0 if the frame didn't follow the last decoded one. Call `_request_full_frame`
+    /// of the app then.
     #[unsafe(no_mangle)]
     #[allow(clippy::missing_safety_doc)]
     pub unsafe extern "C" fn _draw_rendering_tree(
@@ -66,16 +68,21 @@ mod wasi_ffi {
         rendering_tree_bytes_len: usize,
         mouse_x: usize,
         mouse_y: usize,
-    ) {
+    ) -> u8 {
         let slice = unsafe {
             std::slice::from_raw_parts(rendering_tree_bytes_ptr, rendering_tree_bytes_len)
         };
+        let mut is_decoded = true;
         swap_arena_slot(&RENDERING_TREE, || {
-            let (rendering_tree, _): (RenderingTree, usize) =
-                bincode::decode_from_slice(slice, bincode::config::standard()).unwrap();
-            rendering_tree
+            decode_rendering_tree(slice).unwrap_or_else(|error| {
+                eprintln!("[drawer] Failed to decode rendering tree: {error}");
+                is_decoded = false;
+                RenderingTree::Empty
+            })
         });
         unsafe { _redraw(mouse_x, mouse_y) };
+        retain_used_pictures();
+        is_decoded as u8
     }
 
     #[unsafe(no_mangle)]
@@ -93,7 +100,7 @@ mod wasi_ffi {
 
                 let mouse_cursor = calculate_mouse_cursor(rendering_tree, mouse_xy);
 
-                (*rendering_tree).draw(skia);
+                (*rendering_tree).draw(skia.surface().canvas());
 
                 draw_mouse_cursor(
                     skia,
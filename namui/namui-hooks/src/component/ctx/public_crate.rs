@@ -370,8 +370,10 @@ impl ComponentCtx<'_> {
                 // NOTE: This code could be problematic on multi-threaded environment.
                 let value = {
                     let frozen_atoms = self.world.frozen_atoms.borrow();
-                    if let Some(frozen_bytes) = frozen_atoms.get(atom_index) {
-                        State::deserialize(&mut frozen_bytes.as_slice()).unwrap()
+                    if let Some(frozen_bytes) = frozen_atoms.get(atom_index)
+                        && let Ok(value) = State::deserialize(&mut frozen_bytes.as_slice())
+                    {
+                        value
                     } else {
                         init()
                     }
@@ -419,4 +421,45 @@ impl ComponentCtx<'_> {
             .borrow_mut()
             .push(handle.abort_handle())
     }
+
+    pub(crate) fn resource<T, E, Fut>(
+        &self,
+        key: &str,
+        retry: ResourceRetry,
+        fetch: impl FnOnce() -> Fut,
+    ) -> Resource<T>
+    where
+        T: Send + Sync + 'static,
+        E: std::fmt::Display,
+        Fut: std::future::Future<Output = Result<T, E>> + Send + 'static,
+    {
+        let now = self.world.now();
+        let set_state_tx = self.world.set_state_tx;
+
+        let lookup = self.world.resources.borrow_mut().lookup(key, retry, now);
+        if let ResourceLookup::Fetch { generation } = lookup {
+            let future = fetch();
+            let key = key.to_string();
+            // Not `ComponentCtx::spawn`. The fetch belongs to the cache, not to this component.
+            tokio::spawn(async move {
+                let result = future
+                    .await
+                    .map(|value| std::sync::Arc::new(value) as ResourceValue)
+                    .map_err(|error| error.to_string());
+                let _ = set_state_tx.send(SetStateItem::ResourceFetched {
+                    key,
+                    generation,
+                    result,
+                });
+            });
+        }
+
+        let resource = self.world.resources.borrow().get(key, set_state_tx);
+        match &resource {
+            Resource::Pending => self.world.boundaries.report_pending(),
+            Resource::Error(error) => self.world.boundaries.report_error(error.clone()),
+            Resource::Ready(_) => {}
+        }
+        resource
+    }
 }
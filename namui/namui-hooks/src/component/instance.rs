@@ -65,10 +65,12 @@ impl Instance {
             let no_state = state_list.len() <= state_index;
 
             if no_state {
+                // A frozen state which doesn't fit the changed type starts over.
                 let state = if let Some(frozen_instance) = &self.frozen_instance
                     && let Some(bytes) = frozen_instance.state_list.get(state_index)
+                    && let Ok(state) = State::deserialize(&mut bytes.as_slice())
                 {
-                    State::deserialize(&mut bytes.as_slice()).unwrap()
+                    state
                 } else {
                     init()
                 };
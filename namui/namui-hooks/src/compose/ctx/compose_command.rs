@@ -9,6 +9,10 @@ pub enum ComposeCommand {
     Rotate { angle: Angle },
     Scale { scale_xy: Xy<f32> },
     MouseCursor { cursor: MouseCursor },
+    Layer { layer: Layer },
+    Cache { key: u64 },
+    ZLayer { z: i32 },
+    Provide { index: u32 },
 }
 
 pub(crate) struct ComposeCommandNode {
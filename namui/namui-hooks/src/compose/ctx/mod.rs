@@ -4,6 +4,7 @@ mod public;
 use super::RtContainer;
 use crate::*;
 pub(crate) use compose_command::*;
+pub(crate) use public::ProvidedContext;
 
 pub struct ComposeCtx<'a, 'rt> {
     world: &'a World,
@@ -32,6 +33,10 @@ impl<'a, 'rt> ComposeCtx<'a, 'rt> {
         }
     }
 
+    pub(crate) fn world(&self) -> &'a World {
+        self.world
+    }
+
     pub(crate) fn push_command(&self, command: ComposeCommand) -> ComposeCtx<'a, 'rt> {
         let full_stack = Some(self.world.push_compose_command(self.full_stack, command));
         ComposeCtx {
@@ -95,6 +100,27 @@ impl<'a, 'rt> ComposeCtx<'a, 'rt> {
                         rendering_tree: arena_alloc(rendering_tree),
                     }))
                 }
+                ComposeCommand::Cache { key } => {
+                    RenderingTree::Special(SpecialRenderingNode::Cache(CacheNode {
+                        key: *key,
+                        rendering_tree: arena_alloc(rendering_tree),
+                    }))
+                }
+                // Same shortcut as `namui_rendering_tree::layer`.
+                ComposeCommand::Layer { layer } if layer.is_noop() => rendering_tree,
+                ComposeCommand::Layer { layer } => {
+                    RenderingTree::Special(SpecialRenderingNode::Layer(LayerNode {
+                        layer: arena_alloc(layer.clone()),
+                        rendering_tree: arena_alloc(rendering_tree),
+                    }))
+                }
+                ComposeCommand::ZLayer { z } => {
+                    RenderingTree::Special(SpecialRenderingNode::ZLayer(ZLayerNode {
+                        z: *z,
+                        rendering_tree: arena_alloc(rendering_tree),
+                    }))
+                }
+                ComposeCommand::Provide { .. } => rendering_tree,
             };
             cursor = node.parent;
         }
@@ -147,3 +173,31 @@ impl<'a, 'rt> ComposeCtx<'a, 'rt> {
         self.collect_commands(self.parent_stack)
     }
 }
+
+fn commands_matrix(commands: &[ComposeCommand]) -> TransformMatrix {
+    let mut matrix = TransformMatrix::identity();
+    for command in commands {
+        match command {
+            ComposeCommand::Translate { xy } => {
+                matrix = matrix * TransformMatrix::from_translate(xy.x.as_f32(), xy.y.as_f32());
+            }
+            ComposeCommand::Absolute { xy } => {
+                matrix = TransformMatrix::from_translate(xy.x.as_f32(), xy.y.as_f32());
+            }
+            ComposeCommand::Rotate { angle } => {
+                matrix = matrix * TransformMatrix::from_rotate(*angle);
+            }
+            ComposeCommand::Scale { scale_xy } => {
+                matrix = matrix * TransformMatrix::from_scale(scale_xy.x, scale_xy.y);
+            }
+            ComposeCommand::Clip { .. }
+            | ComposeCommand::OnTop
+            | ComposeCommand::MouseCursor { .. }
+            | ComposeCommand::Layer { .. }
+            | ComposeCommand::Cache { .. }
+            | ComposeCommand::ZLayer { .. }
+            | ComposeCommand::Provide { .. } => {}
+        }
+    }
+    matrix
+}
@@ -0,0 +1,50 @@
+use super::*;
+use crate::*;
+use std::any::TypeId;
+
+pub(crate) struct ProvidedContext {
+    type_id: TypeId,
+    sig_id: SigId,
+    value: *const (),
+}
+
+impl<'a, 'rt> ComposeCtx<'a, 'rt> {
+    /// Provides `value` to everything composed under the returned ctx, until another `provide` of the same type.
+    /// Descendants get the same sig by [`ComposeCtx::context`], so their memos and effects follow the changes of it.
+    /// To provide a prop, provide `ctx.track_eq(&prop)`.
+    pub fn provide<T: 'static>(&self, value: Sig<'a, T>) -> Self {
+        let index = {
+            let mut provided_contexts = self.world.provided_contexts.borrow_mut();
+            provided_contexts.push(ProvidedContext {
+                type_id: TypeId::of::<T>(),
+                sig_id: value.id,
+                value: value.untracked() as *const T as *const (),
+            });
+            provided_contexts.len() as u32 - 1
+        };
+        self.push_command(ComposeCommand::Provide { index })
+    }
+
+    /// The value of the nearest ancestor which provides `T`.
+    pub fn context<T: 'static>(&self) -> Option<Sig<'a, T>> {
+        let arena = self.world.compose_command_arena.borrow();
+        let provided_contexts = self.world.provided_contexts.borrow();
+
+        let mut cursor = self.full_stack;
+        while let Some(index) = cursor {
+            let node = &arena[index as usize];
+            if let ComposeCommand::Provide { index } = node.command {
+                let context = &provided_contexts[index as usize];
+                if context.type_id == TypeId::of::<T>() {
+                    // SAFETY: Sigs point into the boxed values of the world, which stay in place
+                    // during the run. `provided_contexts` is cleared at the start of every run.
+                    let value = unsafe { &*(context.value as *const T) };
+                    return Some(Sig::new(value, context.sig_id, self.world));
+                }
+            }
+            cursor = node.parent;
+        }
+
+        None
+    }
+}
@@ -35,25 +35,22 @@ impl ComposeCtx<'_, '_> {
             }
 
             let original_xy = global_xy;
+            let mut is_clip_in = true;
             for command in &self.full_stack_commands() {
                 match command {
                     ComposeCommand::Translate { xy } => global_xy -= xy,
                     ComposeCommand::Absolute { xy } => global_xy = original_xy - xy,
                     ComposeCommand::Clip { path, clip_op } => {
-                        let path_xy_in = path.xy_in(global_xy);
-                        match clip_op {
-                            ClipOp::Intersect => {
-                                if !path_xy_in {
-                                    return false;
-                                }
-                            }
-                            ClipOp::Difference => {
-                                if path_xy_in {
-                                    return false;
-                                }
-                            }
+                        if is_clip_in {
+                            let path_xy_in = path.xy_in(global_xy);
+                            is_clip_in = match clip_op {
+                                ClipOp::Intersect => path_xy_in,
+                                ClipOp::Difference => !path_xy_in,
+                            };
                         }
                     }
+                    // NOTE: Z-layers are drawn out of the clips of their ancestors.
+                    ComposeCommand::ZLayer { .. } => is_clip_in = true,
                     ComposeCommand::Rotate { angle } => {
                         global_xy = TransformMatrix::from_rotate(-angle).transform_xy(global_xy);
                     }
@@ -61,11 +58,15 @@ impl ComposeCtx<'_, '_> {
                         global_xy = TransformMatrix::from_scale(1.0 / scale_xy.x, 1.0 / scale_xy.y)
                             .transform_xy(global_xy);
                     }
-                    ComposeCommand::OnTop | ComposeCommand::MouseCursor { .. } => {}
+                    ComposeCommand::OnTop
+                    | ComposeCommand::MouseCursor { .. }
+                    | ComposeCommand::Layer { .. }
+                    | ComposeCommand::Cache { .. }
+                    | ComposeCommand::Provide { .. } => {}
                 }
             }
 
-            true
+            is_clip_in
         };
 
         /*
@@ -82,6 +83,9 @@ impl ComposeCtx<'_, '_> {
         let to_parent_local_xy = |xy| apply_commands_to_xy(xy, &self.parent_stack_commands());
 
         let xy_in = |global_xy: Xy<Px>| -> bool {
+            if self.is_covered_by_higher_z_layer(global_xy) {
+                return false;
+            }
             let parent_local_xy = to_parent_local_xy(global_xy);
             self.rt_container.with(|rts| {
                 let Some(bounding_box) = rts.iter().bounding_box() else {
@@ -174,10 +178,51 @@ impl ComposeCtx<'_, '_> {
             RawEvent::TextInputSelectionChange { event } => {
                 on_event(Event::TextInputSelectionChange { event })
             }
+            RawEvent::FileDrop { event } => {
+                on_event(Event::FileDrop {
+                    event: FileDropEvent {
+                        local_xy: &move || to_local_xy(event.xy),
+                        is_local_xy_in: &move || is_global_xy_clip_in(event.xy) && xy_in(event.xy),
+                        global_xy: event.xy,
+                        file: &event.file,
+                        is_stop_event_propagation: &self.world.is_stop_event_propagation,
+                    },
+                });
+            }
         }
 
         self
     }
+
+    /// Whether a higher z-layer of the last frame covers `global_xy`.
+    /// Events are dispatched while rendering, so a lower layer can't wait for the higher ones.
+    /// Instead, the lower layer doesn't get the xy in.
+    fn is_covered_by_higher_z_layer(&self, global_xy: Xy<Px>) -> bool {
+        if !self.world.has_z_layer_areas() {
+            return false;
+        }
+        let stack_z = self
+            .full_stack_commands()
+            .iter()
+            .rev()
+            .find_map(|command| match command {
+                ComposeCommand::ZLayer { z } => Some(*z),
+                _ => None,
+            })
+            .unwrap_or_default();
+        // NOTE: With `z_layer` nodes in the added rendering trees, the highest of them counts,
+        // so the element isn't covered by itself.
+        let z = self.rt_container.with(|rts| {
+            let mut z = stack_z;
+            for rt in rts.iter() {
+                visit_z_layers(rt, TransformMatrix::identity(), &mut |z_layer, _| {
+                    z = z.max(z_layer.z);
+                });
+            }
+            z
+        });
+        self.world.is_covered_by_z_layer_above(z, global_xy)
+    }
 }
 
 fn apply_commands_to_xy(mut target_xy: Xy<Px>, commands: &[ComposeCommand]) -> Xy<Px> {
@@ -195,7 +240,11 @@ fn apply_commands_to_xy(mut target_xy: Xy<Px>, commands: &[ComposeCommand]) -> X
             }
             ComposeCommand::Clip { .. }
             | ComposeCommand::OnTop
-            | ComposeCommand::MouseCursor { .. } => {}
+            | ComposeCommand::MouseCursor { .. }
+            | ComposeCommand::Layer { .. }
+            | ComposeCommand::Cache { .. }
+            | ComposeCommand::ZLayer { .. }
+            | ComposeCommand::Provide { .. } => {}
         }
     }
 
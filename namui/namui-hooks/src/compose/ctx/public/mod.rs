@@ -1,22 +1,24 @@
+mod context;
 mod event;
 mod stack;
 
 use super::*;
 use crate::*;
+pub(crate) use context::ProvidedContext;
 use std::sync::atomic::Ordering;
 
 impl<'a, 'rt> ComposeCtx<'a, 'rt> {
-    pub fn compose(&self, compose: impl FnOnce(ComposeCtx)) -> &Self {
+    pub fn compose(&self, compose: impl FnOnce(ComposeCtx<'a, '_>)) -> &Self {
         self.compose_with_key(None, compose)
     }
-    pub fn compose_2(&self, title: &str, compose: impl FnOnce(ComposeCtx)) -> &Self {
+    pub fn compose_2(&self, title: &str, compose: impl FnOnce(ComposeCtx<'a, '_>)) -> &Self {
         let _ = title;
         self.compose_with_key(None, compose)
     }
     pub fn compose_with_key(
         &self,
         key: impl Into<AddKey>,
-        compose: impl FnOnce(ComposeCtx),
+        compose: impl FnOnce(ComposeCtx<'a, '_>),
     ) -> &Self {
         let rt_container = self.ghost_impl(key, compose);
         self.add_rt_container(rt_container);
@@ -26,14 +28,14 @@ impl<'a, 'rt> ComposeCtx<'a, 'rt> {
     pub fn ghost_compose(
         &self,
         key: impl Into<AddKey>,
-        compose: impl FnOnce(ComposeCtx),
+        compose: impl FnOnce(ComposeCtx<'a, '_>),
     ) -> RenderingTree {
         self.ghost_impl(key, compose).into_rendering_tree()
     }
     fn ghost_impl(
         &self,
         key: impl Into<AddKey>,
-        compose: impl FnOnce(ComposeCtx),
+        compose: impl FnOnce(ComposeCtx<'a, '_>),
     ) -> RtContainer<'a> {
         let child_key = match key.into() {
             AddKey::String(key) => ChildKey::string(key),
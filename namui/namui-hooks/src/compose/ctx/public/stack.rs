@@ -28,28 +28,32 @@ impl<'a, 'rt> ComposeCtx<'a, 'rt> {
     pub fn mouse_cursor(&self, cursor: MouseCursor) -> Self {
         self.push(ComposeCommand::MouseCursor { cursor })
     }
+    /// Composites everything composed under the returned ctx as one unit.
+    pub fn layer(&self, layer: Layer) -> Self {
+        self.push(ComposeCommand::Layer { layer })
+    }
+    /// Shorthand of `layer(Layer::new().set_opacity(opacity))`.
+    pub fn opacity(&self, opacity: f32) -> Self {
+        self.layer(Layer::new().set_opacity(opacity))
+    }
+
+    /// Lets the drawer reuse the recording of everything composed under the returned ctx
+    /// while `key` stays the same. Change `key` when the content changes.
+    pub fn cache(&self, key: impl std::hash::Hash) -> Self {
+        self.push(ComposeCommand::Cache {
+            key: cache_key(key),
+        })
+    }
+
+    /// Draws everything composed under the returned ctx at the layer `z`, in front of lower layers.
+    /// Layer 0 is where everything else is. Like `on_top`, clips of the ancestors are ignored.
+    /// Mouse events under a higher layer don't hit lower layers. The areas come from the last
+    /// frame, so hit-testing lags one frame behind a moving layer.
+    pub fn z_layer(&self, z: i32) -> Self {
+        self.push(ComposeCommand::ZLayer { z })
+    }
 
     pub fn accumulated_matrix(&self) -> TransformMatrix {
-        let mut matrix = TransformMatrix::identity();
-        for command in &self.full_stack_commands() {
-            match command {
-                ComposeCommand::Translate { xy } => {
-                    matrix = matrix * TransformMatrix::from_translate(xy.x.as_f32(), xy.y.as_f32());
-                }
-                ComposeCommand::Absolute { xy } => {
-                    matrix = TransformMatrix::from_translate(xy.x.as_f32(), xy.y.as_f32());
-                }
-                ComposeCommand::Rotate { angle } => {
-                    matrix = matrix * TransformMatrix::from_rotate(*angle);
-                }
-                ComposeCommand::Scale { scale_xy } => {
-                    matrix = matrix * TransformMatrix::from_scale(scale_xy.x, scale_xy.y);
-                }
-                ComposeCommand::Clip { .. }
-                | ComposeCommand::OnTop
-                | ComposeCommand::MouseCursor { .. } => {}
-            }
-        }
-        matrix
+        commands_matrix(&self.full_stack_commands())
     }
 }
@@ -4,6 +4,7 @@ mod compose;
 mod ids;
 mod render_child_key;
 mod render_ctx;
+mod resource;
 mod set_state;
 mod sig;
 mod value;
@@ -17,6 +18,7 @@ use namui_rendering_tree::*;
 use namui_type::*;
 pub use render_child_key::*;
 pub use render_ctx::*;
+pub use resource::*;
 pub use set_state::*;
 pub use sig::*;
 use value::*;
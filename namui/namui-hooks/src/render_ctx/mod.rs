@@ -93,6 +93,38 @@ impl RenderCtx<'_, '_> {
     pub fn is_sig_updated<T>(&self, sig: &Sig<'_, T>) -> bool {
         self.component_ctx.is_sig_updated(&sig.id)
     }
+    /// Loads `key` once for every component asking it, and caches the result until it is invalidated.
+    /// `fetch` is called only when a fetch starts. Failed fetches are retried by [`ResourceRetry::default`].
+    pub fn resource<T, E, Fut>(
+        &self,
+        key: impl AsRef<str>,
+        fetch: impl FnOnce() -> Fut,
+    ) -> Resource<T>
+    where
+        T: Send + Sync + 'static,
+        E: std::fmt::Display,
+        Fut: std::future::Future<Output = Result<T, E>> + Send + 'static,
+    {
+        self.component_ctx
+            .resource(key.as_ref(), ResourceRetry::default(), fetch)
+    }
+    /// `retry` applies when the resource is first asked.
+    pub fn resource_with_retry<T, E, Fut>(
+        &self,
+        key: impl AsRef<str>,
+        retry: ResourceRetry,
+        fetch: impl FnOnce() -> Fut,
+    ) -> Resource<T>
+    where
+        T: Send + Sync + 'static,
+        E: std::fmt::Display,
+        Fut: std::future::Future<Output = Result<T, E>> + Send + 'static,
+    {
+        self.component_ctx.resource(key.as_ref(), retry, fetch)
+    }
+    pub fn resources(&self) -> Resources {
+        Resources::new(self.world().set_state_tx)
+    }
 }
 
 pub(crate) fn run<'a>(
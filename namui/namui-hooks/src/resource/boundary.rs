@@ -0,0 +1,122 @@
+use super::*;
+use std::cell::RefCell;
+
+/// Open boundaries of the current render, innermost last.
+#[derive(Default)]
+pub(crate) struct Boundaries {
+    suspense: RefCell<Vec<usize>>,
+    error: RefCell<Vec<Vec<ResourceError>>>,
+}
+
+impl Boundaries {
+    pub(crate) fn report_pending(&self) {
+        if let Some(pending_count) = self.suspense.borrow_mut().last_mut() {
+            *pending_count += 1;
+        }
+    }
+
+    pub(crate) fn report_error(&self, error: ResourceError) {
+        if let Some(errors) = self.error.borrow_mut().last_mut() {
+            errors.push(error);
+        }
+    }
+
+    /// In case a render panicked inside a boundary.
+    pub(crate) fn clear(&mut self) {
+        self.suspense.get_mut().clear();
+        self.error.get_mut().clear();
+    }
+}
+
+/// Shows `fallback` instead of `children` while any resource in `children` is pending.
+///
+/// `children` keep rendering behind the fallback so their states and fetches stay alive,
+/// but they don't get events while the fallback is shown.
+pub struct Suspense<Fallback, Children>
+where
+    Fallback: FnOnce(ComposeCtx),
+    Children: FnOnce(ComposeCtx),
+{
+    pub fallback: Fallback,
+    pub children: Children,
+}
+
+impl<Fallback, Children> Component for Suspense<Fallback, Children>
+where
+    Fallback: FnOnce(ComposeCtx),
+    Children: FnOnce(ComposeCtx),
+{
+    fn render(self, ctx: &RenderCtx) {
+        let Self { fallback, children } = self;
+        let (showing_fallback, set_showing_fallback) = ctx.state(|| false);
+        let boundaries = &ctx.world().boundaries;
+
+        boundaries.suspense.borrow_mut().push(0);
+        let children = ghost_children(ctx, *showing_fallback, children);
+        let pending_count = boundaries.suspense.borrow_mut().pop().unwrap();
+
+        let show_fallback = pending_count > 0;
+        if show_fallback != *showing_fallback {
+            set_showing_fallback.set(show_fallback);
+        }
+
+        if show_fallback {
+            ctx.compose(fallback);
+        } else {
+            ctx.add(children);
+        }
+    }
+}
+
+/// Shows `fallback` instead of `children` when any resource in `children` failed.
+/// Call [`ResourceError::retry`] in the fallback to try again.
+pub struct ErrorBoundary<Fallback, Children>
+where
+    Fallback: FnOnce(ComposeCtx, &[ResourceError]),
+    Children: FnOnce(ComposeCtx),
+{
+    pub fallback: Fallback,
+    pub children: Children,
+}
+
+impl<Fallback, Children> Component for ErrorBoundary<Fallback, Children>
+where
+    Fallback: FnOnce(ComposeCtx, &[ResourceError]),
+    Children: FnOnce(ComposeCtx),
+{
+    fn render(self, ctx: &RenderCtx) {
+        let Self { fallback, children } = self;
+        let (showing_fallback, set_showing_fallback) = ctx.state(|| false);
+        let boundaries = &ctx.world().boundaries;
+
+        boundaries.error.borrow_mut().push(vec![]);
+        let children = ghost_children(ctx, *showing_fallback, children);
+        let errors = boundaries.error.borrow_mut().pop().unwrap();
+
+        let show_fallback = !errors.is_empty();
+        if show_fallback != *showing_fallback {
+            set_showing_fallback.set(show_fallback);
+        }
+
+        if show_fallback {
+            ctx.compose(|ctx| fallback(ctx, &errors));
+        } else {
+            ctx.add(children);
+        }
+    }
+}
+
+fn ghost_children(
+    ctx: &RenderCtx,
+    block_events: bool,
+    children: impl FnOnce(ComposeCtx),
+) -> RenderingTree {
+    if !block_events {
+        return ctx.ghost_compose(None, children);
+    }
+
+    let was_stopped = ctx.set_event_propagation(false);
+    let rendering_tree = ctx.ghost_compose(None, children);
+    ctx.set_event_propagation(!was_stopped);
+    rendering_tree
+}
@@ -0,0 +1,292 @@
+//! Async data keyed by string, shared by every component of the world.
+//!
+//! Components asking the same key while it is loading share one fetch.
+//! The result stays cached until it is invalidated, and a failed fetch is retried with backoff.
+//! [`Suspense`] and [`ErrorBoundary`] collect the pending and failed resources of their descendants.
+
+mod boundary;
+
+use crate::*;
+pub use boundary::*;
+use rustc_hash::FxHashMap;
+use std::{
+    any::Any,
+    fmt::{Debug, Display},
+    sync::{Arc, mpsc},
+};
+
+pub(crate) type ResourceValue = Arc<dyn Any + Send + Sync>;
+
+pub enum Resource<T> {
+    Pending,
+    Ready(Arc<T>),
+    Error(ResourceError),
+}
+
+impl<T> Resource<T> {
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            Resource::Ready(value) => Some(value.as_ref()),
+            _ => None,
+        }
+    }
+    pub fn error(&self) -> Option<&ResourceError> {
+        match self {
+            Resource::Error(error) => Some(error),
+            _ => None,
+        }
+    }
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Resource::Pending)
+    }
+}
+
+impl<T> Clone for Resource<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Resource::Pending => Resource::Pending,
+            Resource::Ready(value) => Resource::Ready(value.clone()),
+            Resource::Error(error) => Resource::Error(error.clone()),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Resource<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Resource::Pending => f.write_str("Pending"),
+            Resource::Ready(value) => f.debug_tuple("Ready").field(value).finish(),
+            Resource::Error(error) => f.debug_tuple("Error").field(error).finish(),
+        }
+    }
+}
+
+/// The last error of a resource which ran out of retries.
+#[derive(Clone)]
+pub struct ResourceError {
+    pub key: String,
+    pub message: String,
+    set_state_tx: &'static mpsc::Sender<SetStateItem>,
+}
+
+impl ResourceError {
+    /// Fetches again from the next render, with a fresh retry count.
+    pub fn retry(&self) {
+        Resources::new(self.set_state_tx).invalidate(&self.key);
+    }
+}
+
+impl Debug for ResourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceError")
+            .field("key", &self.key)
+            .field("message", &self.message)
+            .finish()
+    }
+}
+
+impl Display for ResourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// Retries with `base_delay * 2^n`, up to `max_delay`.
+/// The resource is [`Resource::Pending`] while retrying, and becomes [`Resource::Error`] after `max_retries`.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceRetry {
+    pub max_retries: usize,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for ResourceRetry {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs_f32(10.0),
+        }
+    }
+}
+
+impl ResourceRetry {
+    fn delay(&self, failures: usize) -> Duration {
+        let exponent = failures.saturating_sub(1).min(16) as u32;
+        (self.base_delay * 2_i32.pow(exponent)).min(self.max_delay)
+    }
+}
+
+/// Invalidates cached resources, from anywhere including async tasks.
+/// Resources which have a value keep showing it while refetching.
+#[derive(Clone, Copy)]
+pub struct Resources {
+    set_state_tx: &'static mpsc::Sender<SetStateItem>,
+}
+
+impl Resources {
+    pub(crate) fn new(set_state_tx: &'static mpsc::Sender<SetStateItem>) -> Self {
+        Self { set_state_tx }
+    }
+    pub fn invalidate(&self, key: impl AsRef<str>) {
+        self.send(key, false);
+    }
+    /// Invalidates every key starting with `prefix`, like `"episode/"`.
+    pub fn invalidate_prefix(&self, prefix: impl AsRef<str>) {
+        self.send(prefix, true);
+    }
+    fn send(&self, key: impl AsRef<str>, prefix: bool) {
+        self.set_state_tx
+            .send(SetStateItem::InvalidateResource {
+                key: key.as_ref().to_string(),
+                prefix,
+            })
+            .unwrap();
+    }
+}
+
+impl Debug for Resources {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Resources").finish()
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ResourceCache {
+    entries: FxHashMap<String, ResourceEntry>,
+}
+
+struct ResourceEntry {
+    value: Option<ResourceValue>,
+    error: Option<String>,
+    retry: ResourceRetry,
+    generation: u64,
+    fetching: bool,
+    stale: bool,
+    failures: usize,
+    retry_at: Option<Instant>,
+}
+
+/// What [`ComponentCtx::resource`] should do after looking up the cache.
+pub(crate) enum ResourceLookup {
+    Cached,
+    Fetch { generation: u64 },
+}
+
+impl ResourceCache {
+    pub(crate) fn lookup(
+        &mut self,
+        key: &str,
+        retry: ResourceRetry,
+        now: Instant,
+    ) -> ResourceLookup {
+        if !self.entries.contains_key(key) {
+            self.entries.insert(
+                key.to_string(),
+                ResourceEntry {
+                    value: None,
+                    error: None,
+                    retry,
+                    generation: 0,
+                    fetching: false,
+                    stale: true,
+                    failures: 0,
+                    retry_at: None,
+                },
+            );
+        }
+        let entry = self.entries.get_mut(key).unwrap();
+
+        let retry_due = entry.retry_at.is_some_and(|retry_at| retry_at <= now);
+        if entry.fetching || !(entry.stale || retry_due) {
+            return ResourceLookup::Cached;
+        }
+
+        entry.generation += 1;
+        entry.fetching = true;
+        entry.stale = false;
+        entry.retry_at = None;
+        ResourceLookup::Fetch {
+            generation: entry.generation,
+        }
+    }
+
+    pub(crate) fn get<T: Send + Sync + 'static>(
+        &self,
+        key: &str,
+        set_state_tx: &'static mpsc::Sender<SetStateItem>,
+    ) -> Resource<T> {
+        let entry = self.entries.get(key).unwrap();
+
+        if let Some(value) = &entry.value {
+            let Ok(value) = value.clone().downcast::<T>() else {
+                panic!(
+                    "resource `{key}` is used as {}, but cached with other type",
+                    std::any::type_name::<T>()
+                );
+            };
+            return Resource::Ready(value);
+        }
+
+        match &entry.error {
+            Some(message) if !entry.fetching && entry.retry_at.is_none() => {
+                Resource::Error(ResourceError {
+                    key: key.to_string(),
+                    message: message.clone(),
+                    set_state_tx,
+                })
+            }
+            _ => Resource::Pending,
+        }
+    }
+
+    pub(crate) fn on_fetched(
+        &mut self,
+        key: &str,
+        generation: u64,
+        result: Result<ResourceValue, String>,
+        now: Instant,
+    ) {
+        let Some(entry) = self.entries.get_mut(key) else {
+            return;
+        };
+        if entry.generation != generation {
+            return;
+        }
+
+        entry.fetching = false;
+        match result {
+            Ok(value) => {
+                entry.value = Some(value);
+                entry.error = None;
+                entry.failures = 0;
+            }
+            Err(message) => {
+                entry.error = Some(message);
+                entry.failures += 1;
+                if entry.failures <= entry.retry.max_retries {
+                    entry.retry_at = Some(now + entry.retry.delay(entry.failures));
+                }
+            }
+        }
+    }
+
+    pub(crate) fn invalidate(&mut self, key: &str, prefix: bool) {
+        for (entry_key, entry) in self.entries.iter_mut() {
+            let matched = if prefix {
+                entry_key.starts_with(key)
+            } else {
+                entry_key == key
+            };
+            if !matched {
+                continue;
+            }
+            entry.stale = true;
+            entry.failures = 0;
+            entry.retry_at = None;
+            if entry.value.is_none() {
+                entry.error = None;
+            }
+        }
+    }
+}
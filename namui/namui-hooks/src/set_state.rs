@@ -63,6 +63,15 @@ pub(crate) enum SetStateItem {
         sig_ids: (SigId, SigId, SigId, SigId),
         mutate: MutateFnOnce4,
     },
+    ResourceFetched {
+        key: String,
+        generation: u64,
+        result: Result<ResourceValue, String>,
+    },
+    InvalidateResource {
+        key: String,
+        prefix: bool,
+    },
 }
 pub(crate) type MutateFnOnce = Box<dyn FnOnce(&mut dyn Value) + Send>;
 pub(crate) type MutateFnOnce2 = Box<dyn FnOnce((&mut dyn Value, &mut dyn Value)) + Send>;
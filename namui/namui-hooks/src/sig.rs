@@ -36,6 +36,10 @@ impl<'a, T: ?Sized> Sig<'a, T> {
     pub fn is_updated(&self) -> bool {
         self.world.is_sig_updated(&self.id)
     }
+    /// The value without recording the sig as used.
+    pub(crate) fn untracked(&self) -> &'a T {
+        self.value
+    }
 }
 
 impl<T: ?Sized + Debug> Debug for Sig<'_, T> {
@@ -0,0 +1,130 @@
+use crate::*;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+#[derive(Debug, PartialEq, namui_type::State)]
+struct Theme {
+    size: usize,
+}
+
+struct Provider {
+    size: usize,
+    record: Arc<AtomicUsize>,
+    memo_call_count: Arc<AtomicUsize>,
+}
+
+impl Component for Provider {
+    fn render(self, ctx: &RenderCtx) {
+        let (theme, set_theme) = ctx.state(|| Theme { size: self.size });
+        if theme.size != self.size {
+            set_theme.set(Theme { size: self.size });
+        }
+
+        ctx.provide(theme).add(Middle {
+            record: self.record,
+            memo_call_count: self.memo_call_count,
+        });
+    }
+}
+
+struct Middle {
+    record: Arc<AtomicUsize>,
+    memo_call_count: Arc<AtomicUsize>,
+}
+
+impl Component for Middle {
+    fn render(self, ctx: &RenderCtx) {
+        ctx.translate((10.px(), 10.px())).add(Consumer {
+            record: self.record,
+            memo_call_count: self.memo_call_count,
+        });
+    }
+}
+
+struct Consumer {
+    record: Arc<AtomicUsize>,
+    memo_call_count: Arc<AtomicUsize>,
+}
+
+impl Component for Consumer {
+    fn render(self, ctx: &RenderCtx) {
+        let theme = ctx.context::<Theme>().unwrap();
+        let double = ctx.memo(|| {
+            self.memo_call_count.fetch_add(1, Ordering::Relaxed);
+            theme.size * 2
+        });
+        self.record.store(*double, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn context_should_follow_provided_sig() {
+    let mut world = World::init(Instant::now);
+    let record = Arc::new(AtomicUsize::new(0));
+    let memo_call_count = Arc::new(AtomicUsize::new(0));
+
+    let run = |world: &mut World, size| {
+        world.run(Provider {
+            size,
+            record: record.clone(),
+            memo_call_count: memo_call_count.clone(),
+        });
+    };
+
+    run(&mut world, 1);
+    assert_eq!(record.load(Ordering::Relaxed), 2);
+
+    run(&mut world, 1);
+    assert_eq!(memo_call_count.load(Ordering::Relaxed), 1);
+
+    run(&mut world, 3);
+    run(&mut world, 3);
+    assert_eq!(record.load(Ordering::Relaxed), 6);
+    assert_eq!(memo_call_count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn context_should_be_scoped_to_subtree() {
+    let mut world = World::init(Instant::now);
+    let records = [Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0))];
+    let found_outside = Arc::new(AtomicUsize::new(0));
+
+    world.run(|ctx: &RenderCtx| {
+        for (size, record) in [1, 5].into_iter().zip(records.iter()) {
+            ctx.add(Provider {
+                size,
+                record: record.clone(),
+                memo_call_count: Default::default(),
+            });
+        }
+        ctx.add(|ctx: &RenderCtx| {
+            if ctx.context::<Theme>().is_some() {
+                found_outside.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+    });
+
+    assert_eq!(records[0].load(Ordering::Relaxed), 2);
+    assert_eq!(records[1].load(Ordering::Relaxed), 10);
+    assert_eq!(found_outside.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn nearest_provider_should_win() {
+    let mut world = World::init(Instant::now);
+    let found = Arc::new(AtomicUsize::new(0));
+
+    world.run(|ctx: &RenderCtx| {
+        let (outer, _) = ctx.state(|| 1_usize);
+        let (inner, _) = ctx.state(|| 2_usize);
+        ctx.provide(outer).compose(|ctx| {
+            ctx.provide(inner).add(|ctx: &RenderCtx| {
+                found.store(*ctx.context::<usize>().unwrap(), Ordering::Relaxed);
+            });
+        });
+    });
+
+    assert_eq!(found.load(Ordering::Relaxed), 2);
+}
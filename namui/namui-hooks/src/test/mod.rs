@@ -1,6 +1,8 @@
+mod context;
 mod freeze;
 mod mouse_event;
 mod pass_sig;
+mod resource;
 
 use crate::*;
 use std::sync::Mutex;
@@ -172,3 +172,158 @@ fn event_local_xy_in_after_translate_at_out() {
     }
     assert_eq!(result, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
 }
+
+#[test]
+fn higher_z_layer_should_cover_lower_layer_from_mouse() {
+    let mut world = World::init(Instant::now);
+
+    #[derive(Debug)]
+    struct A {
+        record: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    const RECT_WH: Wh<Px> = Wh::new(px(100.0), px(100.0));
+
+    impl Component for A {
+        fn render(self, ctx: &RenderCtx) {
+            let rect_rt = RenderingTree::Node(DrawCommand::Path {
+                command: arena_alloc(PathDrawCommand {
+                    path: Path::new().add_rect(Rect::from_xy_wh(Xy::zero(), RECT_WH)),
+                    paint: Paint::new(Color::WHITE).set_style(PaintStyle::Fill),
+                }),
+            });
+
+            let record_hit = |name: &'static str| {
+                let record = self.record.clone();
+                move |event: Event| {
+                    let Event::MouseDown { event } = event else {
+                        return;
+                    };
+                    if event.is_local_xy_in() {
+                        record.lock().unwrap().push(name);
+                    }
+                }
+            };
+
+            // NOTE: Added first, so it would be in front without z-layers.
+            ctx.compose(|ctx| {
+                ctx.add(rect_rt).attach_event(record_hit("base"));
+            });
+            ctx.compose(|ctx| {
+                ctx.translate((50.px(), 50.px()))
+                    .z_layer(1)
+                    .add(rect_rt)
+                    .attach_event(record_hit("overlay"));
+            });
+        }
+    }
+
+    let record = Arc::new(Mutex::new(vec![]));
+
+    World::run(
+        &mut world,
+        A {
+            record: record.clone(),
+        },
+    );
+
+    for xy in [
+        Xy::new(25.px(), 25.px()),
+        Xy::new(75.px(), 75.px()),
+        Xy::new(125.px(), 125.px()),
+    ] {
+        World::run_with_event(
+            &mut world,
+            A {
+                record: record.clone(),
+            },
+            RawEvent::MouseDown {
+                event: RawMouseEvent {
+                    xy,
+                    pressing_buttons: Default::default(),
+                    button: Default::default(),
+                },
+            },
+        );
+    }
+
+    assert_eq!(*record.lock().unwrap(), vec!["base", "overlay", "overlay"]);
+}
+
+#[test]
+fn z_layer_node_in_transformed_rendering_tree_should_cover_lower_layer() {
+    let mut world = World::init(Instant::now);
+
+    #[derive(Debug)]
+    struct A {
+        record: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    const RECT_WH: Wh<Px> = Wh::new(px(100.0), px(100.0));
+
+    impl Component for A {
+        fn render(self, ctx: &RenderCtx) {
+            let rect_rt = RenderingTree::Node(DrawCommand::Path {
+                command: arena_alloc(PathDrawCommand {
+                    path: Path::new().add_rect(Rect::from_xy_wh(Xy::zero(), RECT_WH)),
+                    paint: Paint::new(Color::WHITE).set_style(PaintStyle::Fill),
+                }),
+            });
+
+            let record_hit = |name: &'static str| {
+                let record = self.record.clone();
+                move |event: Event| {
+                    let Event::MouseDown { event } = event else {
+                        return;
+                    };
+                    if event.is_local_xy_in() {
+                        record.lock().unwrap().push(name);
+                    }
+                }
+            };
+
+            // NOTE: Added first, so it would be in front without z-layers.
+            ctx.compose(|ctx| {
+                ctx.add(rect_rt).attach_event(record_hit("base"));
+            });
+            ctx.compose(|ctx| {
+                ctx.add(transform(
+                    TransformMatrix::from_translate(50.0, 50.0),
+                    z_layer(1, rect_rt),
+                ))
+                .attach_event(record_hit("overlay"));
+            });
+        }
+    }
+
+    let record = Arc::new(Mutex::new(vec![]));
+
+    World::run(
+        &mut world,
+        A {
+            record: record.clone(),
+        },
+    );
+
+    for xy in [
+        Xy::new(25.px(), 25.px()),
+        Xy::new(75.px(), 75.px()),
+        Xy::new(125.px(), 125.px()),
+    ] {
+        World::run_with_event(
+            &mut world,
+            A {
+                record: record.clone(),
+            },
+            RawEvent::MouseDown {
+                event: RawMouseEvent {
+                    xy,
+                    pressing_buttons: Default::default(),
+                    button: Default::default(),
+                },
+            },
+        );
+    }
+
+    assert_eq!(*record.lock().unwrap(), vec!["base", "overlay", "overlay"]);
+}
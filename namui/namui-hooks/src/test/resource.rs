@@ -0,0 +1,143 @@
+use crate::*;
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicUsize, Ordering},
+};
+
+/// Runs the world until `done`, giving the spawned fetches time to finish.
+fn run_until(world: &mut World, component: impl Fn(&RenderCtx), done: impl Fn() -> bool) {
+    for _ in 0..1000 {
+        world.run(|ctx: &RenderCtx| component(ctx));
+        if done() {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+    panic!("timed out");
+}
+
+#[test]
+fn resource_should_be_fetched_once_for_every_component() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _guard = runtime.enter();
+    let mut world = World::init(Instant::now);
+
+    let fetch_count = Arc::new(AtomicUsize::new(0));
+    let ready_count = Arc::new(AtomicUsize::new(0));
+
+    let component = |ctx: &RenderCtx| {
+        ready_count.store(0, Ordering::Relaxed);
+        for _ in 0..2 {
+            ctx.add(|ctx: &RenderCtx| {
+                let resource = ctx.resource("answer", || {
+                    fetch_count.fetch_add(1, Ordering::Relaxed);
+                    async { Ok::<_, String>(42) }
+                });
+                if resource.ready() == Some(&42) {
+                    ready_count.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    };
+
+    run_until(&mut world, component, || {
+        ready_count.load(Ordering::Relaxed) == 2
+    });
+    assert_eq!(fetch_count.load(Ordering::Relaxed), 1);
+
+    world.run(|ctx: &RenderCtx| ctx.resources().invalidate("answer"));
+    world.run(|ctx: &RenderCtx| component(ctx));
+    assert_eq!(fetch_count.load(Ordering::Relaxed), 2);
+    // Keeps the last value while refetching.
+    assert_eq!(ready_count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn suspense_should_show_fallback_while_pending() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _guard = runtime.enter();
+    let mut world = World::init(Instant::now);
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<usize>();
+    let rx = Mutex::new(Some(rx));
+    let fallback_count = Arc::new(AtomicUsize::new(0));
+    let value = Arc::new(AtomicUsize::new(0));
+
+    let component = |ctx: &RenderCtx| {
+        ctx.add(Suspense {
+            fallback: |_ctx: ComposeCtx| {
+                fallback_count.fetch_add(1, Ordering::Relaxed);
+            },
+            children: |ctx: ComposeCtx| {
+                ctx.add(|ctx: &RenderCtx| {
+                    let resource = ctx.resource("gated", || {
+                        let rx = rx.lock().unwrap().take().unwrap();
+                        async move { rx.await.map_err(|error| error.to_string()) }
+                    });
+                    if let Some(ready) = resource.ready() {
+                        value.store(*ready, Ordering::Relaxed);
+                    }
+                });
+            },
+        });
+    };
+
+    world.run(|ctx: &RenderCtx| component(ctx));
+    world.run(|ctx: &RenderCtx| component(ctx));
+    assert_eq!(fallback_count.load(Ordering::Relaxed), 2);
+
+    tx.send(7).unwrap();
+    run_until(&mut world, component, || value.load(Ordering::Relaxed) == 7);
+    let fallback_count_when_ready = fallback_count.load(Ordering::Relaxed);
+
+    world.run(|ctx: &RenderCtx| component(ctx));
+    assert_eq!(
+        fallback_count.load(Ordering::Relaxed),
+        fallback_count_when_ready
+    );
+}
+
+#[test]
+fn error_boundary_should_show_fallback_after_retries() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _guard = runtime.enter();
+    let mut world = World::init(Instant::now);
+
+    let fetch_count = Arc::new(AtomicUsize::new(0));
+    let errors = Arc::new(Mutex::new(Vec::<ResourceError>::new()));
+
+    let component = |ctx: &RenderCtx| {
+        ctx.add(ErrorBoundary {
+            fallback: |_ctx: ComposeCtx, resource_errors: &[ResourceError]| {
+                *errors.lock().unwrap() = resource_errors.to_vec();
+            },
+            children: |ctx: ComposeCtx| {
+                ctx.add(|ctx: &RenderCtx| {
+                    ctx.resource_with_retry::<usize, _, _>(
+                        "broken",
+                        ResourceRetry {
+                            max_retries: 1,
+                            base_delay: Duration::ZERO,
+                            max_delay: Duration::ZERO,
+                        },
+                        || {
+                            fetch_count.fetch_add(1, Ordering::Relaxed);
+                            async { Err("boom") }
+                        },
+                    );
+                });
+            },
+        });
+    };
+
+    run_until(&mut world, component, || !errors.lock().unwrap().is_empty());
+    assert_eq!(fetch_count.load(Ordering::Relaxed), 2);
+
+    let error = errors.lock().unwrap().pop().unwrap();
+    assert_eq!(error.key, "broken");
+    assert_eq!(error.message, "boom");
+
+    error.retry();
+    world.run(|ctx: &RenderCtx| component(ctx));
+    assert_eq!(fetch_count.load(Ordering::Relaxed), 3);
+}
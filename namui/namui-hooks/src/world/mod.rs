@@ -28,6 +28,17 @@ pub struct World {
     rendered_composer_count: Cell<usize>,
     pub(crate) compose_command_arena: RefCell<Vec<ComposeCommandNode>>,
     rt_vec_pool: RefCell<Vec<Vec<RenderingTree>>>,
+    /// Global areas of z-layers in the last run, used for the events of this run.
+    /// Of the last frame. See `z_layer_areas`.
+    z_layer_areas: Vec<ZLayerArea>,
+    pub(crate) resources: RefCell<ResourceCache>,
+    pub(crate) boundaries: Boundaries,
+    pub(crate) provided_contexts: RefCell<Vec<ProvidedContext>>,
+}
+
+struct ZLayerArea {
+    z: i32,
+    rect: Rect<Px>,
 }
 
 impl World {
@@ -257,6 +268,19 @@ impl World {
                         _ => todo!(),
                     }
                 }
+                SetStateItem::ResourceFetched {
+                    key,
+                    generation,
+                    result,
+                } => {
+                    let now = self.now();
+                    self.resources
+                        .get_mut()
+                        .on_fetched(&key, generation, result, now);
+                }
+                SetStateItem::InvalidateResource { key, prefix } => {
+                    self.resources.get_mut().invalidate(&key, prefix);
+                }
             }
         }
     }
@@ -365,6 +389,16 @@ impl World {
         index
     }
 
+    pub(crate) fn has_z_layer_areas(&self) -> bool {
+        !self.z_layer_areas.is_empty()
+    }
+
+    pub(crate) fn is_covered_by_z_layer_above(&self, z: i32, global_xy: Xy<Px>) -> bool {
+        self.z_layer_areas
+            .iter()
+            .any(|area| area.z > z && area.rect.is_xy_inside(global_xy))
+    }
+
     pub(crate) fn record_used_sig(&self, id: SigId) {
         self.record_used_sig_ids.borrow_mut().push(id);
     }
@@ -394,6 +428,8 @@ impl World {
         self.rendered_instance_count.set(0);
         self.rendered_composer_count.set(0);
         self.compose_command_arena.get_mut().clear();
+        self.provided_contexts.get_mut().clear();
+        self.boundaries.clear();
         reset_render_arena();
         let _arena_scope = enter_arena_scope();
         self.reset_updated_sig_ids();
@@ -427,6 +463,10 @@ impl World {
 
         self.remove_unused_guys();
         self.record_used_sig_ids.get_mut().clear();
+        self.z_layer_areas = z_layer_areas(&rendering_tree)
+            .into_iter()
+            .map(|(z, rect)| ZLayerArea { z, rect })
+            .collect();
 
         rendering_tree
     }
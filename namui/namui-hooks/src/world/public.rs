@@ -24,6 +24,10 @@ impl World {
             rendered_composer_count: Default::default(),
             compose_command_arena: Default::default(),
             rt_vec_pool: Default::default(),
+            z_layer_areas: Default::default(),
+            resources: Default::default(),
+            boundaries: Default::default(),
+            provided_contexts: Default::default(),
         }
     }
 
@@ -83,7 +83,7 @@ impl syn::parse::Parse for TypeDerives {
     }
 }
 
-#[proc_macro_derive(State)]
+#[proc_macro_derive(State, attributes(our_serde))]
 pub fn derive_state(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
 
@@ -142,8 +142,12 @@ pub fn derive_state(input: TokenStream) -> TokenStream {
     };
 
     let deserialize_without_name_impl = match &input.data {
-        syn::Data::Struct(data) => our_serde_impl::generate_struct_deserialize_without_name(data),
-        syn::Data::Enum(data) => our_serde_impl::generate_enum_deserialize_without_name(data),
+        syn::Data::Struct(data) => {
+            our_serde_impl::generate_struct_deserialize_without_name(data, &input.attrs)
+        }
+        syn::Data::Enum(data) => {
+            our_serde_impl::generate_enum_deserialize_without_name(data, &input.attrs)
+        }
         syn::Data::Union(_) => {
             return syn::Error::new_spanned(input, "State cannot be derived for unions")
                 .to_compile_error()
@@ -191,7 +195,7 @@ pub fn derive_state(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
-#[proc_macro_derive(OurSerde)]
+#[proc_macro_derive(OurSerde, attributes(our_serde))]
 pub fn derive_our_serde(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
 
@@ -230,8 +234,12 @@ pub fn derive_our_serde(input: TokenStream) -> TokenStream {
     };
 
     let deserialize_without_name_impl = match &input.data {
-        syn::Data::Struct(data) => our_serde_impl::generate_struct_deserialize_without_name(data),
-        syn::Data::Enum(data) => our_serde_impl::generate_enum_deserialize_without_name(data),
+        syn::Data::Struct(data) => {
+            our_serde_impl::generate_struct_deserialize_without_name(data, &input.attrs)
+        }
+        syn::Data::Enum(data) => {
+            our_serde_impl::generate_enum_deserialize_without_name(data, &input.attrs)
+        }
         syn::Data::Union(_) => {
             return syn::Error::new_spanned(input, "OurSerde cannot be derived for unions")
                 .to_compile_error()
@@ -262,7 +270,7 @@ pub fn derive_our_serde(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
-#[proc_macro_derive(OurSer)]
+#[proc_macro_derive(OurSer, attributes(our_serde))]
 pub fn derive_our_ser(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
 
@@ -304,7 +312,7 @@ pub fn derive_our_ser(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
-#[proc_macro_derive(OurDe)]
+#[proc_macro_derive(OurDe, attributes(our_serde))]
 pub fn derive_our_de(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
 
@@ -323,8 +331,12 @@ pub fn derive_our_de(input: TokenStream) -> TokenStream {
     };
 
     let deserialize_without_name_impl = match &input.data {
-        syn::Data::Struct(data) => our_serde_impl::generate_struct_deserialize_without_name(data),
-        syn::Data::Enum(data) => our_serde_impl::generate_enum_deserialize_without_name(data),
+        syn::Data::Struct(data) => {
+            our_serde_impl::generate_struct_deserialize_without_name(data, &input.attrs)
+        }
+        syn::Data::Enum(data) => {
+            our_serde_impl::generate_enum_deserialize_without_name(data, &input.attrs)
+        }
         syn::Data::Union(_) => {
             return syn::Error::new_spanned(input, "OurDe cannot be derived for unions")
                 .to_compile_error()
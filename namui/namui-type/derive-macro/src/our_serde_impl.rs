@@ -1,5 +1,209 @@
 use quote::{format_ident, quote};
 
+/// `#[our_serde(...)]` on a named field or an enum variant.
+/// Tuple fields are read in order without keys, so they take no attribute.
+///
+/// - `tag = "key"`: Key written in the data instead of the name.
+///   Keep the tag when renaming the field. Change it when the type of the field changes.
+/// - `rename_from = "old"`: Also read the data written under `old`. Can be repeated.
+/// - `default`, `default = "path::to::fn"`: Value when the field is missing in the data,
+///   or fails to read. Only for fields.
+#[derive(Default)]
+struct Attrs {
+    tag: Option<String>,
+    rename_from: Vec<String>,
+    default: Option<FieldDefault>,
+}
+
+enum FieldDefault {
+    Default,
+    Path(syn::ExprPath),
+}
+
+impl Attrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+        for attr in attrs {
+            if !attr.path().is_ident("our_serde") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    result.tag = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path.is_ident("rename_from") {
+                    result
+                        .rename_from
+                        .push(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path.is_ident("default") {
+                    result.default = Some(if meta.input.peek(syn::Token![=]) {
+                        FieldDefault::Path(meta.value()?.parse::<syn::LitStr>()?.parse()?)
+                    } else {
+                        FieldDefault::Default
+                    });
+                } else {
+                    return Err(meta.error("expected `tag`, `rename_from` or `default`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(result)
+    }
+
+    fn key(&self, ident: &syn::Ident) -> String {
+        self.tag.clone().unwrap_or_else(|| ident.to_string())
+    }
+
+    /// Keys accepted on read, the current key first.
+    fn read_keys(&self, ident: &syn::Ident) -> Vec<String> {
+        std::iter::once(self.key(ident))
+            .chain(self.rename_from.iter().cloned())
+            .collect()
+    }
+}
+
+/// `#[our_serde(removed = "key")]` on the type.
+/// Keys of removed fields, which no field may use again with a different meaning.
+/// Their data is skipped like any unknown field.
+fn parse_removed_keys(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::LitStr>> {
+    let mut removed = vec![];
+    for attr in attrs {
+        if !attr.path().is_ident("our_serde") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("removed") {
+                removed.push(meta.value()?.parse::<syn::LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `removed`"))
+            }
+        })?;
+    }
+    Ok(removed)
+}
+
+fn check_removed_keys(removed: &[syn::LitStr], fields: &syn::FieldsNamed) -> syn::Result<()> {
+    for field in &fields.named {
+        let attrs = Attrs::parse(&field.attrs)?;
+        let ident = field.ident.as_ref().unwrap();
+        if let Some(key) = removed
+            .iter()
+            .find(|removed| attrs.read_keys(ident).contains(&removed.value()))
+        {
+            return Err(syn::Error::new_spanned(
+                key,
+                format!("`{}` is removed, give `{ident}` another tag", key.value()),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn reject_unnamed_field_attrs(fields: &syn::FieldsUnnamed) -> syn::Result<()> {
+    match fields
+        .unnamed
+        .iter()
+        .flat_map(|field| &field.attrs)
+        .find(|attr| attr.path().is_ident("our_serde"))
+    {
+        Some(attr) => Err(syn::Error::new_spanned(
+            attr,
+            "`#[our_serde]` is only for named fields, tuple fields are read in order",
+        )),
+        None => Ok(()),
+    }
+}
+
+fn serialize_named_fields(
+    fields: &syn::FieldsNamed,
+    access: impl Fn(&syn::Ident) -> proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let field_count = fields.named.len() as u16;
+    let serialize_fields = fields
+        .named
+        .iter()
+        .map(|f| {
+            let field_name = f.ident.as_ref().unwrap();
+            let key = Attrs::parse(&f.attrs)?.key(field_name);
+            let value = access(field_name);
+            Ok(quote! {
+                buf.write_field(#key, #value);
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        buf.write_field_count(#field_count);
+        #(#serialize_fields)*
+    })
+}
+
+/// Declares a local for each field, with the name of the field.
+fn deserialize_named_fields(fields: &syn::FieldsNamed) -> syn::Result<proc_macro2::TokenStream> {
+    let mut declares = vec![];
+    let mut match_arms = vec![];
+    let mut unwraps = vec![];
+
+    for f in &fields.named {
+        let field_name = f.ident.as_ref().unwrap();
+        let field_name_str = field_name.to_string();
+        let read_value = format_ident!("__field_{}", field_name);
+        let ty = &f.ty;
+        let attrs = Attrs::parse(&f.attrs)?;
+        let read_keys = attrs.read_keys(field_name);
+
+        declares.push(quote! {
+            let mut #read_value: Option<#ty> = None;
+        });
+
+        match &attrs.default {
+            Some(default) => {
+                let default = match default {
+                    FieldDefault::Default => quote! { Default::default() },
+                    FieldDefault::Path(path) => quote! { #path() },
+                };
+                match_arms.push(quote! {
+                    #(#read_keys)|* => {
+                        #read_value = <#ty as Deserialize>::deserialize_field(&__value).ok();
+                    }
+                });
+                unwraps.push(quote! {
+                    let #field_name = match #read_value {
+                        Some(value) => value,
+                        None => #default,
+                    };
+                });
+            }
+            None => {
+                match_arms.push(quote! {
+                    #(#read_keys)|* => {
+                        #read_value = Some(<#ty as Deserialize>::deserialize_field(&__value)?);
+                    }
+                });
+                unwraps.push(quote! {
+                    let Some(#field_name) = #read_value else {
+                        return Err(DeserializeError::MissingField {
+                            name: std::any::type_name::<Self>().to_string(),
+                            field: #field_name_str.to_string(),
+                        });
+                    };
+                });
+            }
+        }
+    }
+
+    Ok(quote! {
+        #(#declares)*
+        for (__key, __value) in buf.read_fields()? {
+            match __key.as_str() {
+                #(#match_arms)*
+                _ => {}
+            }
+        }
+        #(#unwraps)*
+    })
+}
+
 pub fn generate_struct_serialize(_data: &syn::DataStruct) -> proc_macro2::TokenStream {
     quote! {
         buf.write_string(std::any::type_name::<Self>());
@@ -9,18 +213,12 @@ pub fn generate_struct_serialize(_data: &syn::DataStruct) -> proc_macro2::TokenS
 
 pub fn generate_struct_serialize_without_name(data: &syn::DataStruct) -> proc_macro2::TokenStream {
     let serialize_fields = match &data.fields {
-        syn::Fields::Named(fields) => fields
-            .named
-            .iter()
-            .map(|f| {
-                let field_name = &f.ident;
-                let field_name_str = field_name.as_ref().unwrap().to_string();
-                quote! {
-                    buf.write_string(#field_name_str);
-                    self.#field_name.serialize_without_name(buf);
-                }
-            })
-            .collect::<Vec<_>>(),
+        syn::Fields::Named(fields) => {
+            match serialize_named_fields(fields, |field_name| quote! { &self.#field_name }) {
+                Ok(serialize_fields) => vec![serialize_fields],
+                Err(err) => return err.to_compile_error(),
+            }
+        }
         syn::Fields::Unnamed(fields) => fields
             .unnamed
             .iter()
@@ -49,29 +247,32 @@ pub fn generate_struct_deserialize(_data: &syn::DataStruct) -> proc_macro2::Toke
 
 pub fn generate_struct_deserialize_without_name(
     data: &syn::DataStruct,
+    attrs: &[syn::Attribute],
 ) -> proc_macro2::TokenStream {
     let output = match &data.fields {
         syn::Fields::Named(fields) => {
-            let deserialize_fields = fields.named.iter().map(|f| {
-                let field_name = &f.ident;
-                let field_name_str = field_name.as_ref().unwrap().to_string();
-                quote! {
-                    let field_name = buf.read_name(#field_name_str)?;
-                    let #field_name = Deserialize::deserialize_without_name(buf)?;
-                }
-            });
+            let deserialize_fields = match parse_removed_keys(attrs)
+                .and_then(|removed| check_removed_keys(&removed, fields))
+                .and_then(|()| deserialize_named_fields(fields))
+            {
+                Ok(deserialize_fields) => deserialize_fields,
+                Err(err) => return err.to_compile_error(),
+            };
             let field_names = fields
                 .named
                 .iter()
                 .map(|f| f.ident.as_ref().unwrap().clone());
             quote! {
-                #(#deserialize_fields)*
+                #deserialize_fields
                 Ok(Self {
                     #(#field_names),*
                 })
             }
         }
         syn::Fields::Unnamed(fields) => {
+            if let Err(err) = reject_unnamed_field_attrs(fields) {
+                return err.to_compile_error();
+            }
             let deserialize_fields = fields
                 .unnamed
                 .iter()
@@ -111,58 +312,60 @@ pub fn generate_enum_serialize(_data: &syn::DataEnum) -> proc_macro2::TokenStrea
 }
 
 pub fn generate_enum_serialize_without_name(data: &syn::DataEnum) -> proc_macro2::TokenStream {
-    let variants = data.variants.iter().map(|variant| {
-        let variant_name = &variant.ident;
-        let variant_name_str = variant_name.to_string();
-
-        match &variant.fields {
-            syn::Fields::Named(fields) => {
-                let field_names = fields
-                    .named
-                    .iter()
-                    .map(|f| f.ident.as_ref().unwrap().clone());
-                let serialize_fields = fields.named.iter().map(|f| {
-                    let field_name = &f.ident;
-                    let field_name_str = field_name.as_ref().unwrap().to_string();
-                    quote! {
-                        buf.write_string(#field_name_str);
-                        #field_name.serialize_without_name(buf);
-                    }
-                });
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_name = &variant.ident;
+            let variant_name_str = Attrs::parse(&variant.attrs)?.key(variant_name);
 
-                quote! {
-                    Self::#variant_name { #(#field_names),* } => {
-                        buf.write_string(#variant_name_str);
-                        #(#serialize_fields)*
+            Ok(match &variant.fields {
+                syn::Fields::Named(fields) => {
+                    let field_names = fields
+                        .named
+                        .iter()
+                        .map(|f| f.ident.as_ref().unwrap().clone());
+                    let serialize_fields =
+                        serialize_named_fields(fields, |field_name| quote! { #field_name })?;
+
+                    quote! {
+                        Self::#variant_name { #(#field_names),* } => {
+                            buf.write_string(#variant_name_str);
+                            #serialize_fields
+                        }
                     }
                 }
-            }
-            syn::Fields::Unnamed(fields) => {
-                let field_names: Vec<_> = (0..fields.unnamed.len())
-                    .map(|i| format_ident!("field{}", i))
-                    .collect();
-                let serialize_fields = field_names
-                    .iter()
-                    .map(|field_name| {
-                        quote! {
-                            #field_name.serialize_without_name(buf);
+                syn::Fields::Unnamed(fields) => {
+                    let field_names: Vec<_> = (0..fields.unnamed.len())
+                        .map(|i| format_ident!("field{}", i))
+                        .collect();
+                    let serialize_fields = field_names
+                        .iter()
+                        .map(|field_name| {
+                            quote! {
+                                #field_name.serialize_without_name(buf);
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    quote! {
+                        Self::#variant_name ( #(#field_names),* ) => {
+                            buf.write_string(#variant_name_str);
+                            #(#serialize_fields)*
                         }
-                    })
-                    .collect::<Vec<_>>();
-                quote! {
-                    Self::#variant_name ( #(#field_names),* ) => {
-                        buf.write_string(#variant_name_str);
-                        #(#serialize_fields)*
                     }
                 }
-            }
-            syn::Fields::Unit => quote! {
-                Self::#variant_name => {
-                    buf.write_string(#variant_name_str);
-                }
-            },
-        }
-    });
+                syn::Fields::Unit => quote! {
+                    Self::#variant_name => {
+                        buf.write_string(#variant_name_str);
+                    }
+                },
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>();
+    let variants = match variants {
+        Ok(variants) => variants,
+        Err(err) => return err.to_compile_error(),
+    };
 
     quote! {
         match self {
@@ -178,62 +381,72 @@ pub fn generate_enum_deserialize(_data: &syn::DataEnum) -> proc_macro2::TokenStr
     }
 }
 
-pub fn generate_enum_deserialize_without_name(data: &syn::DataEnum) -> proc_macro2::TokenStream {
-    let variants = data.variants.iter().map(|variant| {
-        let variant_name = &variant.ident;
-        let variant_name_str = variant_name.to_string();
+pub fn generate_enum_deserialize_without_name(
+    data: &syn::DataEnum,
+    attrs: &[syn::Attribute],
+) -> proc_macro2::TokenStream {
+    let removed = match parse_removed_keys(attrs) {
+        Ok(removed) => removed,
+        Err(err) => return err.to_compile_error(),
+    };
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_name = &variant.ident;
+            let variant_names = Attrs::parse(&variant.attrs)?.read_keys(variant_name);
 
-        match &variant.fields {
-            syn::Fields::Named(fields) => {
-                let deserialize_fields = fields.named.iter().map(|f| {
-                    let field_name = &f.ident;
-                    let field_name_str = field_name.as_ref().unwrap().to_string();
+            Ok(match &variant.fields {
+                syn::Fields::Named(fields) => {
+                    check_removed_keys(&removed, fields)?;
+                    let deserialize_fields = deserialize_named_fields(fields)?;
+                    let field_names: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
                     quote! {
-                        let field_name = buf.read_name(#field_name_str)?;
-                        let #field_name = Deserialize::deserialize_without_name(buf)?;
-                    }
-                });
-                let field_names: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
-                quote! {
-                    #variant_name_str => {
-                        #(#deserialize_fields)*
-                        Ok(Self::#variant_name {
-                            #(#field_names),*
-                        })
+                        #(#variant_names)|* => {
+                            #deserialize_fields
+                            Ok(Self::#variant_name {
+                                #(#field_names),*
+                            })
+                        }
                     }
                 }
-            }
-            syn::Fields::Unnamed(fields) => {
-                let deserialize_fields = fields.unnamed.iter().enumerate().map(|(i, _)| {
-                    let field_name = format_ident!("field{}", i);
+                syn::Fields::Unnamed(fields) => {
+                    reject_unnamed_field_attrs(fields)?;
+                    let deserialize_fields = fields.unnamed.iter().enumerate().map(|(i, _)| {
+                        let field_name = format_ident!("field{}", i);
+                        quote! {
+                            let #field_name = {
+                                Deserialize::deserialize_without_name(buf)?
+                            };
+                        }
+                    });
+                    let field_names: Vec<_> = (0..fields.unnamed.len())
+                        .map(|i| format_ident!("field{}", i))
+                        .collect();
                     quote! {
-                        let #field_name = {
-                            Deserialize::deserialize_without_name(buf)?
-                        };
-                    }
-                });
-                let field_names: Vec<_> = (0..fields.unnamed.len())
-                    .map(|i| format_ident!("field{}", i))
-                    .collect();
-                quote! {
-                    #variant_name_str => {
-                        #(#deserialize_fields)*
-                        Ok(Self::#variant_name(
-                            #(#field_names),*
-                        ))
+                        #(#variant_names)|* => {
+                            #(#deserialize_fields)*
+                            Ok(Self::#variant_name(
+                                #(#field_names),*
+                            ))
+                        }
                     }
                 }
-            }
-            syn::Fields::Unit => {
-                quote! {
-                    #variant_name_str => Ok(Self::#variant_name)
+                syn::Fields::Unit => {
+                    quote! {
+                        #(#variant_names)|* => Ok(Self::#variant_name)
+                    }
                 }
-            }
-        }
-    });
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>();
+    let variants = match variants {
+        Ok(variants) => variants,
+        Err(err) => return err.to_compile_error(),
+    };
 
     quote! {
-        let variant_name = buf.read_string();
+        let variant_name = buf.read_string()?;
         match variant_name.as_ref() {
             #(#variants,)*
             _ => Err(DeserializeError::InvalidEnumVariant {
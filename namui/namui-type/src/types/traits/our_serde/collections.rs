@@ -20,8 +20,8 @@ impl<T: Deserialize> Deserialize for Vec<T> {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        let len = buf.get_u64() as usize;
-        let mut result = Vec::with_capacity(len);
+        let len = buf.try_get_u64()? as usize;
+        let mut result = Vec::with_capacity(len.min(buf.len()));
         for _ in 0..len {
             result.push(T::deserialize_without_name(buf)?);
         }
@@ -49,7 +49,7 @@ impl<T: Deserialize, const N: usize> Deserialize for [T; N] {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        let len = buf.get_u64() as usize;
+        let len = buf.try_get_u64()? as usize;
         if len != N {
             return Err(DeserializeError::InvalidName {
                 expected: format!("array of length {}", N),
@@ -94,7 +94,7 @@ where
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        let len = buf.get_u64() as usize;
+        let len = buf.try_get_u64()? as usize;
         let mut result = std::collections::BTreeMap::new();
         for _ in 0..len {
             result.insert(
@@ -135,7 +135,7 @@ where
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        let len = buf.get_u64() as usize;
+        let len = buf.try_get_u64()? as usize;
         let mut result = std::collections::HashMap::new();
         for _ in 0..len {
             result.insert(
@@ -170,7 +170,7 @@ where
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        let len = buf.get_u64() as usize;
+        let len = buf.try_get_u64()? as usize;
         let mut result = std::collections::HashSet::new();
         for _ in 0..len {
             result.insert(T::deserialize_without_name(buf)?);
@@ -202,7 +202,7 @@ where
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        let len = buf.get_u64() as usize;
+        let len = buf.try_get_u64()? as usize;
         let mut result = std::collections::BTreeSet::new();
         for _ in 0..len {
             result.insert(T::deserialize_without_name(buf)?);
@@ -231,8 +231,8 @@ impl<T: Deserialize> Deserialize for std::collections::VecDeque<T> {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        let len = buf.get_u64() as usize;
-        let mut result = std::collections::VecDeque::with_capacity(len);
+        let len = buf.try_get_u64()? as usize;
+        let mut result = std::collections::VecDeque::with_capacity(len.min(buf.len()));
         for _ in 0..len {
             result.push_back(T::deserialize_without_name(buf)?);
         }
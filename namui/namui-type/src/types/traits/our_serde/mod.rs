@@ -12,12 +12,28 @@ pub trait Serialize {
 pub trait Deserialize: Sized {
     fn deserialize(buf: &mut &[u8]) -> Result<Self, DeserializeError>;
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError>;
+
+    /// Reads a value written by [`BufMutExt::write_field`], which must take all of `bytes`.
+    fn deserialize_field(mut bytes: &[u8]) -> Result<Self, DeserializeError> {
+        let value = Self::deserialize_without_name(&mut bytes)?;
+        if !bytes.is_empty() {
+            return Err(DeserializeError::TrailingBytes {
+                name: std::any::type_name::<Self>().to_string(),
+                len: bytes.len(),
+            });
+        }
+        Ok(value)
+    }
 }
 
 #[derive(Debug)]
 pub enum DeserializeError {
     InvalidName { expected: String, actual: String },
     InvalidEnumVariant { expected: String, actual: String },
+    MissingField { name: String, field: String },
+    TrailingBytes { name: String, len: usize },
+    UnexpectedEnd { requested: usize, available: usize },
+    InvalidValue { name: String, reason: String },
 }
 
 impl std::fmt::Display for DeserializeError {
@@ -32,14 +48,54 @@ impl std::fmt::Display for DeserializeError {
                     "Invalid enum variant: expected {expected}, actual {actual}",
                 )
             }
+            DeserializeError::MissingField { name, field } => {
+                write!(f, "Missing field: {name}::{field}")
+            }
+            DeserializeError::TrailingBytes { name, len } => {
+                write!(f, "Trailing bytes: {len} bytes left after {name}")
+            }
+            DeserializeError::UnexpectedEnd {
+                requested,
+                available,
+            } => {
+                write!(
+                    f,
+                    "Unexpected end: {requested} bytes requested, {available} bytes left",
+                )
+            }
+            DeserializeError::InvalidValue { name, reason } => {
+                write!(f, "Invalid value of {name}: {reason}")
+            }
         }
     }
 }
 
 impl std::error::Error for DeserializeError {}
 
+impl DeserializeError {
+    fn invalid_value<T: ?Sized>(reason: impl std::fmt::Display) -> Self {
+        DeserializeError::InvalidValue {
+            name: std::any::type_name::<T>().to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl From<bytes::TryGetError> for DeserializeError {
+    fn from(error: bytes::TryGetError) -> Self {
+        DeserializeError::UnexpectedEnd {
+            requested: error.requested,
+            available: error.available,
+        }
+    }
+}
+
+/// Named fields are written as `[count: u16]` and `[key][len: u32][value]` for each field,
+/// so a reader can skip the fields it doesn't know and notice the missing ones.
 pub trait BufMutExt {
     fn write_string(&mut self, name: &str);
+    fn write_field_count(&mut self, count: u16);
+    fn write_field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T);
 }
 
 impl BufMutExt for Vec<u8> {
@@ -47,11 +103,26 @@ impl BufMutExt for Vec<u8> {
         self.put_u16(name.len() as u16);
         self.put_slice(name.as_bytes());
     }
+
+    fn write_field_count(&mut self, count: u16) {
+        self.put_u16(count);
+    }
+
+    fn write_field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) {
+        self.write_string(key);
+        let len_index = self.len();
+        self.put_u32(0);
+        value.serialize_without_name(self);
+        let len = (self.len() - len_index - 4) as u32;
+        self[len_index..len_index + 4].copy_from_slice(&len.to_be_bytes());
+    }
 }
 
 pub trait BufExt {
     fn read_name(&mut self, expected: &'static str) -> Result<String, DeserializeError>;
-    fn read_string(&mut self) -> String;
+    fn read_string(&mut self) -> Result<String, DeserializeError>;
+    /// Reads `(key, value bytes)` of the fields written by [`BufMutExt::write_field`].
+    fn read_fields(&mut self) -> Result<Vec<(String, Vec<u8>)>, DeserializeError>;
 }
 
 impl<T> BufExt for T
@@ -59,7 +130,7 @@ where
     T: Buf + ?Sized,
 {
     fn read_name(&mut self, expected: &'static str) -> Result<String, DeserializeError> {
-        let name = self.read_string();
+        let name = self.read_string()?;
         if name != expected {
             return Err(DeserializeError::InvalidName {
                 expected: expected.to_string(),
@@ -69,12 +140,30 @@ where
         Ok(name)
     }
 
-    fn read_string(&mut self) -> String {
-        let name_len = self.get_u16();
-        let name =
-            std::string::String::from_utf8(self.chunk()[..name_len as usize].to_vec()).unwrap();
-        self.advance(name_len as usize);
-        name
+    fn read_string(&mut self) -> Result<String, DeserializeError> {
+        let name_len = self.try_get_u16()? as usize;
+        let mut name = vec![0; name_len];
+        self.try_copy_to_slice(&mut name)?;
+        std::string::String::from_utf8(name).map_err(DeserializeError::invalid_value::<String>)
+    }
+
+    fn read_fields(&mut self) -> Result<Vec<(String, Vec<u8>)>, DeserializeError> {
+        let count = self.try_get_u16()?;
+        (0..count)
+            .map(|_| {
+                let key = self.read_string()?;
+                let len = self.try_get_u32()? as usize;
+                if self.remaining() < len {
+                    return Err(DeserializeError::UnexpectedEnd {
+                        requested: len,
+                        available: self.remaining(),
+                    });
+                }
+                let mut value = vec![0; len];
+                self.try_copy_to_slice(&mut value)?;
+                Ok((key, value))
+            })
+            .collect()
     }
 }
 
@@ -97,8 +186,10 @@ impl Deserialize for char {
     }
 
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        let value = buf.get_u32();
-        Ok(char::from_u32(value).unwrap())
+        let value = buf.try_get_u32()?;
+        char::from_u32(value).ok_or_else(|| {
+            DeserializeError::invalid_value::<Self>(format!("{value:#x} is not a char"))
+        })
     }
 }
 
@@ -122,9 +213,15 @@ impl Deserialize for String {
     }
 
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        let len = buf.get_u64() as usize;
-        let string_bytes = &buf[..len];
-        let result = String::from_utf8(string_bytes.to_vec()).unwrap();
+        let len = buf.try_get_u64()? as usize;
+        if buf.len() < len {
+            return Err(DeserializeError::UnexpectedEnd {
+                requested: len,
+                available: buf.len(),
+            });
+        }
+        let result = String::from_utf8(buf[..len].to_vec())
+            .map_err(DeserializeError::invalid_value::<Self>)?;
         buf.advance(len);
         Ok(result)
     }
@@ -149,7 +246,7 @@ impl Deserialize for std::time::Duration {
     }
 
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(std::time::Duration::from_nanos(buf.get_u64()))
+        Ok(std::time::Duration::from_nanos(buf.try_get_u64()?))
     }
 }
 
@@ -175,7 +272,7 @@ impl Deserialize for std::time::SystemTime {
     }
 
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_nanos(buf.get_u64()))
+        Ok(std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_nanos(buf.try_get_u64()?))
     }
 }
 
@@ -207,7 +304,7 @@ where
     }
 
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        let is_some = buf.get_u8() != 0;
+        let is_some = buf.try_get_u8()? != 0;
         if is_some {
             Ok(Some(T::deserialize(buf)?))
         } else {
@@ -314,13 +411,14 @@ impl Deserialize for std::path::PathBuf {
     }
 
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(std::path::PathBuf::from(buf.read_string()))
+        Ok(std::path::PathBuf::from(buf.read_string()?))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use derive_macro::OurSerde;
 
     #[test]
     fn test_char_serde() {
@@ -446,4 +544,194 @@ mod tests {
             assert_eq!(original, deserialized);
         }
     }
+
+    fn reserialize<From: Serialize, To: Deserialize>(from: &From) -> Result<To, DeserializeError> {
+        let mut buf = Vec::new();
+        from.serialize_without_name(&mut buf);
+        To::deserialize_without_name(&mut buf.as_slice())
+    }
+
+    #[test]
+    fn test_struct_schema_evolution() {
+        #[derive(OurSerde)]
+        struct Before {
+            name: String,
+            age: u32,
+            score: u32,
+            level: u8,
+        }
+
+        fn default_lives() -> u8 {
+            3
+        }
+
+        #[derive(Debug, PartialEq, OurSerde)]
+        #[our_serde(removed = "score")]
+        struct After {
+            #[our_serde(rename_from = "name")]
+            nickname: String,
+            #[our_serde(tag = "age")]
+            years: u32,
+            #[our_serde(tag = "level_v2", default)]
+            level: u16,
+            #[our_serde(default = "default_lives")]
+            lives: u8,
+        }
+
+        let after: After = reserialize(&Before {
+            name: "Bob".to_string(),
+            age: 30,
+            score: 100,
+            level: 7,
+        })
+        .unwrap();
+
+        assert_eq!(
+            after,
+            After {
+                nickname: "Bob".to_string(),
+                years: 30,
+                level: 0,
+                lives: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_struct_missing_field_without_default() {
+        #[derive(OurSerde)]
+        struct Before {
+            name: String,
+        }
+
+        #[derive(Debug, OurSerde)]
+        #[allow(dead_code)]
+        struct After {
+            name: String,
+            age: u32,
+        }
+
+        let result: Result<After, _> = reserialize(&Before {
+            name: "Bob".to_string(),
+        });
+
+        assert!(matches!(
+            result,
+            Err(DeserializeError::MissingField { field, .. }) if field == "age"
+        ));
+    }
+
+    #[test]
+    fn test_struct_changed_field_type_with_default() {
+        #[derive(OurSerde)]
+        struct Before {
+            value: String,
+        }
+
+        #[derive(Debug, PartialEq, OurSerde)]
+        struct After {
+            #[our_serde(default)]
+            value: u64,
+        }
+
+        let after: After = reserialize(&Before {
+            value: "hello world".to_string(),
+        })
+        .unwrap();
+
+        assert_eq!(after, After { value: 0 });
+    }
+
+    #[test]
+    fn test_enum_schema_evolution() {
+        #[derive(OurSerde)]
+        enum Before {
+            Walking { speed: u32, stamina: u32 },
+        }
+
+        #[derive(Debug, PartialEq, OurSerde)]
+        enum After {
+            #[our_serde(rename_from = "Walking")]
+            Moving {
+                speed: u32,
+                #[our_serde(default)]
+                direction: u8,
+            },
+        }
+
+        let after: After = reserialize(&Before::Walking {
+            speed: 5,
+            stamina: 10,
+        })
+        .unwrap();
+
+        assert_eq!(
+            after,
+            After::Moving {
+                speed: 5,
+                direction: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_truncated_input_is_error() {
+        #[derive(Debug, OurSerde)]
+        #[allow(dead_code)]
+        struct Data {
+            name: String,
+            values: Vec<u32>,
+            ratio: f64,
+        }
+
+        let mut buf = Vec::new();
+        Data {
+            name: "Bob".to_string(),
+            values: vec![1, 2, 3],
+            ratio: 0.5,
+        }
+        .serialize(&mut buf);
+
+        for len in 0..buf.len() {
+            assert!(
+                Data::deserialize(&mut &buf[..len]).is_err(),
+                "read {len} of {} bytes",
+                buf.len()
+            );
+        }
+        assert!(Data::deserialize(&mut buf.as_slice()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_value_is_error() {
+        let mut buf = Vec::new();
+        buf.put_u64(2);
+        buf.put_slice(&[0xc3, 0x28]);
+        assert!(matches!(
+            String::deserialize_without_name(&mut buf.as_slice()),
+            Err(DeserializeError::InvalidValue { .. })
+        ));
+
+        let mut buf = Vec::new();
+        buf.put_u16(1);
+        buf.put_u8(0xff);
+        assert!(matches!(
+            buf.as_slice().read_string(),
+            Err(DeserializeError::InvalidValue { .. })
+        ));
+
+        let mut buf = Vec::new();
+        buf.put_u32(0xd800);
+        assert!(matches!(
+            char::deserialize_without_name(&mut buf.as_slice()),
+            Err(DeserializeError::InvalidValue { .. })
+        ));
+
+        let mut buf = Vec::new();
+        buf.put_u64(0);
+        assert!(matches!(
+            std::num::NonZero::<usize>::deserialize_without_name(&mut buf.as_slice()),
+            Err(DeserializeError::InvalidValue { .. })
+        ));
+    }
 }
@@ -17,7 +17,7 @@ impl Deserialize for bool {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_u8() != 0)
+        Ok(buf.try_get_u8()? != 0)
     }
 }
 
@@ -38,7 +38,7 @@ impl Deserialize for i8 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_i8())
+        Ok(buf.try_get_i8()?)
     }
 }
 
@@ -59,7 +59,7 @@ impl Deserialize for i16 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_i16())
+        Ok(buf.try_get_i16()?)
     }
 }
 
@@ -80,7 +80,7 @@ impl Deserialize for i32 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_i32())
+        Ok(buf.try_get_i32()?)
     }
 }
 
@@ -101,7 +101,7 @@ impl Deserialize for i64 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_i64())
+        Ok(buf.try_get_i64()?)
     }
 }
 
@@ -122,7 +122,7 @@ impl Deserialize for i128 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_i128())
+        Ok(buf.try_get_i128()?)
     }
 }
 
@@ -143,7 +143,7 @@ impl Deserialize for isize {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_i64() as isize)
+        Ok(buf.try_get_i64()? as isize)
     }
 }
 
@@ -164,7 +164,7 @@ impl Deserialize for u8 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_u8())
+        Ok(buf.try_get_u8()?)
     }
 }
 
@@ -185,7 +185,7 @@ impl Deserialize for u16 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_u16())
+        Ok(buf.try_get_u16()?)
     }
 }
 
@@ -206,7 +206,7 @@ impl Deserialize for u32 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_u32())
+        Ok(buf.try_get_u32()?)
     }
 }
 
@@ -227,7 +227,7 @@ impl Deserialize for u64 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_u64())
+        Ok(buf.try_get_u64()?)
     }
 }
 
@@ -248,7 +248,7 @@ impl Deserialize for u128 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_u128())
+        Ok(buf.try_get_u128()?)
     }
 }
 
@@ -269,7 +269,7 @@ impl Deserialize for usize {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_u64() as usize)
+        Ok(buf.try_get_u64()? as usize)
     }
 }
 
@@ -290,7 +290,7 @@ impl Deserialize for f32 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_f32())
+        Ok(buf.try_get_f32()?)
     }
 }
 
@@ -311,7 +311,7 @@ impl Deserialize for f64 {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(buf.get_f64())
+        Ok(buf.try_get_f64()?)
     }
 }
 
@@ -332,7 +332,7 @@ impl Deserialize for std::sync::atomic::AtomicBool {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(std::sync::atomic::AtomicBool::new(buf.get_u8() != 0))
+        Ok(std::sync::atomic::AtomicBool::new(buf.try_get_u8()? != 0))
     }
 }
 
@@ -353,7 +353,8 @@ impl Deserialize for std::num::NonZero<usize> {
         Self::deserialize_without_name(buf)
     }
     fn deserialize_without_name(buf: &mut &[u8]) -> Result<Self, DeserializeError> {
-        Ok(std::num::NonZero::new(buf.get_u64() as usize).unwrap())
+        std::num::NonZero::new(buf.try_get_u64()? as usize)
+            .ok_or_else(|| DeserializeError::invalid_value::<Self>("zero"))
     }
 }
 
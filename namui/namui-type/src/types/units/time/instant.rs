@@ -1,6 +1,8 @@
 use crate::*;
 use std::{fmt::Debug, sync::OnceLock};
 
+static NOW_FN: OnceLock<fn() -> Instant> = OnceLock::new();
+
 #[type_derives(-Debug, Copy, PartialOrd, Eq, Ord)]
 pub struct Instant {
     inner: Duration,
@@ -11,7 +13,17 @@ impl Instant {
         Self { inner }
     }
 
+    /// Makes [`Instant::now`] ask `now` instead of the OS clock.
+    /// namui sets its time system here, so mock time applies to `Instant::now` too.
+    /// Only the first call takes effect.
+    pub fn set_now_fn(now: fn() -> Instant) {
+        let _ = NOW_FN.set(now);
+    }
+
     pub fn now() -> Self {
+        if let Some(now) = NOW_FN.get() {
+            return now();
+        }
         static START: OnceLock<std::time::Instant> = OnceLock::new();
         Self {
             inner: std::time::Instant::now()
@@ -45,6 +45,12 @@ pub extern "C" fn _on_animation_frame() -> *const u8 {
     ffi_catch!(crate::on_event(RawEvent::ScreenRedraw))
 }
 
+/// The drawer lost track of the sent subtrees. The next frame is sent whole.
+#[unsafe(no_mangle)]
+pub extern "C" fn _request_full_frame() {
+    ffi_catch!(reset_rendering_tree_encoder());
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn _on_screen_resize(width: u16, height: u16) -> *const u8 {
     ffi_catch!({
@@ -87,6 +93,12 @@ pub extern "C" fn _on_mouse_wheel(delta_x: f32, delta_y: f32, x: f32, y: f32) ->
     })
 }
 
+/// Raw mouse motion, which the native runner reports while the cursor is locked.
+#[unsafe(no_mangle)]
+pub extern "C" fn _on_mouse_motion(delta_x: f32, delta_y: f32) {
+    ffi_catch!(crate::system::mouse::on_mouse_motion(delta_x, delta_y))
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn _on_key_down(code: u8) -> *const u8 {
     ffi_catch!({
@@ -103,6 +115,32 @@ pub extern "C" fn _on_key_up(code: u8) -> *const u8 {
     })
 }
 
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments, clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn _on_file_drop(
+    x: f32,
+    y: f32,
+    name_ptr: *const u8,
+    name_len: u32,
+    path_ptr: *const u8,
+    path_len: u32,
+    bytes_ptr: *const u8,
+    bytes_len: u32,
+) -> *const u8 {
+    ffi_catch!({
+        let raw_event = unsafe {
+            crate::system::drag_and_drop::on_file_drop(
+                x,
+                y,
+                ffi_slice(name_ptr, name_len),
+                ffi_slice(path_ptr, path_len),
+                ffi_slice(bytes_ptr, bytes_len),
+            )
+        };
+        crate::on_event(raw_event)
+    })
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn _on_blur() -> *const u8 {
     ffi_catch!(crate::on_event(RawEvent::Blur))
@@ -134,3 +172,45 @@ pub extern "C" fn _set_freeze_states(ptr: *const u8, len: usize) {
         });
     });
 }
+
+/// Returns null when there is no request, or pointer to `[len: u32 LE][data...]`
+/// holding bincode encoded `Vec<WindowCommand>`.
+#[unsafe(no_mangle)]
+pub extern "C" fn _window_commands() -> *const u8 {
+    ffi_catch!({
+        let commands = crate::system::window::take_commands();
+        if commands.is_empty() {
+            return std::ptr::null();
+        }
+        let bytes = bincode::encode_to_vec(&commands, bincode::config::standard()).unwrap();
+        crate::write_response(&bytes)
+    })
+}
+
+/// `ptr` holds bincode encoded `WindowState`.
+#[unsafe(no_mangle)]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn _set_window_state(ptr: *const u8, len: u32) {
+    ffi_catch!({
+        let bytes = unsafe { ffi_slice(ptr, len) };
+        let (state, _) =
+            bincode::decode_from_slice::<WindowState, _>(bytes, bincode::config::standard())
+                .unwrap();
+        crate::system::window::set_state(state);
+    })
+}
+
+/// Returns 1 if the app wants to confirm before closing, then the runner keeps the window open.
+#[unsafe(no_mangle)]
+pub extern "C" fn _on_window_close_requested() -> u8 {
+    ffi_catch!(crate::system::window::on_close_requested() as u8)
+}
+
+/// # Safety
+/// `ptr` should be valid for `len` bytes, or `len` should be 0.
+unsafe fn ffi_slice<'a>(ptr: *const u8, len: u32) -> &'a [u8] {
+    if len == 0 {
+        return &[];
+    }
+    unsafe { std::slice::from_raw_parts(ptr, len as usize) }
+}
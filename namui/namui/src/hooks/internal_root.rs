@@ -12,6 +12,7 @@ impl InternalRoot {
 impl Component for &InternalRoot {
     fn render(self, ctx: &RenderCtx) {
         // TODO: Add global systems like fire-and-forget particle.
+        crate::system::drag_and_drop::render_preview(ctx);
         ctx.add(self.root_component);
     }
 }
@@ -36,12 +36,14 @@ impl Looper {
                 (EventType::TextInput, 0),
                 (EventType::TextInputKeyDown, 0),
                 (EventType::TextInputSelectionChange, 0),
+                (EventType::FileDrop, 0),
             ],
             internal_root: InternalRoot::new(root_component),
         }
     }
 
     pub(crate) fn tick(&mut self, event: RawEvent) -> RenderingTree {
+        crate::system::recording::record(&event);
         self.one_sec_render_count += 1;
         self.event_type_count
             .iter_mut()
@@ -49,17 +51,24 @@ impl Looper {
             .unwrap()
             .1 += 1;
 
-        let before_run = crate::time::now();
+        // Real time, as `time` is pinned while recording and replaying.
+        let before_run = std::time::Instant::now();
+        crate::system::drag_and_drop::on_before_event(&event);
+        let is_drag_end_event = crate::system::drag_and_drop::is_drag_end_event(&event);
 
         let rendering_tree = self.world.run_with_event(&self.internal_root, event);
 
+        if is_drag_end_event {
+            crate::system::drag_and_drop::cancel_drag();
+        }
+
         self.post_run(before_run);
 
         rendering_tree
     }
 
-    fn post_run(&mut self, before_run: Instant) {
-        let elapsed = crate::time::now() - before_run;
+    fn post_run(&mut self, before_run: std::time::Instant) {
+        let elapsed: Duration = before_run.elapsed().into();
         if elapsed > 33.ms() {
             tracing::warn!(target: "namui::metrics", "Rendering took {elapsed:?}. Keep it short as possible.");
         }
@@ -105,6 +114,7 @@ enum EventType {
     TextInput,
     TextInputKeyDown,
     TextInputSelectionChange,
+    FileDrop,
 }
 
 impl EventType {
@@ -123,6 +133,7 @@ impl EventType {
             RawEvent::TextInput { .. } => EventType::TextInput,
             RawEvent::TextInputKeyDown { .. } => EventType::TextInputKeyDown,
             RawEvent::TextInputSelectionChange { .. } => EventType::TextInputSelectionChange,
+            RawEvent::FileDrop { .. } => EventType::FileDrop,
         }
     }
 }
@@ -80,8 +80,17 @@ pub(crate) fn write_response(data: &[u8]) -> *const u8 {
 /// The render arena is reset on every `tick`, so the drawer can never safely
 /// reuse a previously sent tree. We therefore always send the full tree on a
 /// screen redraw rather than signalling "redraw with previous data".
+/// The drawer keeps a mirror of the decoded subtrees instead, so
+/// `encode_rendering_tree` sends only the subtrees changed since the last frame.
+///
+/// While replaying a recording, live input is ignored and each screen redraw plays
+/// one recorded frame instead.
 fn on_event(event: RawEvent) -> *const u8 {
     let is_screen_redraw = matches!(event, RawEvent::ScreenRedraw);
+    let is_replaying = system::recording::is_replaying();
+    if is_replaying && !is_screen_redraw {
+        return std::ptr::null();
+    }
 
     let mut result: *const u8 = std::ptr::null();
 
@@ -91,7 +100,15 @@ fn on_event(event: RawEvent) -> *const u8 {
         };
         runtime.block_on(async {
             LOOPER.with_borrow_mut(|looper| {
-                let rendering_tree = looper.as_mut().unwrap().tick(event);
+                let looper = looper.as_mut().unwrap();
+                let rendering_tree = if is_replaying {
+                    let Some(rendering_tree) = system::recording::replay_frame(looper) else {
+                        return;
+                    };
+                    rendering_tree
+                } else {
+                    looper.tick(event)
+                };
 
                 system::audio::flush_audio();
 
@@ -99,8 +116,7 @@ fn on_event(event: RawEvent) -> *const u8 {
                     return;
                 }
 
-                let bytes =
-                    bincode::encode_to_vec(rendering_tree, bincode::config::standard()).unwrap();
+                let bytes = encode_rendering_tree(rendering_tree);
                 result = write_response(&bytes);
             });
 
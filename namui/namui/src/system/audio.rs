@@ -24,17 +24,51 @@ fn next_playback_id() -> usize {
 pub struct AudioAsset {
     id: usize,
     duration: Duration,
+    loudness: Option<AudioLoudness>,
+    loop_region: Option<(Duration, Duration)>,
+}
+
+/// Measured by the bundle audio step, after normalization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioLoudness {
+    pub lufs: f32,
+    pub peak_dbtp: f32,
 }
 
 impl AudioAsset {
     pub const fn new(id: usize, duration: Duration) -> Self {
-        Self { id, duration }
+        Self {
+            id,
+            duration,
+            loudness: None,
+            loop_region: None,
+        }
+    }
+
+    pub const fn with_loudness(mut self, lufs: f32, peak_dbtp: f32) -> Self {
+        self.loudness = Some(AudioLoudness { lufs, peak_dbtp });
+        self
+    }
+
+    pub const fn with_loop_region(mut self, start: Duration, end: Duration) -> Self {
+        self.loop_region = Some((start, end));
+        self
     }
 
     pub const fn duration(&self) -> Duration {
         self.duration
     }
 
+    /// `None` unless the asset went through the bundle audio step.
+    pub const fn loudness(&self) -> Option<AudioLoudness> {
+        self.loudness
+    }
+
+    /// Loop points authored in the source wav file.
+    pub const fn loop_region(&self) -> Option<(Duration, Duration)> {
+        self.loop_region
+    }
+
     pub fn play(&self) -> PlayHandle {
         let playback_id = next_playback_id();
         unsafe { _audio_play(self.id, playback_id, false) }
@@ -0,0 +1,257 @@
+//! Drag and drop between components, and files dropped from the OS.
+//!
+//! ```ignore
+//! // Source
+//! ctx.add(item).attach_event(|event| {
+//!     if let Event::MouseDown { event } = event
+//!         && event.is_local_xy_in()
+//!     {
+//!         drag_and_drop::start_drag(scene_id, move |ctx| {
+//!             ctx.add(scene_thumbnail(scene_id));
+//!         });
+//!     }
+//! });
+//!
+//! // Target
+//! let (is_over, set_is_over) = ctx.state(|| false);
+//! ctx.add(slot).attach_event(|event| {
+//!     match drag_and_drop::drop_target::<SceneId>(&event, |_| true) {
+//!         Some(DropTargetEvent::Hover { is_over }) => set_is_over.set(is_over),
+//!         Some(DropTargetEvent::Drop { payload, .. }) => move_scene(payload),
+//!         None => {}
+//!     }
+//! });
+//! ```
+
+use crate::*;
+use std::{
+    any::Any,
+    sync::{Arc, Mutex},
+};
+
+type Preview = Arc<dyn Fn(&ComposeCtx) + Send + Sync>;
+
+/// How far the mouse moves from where `start_drag` was called before the drag begins,
+/// so a click on a draggable item stays a click.
+pub const DRAG_THRESHOLD: Px = px(4.0);
+
+struct Drag {
+    /// `None` after a drop target took it, or while `drop_target` checks it.
+    payload: Option<Box<dyn Any + Send>>,
+    preview: Preview,
+    /// `Some` until the mouse moves [`DRAG_THRESHOLD`] away from it.
+    pending_origin: Option<Xy<Px>>,
+}
+
+impl Drag {
+    fn is_started(&self) -> bool {
+        self.pending_origin.is_none()
+    }
+}
+
+static DRAG: Mutex<Option<Drag>> = Mutex::new(None);
+
+/// `Hover` tells whether the accepted payload is over the target.
+/// It comes on every mouse move while dragging, and with `false` when the drag ends.
+/// OS file drags don't have it.
+pub enum DropTargetEvent<T> {
+    Hover { is_over: bool },
+    Drop { payload: T, local_xy: Xy<Px> },
+}
+
+/// Starts dragging `payload` once the mouse moves [`DRAG_THRESHOLD`] away, until the next
+/// mouse up. `preview` is drawn on top of everything, with the mouse at its origin.
+pub fn start_drag<T: Send + 'static>(
+    payload: T,
+    preview: impl Fn(&ComposeCtx) + Send + Sync + 'static,
+) {
+    *DRAG.lock().unwrap() = Some(Drag {
+        payload: Some(Box::new(payload)),
+        preview: Arc::new(preview),
+        pending_origin: Some(mouse::position()),
+    });
+}
+
+pub fn cancel_drag() {
+    DRAG.lock().unwrap().take();
+}
+
+pub fn is_dragging() -> bool {
+    DRAG.lock().unwrap().as_ref().is_some_and(Drag::is_started)
+}
+
+/// Calls `func` with the dragged payload if it is `T`.
+///
+/// `func` runs without the lock, so it can use this module. The payload is out of the drag
+/// meanwhile, so `with_dragging_payload` inside `func` gets `None`.
+pub fn with_dragging_payload<T: 'static, R>(func: impl FnOnce(&T) -> R) -> Option<R> {
+    let payload = {
+        let mut drag = DRAG.lock().unwrap();
+        let drag = drag.as_mut().filter(|drag| drag.is_started())?;
+        if !drag.payload.as_ref()?.is::<T>() {
+            return None;
+        }
+        drag.payload.take()?
+    };
+
+    let result = func(payload.downcast_ref::<T>().unwrap());
+
+    // NOTE: `func` may have cancelled or replaced the drag. Then the payload is dropped.
+    if let Some(drag) = DRAG.lock().unwrap().as_mut()
+        && drag.payload.is_none()
+    {
+        drag.payload = Some(payload);
+    }
+    Some(result)
+}
+
+/// Call this in `attach_event` of a drop target.
+/// Payloads which are not `T` or not accepted are ignored.
+///
+/// Files dropped from the OS come as `T = DroppedFile`.
+/// The payload goes to the first target which takes it, in the event dispatch order.
+pub fn drop_target<T: Send + 'static>(
+    event: &Event,
+    accept: impl Fn(&T) -> bool,
+) -> Option<DropTargetEvent<T>> {
+    match event {
+        Event::MouseMove { event } => {
+            with_dragging_payload(|payload: &T| accept(payload))?.then(|| DropTargetEvent::Hover {
+                is_over: event.is_local_xy_in(),
+            })
+        }
+        Event::MouseUp { event } => {
+            if !is_dragging() {
+                return None;
+            }
+            let is_accepted = with_dragging_payload(|payload: &T| accept(payload)) == Some(true);
+            if !is_accepted || !event.is_local_xy_in() {
+                return Some(DropTargetEvent::Hover { is_over: false });
+            }
+            let payload = DRAG.lock().unwrap().as_mut()?.payload.take()?;
+            event.stop_propagation();
+            Some(DropTargetEvent::Drop {
+                payload: *payload.downcast::<T>().ok()?,
+                local_xy: event.local_xy(),
+            })
+        }
+        Event::FileDrop { event } => {
+            let file: Box<dyn Any> = Box::new(event.file.clone());
+            let file = file.downcast::<T>().ok()?;
+            if !accept(&file) || !event.is_local_xy_in() {
+                return None;
+            }
+            event.stop_propagation();
+            Some(DropTargetEvent::Drop {
+                payload: *file,
+                local_xy: event.local_xy(),
+            })
+        }
+        Event::Blur => Some(DropTargetEvent::Hover { is_over: false }),
+        _ => None,
+    }
+}
+
+pub(crate) fn render_preview(ctx: &RenderCtx) {
+    let Some(preview) = DRAG
+        .lock()
+        .unwrap()
+        .as_ref()
+        .filter(|drag| drag.is_started())
+        .map(|drag| drag.preview.clone())
+    else {
+        return;
+    };
+
+    ctx.on_top()
+        .absolute(mouse::position())
+        .compose(|ctx| preview(&ctx));
+}
+
+/// Call before dispatching `event`, so the drag begins with the mouse move past the threshold.
+pub(crate) fn on_before_event(event: &RawEvent) {
+    let RawEvent::MouseMove { event } = event else {
+        return;
+    };
+    let mut drag = DRAG.lock().unwrap();
+    if let Some(drag) = drag.as_mut()
+        && let Some(origin) = drag.pending_origin
+        && event.xy.distance(origin) >= DRAG_THRESHOLD
+    {
+        drag.pending_origin = None;
+    }
+}
+
+/// The drag ends after these events are dispatched, so the drop targets can see the payload.
+pub(crate) fn is_drag_end_event(event: &RawEvent) -> bool {
+    matches!(event, RawEvent::MouseUp { .. } | RawEvent::Blur)
+}
+
+pub(crate) fn on_file_drop(x: f32, y: f32, name: &[u8], path: &[u8], bytes: &[u8]) -> RawEvent {
+    RawEvent::FileDrop {
+        event: RawFileDropEvent {
+            xy: Xy::new(px(x), px(y)),
+            file: DroppedFile {
+                name: String::from_utf8_lossy(name).into_owned(),
+                path: (!path.is_empty()).then(|| String::from_utf8_lossy(path).into_owned().into()),
+                bytes: bytes.into(),
+            },
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// `DRAG` is global.
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    fn mouse_move(x: f32, y: f32) -> RawEvent {
+        RawEvent::MouseMove {
+            event: RawMouseEvent {
+                xy: Xy::new(px(x), px(y)),
+                pressing_buttons: HashSet::new(),
+                button: None,
+            },
+        }
+    }
+
+    #[test]
+    fn drag_begins_past_threshold() {
+        let _lock = TEST_LOCK.lock().unwrap();
+        start_drag(1u32, |_| {});
+        let origin = mouse::position();
+
+        on_before_event(&mouse_move(origin.x.as_f32() + 2.0, origin.y.as_f32()));
+        assert!(!is_dragging());
+        assert_eq!(with_dragging_payload(|payload: &u32| *payload), None);
+
+        on_before_event(&mouse_move(origin.x.as_f32() + 3.0, origin.y.as_f32() + 3.0));
+        assert!(is_dragging());
+        assert_eq!(with_dragging_payload(|payload: &u32| *payload), Some(1));
+
+        cancel_drag();
+    }
+
+    #[test]
+    fn payload_callback_can_use_drag_and_drop() {
+        let _lock = TEST_LOCK.lock().unwrap();
+        start_drag(String::from("scene"), |_| {});
+        let origin = mouse::position();
+        on_before_event(&mouse_move(origin.x.as_f32() + 10.0, origin.y.as_f32()));
+
+        let is_dragging_inside = with_dragging_payload(|_: &String| is_dragging());
+        assert_eq!(is_dragging_inside, Some(true));
+        // Put back after the callback.
+        assert_eq!(
+            with_dragging_payload(|payload: &String| payload.clone()).as_deref(),
+            Some("scene")
+        );
+        assert_eq!(with_dragging_payload(|payload: &u32| *payload), None);
+
+        with_dragging_payload(|_: &String| cancel_drag());
+        assert!(!is_dragging());
+    }
+}
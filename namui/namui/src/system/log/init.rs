@@ -37,6 +37,8 @@ fn install_panic_hook() {
             "<non-string panic payload>".to_string()
         };
         tracing::error!(target: "namui::panic", "panic at {location}: {payload}");
+        #[cfg(target_os = "wasi")]
+        super::wasi_panic::report(&payload, &location);
         prev(info);
     }));
 }
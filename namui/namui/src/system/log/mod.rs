@@ -5,6 +5,8 @@ mod paths;
 mod ring_buffer;
 #[cfg(target_os = "wasi")]
 mod wasi_console;
+#[cfg(target_os = "wasi")]
+mod wasi_panic;
 
 pub use config::{LogConfig, LogConfigBuilder};
 pub use init::{init_log_plugin, init_log_plugin_with_default};
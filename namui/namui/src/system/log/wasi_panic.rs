@@ -0,0 +1,25 @@
+#![cfg(target_os = "wasi")]
+
+//! Hands panics to the web side, which reports them to namsh as `kind: panic`
+//! crash reports. There is no crash-reporter process on the web, so the JS side
+//! takes the stack, signs and uploads the report.
+
+unsafe extern "C" {
+    fn _namui_report_panic(
+        message_ptr: *const u8,
+        message_len: usize,
+        location_ptr: *const u8,
+        location_len: usize,
+    );
+}
+
+pub(super) fn report(message: &str, location: &str) {
+    unsafe {
+        _namui_report_panic(
+            message.as_ptr(),
+            message.len(),
+            location.as_ptr(),
+            location.len(),
+        )
+    };
+}
@@ -1,15 +1,20 @@
 pub mod audio;
+pub mod drag_and_drop;
 pub mod keyboard;
 pub mod kv_store;
 pub mod log;
 pub mod mouse;
+pub mod persist;
+pub mod recording;
 pub mod screen;
 pub mod time;
+pub mod window;
 
 use crate::*;
-pub use audio::AudioAsset;
 pub use audio::AudioGroup;
 pub use audio::{Audio, AudioListener};
+pub use audio::{AudioAsset, AudioLoudness};
+pub use persist::{PersistKey, Persisted, PersistedHook};
 use std::sync::atomic::AtomicBool;
 
 type InitResult = Result<()>;
@@ -20,6 +25,7 @@ pub(crate) fn init_system() -> InitResult {
     log::init_log_plugin_with_default();
     keyboard::init()?;
     screen::init()?;
+    recording::init()?;
     time::init()?;
     mouse::init()?;
 
@@ -1,6 +1,7 @@
 use super::InitResult;
 use crate::*;
 use std::collections::HashSet;
+use std::sync::Mutex;
 use std::sync::atomic::AtomicU32;
 
 pub(crate) fn init() -> InitResult {
@@ -27,6 +28,23 @@ fn update_mouse_position(x: f32, y: f32) {
     );
 }
 
+/// Motion reported while the cursor is locked, not taken yet.
+static MOVEMENT: Mutex<(f32, f32)> = Mutex::new((0.0, 0.0));
+
+/// Mouse motion since the last call. The runner reports it only while the cursor grab is
+/// [`CursorGrab::Locked`], where the cursor stays put and [`position`] doesn't change.
+/// Always zero on web.
+pub fn take_movement() -> Xy<f32> {
+    let (x, y) = std::mem::take(&mut *MOVEMENT.lock().unwrap());
+    Xy::new(x, y)
+}
+
+pub(crate) fn on_mouse_motion(delta_x: f32, delta_y: f32) {
+    let mut movement = MOVEMENT.lock().unwrap();
+    movement.0 += delta_x;
+    movement.1 += delta_y;
+}
+
 // --- Unified mouse event helpers ---
 
 pub(crate) fn on_mouse_down(x: f32, y: f32, button: u8, buttons: u8) -> RawEvent {
@@ -98,3 +116,24 @@ fn buttons_from_bitmask(buttons: u8) -> HashSet<MouseButton> {
     }
     set
 }
+
+/// Inverse of [`button_from_u8`].
+pub(crate) fn button_to_u8(button: MouseButton) -> u8 {
+    match button {
+        MouseButton::Left => 0,
+        MouseButton::Middle => 1,
+        MouseButton::Right => 2,
+    }
+}
+
+/// Inverse of [`buttons_from_bitmask`].
+pub(crate) fn buttons_to_bitmask(buttons: &HashSet<MouseButton>) -> u8 {
+    buttons.iter().fold(0, |bitmask, button| {
+        bitmask
+            | match button {
+                MouseButton::Left => 1 << 0,
+                MouseButton::Right => 1 << 1,
+                MouseButton::Middle => 1 << 2,
+            }
+    })
+}
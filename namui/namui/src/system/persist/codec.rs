@@ -0,0 +1,179 @@
+use serde::{Deserialize as SerdeDeserialize, Serialize as SerdeSerialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::fmt::Display;
+
+/// Converts a stored value of schema version `N` into version `N + 1`.
+///
+/// Return `None` if the value can't be migrated. It will be treated as corrupted.
+pub type Migration = fn(Value) -> Option<Value>;
+
+#[derive(SerdeSerialize)]
+struct EnvelopeRef<'a, T> {
+    version: u32,
+    data: &'a T,
+}
+
+#[derive(SerdeDeserialize)]
+struct Envelope {
+    version: u32,
+    data: Value,
+}
+
+#[derive(Debug)]
+pub enum PersistError {
+    Json(serde_json::Error),
+    /// Stored version is newer than this build knows about.
+    UnknownVersion {
+        stored: u32,
+        current: u32,
+    },
+    MigrationFailed {
+        from: u32,
+    },
+}
+
+impl Display for PersistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersistError::Json(error) => write!(f, "json error: {error}"),
+            PersistError::UnknownVersion { stored, current } => {
+                write!(f, "stored version {stored} is newer than {current}")
+            }
+            PersistError::MigrationFailed { from } => {
+                write!(f, "migration from version {from} failed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PersistError {}
+
+pub(crate) struct Decoded<T> {
+    pub value: T,
+    /// `true` if the stored bytes were older than the current version.
+    pub migrated: bool,
+}
+
+pub(crate) fn encode<T: SerdeSerialize>(version: u32, value: &T) -> Result<Vec<u8>, PersistError> {
+    serde_json::to_vec(&EnvelopeRef {
+        version,
+        data: value,
+    })
+    .map_err(PersistError::Json)
+}
+
+/// Bytes without `{ "version", "data" }` envelope are treated as version 0,
+/// so values written before adopting persist can be migrated too.
+pub(crate) fn decode<T: DeserializeOwned>(
+    bytes: &[u8],
+    migrations: &[Migration],
+) -> Result<Decoded<T>, PersistError> {
+    let current = migrations.len() as u32;
+    let raw = serde_json::from_slice::<Value>(bytes).map_err(PersistError::Json)?;
+
+    let is_envelope = raw.as_object().is_some_and(|object| {
+        object.len() == 2 && object.contains_key("version") && object.contains_key("data")
+    });
+    let (stored, mut data) = if is_envelope {
+        let envelope = serde_json::from_value::<Envelope>(raw).map_err(PersistError::Json)?;
+        (envelope.version, envelope.data)
+    } else {
+        (0, raw)
+    };
+
+    if stored > current {
+        return Err(PersistError::UnknownVersion { stored, current });
+    }
+
+    for (from, migration) in migrations.iter().enumerate().skip(stored as usize) {
+        data = migration(data).ok_or(PersistError::MigrationFailed { from: from as u32 })?;
+    }
+
+    let value = serde_json::from_value(data).map_err(PersistError::Json)?;
+    Ok(Decoded {
+        value,
+        migrated: stored < current,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, SerdeSerialize, SerdeDeserialize)]
+    struct V2 {
+        name: String,
+        count: u32,
+    }
+
+    fn v0_to_v1(value: Value) -> Option<Value> {
+        let name = value.as_str()?;
+        Some(serde_json::json!({ "name": name }))
+    }
+
+    fn v1_to_v2(mut value: Value) -> Option<Value> {
+        value.as_object_mut()?.insert("count".to_string(), 0.into());
+        Some(value)
+    }
+
+    const MIGRATIONS: &[Migration] = &[v0_to_v1, v1_to_v2];
+
+    #[test]
+    fn round_trip_current_version() {
+        let value = V2 {
+            name: "a".to_string(),
+            count: 3,
+        };
+        let bytes = encode(2, &value).unwrap();
+        let decoded = decode::<V2>(&bytes, MIGRATIONS).unwrap();
+        assert_eq!(decoded.value, value);
+        assert!(!decoded.migrated);
+    }
+
+    #[test]
+    fn migrates_unenveloped_bytes_from_version_0() {
+        let decoded = decode::<V2>(br#""legacy""#, MIGRATIONS).unwrap();
+        assert_eq!(
+            decoded.value,
+            V2 {
+                name: "legacy".to_string(),
+                count: 0,
+            }
+        );
+        assert!(decoded.migrated);
+    }
+
+    #[test]
+    fn migrates_from_middle_version() {
+        let decoded = decode::<V2>(br#"{"version":1,"data":{"name":"b"}}"#, MIGRATIONS).unwrap();
+        assert_eq!(decoded.value.name, "b");
+        assert_eq!(decoded.value.count, 0);
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let result = decode::<V2>(br#"{"version":3,"data":{}}"#, MIGRATIONS);
+        assert!(matches!(
+            result,
+            Err(PersistError::UnknownVersion {
+                stored: 3,
+                current: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn failed_migration_is_an_error() {
+        let result = decode::<V2>(b"42", MIGRATIONS);
+        assert!(matches!(
+            result,
+            Err(PersistError::MigrationFailed { from: 0 })
+        ));
+    }
+
+    #[test]
+    fn broken_json_is_an_error() {
+        let result = decode::<V2>(b"{\"version\":", MIGRATIONS);
+        assert!(matches!(result, Err(PersistError::Json(_))));
+    }
+}
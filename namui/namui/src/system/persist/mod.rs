@@ -0,0 +1,310 @@
+//! Typed, versioned values on top of `kv_store`.
+//!
+//! ```ignore
+//! static SETTINGS: Persisted<Settings> = Persisted::new(
+//!     PersistKey::new("settings", Settings::default, &[migrate_v0_to_v1]),
+//! );
+//!
+//! fn render(self, ctx: &RenderCtx) {
+//!     let settings = ctx.persisted(&SETTINGS);
+//!     // ...
+//!     SETTINGS.mutate(|settings| settings.volume = 0.5);
+//! }
+//! ```
+
+mod codec;
+mod writer;
+
+use crate::system::kv_store;
+use crate::*;
+pub use codec::{Migration, PersistError};
+use serde::{Serialize as SerdeSerialize, de::DeserializeOwned};
+use std::sync::atomic::{AtomicBool, Ordering};
+pub use writer::flush;
+
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Schema version is the number of migrations.
+/// Add a migration to the end of the list whenever the stored shape changes.
+pub struct PersistKey<T> {
+    key: &'static str,
+    default: fn() -> T,
+    migrations: &'static [Migration],
+    debounce: Duration,
+}
+
+impl<T> PersistKey<T>
+where
+    T: SerdeSerialize + DeserializeOwned,
+{
+    pub const fn new(
+        key: &'static str,
+        default: fn() -> T,
+        migrations: &'static [Migration],
+    ) -> Self {
+        Self {
+            key,
+            default,
+            migrations,
+            debounce: DEFAULT_DEBOUNCE,
+        }
+    }
+
+    pub const fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    pub fn version(&self) -> u32 {
+        self.migrations.len() as u32
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> Result<T, PersistError> {
+        codec::decode(bytes, self.migrations).map(|decoded| decoded.value)
+    }
+
+    /// Falls back to the backup if the primary value is corrupted, and to the default
+    /// if both are. Corrupted bytes are kept aside under `<key>.corrupted`.
+    pub async fn load(&self) -> T {
+        let Some(bytes) = kv_store::get(self.key).await else {
+            return (self.default)();
+        };
+        let error = match codec::decode(&bytes, self.migrations) {
+            Ok(decoded) => {
+                if decoded.migrated {
+                    self.save(&decoded.value);
+                }
+                return decoded.value;
+            }
+            Err(error) => error,
+        };
+
+        warn!(
+            "persist: `{}` is corrupted ({error}), trying backup",
+            self.key
+        );
+        kv_store::put(writer::corrupted_key(self.key), Some(&bytes)).await;
+
+        let backup = kv_store::get(writer::backup_key(self.key)).await;
+        match backup.map(|bytes| codec::decode::<T>(&bytes, self.migrations)) {
+            Some(Ok(decoded)) => {
+                self.save(&decoded.value);
+                decoded.value
+            }
+            Some(Err(error)) => {
+                warn!(
+                    "persist: backup of `{}` is corrupted too ({error}), using default",
+                    self.key
+                );
+                (self.default)()
+            }
+            None => (self.default)(),
+        }
+    }
+
+    /// Debounced. Use [`flush`] to write immediately.
+    pub fn save(&self, value: &T) {
+        match codec::encode(self.version(), value) {
+            Ok(bytes) => writer::schedule(self.key, bytes, self.debounce),
+            Err(error) => error!("persist: failed to encode `{}`: {error}", self.key),
+        }
+    }
+}
+
+/// `Atom` which is loaded from `kv_store` on first use and saved on every change.
+pub struct Persisted<T: State> {
+    key: PersistKey<T>,
+    atom: Atom<T>,
+    load_started: AtomicBool,
+    loaded: AtomicBool,
+    changed_before_load: AtomicBool,
+}
+
+impl<T> Persisted<T>
+where
+    T: State + SerdeSerialize + DeserializeOwned,
+{
+    pub const fn new(key: PersistKey<T>) -> Self {
+        Self {
+            key,
+            atom: Atom::uninitialized(),
+            load_started: AtomicBool::new(false),
+            loaded: AtomicBool::new(false),
+            changed_before_load: AtomicBool::new(false),
+        }
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.loaded.load(Ordering::SeqCst)
+    }
+
+    /// If this is called before loading finishes, the loaded value is discarded.
+    pub fn set(&'static self, value: T) {
+        self.mark_changed();
+        self.key.save(&value);
+        self.atom.set(value);
+    }
+
+    /// If this is called before loading finishes, the loaded value is discarded.
+    pub fn mutate(&'static self, mutate: impl FnOnce(&mut T) + Send + Sync + 'static) {
+        self.mark_changed();
+        self.atom.mutate(move |value| {
+            mutate(value);
+            self.key.save(value);
+        });
+    }
+
+    fn mark_changed(&self) {
+        if !self.is_loaded() {
+            self.changed_before_load.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn start_load(&'static self) {
+        if self.load_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        crate::spawn(async move {
+            let value = self.key.load().await;
+            if !self.changed_before_load.load(Ordering::SeqCst) {
+                self.atom.set(value);
+            }
+            self.loaded.store(true, Ordering::SeqCst);
+        });
+    }
+}
+
+pub trait PersistedHook {
+    /// Returns the default value until loading finishes. Check [`Persisted::is_loaded`] if it matters.
+    fn persisted<T>(&self, persisted: &'static Persisted<T>) -> Sig<'_, T>
+    where
+        T: State + SerdeSerialize + DeserializeOwned;
+}
+
+impl PersistedHook for RenderCtx<'_, '_> {
+    fn persisted<T>(&self, persisted: &'static Persisted<T>) -> Sig<'_, T>
+    where
+        T: State + SerdeSerialize + DeserializeOwned,
+    {
+        let (sig, _) = self.init_atom(&persisted.atom, persisted.key.default);
+        persisted.start_load();
+        sig
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Stands in for the host side of `kv_store`, answering every request right away.
+    static STORE: Mutex<Option<HashMap<String, Vec<u8>>>> = Mutex::new(None);
+
+    fn store_get(key: &str) -> Option<Vec<u8>> {
+        STORE.lock().unwrap().as_ref()?.get(key).cloned()
+    }
+
+    fn store_put(key: &str, value: &[u8]) {
+        STORE
+            .lock()
+            .unwrap()
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.to_vec());
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn _kv_store_get(request_id: u32, key_ptr: *const u8, key_len: u32) {
+        let key = unsafe { std::slice::from_raw_parts(key_ptr, key_len as usize) };
+        let value = store_get(std::str::from_utf8(key).unwrap());
+        let (ptr, len) = value
+            .as_ref()
+            .map_or((std::ptr::null(), 0), |value| (value.as_ptr(), value.len()));
+        kv_store::_on_kv_store_get_response(request_id, value.is_some() as u32, ptr, len as u32);
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn _kv_store_put(
+        request_id: u32,
+        key_ptr: *const u8,
+        key_len: u32,
+        value_ptr: *const u8,
+        value_len: u32,
+    ) {
+        let key = unsafe { std::slice::from_raw_parts(key_ptr, key_len as usize) };
+        let key = std::str::from_utf8(key).unwrap();
+        if value_ptr.is_null() {
+            if let Some(store) = STORE.lock().unwrap().as_mut() {
+                store.remove(key);
+            }
+        } else {
+            store_put(key, unsafe {
+                std::slice::from_raw_parts(value_ptr, value_len as usize)
+            });
+        }
+        kv_store::_on_kv_store_put_response(request_id);
+    }
+
+    fn decode(bytes: &[u8]) -> u32 {
+        codec::decode::<u32>(bytes, &[]).unwrap().value
+    }
+
+    #[tokio::test]
+    async fn corrupted_value_falls_back_to_backup() {
+        crate::system::time::init_for_test();
+        static KEY: PersistKey<u32> =
+            PersistKey::new("test-corrupted", || 0, &[]).debounce(Duration::from_millis(10));
+        store_put("test-corrupted", b"{\"version\":");
+        store_put("test-corrupted.bak", &codec::encode(0, &7u32).unwrap());
+
+        assert_eq!(KEY.load().await, 7);
+        assert_eq!(
+            store_get("test-corrupted.corrupted").as_deref(),
+            Some(&b"{\"version\":"[..])
+        );
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert_eq!(decode(&store_get("test-corrupted").unwrap()), 7);
+    }
+
+    #[tokio::test]
+    async fn both_corrupted_is_default() {
+        crate::system::time::init_for_test();
+        static KEY: PersistKey<u32> = PersistKey::new("test-both-corrupted", || 3, &[]);
+        store_put("test-both-corrupted", b"{");
+        store_put("test-both-corrupted.bak", b"{");
+
+        assert_eq!(KEY.load().await, 3);
+    }
+
+    #[tokio::test]
+    async fn debounced_writer_writes_last_value() {
+        crate::system::time::init_for_test();
+        static KEY: PersistKey<u32> =
+            PersistKey::new("test-debounce", || 0, &[]).debounce(Duration::from_millis(10));
+        for value in 1..=3u32 {
+            KEY.save(&value);
+        }
+        assert_eq!(store_get("test-debounce"), None);
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert_eq!(decode(&store_get("test-debounce").unwrap()), 3);
+        assert_eq!(decode(&store_get("test-debounce.bak").unwrap()), 3);
+        assert_eq!(KEY.load().await, 3);
+    }
+
+    #[tokio::test]
+    async fn flush_writes_without_waiting() {
+        crate::system::time::init_for_test();
+        static KEY: PersistKey<u32> =
+            PersistKey::new("test-flush", || 0, &[]).debounce(Duration::from_secs(60));
+        KEY.save(&5);
+
+        flush().await;
+        assert_eq!(decode(&store_get("test-flush").unwrap()), 5);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::system::kv_store;
+use namui_type::*;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+struct PendingWrite {
+    latest: Option<Vec<u8>>,
+    worker_running: bool,
+}
+
+static PENDING_WRITES: LazyLock<Mutex<HashMap<&'static str, PendingWrite>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) fn backup_key(key: &str) -> String {
+    format!("{key}.bak")
+}
+
+pub(crate) fn corrupted_key(key: &str) -> String {
+    format!("{key}.corrupted")
+}
+
+/// Only the latest bytes scheduled within `debounce` are written.
+pub(crate) fn schedule(key: &'static str, bytes: Vec<u8>, debounce: Duration) {
+    let should_start_worker = {
+        let mut pending_writes = PENDING_WRITES.lock().unwrap();
+        let pending = pending_writes.entry(key).or_insert(PendingWrite {
+            latest: None,
+            worker_running: false,
+        });
+        pending.latest = Some(bytes);
+        !std::mem::replace(&mut pending.worker_running, true)
+    };
+    if should_start_worker {
+        crate::spawn(write_worker(key, debounce));
+    }
+}
+
+/// Write every scheduled value right now, without waiting for debounce.
+pub async fn flush() {
+    let writes = {
+        let mut pending_writes = PENDING_WRITES.lock().unwrap();
+        pending_writes
+            .iter_mut()
+            .filter_map(|(key, pending)| Some((*key, pending.latest.take()?)))
+            .collect::<Vec<_>>()
+    };
+    for (key, bytes) in writes {
+        write(key, &bytes).await;
+    }
+}
+
+async fn write_worker(key: &'static str, debounce: Duration) {
+    loop {
+        crate::time::sleep(debounce).await;
+
+        let bytes = {
+            let mut pending_writes = PENDING_WRITES.lock().unwrap();
+            let pending = pending_writes.get_mut(key).unwrap();
+            let Some(bytes) = pending.latest.take() else {
+                pending.worker_running = false;
+                return;
+            };
+            bytes
+        };
+        write(key, &bytes).await;
+    }
+}
+
+/// Primary first, then backup. If the process dies while writing one of them,
+/// the other one still holds a complete value.
+async fn write(key: &str, bytes: &[u8]) {
+    kv_store::put(key, Some(bytes)).await;
+    kv_store::put(backup_key(key), Some(bytes)).await;
+}
@@ -0,0 +1,225 @@
+//! File layout:
+//! `[magic: 8][version: u32 LE][seed: u64 LE]`,
+//! then one frame per event: `[len: u32 LE][bincode RecordedEntry]`.
+//!
+//! Frames don't depend on each other, so the header plus any run of whole frames
+//! is a valid recording. The crash reporter keeps only the tail that way.
+//!
+//! Text input and dropped files are recorded without their payload, so a recording never holds
+//! what the player typed or which files they dropped. The replay skips them.
+
+use crate::*;
+use std::io::{Read, Write};
+
+const MAGIC: &[u8; 8] = b"NAMUIREC";
+const VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Header {
+    pub seed: u64,
+}
+
+impl Header {
+    pub fn write(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&self.seed.to_le_bytes())?;
+        Ok(())
+    }
+
+    pub fn read(reader: &mut impl Read) -> Result<Self> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(anyhow!("not a namui recording"));
+        }
+        let version = u32::from_le_bytes(read_array(reader)?);
+        if version != VERSION {
+            return Err(anyhow!("unsupported recording version {version}"));
+        }
+        Ok(Self {
+            seed: u64::from_le_bytes(read_array(reader)?),
+        })
+    }
+}
+
+#[derive(Debug, Clone, bincode::Encode, bincode::Decode)]
+pub(super) struct RecordedEntry {
+    /// `time::since_start` when the event came. The recording pins the app's time to it until
+    /// the next event, as the replay does.
+    pub since_start_secs: f32,
+    pub event: RecordedEvent,
+}
+
+impl RecordedEntry {
+    /// The whole frame, with its length.
+    pub fn encode(&self) -> std::io::Result<Vec<u8>> {
+        let bytes = bincode::encode_to_vec(self, bincode::config::standard())
+            .map_err(std::io::Error::other)?;
+        let mut frame = Vec::with_capacity(4 + bytes.len());
+        frame.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        frame.extend_from_slice(&bytes);
+        Ok(frame)
+    }
+
+    /// Returns `None` at the end of the recording, including a frame cut by a crash.
+    pub fn read(reader: &mut impl Read) -> Option<Self> {
+        let len = u32::from_le_bytes(read_array(reader).ok()?) as usize;
+        let mut bytes = vec![0; len];
+        reader.read_exact(&mut bytes).ok()?;
+        let (entry, _) = bincode::decode_from_slice(&bytes, bincode::config::standard()).ok()?;
+        Some(entry)
+    }
+}
+
+/// [`RawEvent`] in the shape of the FFI input, so replaying it goes through the same
+/// system updates (mouse position, pressing keys, screen size) as the live event.
+#[derive(Debug, Clone, bincode::Encode, bincode::Decode)]
+pub(super) enum RecordedEvent {
+    MouseDown {
+        x: f32,
+        y: f32,
+        button: u8,
+        buttons: u8,
+    },
+    MouseMove {
+        x: f32,
+        y: f32,
+        buttons: u8,
+    },
+    MouseUp {
+        x: f32,
+        y: f32,
+        button: u8,
+        buttons: u8,
+    },
+    Wheel {
+        delta_x: f32,
+        delta_y: f32,
+        x: f32,
+        y: f32,
+    },
+    KeyDown {
+        code: u8,
+    },
+    KeyUp {
+        code: u8,
+    },
+    Blur,
+    VisibilityChange,
+    ScreenResize {
+        width: u16,
+        height: u16,
+    },
+    ScreenRedraw,
+    TextInput,
+    TextInputKeyDown,
+    TextInputSelectionChange,
+    FileDrop {
+        x: f32,
+        y: f32,
+    },
+}
+
+impl RecordedEvent {
+    pub fn from_raw_event(event: &RawEvent) -> Self {
+        use crate::system::mouse::{button_to_u8, buttons_to_bitmask};
+
+        let mouse_button = |event: &RawMouseEvent| event.button.map(button_to_u8).unwrap_or(0);
+
+        match event {
+            RawEvent::MouseDown { event } => Self::MouseDown {
+                x: event.xy.x.as_f32(),
+                y: event.xy.y.as_f32(),
+                button: mouse_button(event),
+                buttons: buttons_to_bitmask(&event.pressing_buttons),
+            },
+            RawEvent::MouseMove { event } => Self::MouseMove {
+                x: event.xy.x.as_f32(),
+                y: event.xy.y.as_f32(),
+                buttons: buttons_to_bitmask(&event.pressing_buttons),
+            },
+            RawEvent::MouseUp { event } => Self::MouseUp {
+                x: event.xy.x.as_f32(),
+                y: event.xy.y.as_f32(),
+                button: mouse_button(event),
+                buttons: buttons_to_bitmask(&event.pressing_buttons),
+            },
+            RawEvent::Wheel { event } => Self::Wheel {
+                delta_x: event.delta_xy.x,
+                delta_y: event.delta_xy.y,
+                x: event.mouse_xy.x.as_f32(),
+                y: event.mouse_xy.y.as_f32(),
+            },
+            RawEvent::KeyDown { event } => Self::KeyDown {
+                code: event.code as u8,
+            },
+            RawEvent::KeyUp { event } => Self::KeyUp {
+                code: event.code as u8,
+            },
+            RawEvent::Blur => Self::Blur,
+            RawEvent::VisibilityChange => Self::VisibilityChange,
+            RawEvent::ScreenResize { wh } => Self::ScreenResize {
+                width: wh.width.as_i32() as u16,
+                height: wh.height.as_i32() as u16,
+            },
+            RawEvent::ScreenRedraw => Self::ScreenRedraw,
+            RawEvent::TextInput { .. } => Self::TextInput,
+            RawEvent::TextInputKeyDown { .. } => Self::TextInputKeyDown,
+            RawEvent::TextInputSelectionChange { .. } => Self::TextInputSelectionChange,
+            RawEvent::FileDrop { event } => Self::FileDrop {
+                x: event.xy.x.as_f32(),
+                y: event.xy.y.as_f32(),
+            },
+        }
+    }
+
+    /// Applies the system updates of the event, like the FFI does for a live one.
+    /// Returns `None` for the events recorded without their payload.
+    pub fn into_raw_event(self) -> Option<RawEvent> {
+        use crate::system::{keyboard, mouse, screen};
+
+        Some(match self {
+            Self::MouseDown {
+                x,
+                y,
+                button,
+                buttons,
+            } => mouse::on_mouse_down(x, y, button, buttons),
+            Self::MouseMove { x, y, buttons } => mouse::on_mouse_move(x, y, buttons),
+            Self::MouseUp {
+                x,
+                y,
+                button,
+                buttons,
+            } => mouse::on_mouse_up(x, y, button, buttons),
+            Self::Wheel {
+                delta_x,
+                delta_y,
+                x,
+                y,
+            } => mouse::on_mouse_wheel(delta_x, delta_y, x, y),
+            Self::KeyDown { code } => keyboard::key_down(code),
+            Self::KeyUp { code } => keyboard::key_up(code),
+            Self::Blur => RawEvent::Blur,
+            Self::VisibilityChange => RawEvent::VisibilityChange,
+            Self::ScreenResize { width, height } => {
+                screen::set_size(width, height);
+                RawEvent::ScreenResize {
+                    wh: Wh::new(int_px(width as i32), int_px(height as i32)),
+                }
+            }
+            Self::ScreenRedraw => RawEvent::ScreenRedraw,
+            Self::TextInput
+            | Self::TextInputKeyDown
+            | Self::TextInputSelectionChange
+            | Self::FileDrop { .. } => return None,
+        })
+    }
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> std::io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
@@ -0,0 +1,379 @@
+//! Records every [`RawEvent`] with its `time::since_start`, and the seed of the session, into a file.
+//! Replaying it feeds the events back through the same `Looper::tick` on the mock time,
+//! so the app goes through the same frames as the recorded session.
+//!
+//! - `NAMUI_RECORD=<path>` records the session.
+//! - `NAMUI_CRASH_INPUT_RECORDING=<path>`, set by the crash reporter when the game opts in,
+//!   records only the last frames of the session, for the crash report.
+//! - `NAMUI_REPLAY=<path>` replays the recording instead of the live input, with rendering.
+//!   Each animation frame of the runner plays one recorded frame.
+//! - [`replay_headless`] replays without a window, for example in a test.
+//!
+//! While recording, `time` is pinned to the time of the last event until the next one, as it
+//! is on the replay. The app has to take its randomness from [`seed`], and its time from `time`.
+//! Timers of spawned tasks still run on the real clock, so keep game logic in the frames.
+
+mod format;
+
+use crate::*;
+use format::*;
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufReader, BufWriter, Seek, Write},
+    path::Path,
+    sync::{Mutex, OnceLock},
+};
+
+const ENV_RECORD: &str = "NAMUI_RECORD";
+const ENV_REPLAY: &str = "NAMUI_REPLAY";
+/// Set by the crash reporter so the recording tail can go with the crash report.
+const ENV_CRASH_INPUT_RECORDING: &str = "NAMUI_CRASH_INPUT_RECORDING";
+/// The crash report takes about the last 12 KiB, so the crash recording keeps a bit more.
+const CRASH_RING_BYTES: usize = 32 * 1024;
+
+static SEED: OnceLock<u64> = OnceLock::new();
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+static REPLAY: Mutex<Option<Replay>> = Mutex::new(None);
+
+struct Recorder {
+    writer: BufWriter<File>,
+    header: Header,
+    /// `Some` when only the last frames are kept.
+    ring: Option<Ring>,
+    wrote_initial_screen_size: bool,
+}
+
+/// The last frames within [`CRASH_RING_BYTES`]. When the file grows to twice of that,
+/// it is rewritten with only these frames.
+struct Ring {
+    frames: VecDeque<Vec<u8>>,
+    len: usize,
+    /// Bytes of the frames in the file.
+    file_len: usize,
+}
+
+struct Replay {
+    reader: BufReader<File>,
+    seed: u64,
+}
+
+pub(crate) fn init() -> Result<()> {
+    if let Some(replay) = REPLAY.lock().unwrap().as_ref() {
+        return set_seed(replay.seed);
+    }
+
+    if !cfg!(target_os = "wasi")
+        && let Some(path) = std::env::var_os(ENV_REPLAY)
+    {
+        let replay = Replay::open(Path::new(&path))?;
+        let seed = replay.seed;
+        *REPLAY.lock().unwrap() = Some(replay);
+        return set_seed(seed);
+    }
+
+    let seed = rand::random();
+    set_seed(seed)?;
+
+    if cfg!(target_os = "wasi") {
+        return Ok(());
+    }
+    let (path, keep_last_frames) = match (
+        std::env::var_os(ENV_RECORD),
+        std::env::var_os(ENV_CRASH_INPUT_RECORDING),
+    ) {
+        (Some(path), _) => (path, false),
+        (None, Some(path)) => (path, true),
+        (None, None) => return Ok(()),
+    };
+    match Recorder::create(Path::new(&path), seed, keep_last_frames) {
+        Ok(recorder) => *RECORDER.lock().unwrap() = Some(recorder),
+        Err(error) => {
+            tracing::error!(target: "namui::recording", "Failed to record to {path:?}: {error}")
+        }
+    }
+
+    Ok(())
+}
+
+/// Seed of the session. Same on the replay of the session.
+/// Random if the system isn't initialized, like in a test.
+pub fn seed() -> u64 {
+    *SEED.get_or_init(rand::random)
+}
+
+pub fn is_replaying() -> bool {
+    REPLAY.lock().unwrap().is_some()
+}
+
+/// Replays the recording at `path` without a window. Returns when the recording ends.
+pub fn replay_headless(root_component: fn(&RenderCtx), path: impl AsRef<Path>) -> Result<()> {
+    *REPLAY.lock().unwrap() = Some(Replay::open(path.as_ref())?);
+    system::init_system()?;
+
+    let mut looper = Looper::new(root_component);
+    crate::TOKIO_RUNTIME.with_borrow(|runtime| {
+        let runtime = runtime.as_ref().unwrap();
+        runtime.block_on(async {
+            while replay_frame(&mut looper).is_some() {
+                for _ in 0..16 {
+                    tokio::task::yield_now().await;
+                }
+            }
+        });
+    });
+
+    Ok(())
+}
+
+pub(crate) fn record(event: &RawEvent) {
+    let mut recorder = RECORDER.lock().unwrap();
+    let Some(writing) = recorder.as_mut() else {
+        return;
+    };
+    if let Err(error) = writing.write(event) {
+        tracing::error!(target: "namui::recording", "Failed to record, stop recording: {error}");
+        *recorder = None;
+        system::time::pin_since_start(None);
+    }
+}
+
+/// Ticks the recorded events up to the next screen redraw, and returns its rendering tree.
+/// Returns `None` when the recording ends.
+pub(crate) fn replay_frame(looper: &mut Looper) -> Option<RenderingTree> {
+    loop {
+        let event = next_replay_event()?;
+        let is_screen_redraw = matches!(event, RawEvent::ScreenRedraw);
+        let rendering_tree = looper.tick(event);
+        if is_screen_redraw {
+            return Some(rendering_tree);
+        }
+    }
+}
+
+fn next_replay_event() -> Option<RawEvent> {
+    let mut replay = REPLAY.lock().unwrap();
+    let replay = replay.as_mut()?;
+    loop {
+        let Some(entry) = RecordedEntry::read(&mut replay.reader) else {
+            tracing::info!(target: "namui::recording", "Replay ended");
+            return None;
+        };
+
+        system::time::mock::set_since_start(Duration::from_secs_f32(entry.since_start_secs));
+
+        if let Some(event) = entry.event.into_raw_event() {
+            return Some(event);
+        }
+    }
+}
+
+fn set_seed(seed: u64) -> Result<()> {
+    SEED.set(seed)
+        .map_err(|_| anyhow!("seed is taken before the recording is initialized"))
+}
+
+impl Recorder {
+    fn create(path: &Path, seed: u64, keep_last_frames: bool) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let header = Header { seed };
+        let mut writer = BufWriter::new(File::create(path)?);
+        header.write(&mut writer)?;
+        writer.flush()?;
+
+        Ok(Self {
+            writer,
+            header,
+            ring: keep_last_frames.then(|| Ring {
+                frames: VecDeque::new(),
+                len: 0,
+                file_len: 0,
+            }),
+            wrote_initial_screen_size: false,
+        })
+    }
+
+    /// Flushes on every screen redraw, so the file is complete up to the last frame when
+    /// the app crashes.
+    fn write(&mut self, event: &RawEvent) -> std::io::Result<()> {
+        system::time::pin_since_start(None);
+        let since_start_secs = system::time::since_start().as_secs_f32();
+        // Exactly what the replay will pin for this event.
+        system::time::pin_since_start(Some(Duration::from_secs_f32(since_start_secs)));
+
+        // The runner sets the first screen size without an event.
+        if !self.wrote_initial_screen_size {
+            self.wrote_initial_screen_size = true;
+            let wh = system::screen::size();
+            self.write_frame(
+                RecordedEntry {
+                    since_start_secs,
+                    event: RecordedEvent::from_raw_event(&RawEvent::ScreenResize { wh }),
+                }
+                .encode()?,
+            )?;
+        }
+
+        self.write_frame(
+            RecordedEntry {
+                since_start_secs,
+                event: RecordedEvent::from_raw_event(event),
+            }
+            .encode()?,
+        )?;
+
+        if !matches!(event, RawEvent::ScreenRedraw) {
+            return Ok(());
+        }
+        if self
+            .ring
+            .as_ref()
+            .is_some_and(|ring| ring.file_len > 2 * CRASH_RING_BYTES)
+        {
+            self.rewrite_with_ring()?;
+        }
+        self.writer.flush()
+    }
+
+    fn write_frame(&mut self, frame: Vec<u8>) -> std::io::Result<()> {
+        self.writer.write_all(&frame)?;
+        let Some(ring) = self.ring.as_mut() else {
+            return Ok(());
+        };
+        ring.file_len += frame.len();
+        ring.len += frame.len();
+        ring.frames.push_back(frame);
+        while ring.len > CRASH_RING_BYTES
+            && let Some(front) = ring.frames.pop_front()
+        {
+            ring.len -= front.len();
+        }
+        Ok(())
+    }
+
+    fn rewrite_with_ring(&mut self) -> std::io::Result<()> {
+        let ring = self.ring.as_mut().unwrap();
+        let mut bytes = Vec::with_capacity(ring.len + 20);
+        self.header.write(&mut bytes)?;
+        for frame in &ring.frames {
+            bytes.extend_from_slice(frame);
+        }
+
+        self.writer.flush()?;
+        let file = self.writer.get_mut();
+        file.rewind()?;
+        file.write_all(&bytes)?;
+        file.set_len(bytes.len() as u64)?;
+        ring.file_len = ring.len;
+        Ok(())
+    }
+}
+
+impl Replay {
+    fn open(path: &Path) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let header = Header::read(&mut reader)?;
+        Ok(Self {
+            reader,
+            seed: header.seed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The recorder pins the global time.
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("namui-recording-{name}-{}", std::process::id()))
+    }
+
+    fn read_events(path: &Path) -> Vec<RecordedEvent> {
+        let mut replay = Replay::open(path).unwrap();
+        std::iter::from_fn(|| RecordedEntry::read(&mut replay.reader))
+            .map(|entry| entry.event)
+            .collect()
+    }
+
+    #[test]
+    fn crash_recording_keeps_only_last_frames() {
+        let _lock = TEST_LOCK.lock().unwrap();
+        system::time::init_for_test();
+        let path = temp_path("ring");
+        let mut recorder = Recorder::create(&path, 7, true).unwrap();
+        for _ in 0..20_000 {
+            recorder.write(&RawEvent::ScreenRedraw).unwrap();
+        }
+
+        let file_len = std::fs::metadata(&path).unwrap().len() as usize;
+        assert!(file_len <= 2 * CRASH_RING_BYTES + 64, "{file_len}");
+        let events = read_events(&path);
+        assert!(events.len() < 20_000);
+        assert!(
+            events
+                .iter()
+                .all(|event| matches!(event, RecordedEvent::ScreenRedraw))
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn text_input_is_recorded_without_text() {
+        let _lock = TEST_LOCK.lock().unwrap();
+        system::time::init_for_test();
+        let path = temp_path("text");
+        let mut recorder = Recorder::create(&path, 7, false).unwrap();
+        recorder
+            .write(&RawEvent::TextInput {
+                event: RawTextInputEvent {
+                    text: "hunter2".to_string(),
+                    selection_direction: SelectionDirection::None,
+                    selection_start: 7,
+                    selection_end: 7,
+                },
+            })
+            .unwrap();
+        recorder.write(&RawEvent::ScreenRedraw).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(!bytes.windows(7).any(|window| window == b"hunter2"));
+        assert!(matches!(
+            read_events(&path).as_slice(),
+            [
+                RecordedEvent::ScreenResize { .. },
+                RecordedEvent::TextInput,
+                RecordedEvent::ScreenRedraw
+            ]
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn time_is_pinned_to_recorded_time_until_next_event() {
+        let _lock = TEST_LOCK.lock().unwrap();
+        system::time::init_for_test();
+        let path = temp_path("pin");
+        let mut recorder = Recorder::create(&path, 7, false).unwrap();
+        recorder.write(&RawEvent::ScreenRedraw).unwrap();
+
+        let first_read = system::time::since_start();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert_eq!(system::time::since_start(), first_read);
+
+        let mut replay = Replay::open(&path).unwrap();
+        let recorded = std::iter::from_fn(|| RecordedEntry::read(&mut replay.reader))
+            .last()
+            .unwrap();
+        assert_eq!(
+            Duration::from_secs_f32(recorded.since_start_secs),
+            first_read
+        );
+        system::time::pin_since_start(None);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -14,6 +14,12 @@ pub(crate) fn init() -> InitResult {
 
 static INSTANT_NOW: OnceLock<Mutex<std::time::Instant>> = OnceLock::new();
 static SYSTEM_TIME_NOW: OnceLock<Mutex<SystemTime>> = OnceLock::new();
+static SINCE_START: Mutex<Option<Duration>> = Mutex::new(None);
+
+/// Fixes `since_start` and `now` to `since_start` until the next call.
+pub fn set_since_start(since_start: Duration) {
+    *SINCE_START.lock().unwrap() = Some(since_start);
+}
 
 pub fn set_instant_now(now: std::time::Instant) {
     *INSTANT_NOW.get_or_init(|| Mutex::new(now)).lock().unwrap() = now;
@@ -30,6 +36,9 @@ struct MockTimeSystem;
 
 impl TimeSystem for MockTimeSystem {
     fn since_start(&self) -> Duration {
+        if let Some(since_start) = *SINCE_START.lock().unwrap() {
+            return since_start;
+        }
         INSTANT_NOW
             .get_or_init(|| Mutex::new(std::time::Instant::now()))
             .lock()
@@ -54,4 +63,8 @@ impl TimeSystem for MockTimeSystem {
             duration.as_secs_f32().max(0.0),
         ))
     }
+
+    fn pin_since_start(&self, since_start: Option<Duration>) {
+        *SINCE_START.lock().unwrap() = since_start;
+    }
 }
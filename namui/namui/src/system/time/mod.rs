@@ -1,24 +1,21 @@
-#[cfg(test)]
-mod mock;
-#[cfg(not(test))]
+pub mod mock;
 mod non_mock;
 
-#[cfg(test)]
-use mock as inner;
-#[cfg(not(test))]
-use non_mock as inner;
-
 use super::InitResult;
-#[cfg(test)]
-pub use inner::*;
 use namui_type::*;
 use std::sync::{Arc, OnceLock};
 use tokio::time;
 
 static TIME_SYSTEM: OnceLock<Arc<dyn TimeSystem + Send + Sync>> = OnceLock::new();
 
+/// Tests and replays run on the mock time.
 pub(crate) fn init() -> InitResult {
-    inner::init()?;
+    if cfg!(test) || crate::system::recording::is_replaying() {
+        mock::init()?;
+    } else {
+        non_mock::init()?;
+    }
+    Instant::set_now_fn(now);
 
     Ok(())
 }
@@ -37,6 +34,12 @@ pub fn now() -> Instant {
     TIME_SYSTEM.get().unwrap().now()
 }
 
+/// Pins `since_start` and `now` to `since_start` until the next call, or unpins them with `None`.
+/// The recording pins them for every event, as the replay does.
+pub(crate) fn pin_since_start(since_start: Option<Duration>) {
+    TIME_SYSTEM.get().unwrap().pin_since_start(since_start)
+}
+
 pub fn stop_watch(key: impl AsRef<str>) -> StopWatch {
     StopWatch::new(key.as_ref().to_string())
 }
@@ -58,4 +61,11 @@ trait TimeSystem {
     fn now(&self) -> Instant;
     /// Sleep 0 duration if passed duration is less than 0.
     fn sleep(&self, duration: Duration) -> time::Sleep;
+    fn pin_since_start(&self, since_start: Option<Duration>);
+}
+
+#[cfg(test)]
+pub(crate) fn init_for_test() {
+    static INIT: std::sync::Once = std::sync::Once::new();
+    INIT.call_once(|| init().unwrap());
 }
@@ -2,12 +2,13 @@ use super::TimeSystem;
 use crate::system::InitResult;
 use anyhow::*;
 use namui_type::*;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 pub(crate) fn init() -> InitResult {
     super::TIME_SYSTEM
         .set(Arc::new(NonWasmTimeSystem {
             start_instant: std::time::Instant::now(),
+            pinned_since_start: Mutex::new(None),
         }))
         .map_err(|_| anyhow!("Failed to set time system"))?;
 
@@ -16,10 +17,14 @@ pub(crate) fn init() -> InitResult {
 
 struct NonWasmTimeSystem {
     start_instant: std::time::Instant,
+    pinned_since_start: Mutex<Option<Duration>>,
 }
 
 impl TimeSystem for NonWasmTimeSystem {
     fn since_start(&self) -> Duration {
+        if let Some(since_start) = *self.pinned_since_start.lock().unwrap() {
+            return since_start;
+        }
         self.start_instant.elapsed().into()
     }
 
@@ -36,4 +41,8 @@ impl TimeSystem for NonWasmTimeSystem {
             duration.as_secs_f32().max(0.0),
         ))
     }
+
+    fn pin_since_start(&self, since_start: Option<Duration>) {
+        *self.pinned_since_start.lock().unwrap() = since_start;
+    }
 }
@@ -0,0 +1,116 @@
+//! Native window control. On web, every request is ignored.
+//!
+//! Requests are applied by the runner after the current frame.
+//! The runner saves the window geometry, mode and vsync in the user data directory shortly after
+//! they change, and restores them on the next launch,
+//! so don't set them on every start unless you mean to override what the player chose.
+
+use crate::*;
+use std::sync::{
+    Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+static COMMANDS: Mutex<Vec<WindowCommand>> = Mutex::new(Vec::new());
+static STATE: Mutex<Option<WindowState>> = Mutex::new(None);
+static CONFIRM_CLOSE: AtomicBool = AtomicBool::new(false);
+static CLOSE_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+fn push(command: WindowCommand) {
+    if cfg!(target_os = "wasi") {
+        return;
+    }
+    COMMANDS.lock().unwrap().push(command);
+}
+
+pub fn set_title(title: impl ToString) {
+    push(WindowCommand::SetTitle {
+        title: title.to_string(),
+    });
+}
+
+/// Size of the drawable area, not including the title bar.
+pub fn set_size(wh: Wh<IntPx>) {
+    push(WindowCommand::SetSize { wh });
+}
+
+pub fn set_min_size(wh: Option<Wh<IntPx>>) {
+    push(WindowCommand::SetMinSize { wh });
+}
+
+pub fn set_max_size(wh: Option<Wh<IntPx>>) {
+    push(WindowCommand::SetMaxSize { wh });
+}
+
+/// Position of the window on the desktop, including the title bar.
+pub fn set_position(xy: Xy<IntPx>) {
+    push(WindowCommand::SetPosition { xy });
+}
+
+pub fn set_mode(mode: WindowMode) {
+    push(WindowCommand::SetMode { mode });
+}
+
+pub fn set_vsync(vsync: bool) {
+    push(WindowCommand::SetVsync { vsync });
+}
+
+/// With [`CursorGrab::Locked`], read the mouse motion from [`mouse::take_movement`].
+pub fn set_cursor_grab(grab: CursorGrab) {
+    push(WindowCommand::SetCursorGrab { grab });
+}
+
+/// Hides the mouse cursor which namui draws. The OS cursor is always hidden.
+pub fn set_cursor_visible(visible: bool) {
+    push(WindowCommand::SetCursorVisible { visible });
+}
+
+/// The last state which the runner reported. `None` on web or before the window is created.
+pub fn state() -> Option<WindowState> {
+    *STATE.lock().unwrap()
+}
+
+pub fn mode() -> WindowMode {
+    state().map(|state| state.mode).unwrap_or_default()
+}
+
+pub fn vsync() -> bool {
+    state().is_none_or(|state| state.vsync)
+}
+
+/// With `true`, closing the window doesn't quit the app.
+/// Instead [`is_close_requested`] becomes `true`, so the app can ask the player
+/// and then call [`close`] or [`cancel_close`].
+pub fn set_confirm_close(confirm: bool) {
+    CONFIRM_CLOSE.store(confirm, Ordering::Relaxed);
+}
+
+pub fn is_close_requested() -> bool {
+    CLOSE_REQUESTED.load(Ordering::Relaxed)
+}
+
+pub fn cancel_close() {
+    CLOSE_REQUESTED.store(false, Ordering::Relaxed);
+}
+
+/// Shuts down the app and closes the window.
+pub fn close() {
+    push(WindowCommand::Close);
+}
+
+/// Returns whether the runner should wait for the app instead of closing the window.
+pub(crate) fn on_close_requested() -> bool {
+    if !CONFIRM_CLOSE.load(Ordering::Relaxed) {
+        return false;
+    }
+    CLOSE_REQUESTED.store(true, Ordering::Relaxed);
+    true
+}
+
+pub(crate) fn take_commands() -> Vec<WindowCommand> {
+    std::mem::take(&mut *COMMANDS.lock().unwrap())
+}
+
+pub(crate) fn set_state(state: WindowState) {
+    *STATE.lock().unwrap() = Some(state);
+}
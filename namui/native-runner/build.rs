@@ -8,6 +8,8 @@ fn main() {
         ("NAMSH_BUILD_ID", "NAMUI_CRASH_BUILD_ID"),
         ("NAMSH_HMAC_KEY", "NAMUI_CRASH_HMAC_KEY"),
         ("NAMSH_URL", "NAMUI_CRASH_NAMSH_URL"),
+        ("NAMSH_REQUIRE_CONSENT", "NAMUI_CRASH_REQUIRE_CONSENT"),
+        ("NAMSH_RECORD_INPUT", "NAMUI_CRASH_RECORD_INPUT"),
     ];
     for (src, dst) in crash_env_mappings {
         println!("cargo:rerun-if-env-changed={src}");
@@ -2,11 +2,11 @@ use namui_rendering_tree::*;
 use namui_type::*;
 use winit::{
     application::ApplicationHandler,
-    dpi::LogicalSize,
-    event::WindowEvent,
-    event_loop::{ActiveEventLoop, EventLoop},
+    dpi::{LogicalSize, PhysicalPosition, PhysicalSize},
+    event::{DeviceEvent, DeviceId, WindowEvent},
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy},
     keyboard::PhysicalKey,
-    window::{Window, WindowAttributes, WindowId},
+    window::{CursorGrabMode, Fullscreen, Window, WindowAttributes, WindowId},
 };
 
 #[cfg(target_os = "windows")]
@@ -75,15 +75,31 @@ unsafe extern "C" {
     fn _set_screen_size(width: u16, height: u16);
     fn _shutdown();
     fn _on_animation_frame() -> *const u8;
+    fn _request_full_frame();
     fn _on_screen_resize(width: u16, height: u16) -> *const u8;
     fn _on_mouse_down(x: f32, y: f32, button: u8, buttons: u8) -> *const u8;
     fn _on_mouse_move(x: f32, y: f32, button: u8, buttons: u8) -> *const u8;
     fn _on_mouse_up(x: f32, y: f32, button: u8, buttons: u8) -> *const u8;
     fn _on_mouse_wheel(delta_x: f32, delta_y: f32, x: f32, y: f32) -> *const u8;
+    fn _on_mouse_motion(delta_x: f32, delta_y: f32);
     fn _on_key_down(code: u8) -> *const u8;
     fn _on_key_up(code: u8) -> *const u8;
     fn _on_blur() -> *const u8;
     fn _on_visibility_change() -> *const u8;
+    #[allow(clippy::too_many_arguments)]
+    fn _on_file_drop(
+        x: f32,
+        y: f32,
+        name_ptr: *const u8,
+        name_len: u32,
+        path_ptr: *const u8,
+        path_len: u32,
+        bytes_ptr: *const u8,
+        bytes_len: u32,
+    ) -> *const u8;
+    fn _window_commands() -> *const u8;
+    fn _set_window_state(ptr: *const u8, len: u32);
+    fn _on_window_close_requested() -> u8;
     fn _dylib_image_buffer_list(out: *mut usize, max_count: usize) -> usize;
     fn _dylib_register_font(
         name_ptr: *const u8,
@@ -109,10 +125,305 @@ unsafe fn decode_response(ptr: *const u8) -> Option<&'static [u8]> {
     Some(unsafe { std::slice::from_raw_parts(ptr.add(4), len) })
 }
 
+/// Events sent to the event loop from runner worker threads.
+enum RunnerEvent {
+    FileRead {
+        x: f32,
+        y: f32,
+        path: std::path::PathBuf,
+        bytes: Vec<u8>,
+    },
+}
+
 struct NamuiApp {
+    proxy: EventLoopProxy<RunnerEvent>,
     window: Option<Window>,
     skia: Option<namui_skia::NativeSkia>,
     cursor_sprite_set: Option<StandardCursorSpriteSet>,
+    window_state: Option<WindowState>,
+    /// When to save `window_state`, pushed back on every change while the window is dragged.
+    window_state_save_at: Option<std::time::Instant>,
+    cursor_grab: CursorGrab,
+    cursor_visible: bool,
+}
+
+const WINDOW_STATE_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// In the user data directory, so launching from another working directory keeps the window.
+fn window_state_path() -> Option<std::path::PathBuf> {
+    Some(
+        dirs::data_local_dir()?
+            .join(CRASH_APP_NAME)
+            .join("window_state.bin"),
+    )
+}
+
+fn load_window_state() -> Option<WindowState> {
+    let bytes = std::fs::read(window_state_path()?).ok()?;
+    let (state, _) = bincode::decode_from_slice(&bytes, bincode::config::standard()).ok()?;
+    Some(state)
+}
+
+fn save_window_state(state: &WindowState) {
+    let Some(path) = window_state_path() else {
+        eprintln!("[runner] Failed to save window state: no user data directory");
+        return;
+    };
+    let bytes = bincode::encode_to_vec(state, bincode::config::standard()).unwrap();
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, bytes));
+    if let Err(e) = result {
+        eprintln!("[runner] Failed to save window state: {e}");
+    }
+}
+
+fn is_on_any_monitor(event_loop: &ActiveEventLoop, xy: Xy<IntPx>) -> bool {
+    event_loop.available_monitors().any(|monitor| {
+        let position = monitor.position();
+        let size = monitor.size();
+        (position.x..position.x + size.width as i32).contains(&xy.x.as_i32())
+            && (position.y..position.y + size.height as i32).contains(&xy.y.as_i32())
+    })
+}
+
+fn apply_window_mode(window: &Window, mode: WindowMode) {
+    match mode {
+        WindowMode::Windowed => {
+            window.set_fullscreen(None);
+            window.set_decorations(true);
+        }
+        WindowMode::Borderless => {
+            window.set_fullscreen(None);
+            window.set_decorations(false);
+        }
+        WindowMode::Fullscreen => {
+            window.set_fullscreen(Some(Fullscreen::Borderless(None)));
+        }
+    }
+}
+
+fn apply_cursor_grab(window: &Window, grab: CursorGrab) {
+    let result = match grab {
+        CursorGrab::None => window.set_cursor_grab(CursorGrabMode::None),
+        CursorGrab::Confined => window
+            .set_cursor_grab(CursorGrabMode::Confined)
+            .or_else(|_| window.set_cursor_grab(CursorGrabMode::Locked)),
+        CursorGrab::Locked => window
+            .set_cursor_grab(CursorGrabMode::Locked)
+            .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined)),
+    };
+    if let Err(e) = result {
+        eprintln!("[runner] Failed to grab cursor: {e}");
+    }
+}
+
+impl NamuiApp {
+    fn report_window_state(&self) {
+        let Some(state) = self.window_state.as_ref() else {
+            return;
+        };
+        let bytes = bincode::encode_to_vec(state, bincode::config::standard()).unwrap();
+        unsafe {
+            _set_window_state(bytes.as_ptr(), bytes.len() as u32);
+        }
+    }
+
+    /// Saves the window state once it stops changing, so it survives a crash.
+    fn schedule_window_state_save(&mut self) {
+        self.window_state_save_at = Some(std::time::Instant::now() + WINDOW_STATE_SAVE_DELAY);
+    }
+
+    fn exit(&mut self, event_loop: &ActiveEventLoop) {
+        if let Some(state) = self.window_state.as_ref() {
+            save_window_state(state);
+        }
+        unsafe {
+            _shutdown();
+        }
+        event_loop.exit();
+    }
+
+    fn apply_window_commands(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(data) = (unsafe { decode_response(_window_commands()) }) else {
+            return;
+        };
+        let Ok((commands, _)) =
+            bincode::decode_from_slice::<Vec<WindowCommand>, _>(data, bincode::config::standard())
+        else {
+            eprintln!("[runner] Failed to decode window commands");
+            return;
+        };
+        let (Some(window), Some(skia), Some(state)) = (
+            self.window.as_ref(),
+            self.skia.as_mut(),
+            self.window_state.as_mut(),
+        ) else {
+            return;
+        };
+
+        let mut should_close = false;
+        for command in commands {
+            match command {
+                WindowCommand::SetTitle { title } => window.set_title(&title),
+                WindowCommand::SetSize { wh } => {
+                    let _ = window.request_inner_size(PhysicalSize::new(
+                        wh.width.as_i32() as u32,
+                        wh.height.as_i32() as u32,
+                    ));
+                }
+                WindowCommand::SetMinSize { wh } => window.set_min_inner_size(
+                    wh.map(|wh| PhysicalSize::new(wh.width.as_i32(), wh.height.as_i32())),
+                ),
+                WindowCommand::SetMaxSize { wh } => window.set_max_inner_size(
+                    wh.map(|wh| PhysicalSize::new(wh.width.as_i32(), wh.height.as_i32())),
+                ),
+                WindowCommand::SetPosition { xy } => {
+                    window.set_outer_position(PhysicalPosition::new(xy.x.as_i32(), xy.y.as_i32()))
+                }
+                WindowCommand::SetMode { mode } => {
+                    // Set before applying, so the fullscreen resize doesn't overwrite the windowed size.
+                    state.mode = mode;
+                    apply_window_mode(window, mode);
+                }
+                WindowCommand::SetVsync { vsync } => {
+                    state.vsync = vsync;
+                    skia.set_vsync(vsync);
+                }
+                WindowCommand::SetCursorGrab { grab } => {
+                    self.cursor_grab = grab;
+                    apply_cursor_grab(window, grab);
+                }
+                WindowCommand::SetCursorVisible { visible } => self.cursor_visible = visible,
+                WindowCommand::Close => {
+                    should_close = true;
+                    break;
+                }
+            }
+        }
+
+        if should_close {
+            self.exit(event_loop);
+            return;
+        }
+        self.report_window_state();
+        self.schedule_window_state_save();
+    }
+}
+
+/// Reads a dropped file on a worker thread. Files the FFI cannot describe with a
+/// u32 length are rejected instead of being truncated.
+fn read_dropped_file(path: &std::path::Path) -> Option<Vec<u8>> {
+    let too_large = |len: u64| {
+        eprintln!("[runner] Dropped file {path:?} is too large ({len} bytes)");
+    };
+    match std::fs::metadata(path) {
+        Ok(metadata) if metadata.len() > u32::MAX as u64 => {
+            too_large(metadata.len());
+            return None;
+        }
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("[runner] Failed to read dropped file {path:?}: {e}");
+            return None;
+        }
+    }
+    let bytes = std::fs::read(path)
+        .inspect_err(|e| eprintln!("[runner] Failed to read dropped file {path:?}: {e}"))
+        .ok()?;
+    if u32::try_from(bytes.len()).is_err() {
+        too_large(bytes.len() as u64);
+        return None;
+    }
+    Some(bytes)
+}
+
+/// winit sends no `CursorMoved` while the OS runs a drag session, so the drop
+/// position is asked from the OS instead of taken from `MOUSE_STATE`.
+fn drop_cursor_position(window: &Window) -> (f32, f32) {
+    let position = os_cursor_position(window).and_then(|screen| {
+        let inner = window.inner_position().ok()?;
+        Some((
+            (screen.x - inner.x as f64) as f32,
+            (screen.y - inner.y as f64) as f32,
+        ))
+    });
+    position.unwrap_or_else(|| {
+        MOUSE_STATE.with(|s| {
+            let s = s.borrow();
+            (s.x, s.y)
+        })
+    })
+}
+
+/// Cursor position in physical screen coordinates.
+#[cfg(target_os = "windows")]
+fn os_cursor_position(_window: &Window) -> Option<PhysicalPosition<f64>> {
+    use windows::Win32::{Foundation::POINT, UI::WindowsAndMessaging::GetCursorPos};
+
+    let mut point = POINT::default();
+    unsafe { GetCursorPos(&mut point) }.ok()?;
+    Some(PhysicalPosition::new(point.x as f64, point.y as f64))
+}
+
+/// Cursor position in physical screen coordinates.
+#[cfg(target_os = "macos")]
+fn os_cursor_position(window: &Window) -> Option<PhysicalPosition<f64>> {
+    use objc2::{Encode, Encoding, class, msg_send, runtime::AnyObject};
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct CGPoint {
+        x: f64,
+        y: f64,
+    }
+    unsafe impl Encode for CGPoint {
+        const ENCODING: Encoding = Encoding::Struct("CGPoint", &[f64::ENCODING, f64::ENCODING]);
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct CGSize {
+        width: f64,
+        height: f64,
+    }
+    unsafe impl Encode for CGSize {
+        const ENCODING: Encoding = Encoding::Struct("CGSize", &[f64::ENCODING, f64::ENCODING]);
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct CGRect {
+        origin: CGPoint,
+        size: CGSize,
+    }
+    unsafe impl Encode for CGRect {
+        const ENCODING: Encoding =
+            Encoding::Struct("CGRect", &[CGPoint::ENCODING, CGSize::ENCODING]);
+    }
+
+    // Cocoa screen coordinates start at the bottom-left of the primary screen.
+    let (location, primary_frame) = unsafe {
+        let location: CGPoint = msg_send![class!(NSEvent), mouseLocation];
+        let screens: *mut AnyObject = msg_send![class!(NSScreen), screens];
+        let primary: *mut AnyObject = msg_send![screens, firstObject];
+        if primary.is_null() {
+            return None;
+        }
+        let frame: CGRect = msg_send![primary, frame];
+        (location, frame)
+    };
+    let scale = window.scale_factor();
+    Some(PhysicalPosition::new(
+        location.x * scale,
+        (primary_frame.size.height - location.y) * scale,
+    ))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn os_cursor_position(_window: &Window) -> Option<PhysicalPosition<f64>> {
+    None
 }
 
 std::thread_local! {
@@ -158,16 +469,34 @@ fn dom_button_to_bitmask(button: u8) -> u8 {
     }
 }
 
-impl ApplicationHandler for NamuiApp {
+impl ApplicationHandler<RunnerEvent> for NamuiApp {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if self.window.is_some() {
             return;
         }
 
-        let size = LogicalSize::new(1280, 720);
+        let saved_state = load_window_state();
+
         let mut window_attributes = WindowAttributes::default();
-        window_attributes.inner_size = Some(winit::dpi::Size::new(size));
         window_attributes.title = "namui".to_string();
+        match saved_state {
+            Some(state) => {
+                window_attributes.inner_size = Some(winit::dpi::Size::new(PhysicalSize::new(
+                    state.wh.width.as_i32() as u32,
+                    state.wh.height.as_i32() as u32,
+                )));
+                // The monitor could be gone since the last launch.
+                if is_on_any_monitor(event_loop, state.xy) {
+                    window_attributes.position = Some(winit::dpi::Position::new(
+                        PhysicalPosition::new(state.xy.x.as_i32(), state.xy.y.as_i32()),
+                    ));
+                }
+            }
+            None => {
+                let size = LogicalSize::new(1280, 720);
+                window_attributes.inner_size = Some(winit::dpi::Size::new(size));
+            }
+        }
 
         let window = event_loop
             .create_window(window_attributes)
@@ -183,9 +512,20 @@ impl ApplicationHandler for NamuiApp {
             int_px(inner_size.height as i32),
         );
 
-        let skia = namui_skia::init_skia(&window, window_wh)
+        let mut skia = namui_skia::init_skia(&window, window_wh)
             .expect("Failed to initialize Skia Metal backend");
 
+        let position = window.outer_position().unwrap_or_default();
+        let window_state = WindowState {
+            xy: Xy::new(int_px(position.x), int_px(position.y)),
+            wh: window_wh,
+            mode: saved_state.map(|state| state.mode).unwrap_or_default(),
+            vsync: saved_state.is_none_or(|state| state.vsync),
+        };
+        apply_window_mode(&window, window_state.mode);
+        skia.set_vsync(window_state.vsync);
+        self.window_state = Some(window_state);
+
         unsafe {
             _init_system();
             namui_main();
@@ -217,26 +557,97 @@ impl ApplicationHandler for NamuiApp {
 
         self.window = Some(window);
         self.skia = Some(skia);
+        self.report_window_state();
 
         self.window.as_ref().unwrap().request_redraw();
     }
 
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, event: RunnerEvent) {
+        match event {
+            RunnerEvent::FileRead { x, y, path, bytes } => {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let path = path.to_string_lossy();
+                unsafe {
+                    _on_file_drop(
+                        x,
+                        y,
+                        name.as_ptr(),
+                        name.len() as u32,
+                        path.as_ptr(),
+                        path.len() as u32,
+                        bytes.as_ptr(),
+                        bytes.len() as u32,
+                    );
+                }
+                if let Some(window) = self.window.as_ref() {
+                    window.request_redraw();
+                }
+            }
+        }
+    }
+
+    /// A locked cursor doesn't move, so only the raw motion tells how the mouse moved.
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        if self.cursor_grab != CursorGrab::Locked {
+            return;
+        }
+        let DeviceEvent::MouseMotion { delta: (dx, dy) } = event else {
+            return;
+        };
+        unsafe {
+            _on_mouse_motion(dx as f32, dy as f32);
+        }
+        if let Some(window) = self.window.as_ref() {
+            window.request_redraw();
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(save_at) = self.window_state_save_at else {
+            return;
+        };
+        if std::time::Instant::now() < save_at {
+            event_loop.set_control_flow(ControlFlow::WaitUntil(save_at));
+            return;
+        }
+        self.window_state_save_at = None;
+        event_loop.set_control_flow(ControlFlow::Wait);
+        if let Some(state) = self.window_state.as_ref() {
+            save_window_state(state);
+        }
+    }
+
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
         _window_id: WindowId,
         event: WindowEvent,
     ) {
+        // Before borrowing skia below. Requests from this frame are applied on the next one.
+        if matches!(event, WindowEvent::RedrawRequested) {
+            self.apply_window_commands(event_loop);
+            if event_loop.exiting() {
+                return;
+            }
+        }
+
         let Some(skia) = self.skia.as_mut() else {
             return;
         };
 
         match event {
             WindowEvent::CloseRequested => {
-                unsafe {
-                    _shutdown();
+                if unsafe { _on_window_close_requested() } == 0 {
+                    self.exit(event_loop);
                 }
-                event_loop.exit();
             }
             WindowEvent::Resized(size) => {
                 let wh = Wh::new(int_px(size.width as i32), int_px(size.height as i32));
@@ -244,8 +655,24 @@ impl ApplicationHandler for NamuiApp {
                 unsafe {
                     _on_screen_resize(size.width as u16, size.height as u16);
                 }
+                if let Some(state) = self.window_state.as_mut()
+                    && state.mode != WindowMode::Fullscreen
+                {
+                    state.wh = wh;
+                    self.report_window_state();
+                    self.schedule_window_state_save();
+                }
                 self.window.as_ref().unwrap().request_redraw();
             }
+            WindowEvent::Moved(position) => {
+                if let Some(state) = self.window_state.as_mut()
+                    && state.mode != WindowMode::Fullscreen
+                {
+                    state.xy = Xy::new(int_px(position.x), int_px(position.y));
+                    self.report_window_state();
+                    self.schedule_window_state_save();
+                }
+            }
             WindowEvent::RedrawRequested => {
                 let response = unsafe { decode_response(_on_animation_frame()) };
 
@@ -257,14 +684,24 @@ impl ApplicationHandler for NamuiApp {
                 skia.move_to_next_frame();
                 skia.surface().canvas().clear(Color::WHITE);
 
-                let sprite_set = self.cursor_sprite_set.as_ref();
+                let sprite_set = self
+                    .cursor_sprite_set
+                    .as_ref()
+                    .filter(|_| self.cursor_visible);
 
                 match response {
                     Some(data) if !data.is_empty() => {
                         let _arena_scope = (!namui_rendering_tree::is_arena_scope_active())
                             .then(namui_rendering_tree::enter_arena_scope);
-                        let (rendering_tree, _): (namui_rendering_tree::RenderingTree, usize) =
-                            bincode::decode_from_slice(data, bincode::config::standard()).unwrap();
+                        let rendering_tree =
+                            match namui_rendering_tree::decode_rendering_tree(data) {
+                                Ok(rendering_tree) => rendering_tree,
+                                Err(error) => {
+                                    eprintln!("[runner] Failed to decode rendering tree: {error}");
+                                    unsafe { _request_full_frame() };
+                                    namui_rendering_tree::RenderingTree::Empty
+                                }
+                            };
                         namui_drawer::draw_rendering_tree(skia, rendering_tree, mx, my, sprite_set);
                     }
                     _ => {
@@ -338,6 +775,21 @@ impl ApplicationHandler for NamuiApp {
                     self.window.as_ref().unwrap().request_redraw();
                 }
             }
+            WindowEvent::DroppedFile(path) => {
+                let (x, y) = drop_cursor_position(self.window.as_ref().unwrap());
+                MOUSE_STATE.with(|s| {
+                    let mut s = s.borrow_mut();
+                    s.x = x;
+                    s.y = y;
+                });
+                let proxy = self.proxy.clone();
+                std::thread::spawn(move || {
+                    let Some(bytes) = read_dropped_file(&path) else {
+                        return;
+                    };
+                    let _ = proxy.send_event(RunnerEvent::FileRead { x, y, path, bytes });
+                });
+            }
             WindowEvent::Focused(false) => unsafe {
                 _on_blur();
             },
@@ -365,7 +817,10 @@ pub const CRASH_APP_NAME: &str = "namui-game";
 
 /// Build the crash-reporter [`Config`] from compile-time env that
 /// `native-runner/build.rs` forwards (`NAMUI_CRASH_BUILD_ID`,
-/// `NAMUI_CRASH_HMAC_KEY`, `NAMUI_CRASH_NAMSH_URL`).
+/// `NAMUI_CRASH_HMAC_KEY`, `NAMUI_CRASH_NAMSH_URL`, and the optional
+/// `NAMUI_CRASH_REQUIRE_CONSENT=1` for builds that must ask the player first,
+/// and `NAMUI_CRASH_RECORD_INPUT=1` to keep the input recording with crash
+/// reports).
 ///
 /// Returns `None` when any value is missing/empty — i.e. when the binary was
 /// built without `NAMSH_*` env set (dev builds, `cargo run` without the
@@ -383,6 +838,15 @@ pub fn build_crash_config() -> Option<namui_crash_reporter::Config> {
         hmac_key_hex: hmac_key_hex.into(),
         namsh_url: namsh_url.trim_end_matches('/').into(),
         app_name: CRASH_APP_NAME.into(),
+        require_consent: matches!(
+            option_env!("NAMUI_CRASH_REQUIRE_CONSENT"),
+            Some("1" | "true")
+        ),
+        record_input: matches!(
+            option_env!("NAMUI_CRASH_RECORD_INPUT"),
+            Some("1" | "true")
+        ),
+        scrub_rules: namui_crash_reporter::default_scrub_rules(),
     })
 }
 
@@ -443,12 +907,19 @@ pub fn run_with_font_dir(font_dir: &std::path::Path) {
     let system_bundle_dir = font_dir.parent().unwrap();
     let cursor_sprite_set = load_cursor_sprite_set(system_bundle_dir);
 
-    let event_loop = EventLoop::new().expect("Failed to create event loop");
+    let event_loop = EventLoop::<RunnerEvent>::with_user_event()
+        .build()
+        .expect("Failed to create event loop");
 
     let mut app = NamuiApp {
+        proxy: event_loop.create_proxy(),
         window: None,
         skia: None,
         cursor_sprite_set,
+        window_state: None,
+        window_state_save_at: None,
+        cursor_grab: CursorGrab::None,
+        cursor_visible: true,
     };
 
     #[cfg(target_os = "macos")]
@@ -891,4 +1362,16 @@ mod tests {
             width_ga.as_f32()
         );
     }
+
+    #[test]
+    fn dropped_file_is_read_whole_or_not_at_all() {
+        let path = std::env::temp_dir().join(format!("namui-dropped-{}", std::process::id()));
+        std::fs::write(&path, b"scene").unwrap();
+        assert_eq!(
+            super::read_dropped_file(&path).as_deref(),
+            Some(&b"scene"[..])
+        );
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(super::read_dropped_file(&path), None);
+    }
 }
@@ -114,8 +114,8 @@ fn main() {
     };
 
     // Note: do NOT install a panic hook here or wrap `run_with_font_dir` in
-    // `catch_unwind`. crash-reporter::init() installs the panic→abort hook
-    // that ferries Rust panics into the minidumper; overriding it or
-    // swallowing the unwind would prevent the dump from ever being produced.
+    // `catch_unwind`. crash-reporter::init() installs the hook that queues
+    // Rust panics as crash reports; overriding it would drop those reports,
+    // and swallowing the unwind would hide a panic that should end the run.
     native_runner::run_with_font_dir(font_dir);
 }
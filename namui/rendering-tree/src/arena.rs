@@ -15,6 +15,8 @@ thread_local! {
         drops: RefCell::new(Vec::new()),
     };
     static SCOPE_ACTIVE: Cell<bool> = const { Cell::new(false) };
+    /// Set while [`RetainedArena::scope`] runs.
+    static REDIRECT: Cell<*const RenderArena> = const { Cell::new(std::ptr::null()) };
 }
 
 unsafe fn drop_in_place_as<T>(ptr: *mut u8) {
@@ -22,6 +24,11 @@ unsafe fn drop_in_place_as<T>(ptr: *mut u8) {
 }
 
 fn with_arena<R>(f: impl FnOnce(&'static RenderArena) -> R) -> R {
+    let redirect = REDIRECT.with(Cell::get);
+    if !redirect.is_null() {
+        // SAFETY: `RetainedArena::scope` clears the redirect before the arena can be dropped.
+        return f(unsafe { &*redirect });
+    }
     ARENA.with(|arena| {
         // SAFETY: the thread-local arena lives for the whole thread lifetime,
         // so a `'static` view is valid as long as nothing reads an allocation
@@ -33,7 +40,7 @@ fn with_arena<R>(f: impl FnOnce(&'static RenderArena) -> R) -> R {
 
 fn debug_assert_scope_active() {
     debug_assert!(
-        SCOPE_ACTIVE.with(|s| s.get()),
+        SCOPE_ACTIVE.with(|s| s.get()) || !REDIRECT.with(Cell::get).is_null(),
         "arena_alloc/arena_alloc_slice called outside an arena scope. \
          Wrap the entry point with `swap_arena_slot`, or hold an `ArenaScopeGuard` \
          around the build (see `enter_arena_scope`)."
@@ -133,6 +140,50 @@ pub fn reset_render_arena() {
     })
 }
 
+/// Arena whose allocations outlive frames, for receivers which keep decoded subtrees across
+/// frames. Unlike the frame arena, it is never reset; its allocations are freed when it is
+/// dropped.
+pub struct RetainedArena {
+    arena: Box<RenderArena>,
+}
+
+impl Default for RetainedArena {
+    fn default() -> Self {
+        Self {
+            arena: Box::new(RenderArena {
+                bump: UnsafeCell::new(Bump::new()),
+                drops: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+}
+
+impl RetainedArena {
+    /// Runs `build` with `arena_alloc`/`arena_alloc_slice` allocating in this arena instead of
+    /// the frame arena. The allocations stay valid until this arena is dropped.
+    pub fn scope<R>(&self, build: impl FnOnce() -> R) -> R {
+        struct RestoreRedirect(*const RenderArena);
+        impl Drop for RestoreRedirect {
+            fn drop(&mut self) {
+                REDIRECT.with(|redirect| redirect.set(self.0));
+            }
+        }
+
+        let _restore = RestoreRedirect(
+            REDIRECT.with(|redirect| redirect.replace(&*self.arena as *const RenderArena)),
+        );
+        build()
+    }
+}
+
+impl Drop for RetainedArena {
+    fn drop(&mut self) {
+        for (ptr, drop_fn) in self.arena.drops.take() {
+            unsafe { drop_fn(ptr) }
+        }
+    }
+}
+
 /// Swaps the value held by a thread-local slot, replacing any arena-borrowing
 /// payload safely:
 ///
@@ -148,9 +148,9 @@ impl BoundingBox for &RenderingTree {
                             bounding_box_context,
                         )
                     }
-                    SpecialRenderingNode::OnTop(on_top) => {
+                    SpecialRenderingNode::OnTop(_) | SpecialRenderingNode::ZLayer(_) => {
                         let bounding_box = get_bounding_box_with_matrix_of_rendering_trees(
-                            [on_top.rendering_tree],
+                            [special.inner_rendering_tree_ref()],
                             matrix,
                             bounding_box_context,
                         );
@@ -159,13 +159,29 @@ impl BoundingBox for &RenderingTree {
                             .push(bounding_box);
                         bounding_box
                     }
-                    SpecialRenderingNode::MouseCursor(_) => {
+                    SpecialRenderingNode::MouseCursor(_) | SpecialRenderingNode::Cache(_) => {
                         get_bounding_box_with_matrix_of_rendering_trees(
                             [special.inner_rendering_tree_ref()],
                             matrix,
                             bounding_box_context,
                         )
                     }
+                    SpecialRenderingNode::Layer(layer) => {
+                        let bounding_box = get_bounding_box_with_matrix_of_rendering_trees(
+                            [layer.rendering_tree],
+                            matrix,
+                            bounding_box_context,
+                        );
+                        if layer.layer.image_filter.is_none() {
+                            return bounding_box;
+                        }
+                        // Image filters work in the local space of the layer.
+                        let inverse_matrix = matrix.inverse()?;
+                        bounding_box.map(|bounding_box| {
+                            let local_bounding_box = inverse_matrix.transform_rect(bounding_box);
+                            matrix.transform_rect(layer.layer.output_bounds(local_bounding_box))
+                        })
+                    }
                 },
                 RenderingTree::Empty => None,
             }
@@ -1,5 +1,12 @@
 use crate::*;
 use bincode::{Decode, de::Decoder, error::DecodeError};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+    ops::Range,
+    sync::Arc,
+};
 
 type Ctx = ();
 
@@ -54,6 +61,9 @@ impl Decode<Ctx> for SpecialRenderingNode {
             5 => SpecialRenderingNode::Transform(TransformNode::decode(d)?),
             6 => SpecialRenderingNode::OnTop(OnTopNode::decode(d)?),
             7 => SpecialRenderingNode::MouseCursor(MouseCursorNode::decode(d)?),
+            8 => SpecialRenderingNode::Layer(LayerNode::decode(d)?),
+            9 => SpecialRenderingNode::Cache(CacheNode::decode(d)?),
+            10 => SpecialRenderingNode::ZLayer(ZLayerNode::decode(d)?),
             _ => return Err(DecodeError::Other("invalid SpecialRenderingNode variant")),
         })
     }
@@ -144,6 +154,359 @@ impl Decode<Ctx> for MouseCursorNode {
     }
 }
 
+impl Decode<Ctx> for LayerNode {
+    fn decode<D: Decoder<Context = Ctx>>(d: &mut D) -> Result<Self, DecodeError> {
+        Ok(LayerNode {
+            layer: decode_ref(d)?,
+            rendering_tree: decode_ref(d)?,
+        })
+    }
+}
+
+impl Decode<Ctx> for CacheNode {
+    fn decode<D: Decoder<Context = Ctx>>(d: &mut D) -> Result<Self, DecodeError> {
+        Ok(CacheNode {
+            key: Decode::decode(d)?,
+            rendering_tree: decode_ref(d)?,
+        })
+    }
+}
+
+impl Decode<Ctx> for ZLayerNode {
+    fn decode<D: Decoder<Context = Ctx>>(d: &mut D) -> Result<Self, DecodeError> {
+        Ok(ZLayerNode {
+            z: Decode::decode(d)?,
+            rendering_tree: decode_ref(d)?,
+        })
+    }
+}
+
+// Incremental transfer
+//
+// A frame is encoded as a tree of tagged nodes. Every non-empty node carries its content
+// hash, so the receiver can keep it. A node which was in the previous frame is sent as
+// `TAG_SENT` with its hash only, and the receiver reuses the subtree it decoded before.
+//
+// - `TAG_EMPTY`
+// - `TAG_NODE` hash `DrawCommand`
+// - `TAG_CHILDREN` hash len children...
+// - `TAG_SPECIAL` hash `SpecialRenderingNode` with empty inner tree, inner tree
+// - `TAG_SENT` hash
+
+const TAG_EMPTY: u8 = 0;
+const TAG_NODE: u8 = 1;
+const TAG_CHILDREN: u8 = 2;
+const TAG_SPECIAL: u8 = 3;
+const TAG_SENT: u8 = 4;
+
+const EMPTY: &RenderingTree = &RenderingTree::Empty;
+
+/// The receiver can't tell two subtrees with the same hash apart, so 64 bits is too few for
+/// all the subtrees of a long session.
+type TreeHash = u128;
+
+/// Two SipHash streams, one of them salted. The input is not adversarial, so the salt
+/// separates them as well as a second key would.
+struct TreeHasher([DefaultHasher; 2]);
+
+impl TreeHasher {
+    fn new() -> Self {
+        let mut salted = DefaultHasher::new();
+        salted.write_u8(0xa5);
+        Self([DefaultHasher::new(), salted])
+    }
+    fn finish_tree_hash(&self) -> TreeHash {
+        ((self.0[0].finish() as TreeHash) << 64) | self.0[1].finish() as TreeHash
+    }
+}
+
+impl Hasher for TreeHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for hasher in &mut self.0 {
+            hasher.write(bytes);
+        }
+    }
+    fn finish(&self) -> u64 {
+        self.0[0].finish()
+    }
+}
+
+thread_local! {
+    static SENT_HASHES: RefCell<HashSet<TreeHash>> = RefCell::default();
+    static MIRROR: RefCell<Mirror> = RefCell::default();
+}
+
+/// Encode the rendering tree of a frame, sending only subtrees which were not in the
+/// previous frame. The receiver must decode every encoded frame in order with
+/// [`decode_rendering_tree`].
+pub fn encode_rendering_tree(rendering_tree: RenderingTree) -> Vec<u8> {
+    let mut hashes = Vec::new();
+    hash_tree(&rendering_tree, &mut hashes);
+
+    let mut bytes = Vec::new();
+    SENT_HASHES.with_borrow_mut(|sent_hashes| {
+        write_tree(&rendering_tree, &hashes, &mut 0, sent_hashes, &mut bytes);
+        *sent_hashes = hashes.iter().map(|(hash, _)| *hash).collect();
+    });
+    bytes
+}
+
+/// Makes the next [`encode_rendering_tree`] send the whole tree.
+/// Call this when the receiver failed to decode a frame.
+pub fn reset_rendering_tree_encoder() {
+    SENT_HASHES.with_borrow_mut(HashSet::clear);
+}
+
+/// Returns the content hash, and pushes `(hash, subtree node count)` in pre-order.
+fn hash_tree(rendering_tree: &RenderingTree, hashes: &mut Vec<(TreeHash, usize)>) -> TreeHash {
+    let index = hashes.len();
+    hashes.push((0, 0));
+
+    let mut hasher = TreeHasher::new();
+    match rendering_tree {
+        RenderingTree::Empty => TAG_EMPTY.hash(&mut hasher),
+        RenderingTree::Node(draw_command) => {
+            TAG_NODE.hash(&mut hasher);
+            draw_command.hash(&mut hasher);
+        }
+        RenderingTree::Children(children) => {
+            TAG_CHILDREN.hash(&mut hasher);
+            children.len().hash(&mut hasher);
+            for child in children.iter() {
+                hash_tree(child, hashes).hash(&mut hasher);
+            }
+        }
+        RenderingTree::Special(special) => {
+            TAG_SPECIAL.hash(&mut hasher);
+            special.with_inner_rendering_tree(EMPTY).hash(&mut hasher);
+            hash_tree(special.inner_rendering_tree_ref(), hashes).hash(&mut hasher);
+        }
+    }
+    let hash = hasher.finish_tree_hash();
+
+    hashes[index] = (hash, hashes.len() - index);
+    hash
+}
+
+fn write_tree(
+    rendering_tree: &RenderingTree,
+    hashes: &[(TreeHash, usize)],
+    index: &mut usize,
+    sent_hashes: &HashSet<TreeHash>,
+    bytes: &mut Vec<u8>,
+) {
+    let (hash, node_count) = hashes[*index];
+
+    if *rendering_tree == RenderingTree::Empty {
+        *index += 1;
+        bytes.push(TAG_EMPTY);
+        return;
+    }
+    if sent_hashes.contains(&hash) {
+        *index += node_count;
+        bytes.push(TAG_SENT);
+        bytes.extend_from_slice(&hash.to_le_bytes());
+        return;
+    }
+    *index += 1;
+
+    let config = bincode::config::standard();
+    match rendering_tree {
+        RenderingTree::Empty => unreachable!(),
+        RenderingTree::Node(draw_command) => {
+            bytes.push(TAG_NODE);
+            bytes.extend_from_slice(&hash.to_le_bytes());
+            bincode::encode_into_std_write(draw_command, bytes, config).unwrap();
+        }
+        RenderingTree::Children(children) => {
+            bytes.push(TAG_CHILDREN);
+            bytes.extend_from_slice(&hash.to_le_bytes());
+            bytes.extend_from_slice(&(children.len() as u32).to_le_bytes());
+            for child in children.iter() {
+                write_tree(child, hashes, index, sent_hashes, bytes);
+            }
+        }
+        RenderingTree::Special(special) => {
+            bytes.push(TAG_SPECIAL);
+            bytes.extend_from_slice(&hash.to_le_bytes());
+            bincode::encode_into_std_write(special.with_inner_rendering_tree(EMPTY), bytes, config)
+                .unwrap();
+            write_tree(
+                special.inner_rendering_tree_ref(),
+                hashes,
+                index,
+                sent_hashes,
+                bytes,
+            );
+        }
+    }
+}
+
+/// Decode a frame encoded by [`encode_rendering_tree`], keeping its subtrees for the
+/// following frames. Subtrees not in this frame are dropped.
+///
+/// The returned tree is valid until the next call. Fails if a frame was lost or decoded out
+/// of order; the kept subtrees are dropped then, so call [`reset_rendering_tree_encoder`] on
+/// the sender to get a whole frame next.
+pub fn decode_rendering_tree(bytes: &[u8]) -> Result<RenderingTree, DecodeError> {
+    MIRROR.with_borrow_mut(|mirror| {
+        let result = mirror.decode(bytes);
+        if result.is_err() {
+            *mirror = Mirror::default();
+        }
+        result
+    })
+}
+
+#[derive(Default)]
+struct Mirror {
+    /// Holds the decoded subtrees. Subtrees of dropped entries stay in it until it is
+    /// compacted.
+    arena: RetainedArena,
+    entries: HashMap<TreeHash, MirrorEntry>,
+    /// Entries dropped since `arena` was made.
+    evicted_count: usize,
+}
+
+struct MirrorEntry {
+    rendering_tree: RenderingTree,
+    /// Encoded subtree, to decode it again into a new arena on compaction.
+    bytes: Arc<[u8]>,
+    range: Range<usize>,
+    children: Vec<TreeHash>,
+    used: bool,
+}
+
+impl Mirror {
+    fn decode(&mut self, bytes: &[u8]) -> Result<RenderingTree, DecodeError> {
+        let bytes: Arc<[u8]> = bytes.into();
+        let (_, rendering_tree) = self
+            .arena
+            .scope(|| read_tree(&bytes, &mut 0, &mut self.entries, None))?;
+
+        let entry_count = self.entries.len();
+        self.entries
+            .retain(|_, entry| std::mem::take(&mut entry.used));
+        self.evicted_count += entry_count - self.entries.len();
+
+        if self.evicted_count > self.entries.len() {
+            return self.compact(&bytes);
+        }
+        Ok(rendering_tree)
+    }
+
+    /// Decodes the frame again into a new arena, so the subtrees of dropped entries are
+    /// freed. Costs as much as decoding a whole frame, once in a while.
+    fn compact(&mut self, bytes: &Arc<[u8]>) -> Result<RenderingTree, DecodeError> {
+        let previous = std::mem::take(self);
+        let (_, rendering_tree) = self
+            .arena
+            .scope(|| read_tree(bytes, &mut 0, &mut self.entries, Some(&previous.entries)))?;
+        for entry in self.entries.values_mut() {
+            entry.used = false;
+        }
+        Ok(rendering_tree)
+    }
+}
+
+/// Returns the hash of the subtree, `None` for `Empty`.
+///
+/// `TAG_SENT` is resolved from `entries`, or while compacting, by decoding the subtree
+/// again from `previous_entries`.
+fn read_tree(
+    bytes: &Arc<[u8]>,
+    position: &mut usize,
+    entries: &mut HashMap<TreeHash, MirrorEntry>,
+    previous_entries: Option<&HashMap<TreeHash, MirrorEntry>>,
+) -> Result<(Option<TreeHash>, RenderingTree), DecodeError> {
+    let start = *position;
+    let tag = read_bytes::<1>(bytes, position)?[0];
+    if tag == TAG_EMPTY {
+        return Ok((None, RenderingTree::Empty));
+    }
+    let hash = TreeHash::from_le_bytes(read_bytes(bytes, position)?);
+
+    if tag == TAG_SENT {
+        if let Some(entry) = entries.get(&hash) {
+            let rendering_tree = entry.rendering_tree;
+            mark_used(entries, hash);
+            return Ok((Some(hash), rendering_tree));
+        }
+        let entry = previous_entries
+            .and_then(|previous_entries| previous_entries.get(&hash))
+            .ok_or(DecodeError::Other("rendering tree is not in the mirror"))?;
+        let mut entry_position = entry.range.start;
+        return read_tree(&entry.bytes, &mut entry_position, entries, previous_entries);
+    }
+
+    let config = bincode::config::standard();
+    let mut children = Vec::new();
+    let rendering_tree = match tag {
+        TAG_NODE => {
+            let (draw_command, length) = bincode::decode_from_slice(&bytes[*position..], config)?;
+            *position += length;
+            RenderingTree::Node(draw_command)
+        }
+        TAG_CHILDREN => {
+            let length = u32::from_le_bytes(read_bytes(bytes, position)?) as usize;
+            let mut child_trees = Vec::with_capacity(length);
+            for _ in 0..length {
+                let (child_hash, child_tree) =
+                    read_tree(bytes, position, entries, previous_entries)?;
+                children.extend(child_hash);
+                child_trees.push(child_tree);
+            }
+            RenderingTree::Children(arena_alloc_slice(child_trees))
+        }
+        TAG_SPECIAL => {
+            let (special, length): (SpecialRenderingNode, usize) =
+                bincode::decode_from_slice(&bytes[*position..], config)?;
+            *position += length;
+            let (inner_hash, inner) = read_tree(bytes, position, entries, previous_entries)?;
+            children.extend(inner_hash);
+            RenderingTree::Special(special.with_inner_rendering_tree(arena_alloc(inner)))
+        }
+        _ => return Err(DecodeError::Other("invalid rendering tree tag")),
+    };
+
+    entries.insert(
+        hash,
+        MirrorEntry {
+            rendering_tree,
+            bytes: bytes.clone(),
+            range: start..*position,
+            children,
+            used: true,
+        },
+    );
+    Ok((Some(hash), rendering_tree))
+}
+
+/// Keeps the entry and its descendants, which the sender can refer to in the next frame.
+fn mark_used(entries: &mut HashMap<TreeHash, MirrorEntry>, hash: TreeHash) {
+    let Some(entry) = entries.get_mut(&hash) else {
+        return;
+    };
+    if std::mem::replace(&mut entry.used, true) {
+        return;
+    }
+    let children = std::mem::take(&mut entry.children);
+    for child in &children {
+        mark_used(entries, *child);
+    }
+    entries.get_mut(&hash).unwrap().children = children;
+}
+
+fn read_bytes<const N: usize>(bytes: &[u8], position: &mut usize) -> Result<[u8; N], DecodeError> {
+    let slice = bytes
+        .get(*position..*position + N)
+        .ok_or(DecodeError::UnexpectedEnd {
+            additional: *position + N - bytes.len(),
+        })?;
+    *position += N;
+    Ok(slice.try_into().unwrap())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,4 +534,176 @@ mod tests {
 
         assert_eq!(tree, decoded);
     }
+
+    #[test]
+    fn layer_encode_decode_round_trip() {
+        let _scope = enter_arena_scope();
+        let tree = layer(
+            Layer::new()
+                .set_opacity(0.5)
+                .set_blend_mode(BlendMode::Multiply)
+                .set_image_filter(ImageFilter::drop_shadow(
+                    Xy::new(2.px(), 3.px()),
+                    4.0,
+                    Color::BLACK,
+                ))
+                .set_backdrop_filter(ImageFilter::blur(8.0)),
+            RenderingTree::Special(SpecialRenderingNode::OnTop(OnTopNode {
+                rendering_tree: arena_alloc(RenderingTree::Empty),
+            })),
+        );
+
+        let bytes = bincode::encode_to_vec(tree, bincode::config::standard()).unwrap();
+        let (decoded, _): (RenderingTree, usize) =
+            bincode::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+
+        assert_eq!(tree, decoded);
+    }
+
+    fn leaf(width: i32) -> RenderingTree {
+        RenderingTree::Node(DrawCommand::Path {
+            command: arena_alloc(PathDrawCommand {
+                path: Path::new().add_rect(Rect::Xywh {
+                    x: 0.px(),
+                    y: 0.px(),
+                    width: width.px(),
+                    height: 10.px(),
+                }),
+                paint: Paint::default(),
+            }),
+        })
+    }
+
+    #[test]
+    fn incremental_encode_sends_only_changed_subtrees() {
+        let _scope = enter_arena_scope();
+        let static_subtree = RenderingTree::Children(arena_alloc_slice(
+            (0..32)
+                .map(|i| translate(i.px(), 0.px(), opacity(0.5, leaf(i))))
+                .collect::<Vec<_>>(),
+        ));
+        let frame = |width: i32| {
+            RenderingTree::Children(arena_alloc_slice(vec![leaf(width), static_subtree]))
+        };
+
+        let first = frame(1);
+        let first_bytes = encode_rendering_tree(first);
+        assert_eq!(decode_rendering_tree(&first_bytes).unwrap(), first);
+
+        let second = frame(2);
+        let second_bytes = encode_rendering_tree(second);
+        assert!(second_bytes.len() * 4 < first_bytes.len());
+        assert_eq!(decode_rendering_tree(&second_bytes).unwrap(), second);
+
+        let third = frame(3);
+        assert_eq!(
+            decode_rendering_tree(&encode_rendering_tree(third)).unwrap(),
+            third
+        );
+    }
+
+    fn mirror_entry_count() -> usize {
+        MIRROR.with_borrow(|mirror| mirror.entries.len())
+    }
+
+    fn static_children(rendering_tree: RenderingTree) -> &'static [RenderingTree] {
+        let RenderingTree::Children(children) = rendering_tree else {
+            panic!("frame is not children");
+        };
+        let RenderingTree::Children(static_children) = children[1] else {
+            panic!("static subtree is not children");
+        };
+        static_children
+    }
+
+    #[test]
+    fn unchanged_subtrees_are_not_decoded_again() {
+        let _scope = enter_arena_scope();
+        let static_subtree = RenderingTree::Children(arena_alloc_slice(
+            (0..4)
+                .map(|i| translate(i.px(), 0.px(), leaf(i)))
+                .collect::<Vec<_>>(),
+        ));
+        let frame = |width: i32| {
+            RenderingTree::Children(arena_alloc_slice(vec![leaf(width), static_subtree]))
+        };
+
+        let first = decode_rendering_tree(&encode_rendering_tree(frame(1))).unwrap();
+        let first_static_children = static_children(first);
+        let second = decode_rendering_tree(&encode_rendering_tree(frame(2))).unwrap();
+
+        assert_eq!(second, frame(2));
+        assert!(std::ptr::eq(first_static_children, static_children(second)));
+    }
+
+    #[test]
+    fn lost_frame_fails_decoding_until_whole_frame_is_sent() {
+        let _scope = enter_arena_scope();
+        let frame = |widths: &[i32]| {
+            RenderingTree::Children(arena_alloc_slice(
+                widths.iter().map(|width| leaf(*width)).collect::<Vec<_>>(),
+            ))
+        };
+
+        decode_rendering_tree(&encode_rendering_tree(frame(&[1]))).unwrap();
+        // Lost on the way, so the receiver never sees `leaf(2)`.
+        let _ = encode_rendering_tree(frame(&[1, 2]));
+
+        assert!(decode_rendering_tree(&encode_rendering_tree(frame(&[1, 2, 3]))).is_err());
+        assert_eq!(mirror_entry_count(), 0);
+
+        reset_rendering_tree_encoder();
+        let whole = frame(&[1, 2, 3]);
+        assert_eq!(
+            decode_rendering_tree(&encode_rendering_tree(whole)).unwrap(),
+            whole
+        );
+    }
+
+    #[test]
+    fn subtrees_missing_from_frame_are_evicted() {
+        let _scope = enter_arena_scope();
+        let frame = |widths: &[i32]| {
+            RenderingTree::Children(arena_alloc_slice(
+                widths.iter().map(|width| leaf(*width)).collect::<Vec<_>>(),
+            ))
+        };
+
+        decode_rendering_tree(&encode_rendering_tree(frame(&[1, 2, 3]))).unwrap();
+        assert_eq!(mirror_entry_count(), 4);
+
+        decode_rendering_tree(&encode_rendering_tree(frame(&[1]))).unwrap();
+        assert_eq!(mirror_entry_count(), 2);
+
+        let again = frame(&[1, 2, 3]);
+        assert_eq!(
+            decode_rendering_tree(&encode_rendering_tree(again)).unwrap(),
+            again
+        );
+        assert_eq!(mirror_entry_count(), 4);
+    }
+
+    #[test]
+    fn compaction_keeps_decoding_frames() {
+        let _scope = enter_arena_scope();
+        let static_subtree = RenderingTree::Children(arena_alloc_slice(
+            (0..4)
+                .map(|i| translate(i.px(), 0.px(), leaf(i)))
+                .collect::<Vec<_>>(),
+        ));
+        let frame = |width: i32| {
+            RenderingTree::Children(arena_alloc_slice(vec![leaf(width), static_subtree]))
+        };
+
+        for width in 0..64 {
+            let rendering_tree = frame(width);
+            assert_eq!(
+                decode_rendering_tree(&encode_rendering_tree(rendering_tree)).unwrap(),
+                rendering_tree
+            );
+            MIRROR.with_borrow(|mirror| {
+                assert!(mirror.evicted_count <= mirror.entries.len());
+            });
+        }
+    }
 }
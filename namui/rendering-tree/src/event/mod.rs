@@ -19,6 +19,7 @@ pub enum Event<'a> {
     TextInput { event: &'a RawTextInputEvent },
     TextInputKeyDown { event: &'a RawTextInputKeyDownEvent },
     TextInputSelectionChange { event: &'a RawTextInputEvent },
+    FileDrop { event: FileDropEvent<'a> },
 }
 
 pub trait EventExt {
@@ -95,6 +96,37 @@ impl WheelEvent<'_> {
     }
 }
 
+pub struct FileDropEvent<'a> {
+    pub local_xy: &'a dyn Fn() -> Xy<Px>,
+    pub is_local_xy_in: &'a dyn Fn() -> bool,
+    pub global_xy: Xy<Px>,
+    pub file: &'a DroppedFile,
+    pub is_stop_event_propagation: &'a AtomicBool,
+}
+impl Debug for FileDropEvent<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileDropEvent")
+            .field("global_xy", &self.global_xy)
+            .field("file", &self.file)
+            .field("is_stop_event_propagation", &self.is_stop_event_propagation)
+            .finish()
+    }
+}
+impl EventExt for FileDropEvent<'_> {
+    fn stop_propagation(&self) {
+        self.is_stop_event_propagation
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+impl FileDropEvent<'_> {
+    pub fn local_xy(&self) -> Xy<Px> {
+        (self.local_xy)()
+    }
+    pub fn is_local_xy_in(&self) -> bool {
+        (self.is_local_xy_in)()
+    }
+}
+
 #[derive(Debug)]
 pub struct KeyboardEvent<'a> {
     pub code: Code,
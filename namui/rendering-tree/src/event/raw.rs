@@ -15,6 +15,7 @@ pub enum RawEvent {
     TextInput { event: RawTextInputEvent },
     TextInputKeyDown { event: RawTextInputKeyDownEvent },
     TextInputSelectionChange { event: RawTextInputEvent },
+    FileDrop { event: RawFileDropEvent },
 }
 
 #[derive(Debug, Clone)]
@@ -54,6 +55,30 @@ pub struct RawTextInputKeyDownEvent {
     pub code: Code,
 }
 
+/// A file dropped from the OS. Each dropped file comes as its own event.
+#[derive(Debug, Clone)]
+pub struct RawFileDropEvent {
+    pub xy: Xy<Px>,
+    pub file: DroppedFile,
+}
+
+#[derive(Clone)]
+pub struct DroppedFile {
+    pub name: String,
+    /// Only on native. Browsers don't expose the path of a dropped file.
+    pub path: Option<std::path::PathBuf>,
+    pub bytes: std::sync::Arc<[u8]>,
+}
+impl Debug for DroppedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DroppedFile")
+            .field("name", &self.name)
+            .field("path", &self.path)
+            .field("bytes_len", &self.bytes.len())
+            .finish()
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum SelectionDirection {
     None = 0,
@@ -7,15 +7,18 @@ mod paragraph;
 mod rendering_tree;
 mod skia_types;
 mod types;
+mod window;
 mod xy_in;
 
 pub use arena::*;
 pub use bounding_box::*;
 pub use command::*;
+pub use decode::{decode_rendering_tree, encode_rendering_tree};
 pub use event::*;
 use namui_type::*;
 pub use paragraph::*;
 pub use rendering_tree::*;
 pub use skia_types::*;
 pub use types::*;
+pub use window::*;
 pub use xy_in::*;
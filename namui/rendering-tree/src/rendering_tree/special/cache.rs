@@ -0,0 +1,35 @@
+use super::*;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+/// `CacheNode` lets the drawer record its subtree once and replay the recording
+/// while the same key keeps being drawn on every frame.
+///
+/// The key must change when the content of the subtree changes.
+/// OnTop and Absolute inside the subtree are relative to the cache node.
+#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq, bincode::Encode)]
+pub struct CacheNode {
+    pub key: u64,
+    pub rendering_tree: &'static RenderingTree,
+}
+
+/// Mark `rendering_tree` as cacheable in the drawer with `key`.
+/// Use a new key to invalidate the cache, for example `(map_id, wh)`.
+pub fn cache(key: impl Hash, rendering_tree: RenderingTree) -> RenderingTree {
+    if rendering_tree == RenderingTree::Empty {
+        return RenderingTree::Empty;
+    }
+
+    RenderingTree::Special(SpecialRenderingNode::Cache(CacheNode {
+        key: cache_key(key),
+        rendering_tree: arena_alloc(rendering_tree),
+    }))
+}
+
+pub fn cache_key(key: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::default();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
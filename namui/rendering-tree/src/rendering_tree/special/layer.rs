@@ -0,0 +1,93 @@
+use super::*;
+
+/// `LayerNode` draws its subtree into an offscreen layer and composites it as a unit.
+#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq, bincode::Encode)]
+pub struct LayerNode {
+    pub layer: &'static Layer,
+    pub rendering_tree: &'static RenderingTree,
+}
+
+#[derive(Debug, PartialEq, Clone, Hash, Eq, State)]
+pub struct Layer {
+    /// 0.0 ~ 1.0
+    pub opacity: OrderedFloat,
+    pub blend_mode: Option<BlendMode>,
+    pub color_filter: Option<ColorFilter>,
+    /// Applied to the layer content, e.g. blur or drop shadow.
+    pub image_filter: Option<ImageFilter>,
+    /// Applied to what is already drawn behind the layer, e.g. backdrop blur.
+    /// The backdrop is clipped by the current clip, so put a `clip` outside of the layer
+    /// to limit it to the area of the layer.
+    pub backdrop_filter: Option<ImageFilter>,
+}
+
+impl Default for Layer {
+    fn default() -> Self {
+        Self {
+            opacity: 1.0f32.into(),
+            blend_mode: None,
+            color_filter: None,
+            image_filter: None,
+            backdrop_filter: None,
+        }
+    }
+}
+
+impl Layer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn set_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0).into();
+        self
+    }
+    pub fn set_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = Some(blend_mode);
+        self
+    }
+    pub fn set_color_filter(mut self, color_filter: ColorFilter) -> Self {
+        self.color_filter = Some(color_filter);
+        self
+    }
+    pub fn set_image_filter(mut self, image_filter: ImageFilter) -> Self {
+        self.image_filter = Some(image_filter);
+        self
+    }
+    pub fn set_backdrop_filter(mut self, backdrop_filter: ImageFilter) -> Self {
+        self.backdrop_filter = Some(backdrop_filter);
+        self
+    }
+    /// Layer which changes nothing. Drawing it is the same as drawing the subtree directly.
+    pub fn is_noop(&self) -> bool {
+        *self == Self::default()
+    }
+    /// Layer which draws nothing, and so can't be hit by the mouse either.
+    pub fn is_transparent(&self) -> bool {
+        self.opacity.get() == 0.0
+    }
+    /// Returns the area which the layer can draw on, from the area of its content.
+    pub fn output_bounds(&self, content_bounds: Rect<Px>) -> Rect<Px> {
+        match &self.image_filter {
+            Some(image_filter) => image_filter.output_bounds(content_bounds),
+            None => content_bounds,
+        }
+    }
+}
+
+pub fn layer(layer: Layer, rendering_tree: RenderingTree) -> RenderingTree {
+    if rendering_tree == RenderingTree::Empty {
+        return RenderingTree::Empty;
+    }
+    if layer.is_noop() {
+        return rendering_tree;
+    }
+
+    RenderingTree::Special(SpecialRenderingNode::Layer(LayerNode {
+        layer: arena_alloc(layer),
+        rendering_tree: arena_alloc(rendering_tree),
+    }))
+}
+
+pub fn opacity(opacity: f32, rendering_tree: RenderingTree) -> RenderingTree {
+    layer(Layer::new().set_opacity(opacity), rendering_tree)
+}
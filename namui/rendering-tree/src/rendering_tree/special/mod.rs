@@ -1,21 +1,27 @@
 pub mod absolute;
+pub mod cache;
 pub mod clip;
+pub mod layer;
 pub mod mouse_cursor;
 pub mod on_top;
 pub mod rotate;
 pub mod scale;
 pub mod transform;
 pub mod translate;
+pub mod z_layer;
 
 use crate::*;
 pub use absolute::*;
+pub use cache::*;
 pub use clip::*;
+pub use layer::*;
 pub use mouse_cursor::*;
 pub use on_top::*;
 pub use rotate::*;
 pub use scale::*;
 pub use transform::*;
 pub use translate::*;
+pub use z_layer::*;
 
 #[derive(Debug, PartialEq, Clone, Copy, Hash, Eq, bincode::Encode)]
 pub enum SpecialRenderingNode {
@@ -27,6 +33,9 @@ pub enum SpecialRenderingNode {
     Transform(TransformNode),
     OnTop(OnTopNode),
     MouseCursor(MouseCursorNode),
+    Layer(LayerNode),
+    Cache(CacheNode),
+    ZLayer(ZLayerNode),
 }
 
 impl SpecialRenderingNode {
@@ -40,9 +49,28 @@ impl SpecialRenderingNode {
             SpecialRenderingNode::Transform(node) => node.rendering_tree,
             SpecialRenderingNode::OnTop(node) => node.rendering_tree,
             SpecialRenderingNode::MouseCursor(node) => node.rendering_tree,
+            SpecialRenderingNode::Layer(node) => node.rendering_tree,
+            SpecialRenderingNode::Cache(node) => node.rendering_tree,
+            SpecialRenderingNode::ZLayer(node) => node.rendering_tree,
         }
     }
     pub fn inner_rendering_tree(self) -> RenderingTree {
         *self.inner_rendering_tree_ref()
     }
+    pub fn with_inner_rendering_tree(mut self, rendering_tree: &'static RenderingTree) -> Self {
+        match &mut self {
+            SpecialRenderingNode::Translate(node) => node.rendering_tree = rendering_tree,
+            SpecialRenderingNode::Clip(node) => node.rendering_tree = rendering_tree,
+            SpecialRenderingNode::Absolute(node) => node.rendering_tree = rendering_tree,
+            SpecialRenderingNode::Rotate(node) => node.rendering_tree = rendering_tree,
+            SpecialRenderingNode::Scale(node) => node.rendering_tree = rendering_tree,
+            SpecialRenderingNode::Transform(node) => node.rendering_tree = rendering_tree,
+            SpecialRenderingNode::OnTop(node) => node.rendering_tree = rendering_tree,
+            SpecialRenderingNode::MouseCursor(node) => node.rendering_tree = rendering_tree,
+            SpecialRenderingNode::Layer(node) => node.rendering_tree = rendering_tree,
+            SpecialRenderingNode::Cache(node) => node.rendering_tree = rendering_tree,
+            SpecialRenderingNode::ZLayer(node) => node.rendering_tree = rendering_tree,
+        }
+        self
+    }
 }
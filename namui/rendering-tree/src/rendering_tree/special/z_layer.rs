@@ -0,0 +1,84 @@
+use super::*;
+
+/// `ZLayerNode` draws its subtree at the numeric layer `z`.
+///
+/// Everything outside of z-layer nodes is at layer 0.
+/// The drawer sorts subtrees by (layer, traversal order), so a higher layer is drawn in front
+/// and, within the same layer, the traversal order decides as usual.
+/// Like `OnTopNode`, the subtree ignores the clips of its ancestors but keeps their transform.
+/// OnTop nodes are still drawn after every layer.
+#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq, bincode::Encode)]
+pub struct ZLayerNode {
+    pub z: i32,
+    pub rendering_tree: &'static RenderingTree,
+}
+
+/// `z_layer` draws `rendering_tree` at the layer `z`. Layer 0 is where everything else is.
+/// Events are dispatched in the reverse of the drawing order,
+/// so put `attach_event` inside `z_layer` like `on_top`.
+/// Mouse events under it don't hit lower layers, from the next frame on. See [`z_layer_areas`].
+pub fn z_layer(z: i32, rendering_tree: RenderingTree) -> RenderingTree {
+    if rendering_tree == RenderingTree::Empty {
+        return RenderingTree::Empty;
+    }
+    RenderingTree::Special(SpecialRenderingNode::ZLayer(ZLayerNode {
+        z,
+        rendering_tree: arena_alloc(rendering_tree),
+    }))
+}
+
+/// Calls `callback` with every z-layer node in `rendering_tree` and the matrix it is drawn
+/// with, where `matrix` is the matrix of `rendering_tree`.
+/// Unlike drawing, z-layers inside OnTop and Cache nodes are visited in place.
+pub fn visit_z_layers(
+    rendering_tree: &RenderingTree,
+    matrix: TransformMatrix,
+    callback: &mut impl FnMut(&ZLayerNode, TransformMatrix),
+) {
+    match rendering_tree {
+        RenderingTree::Children(children) => {
+            for child in children.iter() {
+                visit_z_layers(child, matrix, callback);
+            }
+        }
+        RenderingTree::Special(special) => {
+            let matrix = match special {
+                SpecialRenderingNode::Translate(translate) => matrix * translate.get_matrix(),
+                SpecialRenderingNode::Absolute(absolute) => absolute.get_matrix(),
+                SpecialRenderingNode::Rotate(rotate) => matrix * rotate.get_matrix(),
+                SpecialRenderingNode::Scale(scale) => matrix * scale.get_matrix(),
+                SpecialRenderingNode::Transform(transform) => matrix * transform.matrix,
+                SpecialRenderingNode::ZLayer(z_layer) => {
+                    callback(z_layer, matrix);
+                    matrix
+                }
+                SpecialRenderingNode::Clip(_)
+                | SpecialRenderingNode::OnTop(_)
+                | SpecialRenderingNode::MouseCursor(_)
+                | SpecialRenderingNode::Layer(_)
+                | SpecialRenderingNode::Cache(_) => matrix,
+            };
+            visit_z_layers(special.inner_rendering_tree_ref(), matrix, callback);
+        }
+        RenderingTree::Node(_) | RenderingTree::Empty => {}
+    }
+}
+
+/// The global areas of the z-layers in the frame, for hit-testing.
+///
+/// Events are dispatched while the next frame renders, before its z-layers are known,
+/// so hit-testing uses the areas of the last frame. A moving z-layer blocks the area it had
+/// in the last frame, one frame behind what is drawn.
+pub fn z_layer_areas(rendering_tree: &RenderingTree) -> Vec<(i32, Rect<Px>)> {
+    let mut areas = vec![];
+    visit_z_layers(
+        rendering_tree,
+        TransformMatrix::identity(),
+        &mut |z_layer, matrix| {
+            if let Some(bounding_box) = z_layer.rendering_tree.bounding_box() {
+                areas.push((z_layer.z, matrix.transform_rect(bounding_box)));
+            }
+        },
+    );
+    areas
+}
@@ -53,6 +53,60 @@ impl Image {
     }
 }
 
+/// A region of an atlas page. `register_assets!` generates these for images under a
+/// directory with `atlas.toml`, instead of one `Image` per file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, State)]
+pub struct AtlasImage {
+    page: Image,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl AtlasImage {
+    pub const fn new(page: Image, x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            page,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+    pub const fn page(&self) -> Image {
+        self.page
+    }
+    pub fn src_rect(&self) -> Rect<Px> {
+        Rect::Xywh {
+            x: px(self.x as f32),
+            y: px(self.y as f32),
+            width: px(self.width as f32),
+            height: px(self.height as f32),
+        }
+    }
+    pub fn wh(&self) -> Wh<Px> {
+        Wh::new(px(self.width as f32), px(self.height as f32))
+    }
+    pub fn sprite(&self, xform: RSXform) -> ImageSprite {
+        ImageSprite {
+            src_rect: self.src_rect(),
+            xform,
+            color: None,
+        }
+    }
+    pub fn fit_sprite(&self, fit: ImageFit, rect: Rect<Px>) -> ImageSprite {
+        let mut sprite = fit_to_sprite(fit, self.wh(), rect);
+        sprite.src_rect = Rect::Xywh {
+            x: sprite.src_rect.x() + px(self.x as f32),
+            y: sprite.src_rect.y() + px(self.y as f32),
+            width: sprite.src_rect.width(),
+            height: sprite.src_rect.height(),
+        };
+        sprite
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, namui_type::State)]
 pub struct ImageInfo {
     pub alpha_type: AlphaType,
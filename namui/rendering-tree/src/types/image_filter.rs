@@ -38,9 +38,36 @@ pub enum ImageFilter {
         /// crop_rect is not supported in wasm
         crop_rect: Option<Rect<Px>>,
     },
+    DropShadow {
+        offset: Xy<Px>,
+        sigma_xy: Xy<OrderedFloat>,
+        color: Color,
+        /// Draw only the shadow, not the input.
+        shadow_only: bool,
+        input: Option<Box<ImageFilter>>,
+    },
 }
 
 impl ImageFilter {
+    pub fn blur(sigma: f32) -> Self {
+        ImageFilter::Blur {
+            sigma_xy: Xy::single(sigma.into()),
+            tile_mode: None,
+            input: None,
+            crop_rect: None,
+        }
+    }
+
+    pub fn drop_shadow(offset: Xy<Px>, sigma: f32, color: Color) -> Self {
+        ImageFilter::DropShadow {
+            offset,
+            sigma_xy: Xy::single(sigma.into()),
+            color,
+            shadow_only: false,
+            input: None,
+        }
+    }
+
     pub fn offset(self, offset: Xy<Px>) -> Self {
         ImageFilter::Offset {
             offset,
@@ -83,6 +110,94 @@ impl ImageFilter {
     }
 }
 
+impl ImageFilter {
+    /// Conservative area which the filter can draw on when its source covers `source_bounds`.
+    /// Gaussian blur is treated as reaching 3 sigma.
+    pub fn output_bounds(&self, source_bounds: Rect<Px>) -> Rect<Px> {
+        fn input_bounds(input: &Option<Box<ImageFilter>>, source_bounds: Rect<Px>) -> Rect<Px> {
+            match input {
+                Some(input) => input.output_bounds(source_bounds),
+                None => source_bounds,
+            }
+        }
+        fn outset(rect: Rect<Px>, xy: Xy<Px>) -> Rect<Px> {
+            Rect::Ltrb {
+                left: rect.left() - xy.x,
+                top: rect.top() - xy.y,
+                right: rect.right() + xy.x,
+                bottom: rect.bottom() + xy.y,
+            }
+        }
+        fn three_sigma(sigma_xy: Xy<OrderedFloat>) -> Xy<Px> {
+            Xy::new(px(sigma_xy.x.as_f32() * 3.0), px(sigma_xy.y.as_f32() * 3.0))
+        }
+
+        match self {
+            ImageFilter::Empty => source_bounds,
+            &ImageFilter::Blur {
+                sigma_xy,
+                ref input,
+                crop_rect,
+                ..
+            } => {
+                let bounds = outset(input_bounds(input, source_bounds), three_sigma(sigma_xy));
+                match crop_rect {
+                    Some(crop_rect) => bounds.intersect(crop_rect).unwrap_or(crop_rect),
+                    None => bounds,
+                }
+            }
+            ImageFilter::Image { src } => Rect::zero_wh(src.info().wh()),
+            ImageFilter::Blend {
+                background,
+                foreground,
+                ..
+            } => background
+                .output_bounds(source_bounds)
+                .get_minimum_rectangle_containing(foreground.output_bounds(source_bounds)),
+            ImageFilter::Offset { offset, input } => {
+                let bounds = input.output_bounds(source_bounds);
+                Rect::from_xy_wh(bounds.xy() + *offset, bounds.wh())
+            }
+            ImageFilter::ColorFilter { input, .. } => input.output_bounds(source_bounds),
+            ImageFilter::MatrixTransform { matrix, input } => {
+                matrix.transform_rect(input.output_bounds(source_bounds))
+            }
+            &ImageFilter::Dilate {
+                radius_xy,
+                ref input,
+                crop_rect,
+            } => {
+                let bounds = outset(
+                    input_bounds(input, source_bounds),
+                    Xy::new(px(radius_xy.x.as_f32()), px(radius_xy.y.as_f32())),
+                );
+                match crop_rect {
+                    Some(crop_rect) => bounds.intersect(crop_rect).unwrap_or(crop_rect),
+                    None => bounds,
+                }
+            }
+            &ImageFilter::DropShadow {
+                offset,
+                sigma_xy,
+                shadow_only,
+                ref input,
+                ..
+            } => {
+                let bounds = input_bounds(input, source_bounds);
+                let shadow_bounds = outset(
+                    Rect::from_xy_wh(bounds.xy() + offset, bounds.wh()),
+                    three_sigma(sigma_xy),
+                );
+                if shadow_only {
+                    shadow_bounds
+                } else {
+                    bounds.get_minimum_rectangle_containing(shadow_bounds)
+                }
+            }
+        }
+    }
+}
+
 impl From<&ImageFilter> for skia_safe::ImageFilter {
     fn from(image_filter: &ImageFilter) -> Self {
         match image_filter {
@@ -149,6 +264,27 @@ impl From<&ImageFilter> for skia_safe::ImageFilter {
                 crop_rect.map(|x| skia_safe::Rect::from(x).into()),
             )
             .unwrap(),
+            &ImageFilter::DropShadow {
+                offset,
+                sigma_xy,
+                color,
+                shadow_only,
+                ref input,
+            } => {
+                let input = input
+                    .as_ref()
+                    .map(|input| skia_safe::ImageFilter::from(input.as_ref()));
+                let offset = (offset.x.as_f32(), offset.y.as_f32());
+                let sigma = (sigma_xy.x.as_f32(), sigma_xy.y.as_f32());
+                if shadow_only {
+                    skia_safe::image_filters::drop_shadow_only(
+                        offset, sigma, color, None, input, None,
+                    )
+                } else {
+                    skia_safe::image_filters::drop_shadow(offset, sigma, color, None, input, None)
+                }
+                .unwrap()
+            }
             ImageFilter::Empty => skia_safe::image_filters::empty(),
         }
     }
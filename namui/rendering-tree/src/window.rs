@@ -0,0 +1,47 @@
+use crate::*;
+
+/// Requests from the app to the native window.
+/// The runner takes them after every frame and applies them in order.
+#[derive(Debug, Clone, PartialEq, State)]
+pub enum WindowCommand {
+    SetTitle { title: String },
+    SetSize { wh: Wh<IntPx> },
+    SetMinSize { wh: Option<Wh<IntPx>> },
+    SetMaxSize { wh: Option<Wh<IntPx>> },
+    SetPosition { xy: Xy<IntPx> },
+    SetMode { mode: WindowMode },
+    SetVsync { vsync: bool },
+    SetCursorGrab { grab: CursorGrab },
+    SetCursorVisible { visible: bool },
+    Close,
+}
+
+/// `Borderless` is a window without decorations, `Fullscreen` covers the current monitor
+/// without changing its video mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, State)]
+pub enum WindowMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+/// `Confined` keeps the cursor inside the window, `Locked` keeps it at its position.
+/// Not every platform supports both. The runner falls back to the other one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, State)]
+pub enum CursorGrab {
+    #[default]
+    None,
+    Confined,
+    Locked,
+}
+
+/// State of the native window which the runner reports to the app, and saves across launches.
+/// `xy` and `wh` are the windowed geometry, kept while the window is fullscreen.
+#[derive(Debug, Clone, Copy, PartialEq, State)]
+pub struct WindowState {
+    pub xy: Xy<IntPx>,
+    pub wh: Wh<IntPx>,
+    pub mode: WindowMode,
+    pub vsync: bool,
+}
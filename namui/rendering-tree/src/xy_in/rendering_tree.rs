@@ -95,7 +95,10 @@ impl Visit for RenderingTree {
                     }
                     SpecialRenderingNode::Clip(_)
                     | SpecialRenderingNode::OnTop(_)
-                    | SpecialRenderingNode::MouseCursor(_) => {}
+                    | SpecialRenderingNode::MouseCursor(_)
+                    | SpecialRenderingNode::Layer(_)
+                    | SpecialRenderingNode::Cache(_)
+                    | SpecialRenderingNode::ZLayer(_) => {}
                 }
             }
         }
@@ -132,7 +135,10 @@ impl Visit for RenderingTree {
                     }
                     SpecialRenderingNode::Clip(_)
                     | SpecialRenderingNode::OnTop(_)
-                    | SpecialRenderingNode::MouseCursor(_) => {}
+                    | SpecialRenderingNode::MouseCursor(_)
+                    | SpecialRenderingNode::Layer(_)
+                    | SpecialRenderingNode::Cache(_)
+                    | SpecialRenderingNode::ZLayer(_) => {}
                 }
             }
         }
@@ -175,7 +181,14 @@ fn is_xy_clip_in_by_ancestors(xy: Xy<Px>, ancestors: &[&RenderingTree]) -> bool
                 if !clip.clip_in(local_xy) {
                     return false;
                 }
-            } else if let SpecialRenderingNode::OnTop(_) = special {
+            } else if let SpecialRenderingNode::Layer(layer) = special {
+                // `save_layer` has no bounds, so a layer never cuts its content. It only hides it
+                // when it is fully transparent.
+                if layer.layer.is_transparent() {
+                    return false;
+                }
+            } else if let SpecialRenderingNode::OnTop(_) | SpecialRenderingNode::ZLayer(_) = special
+            {
                 return true;
             }
         }
@@ -628,6 +641,22 @@ mod tests {
         );
         assert_eq!(with_ancestors_call_count, 6);
     }
+    #[test]
+    fn transparent_layer_should_block_checking_xy_in() {
+        let _scope = enter_arena_scope();
+        let xy = Xy::new(10.px(), 10.px());
+
+        let transparent = layer(Layer::new().set_opacity(0.0), dummy_leaf());
+        assert!(!is_xy_clip_in_by_ancestors(xy, &[&transparent]));
+
+        let translucent = layer(Layer::new().set_opacity(0.5), dummy_leaf());
+        assert!(is_xy_clip_in_by_ancestors(xy, &[&translucent]));
+
+        // Drawn out of the layer, so the layer doesn't hide it.
+        let on_top_node = on_top(dummy_leaf());
+        assert!(is_xy_clip_in_by_ancestors(xy, &[&transparent, &on_top_node]));
+    }
+
     #[test]
     fn clip_should_block_checking_xy_in() {
         // TODO: Test this. We cannot test it right now because test runtime doesn't load canvaskit.
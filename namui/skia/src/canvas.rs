@@ -48,12 +48,39 @@ impl SkCanvas for skia_safe::Canvas {
 
         self.restore();
     }
+    fn draw_picture(&self, picture: &NativePicture) {
+        self.draw_picture(picture.skia(), None, None);
+    }
     fn translate(&self, dx: Px, dy: Px) {
         self.translate(skia_safe::Point::new(dx.as_f32(), dy.as_f32()));
     }
     fn save(&self) {
         self.save();
     }
+    fn save_layer(&self, layer: &Layer) {
+        let mut paint = skia_safe::Paint::default();
+        paint.set_alpha_f(*layer.opacity);
+        if let Some(blend_mode) = layer.blend_mode {
+            paint.set_blend_mode(blend_mode.into());
+        }
+        if let Some(color_filter) = layer.color_filter {
+            let native_color_filter = NativeColorFilter::get(color_filter);
+            paint.set_color_filter(Some(native_color_filter.skia().clone()));
+        }
+        if let Some(image_filter) = &layer.image_filter {
+            paint.set_image_filter(Some(image_filter.into()));
+        }
+        let backdrop_filter = layer
+            .backdrop_filter
+            .as_ref()
+            .map(skia_safe::ImageFilter::from);
+
+        let mut save_layer_rec = skia_safe::canvas::SaveLayerRec::default().paint(&paint);
+        if let Some(backdrop_filter) = &backdrop_filter {
+            save_layer_rec = save_layer_rec.backdrop(backdrop_filter);
+        }
+        self.save_layer(&save_layer_rec);
+    }
     fn clip_path(&self, path: &Path, clip_op: ClipOp, do_anti_alias: bool) {
         self.clip_path(
             NativePath::get(path).skia(),
@@ -3,6 +3,7 @@ mod canvas;
 mod linux;
 #[cfg(target_os = "macos")]
 mod macos;
+mod picture;
 mod traits;
 #[cfg(target_os = "wasi")]
 mod wasi;
@@ -12,6 +13,7 @@ mod windows;
 use anyhow::Result;
 pub use namui_rendering_tree::*;
 use namui_type::*;
+pub use picture::*;
 pub use skia_safe;
 pub use traits::*;
 
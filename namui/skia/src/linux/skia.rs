@@ -15,4 +15,7 @@ impl NativeSkia {
     pub fn on_resize(&mut self, wh: Wh<IntPx>) {
         self.surface.resize(wh);
     }
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.surface.set_vsync(vsync);
+    }
 }
@@ -8,6 +8,10 @@ impl NativeSurface {
         unimplemented!()
     }
 
+    pub fn set_vsync(&mut self, _vsync: bool) {
+        unimplemented!()
+    }
+
     pub fn move_to_next_frame(&mut self) {
         unimplemented!()
     }
@@ -38,6 +38,10 @@ impl NativeSurface {
         ));
     }
 
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.metal_layer.set_display_sync_enabled(vsync);
+    }
+
     /// Should be called before use surface
     pub fn move_to_next_frame(&mut self) {
         self.current_surface = None;
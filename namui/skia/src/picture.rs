@@ -0,0 +1,22 @@
+use crate::*;
+
+/// Recorded draw calls, replayed with [`SkCanvas::draw_picture`].
+#[derive(Clone)]
+pub struct NativePicture {
+    skia_picture: skia_safe::Picture,
+}
+
+impl NativePicture {
+    /// Records what `draw` draws in `bounds`. `None` if the recording failed.
+    pub fn record(bounds: Rect<Px>, draw: impl FnOnce(&dyn SkCanvas)) -> Option<Self> {
+        let mut recorder = skia_safe::PictureRecorder::new();
+        let recording_canvas = recorder.begin_recording(skia_safe::Rect::from(bounds), None);
+        draw(recording_canvas);
+        let skia_picture = recorder.finish_recording_as_picture(None)?;
+        Some(Self { skia_picture })
+    }
+
+    pub fn skia(&self) -> &skia_safe::Picture {
+        &self.skia_picture
+    }
+}
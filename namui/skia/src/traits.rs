@@ -21,8 +21,11 @@ pub trait SkCanvas {
         sprite_colors_blend_mode: BlendMode,
         paint: &Option<Paint>,
     );
+    fn draw_picture(&self, picture: &NativePicture);
     fn translate(&self, dx: Px, dy: Px);
     fn save(&self);
+    /// Following draws go to an offscreen layer, composited by `layer` on `restore`.
+    fn save_layer(&self, layer: &Layer);
     fn clip_path(&self, path: &Path, clip_op: ClipOp, do_anti_alias: bool);
     fn restore(&self);
     fn get_matrix(&self) -> TransformMatrix;
@@ -37,6 +37,8 @@ pub struct NativeSurface {
     command_queue: ID3D12CommandQueue,
     device: ID3D12Device,
     rtv_heap: ID3D12DescriptorHeap,
+    /// 1 waits for the vertical blank, 0 presents immediately.
+    sync_interval: u32,
 }
 unsafe impl Send for NativeSurface {}
 unsafe impl Sync for NativeSurface {}
@@ -102,9 +104,14 @@ impl NativeSurface {
             fence_event,
             command_queue: command_queue.clone(),
             rtv_heap,
+            sync_interval: 1,
         })
     }
 
+    pub(crate) fn set_vsync(&mut self, vsync: bool) {
+        self.sync_interval = vsync as u32;
+    }
+
     pub(crate) fn resize(&mut self, window_wh: Wh<IntPx>) {
         let desc = unsafe { self.swap_chain.GetDesc1() }.unwrap();
 
@@ -186,7 +193,7 @@ impl NativeSurface {
 
         unsafe {
             self.swap_chain
-                .Present(1, DXGI_PRESENT(0))
+                .Present(self.sync_interval, DXGI_PRESENT(0))
                 .ok()
                 .expect("swap_chain.present failed")
         };
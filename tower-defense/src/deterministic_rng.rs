@@ -14,6 +14,7 @@ pub mod domain {
     pub const SHOP_CONTENT_UPGRADE: u64 = 0x5348_4f50_0000_0007;
     pub const SHOP_ITEM_PAYLOAD: u64 = 0x5348_4f50_0000_0008;
     pub const SHOP_PRICE: u64 = 0x5348_4f50_0000_0009;
+    pub const SESSION_GAME: u64 = 0x5345_5353_0000_0001;
 }
 
 fn split_mix(mut value: u64) -> u64 {
@@ -177,6 +177,17 @@ pub struct Background {
     kind: BackgroundKind,
     flip_horizontally: bool,
 }
+impl Background {
+    pub fn cache_key(&self) -> impl std::hash::Hash {
+        (
+            "map_background",
+            self.coord.x.to_bits(),
+            self.coord.y.to_bits(),
+            self.kind.image().id(),
+            self.flip_horizontally,
+        )
+    }
+}
 impl Component for &Background {
     fn render(self, ctx: &RenderCtx) {
         let image = self.kind.image();
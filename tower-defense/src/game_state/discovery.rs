@@ -5,10 +5,12 @@ use crate::game_state::{GameFlow, GameState};
 use crate::shop::ShopSlot;
 use namui::*;
 use serde::{Deserialize as SerdeDeserialize, Serialize as SerdeSerialize};
-use std::sync::{Mutex, OnceLock};
 
-const STORAGE_KEY: &str = "tower-defense-encyclopedia";
-const VERSION: u32 = 1;
+static DISCOVERY_KEY: PersistKey<DiscoveryState> = PersistKey::new(
+    "tower-defense-encyclopedia",
+    DiscoveryState::default,
+    &[migrate_v0],
+);
 
 #[derive(Clone, Debug, PartialEq, Eq, State)]
 pub(crate) enum Discovery {
@@ -22,8 +24,6 @@ pub(crate) struct DiscoveryState {
     #[serde(skip)]
     revision: u64,
     #[serde(default)]
-    pub(crate) version: u32,
-    #[serde(default)]
     pub(crate) items: Vec<String>,
     #[serde(default)]
     pub(crate) card_services: Vec<String>,
@@ -37,7 +37,6 @@ pub(crate) struct DiscoveryState {
 
 impl DiscoveryState {
     fn sanitized(mut self) -> Self {
-        self.version = VERSION;
         deduplicate(&mut self.items);
         deduplicate(&mut self.card_services);
         deduplicate(&mut self.treasures);
@@ -94,63 +93,18 @@ fn deduplicate(keys: &mut Vec<String>) {
     keys.dedup();
 }
 
-pub(crate) async fn load_async() -> DiscoveryState {
-    let raw = namui::system::kv_store::get(STORAGE_KEY).await;
-    raw.and_then(|bytes| String::from_utf8(bytes).ok())
-        .and_then(|raw| serde_json::from_str::<DiscoveryState>(&raw).ok())
-        .unwrap_or_default()
-        .sanitized()
+/// Before persist, the state was stored without envelope and with its own `version` field.
+fn migrate_v0(mut value: serde_json::Value) -> Option<serde_json::Value> {
+    value.as_object_mut()?.remove("version");
+    Some(value)
 }
 
-struct SaveQueue {
-    latest: Option<String>,
-    worker_running: bool,
+pub(crate) async fn load_async() -> DiscoveryState {
+    DISCOVERY_KEY.load().await.sanitized()
 }
 
-static SAVE_QUEUE: OnceLock<Mutex<SaveQueue>> = OnceLock::new();
-
 pub(crate) fn request_save(snapshot: DiscoveryState) {
-    let Ok(serialized) = serde_json::to_string(&snapshot) else {
-        return;
-    };
-    let queue = SAVE_QUEUE.get_or_init(|| {
-        Mutex::new(SaveQueue {
-            latest: None,
-            worker_running: false,
-        })
-    });
-    let should_start_worker = {
-        let Ok(mut queue) = queue.lock() else {
-            return;
-        };
-        queue.latest = Some(serialized);
-        if queue.worker_running {
-            false
-        } else {
-            queue.worker_running = true;
-            true
-        }
-    };
-    if should_start_worker {
-        spawn(save_worker());
-    }
-}
-
-async fn save_worker() {
-    loop {
-        let serialized = {
-            let queue = SAVE_QUEUE.get().expect("save queue initialized");
-            let Ok(mut queue) = queue.lock() else {
-                return;
-            };
-            let Some(serialized) = queue.latest.take() else {
-                queue.worker_running = false;
-                return;
-            };
-            serialized
-        };
-        namui::system::kv_store::put(STORAGE_KEY, Some(serialized.as_bytes())).await;
-    }
+    DISCOVERY_KEY.save(&snapshot);
 }
 
 impl GameState {
@@ -212,7 +166,6 @@ impl GameState {
         let runtime = self.discovery.clone();
         let mut merged = loaded;
         let runtime_inserted = merged.merge(&runtime);
-        merged.version = VERSION;
         merged.loaded = true;
         merged.dirty = runtime_inserted;
         merged.revision = merged.revision.wrapping_add(1);
@@ -65,7 +65,10 @@ use rand::Rng;
 pub use render::*;
 use rng::GameRngState;
 pub(crate) use status_effect_particle_generator::StatusEffectParticleGenerator;
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
 use tower::*;
 pub use ui_state::UIState;
 use upgrade::UpgradeState;
@@ -603,8 +606,16 @@ impl Component for &FloorTile {
 
 static GAME_STATE_ATOM: Atom<GameState> = Atom::uninitialized();
 
+/// Seeded from the session seed, so replaying a recorded session plays the same games.
 fn create_initial_game_state() -> GameState {
-    create_game_state_with_seed(rand::thread_rng().r#gen())
+    static GAME_INDEX: AtomicU64 = AtomicU64::new(0);
+    let game_index = GAME_INDEX.fetch_add(1, Ordering::Relaxed);
+    let mut rng = crate::deterministic_rng::rng_for(
+        namui::recording::seed(),
+        crate::deterministic_rng::domain::SESSION_GAME,
+        &[game_index],
+    );
+    create_game_state_with_seed(rng.r#gen())
 }
 
 pub fn create_game_state_with_seed(seed: u64) -> GameState {
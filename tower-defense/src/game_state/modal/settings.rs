@@ -2,7 +2,7 @@ use crate::game_state::set_overlay_modal;
 use crate::icon::{Icon, IconKind, IconSize};
 use crate::l10n::ui::{SettingsText, TopBarText};
 use crate::sound::{self, SoundGroup};
-use crate::theme::button::{Button, ButtonVariant};
+use crate::theme::button::{Button, ButtonColor, ButtonVariant};
 use crate::theme::{
     palette,
     paper_container::{PaperContainerBackground, PaperTexture, PaperVariant},
@@ -19,6 +19,8 @@ const VOLUME_ROW_GAP: Px = px(8.);
 const VOLUME_SLIDER_WIDTH: Px = px(200.);
 const VOLUME_SLIDER_HEIGHT: Px = px(24.);
 const VOLUME_VALUE_WIDTH: Px = px(56.);
+const CHOICE_BUTTON_WIDTH: Px = px(80.);
+const CHOICE_BUTTON_HEIGHT: Px = px(28.);
 
 pub struct SettingsModal;
 
@@ -117,6 +119,44 @@ impl Component for SettingsModal {
                                         volume_settings.music,
                                         &|v| sound::set_group_volume(SoundGroup::Music, v),
                                     );
+                                    // Only native targets have a window to control.
+                                    if window::state().is_some() {
+                                        let text = game_state.text();
+                                        render_choice_row(
+                                            &ctx,
+                                            content_width,
+                                            (VOLUME_ROW_HEIGHT + VOLUME_ROW_GAP) * 5.0,
+                                            text.settings(SettingsText::DisplayMode),
+                                            &[
+                                                (
+                                                    WindowMode::Windowed,
+                                                    text.settings(SettingsText::Windowed),
+                                                ),
+                                                (
+                                                    WindowMode::Borderless,
+                                                    text.settings(SettingsText::Borderless),
+                                                ),
+                                                (
+                                                    WindowMode::Fullscreen,
+                                                    text.settings(SettingsText::Fullscreen),
+                                                ),
+                                            ],
+                                            window::mode(),
+                                            &window::set_mode,
+                                        );
+                                        render_choice_row(
+                                            &ctx,
+                                            content_width,
+                                            (VOLUME_ROW_HEIGHT + VOLUME_ROW_GAP) * 6.0,
+                                            text.settings(SettingsText::Vsync),
+                                            &[
+                                                (true, text.settings(SettingsText::On)),
+                                                (false, text.settings(SettingsText::Off)),
+                                            ],
+                                            window::vsync(),
+                                            &window::set_vsync,
+                                        );
+                                    }
                                 },
                             });
                         }),
@@ -228,3 +268,56 @@ fn render_volume_row(
             }));
     });
 }
+
+fn render_choice_row<T: Copy + PartialEq>(
+    ctx: &ComposeCtx,
+    width: Px,
+    y: Px,
+    label: &'static str,
+    choices: &[(T, &'static str)],
+    value: T,
+    on_change: &dyn Fn(T),
+) {
+    let controls_width = CHOICE_BUTTON_WIDTH * choices.len() as f32
+        + VOLUME_ROW_GAP * (choices.len().saturating_sub(1)) as f32;
+    let label_width = (width - controls_width).max(80.px());
+
+    ctx.translate((0.px(), y)).compose(|ctx| {
+        ctx.add(memoized_text((), |mut builder| {
+            builder
+                .headline()
+                .size(typography::FontSize::Small)
+                .text(label)
+                .render_left_center(VOLUME_ROW_HEIGHT)
+        }));
+
+        for (index, &(choice, choice_label)) in choices.iter().enumerate() {
+            let x = label_width + (CHOICE_BUTTON_WIDTH + VOLUME_ROW_GAP) * index as f32;
+            let variant = if choice == value {
+                ButtonVariant::Contained
+            } else {
+                ButtonVariant::Outlined
+            };
+
+            ctx.translate((x, (VOLUME_ROW_HEIGHT - CHOICE_BUTTON_HEIGHT) * 0.5))
+                .add(
+                    Button::new(
+                        Wh::new(CHOICE_BUTTON_WIDTH, CHOICE_BUTTON_HEIGHT),
+                        &|| on_change(choice),
+                        &|wh, text_color, ctx| {
+                            ctx.add(memoized_text(&text_color, |mut builder| {
+                                builder
+                                    .paragraph()
+                                    .size(typography::FontSize::Small)
+                                    .color(text_color)
+                                    .text(choice_label)
+                                    .render_center(wh)
+                            }));
+                        },
+                    )
+                    .color(ButtonColor::Primary)
+                    .variant(variant),
+                );
+        }
+    });
+}
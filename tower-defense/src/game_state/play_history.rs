@@ -79,6 +79,8 @@ impl PlayHistory {
 
 impl GameState {
     pub(crate) fn record_event(&mut self, event_type: HistoryEventType) {
+        #[cfg(not(target_os = "wasi"))]
+        leave_breadcrumb(self.stage, &event_type);
         self.play_history.events.push(HistoryEvent {
             stage: self.stage,
             timestamp: self.now(),
@@ -86,3 +88,68 @@ impl GameState {
         });
     }
 }
+
+/// Mirrors play events into the crash reporter, so a crash report shows what the player was doing.
+#[cfg(not(target_os = "wasi"))]
+fn leave_breadcrumb(stage: usize, event_type: &HistoryEventType) {
+    use crate::game_state::{item::ItemDiscriminants, upgrade::UpgradeDiscriminants};
+
+    let stage = ("stage", stage.to_string());
+    let (message, data) = match event_type {
+        HistoryEventType::GameStart => ("game started".to_string(), vec![stage]),
+        HistoryEventType::StageStart { stage, boss } => (
+            format!("stage {stage} started"),
+            vec![("boss", boss.to_string())],
+        ),
+        HistoryEventType::StagePerfectClear { stage } => {
+            (format!("stage {stage} perfectly cleared"), vec![])
+        }
+        HistoryEventType::TowerPlaced {
+            tower_kind,
+            rank,
+            suit,
+            left_top,
+        } => (
+            format!("placed {tower_kind:?}"),
+            vec![
+                stage,
+                ("rank", format!("{rank:?}")),
+                ("suit", format!("{suit:?}")),
+                ("left_top", format!("{},{}", left_top.x, left_top.y)),
+            ],
+        ),
+        HistoryEventType::TowerRemovedById { tower_id } => (
+            "removed tower".to_string(),
+            vec![stage, ("tower_id", tower_id.to_string())],
+        ),
+        HistoryEventType::DamageTaken { amount } => (
+            "took damage".to_string(),
+            vec![stage, ("amount", amount.to_string())],
+        ),
+        HistoryEventType::ItemPurchased { item, cost } => (
+            format!("bought {}", ItemDiscriminants::from(item).as_ref()),
+            vec![stage, ("cost", cost.to_string())],
+        ),
+        HistoryEventType::ItemUsed { item } => (
+            format!("used {}", ItemDiscriminants::from(item).as_ref()),
+            vec![stage],
+        ),
+        HistoryEventType::UpgradeAcquired { upgrade, cost } => (
+            format!("acquired {}", UpgradeDiscriminants::from(upgrade).as_ref()),
+            vec![stage, ("cost", format!("{cost:?}"))],
+        ),
+        HistoryEventType::CardServicePurchased { service_kind, cost } => (
+            format!("bought {service_kind}"),
+            vec![stage, ("cost", cost.to_string())],
+        ),
+        HistoryEventType::CardServiceUsed {
+            service_kind,
+            cards_selected,
+        } => (
+            format!("used {service_kind}"),
+            vec![stage, ("cards_selected", cards_selected.to_string())],
+        ),
+        HistoryEventType::GameOver => ("game over".to_string(), vec![stage]),
+    };
+    namui_crash_reporter::breadcrumb("play", message, data);
+}
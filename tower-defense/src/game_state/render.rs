@@ -129,7 +129,7 @@ fn render_backgrounds(ctx: &RenderCtx, game_state: &GameState) {
             px(xy.y * TILE_PX_SIZE.height.as_f32()),
         );
 
-        ctx.translate(px_xy).compose({
+        ctx.translate(px_xy).cache(background.cache_key()).compose({
             let background = *background;
             move |ctx| {
                 let rendering_tree = ctx.ghost_add("", &background);
@@ -385,9 +385,10 @@ fn render_field_particles(ctx: &RenderCtx, _game_state: &GameState) {
 }
 
 fn render_decorations(ctx: &RenderCtx, game_state: &GameState) {
-    ctx.add(background::decoration_rendering_tree(
-        &game_state.decorations,
-    ));
+    // Decorations don't change during a game, so the drawer can replay its recording.
+    ctx.cache(("map_decorations", &game_state.decorations)).add(
+        background::decoration_rendering_tree(&game_state.decorations),
+    );
 }
 
 fn render_map_border_gradient(ctx: &RenderCtx, _game_state: &GameState) {
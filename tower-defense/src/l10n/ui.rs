@@ -313,6 +313,13 @@ pub enum SettingsText {
     UiVolume,
     AmbientVolume,
     MusicVolume,
+    DisplayMode,
+    Windowed,
+    Borderless,
+    Fullscreen,
+    Vsync,
+    On,
+    Off,
 }
 
 impl LocalizedText for SettingsText {
@@ -336,6 +343,13 @@ impl SettingsText {
             SettingsText::UiVolume => "UI",
             SettingsText::AmbientVolume => "환경음",
             SettingsText::MusicVolume => "음악",
+            SettingsText::DisplayMode => "화면 모드",
+            SettingsText::Windowed => "창 모드",
+            SettingsText::Borderless => "테두리 없음",
+            SettingsText::Fullscreen => "전체 화면",
+            SettingsText::Vsync => "수직 동기화",
+            SettingsText::On => "켜기",
+            SettingsText::Off => "끄기",
         }
     }
 
@@ -346,6 +360,13 @@ impl SettingsText {
             SettingsText::UiVolume => "UI",
             SettingsText::AmbientVolume => "Ambient",
             SettingsText::MusicVolume => "Music",
+            SettingsText::DisplayMode => "Display",
+            SettingsText::Windowed => "Windowed",
+            SettingsText::Borderless => "Borderless",
+            SettingsText::Fullscreen => "Fullscreen",
+            SettingsText::Vsync => "VSync",
+            SettingsText::On => "On",
+            SettingsText::Off => "Off",
         }
     }
 }
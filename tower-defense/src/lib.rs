@@ -212,7 +212,7 @@ impl Component for Game {
             ),
         );
 
-        ctx.compose(|ctx| {
+        ctx.z_layer(theme::z_layer::MODAL).compose(|ctx| {
             if let Some(overlay) = game_state.opened_modals.system.as_ref() {
                 ctx.add(overlay);
             }
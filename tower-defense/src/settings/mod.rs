@@ -2,14 +2,13 @@ use crate::sound::VolumeSettings;
 use namui::*;
 use serde::{Deserialize as SerdeDeserialize, Serialize as SerdeSerialize};
 
-const SETTINGS_STORAGE_KEY: &str = "tower-defense-settings";
-const SETTINGS_VERSION: u32 = 1;
+static SETTINGS_KEY: PersistKey<Settings> =
+    PersistKey::new("tower-defense-settings", Settings::default, &[migrate_v0]);
 
 static SETTINGS_ATOM: Atom<Settings> = Atom::uninitialized();
 
-#[derive(Clone, Debug, PartialEq, SerdeSerialize, SerdeDeserialize, State)]
+#[derive(Clone, Debug, Default, PartialEq, SerdeSerialize, SerdeDeserialize, State)]
 pub struct Settings {
-    pub version: u32,
     #[serde(default)]
     pub audio: AudioSettings,
 }
@@ -20,26 +19,13 @@ pub struct AudioSettings {
     pub volume: VolumeSettings,
 }
 
-impl Default for Settings {
-    fn default() -> Self {
-        Self {
-            version: SETTINGS_VERSION,
-            audio: AudioSettings::default(),
-        }
-    }
-}
-
 impl Settings {
     pub fn init<'a>(ctx: &'a RenderCtx) -> Sig<'a, Settings> {
         ctx.init_atom(&SETTINGS_ATOM, Settings::default).0
     }
 
     pub async fn load_async() -> Self {
-        let raw = namui::system::kv_store::get(SETTINGS_STORAGE_KEY).await;
-        raw.and_then(|bytes| String::from_utf8(bytes).ok())
-            .and_then(|raw_string| Self::from_storage_str(&raw_string))
-            .unwrap_or_default()
-            .sanitize()
+        SETTINGS_KEY.load().await.sanitize()
     }
 
     pub fn set_settings(self) {
@@ -49,59 +35,46 @@ impl Settings {
     }
 
     pub fn save(&self) {
-        let sanitized = self.clone().sanitize();
-        let Ok(serialized) = serde_json::to_string(&sanitized) else {
-            return;
-        };
-        spawn(async move {
-            namui::system::kv_store::put(SETTINGS_STORAGE_KEY, Some(serialized.as_bytes())).await;
-        });
+        SETTINGS_KEY.save(&self.clone().sanitize());
     }
 
     pub fn replace_volume_settings(volume: VolumeSettings) {
         SETTINGS_ATOM.mutate(move |settings| {
             settings.audio.volume = volume.clamped();
-            settings.version = SETTINGS_VERSION;
             settings.save();
         });
     }
 
-    fn from_storage_str(raw: &str) -> Option<Self> {
-        let value = serde_json::from_str::<serde_json::Value>(raw).ok()?;
-        Self::from_value(value)
-    }
-
-    fn from_value(value: serde_json::Value) -> Option<Self> {
-        let version = value
-            .get("version")
-            .and_then(serde_json::Value::as_u64)
-            .map(|version| version as u32)
-            .unwrap_or(0);
-
-        match version {
-            SETTINGS_VERSION => serde_json::from_value(value).ok().map(Self::sanitize),
-            0 => Some(Self::migrate_v0(value)),
-            _ => None,
-        }
-    }
-
-    fn migrate_v0(value: serde_json::Value) -> Self {
-        let mut settings = Self::default();
-        settings.audio.volume.master = read_volume(&value, "master").unwrap_or(1.0);
-        settings.audio.volume.sfx = read_volume(&value, "sfx").unwrap_or(1.0);
-        settings.audio.volume.ui = read_volume(&value, "ui").unwrap_or(1.0);
-        settings.audio.volume.ambient = read_volume(&value, "ambient").unwrap_or(1.0);
-        settings.audio.volume.music = read_volume(&value, "music").unwrap_or(1.0);
-        settings.sanitize()
-    }
-
     fn sanitize(mut self) -> Self {
-        self.version = SETTINGS_VERSION;
         self.audio.volume = self.audio.volume.clamped();
         self
     }
 }
 
+/// Before persist, settings were stored without envelope. `version: 1` had the current shape
+/// plus the version field, and unversioned ones had flat volume fields.
+fn migrate_v0(mut value: serde_json::Value) -> Option<serde_json::Value> {
+    let object = value.as_object_mut()?;
+    match object
+        .remove("version")
+        .and_then(|version| version.as_u64())
+    {
+        Some(1) => Some(value),
+        None | Some(0) => Some(serde_json::json!({
+            "audio": {
+                "volume": {
+                    "master": read_volume(&value, "master").unwrap_or(1.0),
+                    "sfx": read_volume(&value, "sfx").unwrap_or(1.0),
+                    "ui": read_volume(&value, "ui").unwrap_or(1.0),
+                    "ambient": read_volume(&value, "ambient").unwrap_or(1.0),
+                    "music": read_volume(&value, "music").unwrap_or(1.0),
+                }
+            }
+        })),
+        Some(_) => None,
+    }
+}
+
 fn read_volume(value: &serde_json::Value, key: &str) -> Option<f32> {
     value
         .get(key)
@@ -111,15 +84,21 @@ fn read_volume(value: &serde_json::Value, key: &str) -> Option<f32> {
 
 #[cfg(test)]
 mod tests {
-    use super::Settings;
+    use super::{SETTINGS_KEY, Settings};
+
+    fn from_storage_str(raw: &str) -> Option<Settings> {
+        SETTINGS_KEY
+            .decode(raw.as_bytes())
+            .ok()
+            .map(Settings::sanitize)
+    }
 
     #[test]
     fn migrates_unversioned_volume_settings() {
         let raw = r#"{"master":0.3,"sfx":0.4,"ui":0.5,"ambient":0.6,"music":0.7}"#;
 
-        let settings = Settings::from_storage_str(raw).expect("settings should migrate");
+        let settings = from_storage_str(raw).expect("settings should migrate");
 
-        assert_eq!(settings.version, 1);
         assert_eq!(settings.audio.volume.master, 0.3);
         assert_eq!(settings.audio.volume.sfx, 0.4);
         assert_eq!(settings.audio.volume.ui, 0.5);
@@ -131,7 +110,7 @@ mod tests {
     fn clamps_loaded_values() {
         let raw = r#"{"version":1,"audio":{"volume":{"master":1.4,"sfx":-0.1,"ui":0.5,"ambient":2.0,"music":0.2}}}"#;
 
-        let settings = Settings::from_storage_str(raw).expect("settings should parse");
+        let settings = from_storage_str(raw).expect("settings should parse");
 
         assert_eq!(settings.audio.volume.master, 1.0);
         assert_eq!(settings.audio.volume.sfx, 0.0);
@@ -139,4 +118,13 @@ mod tests {
         assert_eq!(settings.audio.volume.ambient, 1.0);
         assert_eq!(settings.audio.volume.music, 0.2);
     }
+
+    #[test]
+    fn reads_enveloped_settings() {
+        let raw = r#"{"version":1,"data":{"audio":{"volume":{"master":0.8,"sfx":1.0,"ui":1.0,"ambient":1.0,"music":1.0}}}}"#;
+
+        let settings = from_storage_str(raw).expect("settings should parse");
+
+        assert_eq!(settings.audio.volume.master, 0.8);
+    }
 }
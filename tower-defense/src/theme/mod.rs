@@ -6,3 +6,4 @@ pub mod palette;
 pub mod paper_container;
 pub mod slider;
 pub mod typography;
+pub mod z_layer;
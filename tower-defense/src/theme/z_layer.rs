@@ -0,0 +1,6 @@
+//! Z-layers of the overlays. Everything else is at layer 0.
+
+/// Modals, in front of the map and the HUD.
+pub const MODAL: i32 = 100;
+/// Tooltips, in front of everything including modals.
+pub const TOOLTIP: i32 = 200;
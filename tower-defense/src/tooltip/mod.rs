@@ -16,6 +16,7 @@ use crate::l10n::{self, Locale};
 use crate::theme::palette;
 use crate::theme::paper_container::{PaperContainerBackground, PaperTexture, PaperVariant};
 use crate::theme::typography::{FontSize, TypographyBuilder, memoized_text};
+use crate::theme::z_layer;
 pub use hover_area::WithHoverArea;
 use namui::*;
 use namui_prebuilt::table;
@@ -345,7 +346,8 @@ impl Component for TooltipLayer {
 
             let pos = compute_position(request.anchor, request.placement, tooltip_wh);
             let pivot = tooltip_wh.to_xy() * 0.5;
-            ctx.absolute(pos + pivot)
+            ctx.z_layer(z_layer::TOOLTIP)
+                .absolute(pos + pivot)
                 .scale(Xy::new(scale, scale))
                 .translate(-pivot)
                 .add(tooltip);